@@ -41,12 +41,15 @@ Usage:
 """
 
 from .generator import (
+    IdentifierVerification,
     Snfei,
     SnfeiResult,
     compute_snfei,
     generate_snfei,
     generate_snfei_simple,
     generate_snfei_with_confidence,
+    is_valid_lei,
+    is_valid_sam_uei,
 )
 from .localization import (
     LocalizationConfig,
@@ -71,6 +74,9 @@ __all__ = [
     "generate_snfei_simple",
     "generate_snfei_with_confidence",
     "compute_snfei",
+    "IdentifierVerification",
+    "is_valid_lei",
+    "is_valid_sam_uei",
     # Normalization
     "normalize_legal_name",
     "normalize_address",
@@ -15,6 +15,7 @@ All inputs must pass through the Normalizing Functor before hashing.
 """
 
 from dataclasses import dataclass
+from enum import Enum
 import hashlib
 
 from .normalizer import (
@@ -53,6 +54,22 @@ class Snfei:
         return self.value[:length]
 
 
+class IdentifierVerification(Enum):
+    """How far the LEI or SAM UEI behind a result was checked.
+
+    A well-formed identifier alone does not prove the entity holds it, so a
+    format-valid but unverified LEI or UEI leaves the result at Tier 3.
+    """
+
+    UNVERIFIED = "unverified"
+    REGISTRY_VERIFIED = "registryVerified"
+    OFFLINE_ACCEPTED = "offlineAccepted"
+
+    def allows_federal_tier(self) -> bool:
+        """Return True if an identifier checked this far may raise the tier."""
+        return self is not IdentifierVerification.UNVERIFIED
+
+
 @dataclass
 class SnfeiResult:
     """Result of SNFEI generation with confidence metadata."""
@@ -62,6 +79,8 @@ class SnfeiResult:
     confidence_score: float  # 0.0 to 1.0
     tier: int  # 1, 2, or 3
     fields_used: list  # Which fields contributed
+    format_valid: bool = False  # LEI or SAM UEI supplied and well-formed
+    verification: IdentifierVerification = IdentifierVerification.UNVERIFIED
 
     def to_dict(self) -> dict:
         """Convert result to dictionary for serialization."""
@@ -70,6 +89,8 @@ class SnfeiResult:
             "confidence_score": self.confidence_score,
             "tier": self.tier,
             "fields_used": self.fields_used,
+            "format_valid": self.format_valid,
+            "verification": self.verification.value,
             "canonical": {
                 "legal_name_normalized": self.canonical.legal_name_normalized,
                 "address_normalized": self.canonical.address_normalized,
@@ -79,6 +100,29 @@ class SnfeiResult:
         }
 
 
+def is_valid_lei(lei: str) -> bool:
+    """Check an LEI: 20 letters or digits ending in two check digits (ISO 17442).
+
+    The check digits must satisfy ISO 7064 mod 97-10, with letters counted
+    as 10-35.
+    """
+    if len(lei) != 20 or not lei.isascii() or not lei.isalnum() or not lei[18:].isdigit():
+        return False
+    return int("".join(str(int(c, 36)) for c in lei)) % 97 == 1
+
+
+def is_valid_sam_uei(uei: str) -> bool:
+    """Check a SAM.gov UEI: 12 uppercase letters or digits, not starting with 0.
+
+    The letters I and O are never used.
+    """
+    return (
+        len(uei) == 12
+        and not uei.startswith("0")
+        and all(c in "0123456789ABCDEFGHJKLMNPQRSTUVWXYZ" for c in uei)
+    )
+
+
 def compute_snfei(canonical: CanonicalInput) -> Snfei:
     """Compute SNFEI from canonical input.
 
@@ -193,6 +237,7 @@ def generate_snfei_with_confidence(
     registration_date: str | None = None,
     lei: str | None = None,
     sam_uei: str | None = None,
+    verification: IdentifierVerification = IdentifierVerification.UNVERIFIED,
 ) -> SnfeiResult:
     """Generate SNFEI with confidence scoring and tier classification.
 
@@ -201,6 +246,11 @@ def generate_snfei_with_confidence(
     - Tier 2: Entity has SAM UEI (federal identifier) - confidence 0.95
     - Tier 3: Entity uses SNFEI (computed hash) - confidence varies
 
+    Tier 1 and Tier 2 need a format-valid identifier (see ``is_valid_lei``
+    and ``is_valid_sam_uei``) and a ``verification`` other than
+    ``IdentifierVerification.UNVERIFIED``. A format-valid but unverified
+    identifier leaves the result at Tier 3 with ``format_valid`` set.
+
     Tier 3 Confidence Scoring:
     - Base: 0.5 (name + country only)
     - +0.2 if address is provided
@@ -214,42 +264,36 @@ def generate_snfei_with_confidence(
         registration_date: Optional registration date.
         lei: Optional LEI (Legal Entity Identifier).
         sam_uei: Optional SAM.gov Unique Entity Identifier.
+        verification: How far ``lei`` or ``sam_uei`` was checked against its registry.
 
     Returns:
         SnfeiResult with SNFEI, confidence score, and metadata.
     """
     fields_used = ["legal_name", "country_code"]
+    canonical = build_canonical_input(legal_name, country_code, address, registration_date)
+    snfei = compute_snfei(canonical)
 
-    # Tier 1: LEI available
-    if lei and len(lei) == 20:
-        canonical = build_canonical_input(legal_name, country_code, address, registration_date)
-        # For Tier 1, we still compute SNFEI but confidence is 1.0
-        snfei = compute_snfei(canonical)
-        return SnfeiResult(
-            snfei=snfei,
-            canonical=canonical,
-            confidence_score=1.0,
-            tier=1,
-            fields_used=["lei"] + fields_used,
-        )
+    # Tier 1 (LEI) or Tier 2 (SAM UEI): well-formed and verified identifier
+    if lei and is_valid_lei(lei):
+        federal = (1, 1.0, "lei")
+    elif sam_uei and is_valid_sam_uei(sam_uei):
+        federal = (2, 0.95, "sam_uei")
+    else:
+        federal = None
 
-    # Tier 2: SAM UEI available
-    if sam_uei and len(sam_uei) == 12:
-        canonical = build_canonical_input(legal_name, country_code, address, registration_date)
-        snfei = compute_snfei(canonical)
+    if federal and verification.allows_federal_tier():
+        tier, confidence_score, field = federal
         return SnfeiResult(
             snfei=snfei,
             canonical=canonical,
-            confidence_score=0.95,
-            tier=2,
-            fields_used=["sam_uei"] + fields_used,
+            confidence_score=confidence_score,
+            tier=tier,
+            fields_used=[field] + fields_used,
+            format_valid=True,
+            verification=verification,
         )
 
     # Tier 3: Compute SNFEI from attributes
-    canonical = build_canonical_input(legal_name, country_code, address, registration_date)
-    snfei = compute_snfei(canonical)
-
-    # Calculate confidence score
     confidence = 0.5  # Base score
 
     if address:
@@ -274,4 +318,6 @@ def generate_snfei_with_confidence(
         confidence_score=round(confidence, 2),
         tier=3,
         fields_used=fields_used,
+        format_valid=federal is not None,
+        verification=verification if federal else IdentifierVerification.UNVERIFIED,
     )
@@ -0,0 +1,75 @@
+//! Generates the sample dataset, seals it and writes one file per record.
+//!
+//! ```text
+//! cargo run -p cep-cli --features samples --example generate_and_seal -- ./sample-records
+//! cep verify ./sample-records/records/entity/<id>.json --key ./sample-records/keys/county.pub.json
+//! ```
+//!
+//! The public test keys are written to `keys/` next to `records/`.
+
+use cep_cli::samples::{SampleAttestor, SampleDataset};
+use cep_core::{Canonicalize, CepError, CepResult};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn main() -> CepResult<()> {
+    let out = env::args_os()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| env::temp_dir().join("cep-sample-records"));
+
+    // 1. Generate: the builders turn the embedded tables into records,
+    //    computing SNFEIs for every entity.
+    let mut dataset = SampleDataset::generate()?;
+    println!(
+        "generated {} entities, {} relationships, {} exchanges",
+        dataset.entities.len(),
+        dataset.relationships.len(),
+        dataset.exchanges.len()
+    );
+    let first = &dataset.entities[0];
+    println!("  {} -> {}", first.legal_name, first.verifiable_id);
+
+    // 2. Seal: sign every record with its attestor's test key. The signature
+    //    covers the canonical form, not the JSON layout.
+    dataset.seal()?;
+    println!("  canonical hash {}", dataset.entities[0].calculate_hash());
+
+    // 3. Write one file per record, plus the public keys to verify them.
+    for record in dataset.records() {
+        let dir = out.join("records").join(record.kind().as_str());
+        let name = record
+            .verifiable_id()
+            .rsplit(':')
+            .next()
+            .unwrap_or_default();
+        write(
+            &dir.join(format!("{}.json", name)),
+            &record.to_json_pretty()?,
+        )?;
+    }
+    for attestor in SampleAttestor::ALL {
+        let name = format!("{:?}.pub.json", attestor).to_lowercase();
+        let path = out.join("keys").join(name);
+        fs::create_dir_all(out.join("keys")).map_err(|e| io_error(&path, e))?;
+        attestor.keys().public().save(&path)?;
+    }
+    println!(
+        "wrote {} records to {}",
+        dataset.records().len(),
+        out.display()
+    );
+    Ok(())
+}
+
+fn write(path: &Path, content: &str) -> CepResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+    }
+    fs::write(path, content).map_err(|e| io_error(path, e))
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> CepError {
+    CepError::Configuration(format!("Failed to write {}: {}", path.display(), e))
+}
@@ -0,0 +1,129 @@
+//! Field-level diff of canonical forms.
+//!
+//! Two records hash differently exactly when some canonical field differs, so
+//! comparing [`Canonicalize::canonical_fields`](cep_core::Canonicalize) field
+//! by field shows why two hashes disagree.
+
+use crate::style::Style;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// How one canonical field differs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldChange {
+    Added(String),
+    Removed(String),
+    Changed { old: String, new: String },
+}
+
+/// A canonical field that differs between two records.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldDiff {
+    pub field: String,
+    pub change: FieldChange,
+}
+
+/// Compares two canonical field maps, in canonical field order.
+pub fn diff_fields(
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+) -> Vec<FieldDiff> {
+    let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let change = match (old.get(key), new.get(key)) {
+                (Some(a), Some(b)) if a == b => return None,
+                (Some(a), Some(b)) => FieldChange::Changed {
+                    old: a.clone(),
+                    new: b.clone(),
+                },
+                (Some(a), None) => FieldChange::Removed(a.clone()),
+                (None, Some(b)) => FieldChange::Added(b.clone()),
+                (None, None) => return None,
+            };
+            Some(FieldDiff {
+                field: key.clone(),
+                change,
+            })
+        })
+        .collect()
+}
+
+/// Renders a diff with `-` for the old value and `+` for the new value.
+pub fn render_diff(diffs: &[FieldDiff], style: &Style) -> String {
+    if diffs.is_empty() {
+        return "canonical forms are identical\n".to_string();
+    }
+    let mut out = String::new();
+    for diff in diffs {
+        out.push_str(&format!("{}\n", style.yellow(&format!("~ {}", diff.field))));
+        match &diff.change {
+            FieldChange::Added(value) => {
+                out.push_str(&format!("{}\n", style.green(&format!("+ {}", value))));
+            }
+            FieldChange::Removed(value) => {
+                out.push_str(&format!("{}\n", style.red(&format!("- {}", value))));
+            }
+            FieldChange::Changed { old, new } => {
+                out.push_str(&format!("{}\n", style.red(&format!("- {}", old))));
+                out.push_str(&format!("{}\n", style.green(&format!("+ {}", new))));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_fields() {
+        let old = fields(&[("a", "1"), ("b", "2"), ("c", "3")]);
+        let new = fields(&[("b", "2"), ("c", "4"), ("d", "5")]);
+        let diffs = diff_fields(&old, &new);
+        assert_eq!(
+            diffs,
+            vec![
+                FieldDiff {
+                    field: "a".to_string(),
+                    change: FieldChange::Removed("1".to_string()),
+                },
+                FieldDiff {
+                    field: "c".to_string(),
+                    change: FieldChange::Changed {
+                        old: "3".to_string(),
+                        new: "4".to_string(),
+                    },
+                },
+                FieldDiff {
+                    field: "d".to_string(),
+                    change: FieldChange::Added("5".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_diff() {
+        let old = fields(&[("a", "1")]);
+        let new = fields(&[("a", "2")]);
+        let plain = render_diff(&diff_fields(&old, &new), &Style::plain());
+        assert_eq!(plain, "~ a\n- 1\n+ 2\n");
+        assert_eq!(
+            render_diff(&[], &Style::plain()),
+            "canonical forms are identical\n"
+        );
+    }
+}
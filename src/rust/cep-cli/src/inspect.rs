@@ -0,0 +1,112 @@
+//! `cep inspect`: summarize a record for debugging.
+
+use crate::record::{AnyRecord, RecordKind};
+use crate::style::Style;
+use cep_core::{Attestation, CanonicalHash, Canonicalize, SCHEMA_VERSION};
+use serde::Serialize;
+
+/// Summary of one record.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InspectReport {
+    #[serde(rename = "type")]
+    pub kind: RecordKind,
+    pub verifiable_id: String,
+    pub schema_version: String,
+    pub hash: CanonicalHash,
+    pub proof_type: String,
+    /// Problems found in the attestation block; empty if it is well-formed.
+    pub attestation_issues: Vec<String>,
+}
+
+impl InspectReport {
+    pub fn new(record: &AnyRecord) -> Self {
+        let attestation = record.attestation();
+        Self {
+            kind: record.kind(),
+            verifiable_id: record.verifiable_id().to_string(),
+            schema_version: record.schema_version().to_string(),
+            hash: record.calculate_hash(),
+            proof_type: attestation.proof_type.to_string(),
+            attestation_issues: attestation_issues(attestation),
+        }
+    }
+
+    /// Renders the report as aligned `label: value` lines.
+    pub fn render(&self, style: &Style) -> String {
+        let schema_version = if self.schema_version == SCHEMA_VERSION {
+            self.schema_version.clone()
+        } else {
+            style.yellow(&format!(
+                "{} (this build supports {})",
+                self.schema_version, SCHEMA_VERSION
+            ))
+        };
+        let attestation = if self.attestation_issues.is_empty() {
+            style.green("well-formed")
+        } else {
+            style.red(&self.attestation_issues.join("; "))
+        };
+
+        let mut out = String::new();
+        for (label, value) in [
+            ("type", self.kind.to_string()),
+            ("id", self.verifiable_id.clone()),
+            ("schema", schema_version),
+            ("hash", self.hash.to_string()),
+            ("proof", self.proof_type.clone()),
+            ("attestation", attestation),
+        ] {
+            out.push_str(&format!(
+                "{} {}\n",
+                style.bold(&format!("{:<12}", label)),
+                value
+            ));
+        }
+        out
+    }
+}
+
+/// Checks the attestation block for missing or malformed fields.
+///
+/// This checks structure only; the signature itself is not verified.
+pub fn attestation_issues(attestation: &Attestation) -> Vec<String> {
+    let mut issues = Vec::new();
+    if attestation.attestor_id.is_empty() {
+        issues.push("attestorId is empty".to_string());
+    }
+    if attestation.proof_type.as_str().is_empty() {
+        issues.push("proofType is empty".to_string());
+    }
+    if attestation.proof_value.is_empty() {
+        issues.push("proofValue is empty (unsigned)".to_string());
+    }
+    if attestation.verification_method_uri.is_empty() {
+        issues.push("verificationMethodUri is empty".to_string());
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cep_core::find_repo_root;
+
+    #[test]
+    fn test_report_for_example() {
+        let root = find_repo_root().unwrap();
+        let record = AnyRecord::read(&root.join("examples/entity/municipality_01.json")).unwrap();
+        let report = InspectReport::new(&record);
+        assert_eq!(report.kind, RecordKind::Entity);
+        assert_eq!(report.hash, record.calculate_hash());
+        // The example carries a manual attestation with no proof value.
+        assert_eq!(
+            report.attestation_issues,
+            vec!["proofValue is empty (unsigned)"]
+        );
+
+        let text = report.render(&Style::plain());
+        assert!(text.starts_with("type         entity\n"));
+        assert!(text.contains(&format!("hash         {}\n", report.hash)));
+    }
+}
@@ -0,0 +1,40 @@
+//! # CEP CLI
+//!
+//! Command-line tools for working with Civic Exchange Protocol records. The
+//! `cep` binary is a thin wrapper over the modules here:
+//!
+//! - [`record`]: parse a record of any type, detecting which one it is
+//! - [`inspect`]: record type, schema version, hash and attestation status
+//! - [`diff`]: field-level comparison of two records' canonical forms
+//! - [`sign`]: Ed25519 attestations using [`KeyFile`](cep_core::KeyFile)s
+//! - [`output`]: text or JSON output for scripting
+//! - [`validate`]: JSON Schema and business-rule checks over record files
+//! - `samples`: a sample dataset with test keys (feature `samples`)
+//!
+//! ```text
+//! cep inspect examples/entity/municipality_01.json
+//! cep inspect old.json --diff new.json
+//! cep keygen --type ed25519 --out key.json --public key.pub.json
+//! cep sign record.json --key key.json --attestor did:web:example.gov --out signed.json
+//! cep verify signed.json --key key.pub.json
+//! cep --output json inspect record.json
+//! cep validate ./records/ --schema entity-record@1.0.0
+//! cep completions bash > /etc/bash_completion.d/cep
+//! ```
+
+pub mod diff;
+pub mod inspect;
+pub mod output;
+pub mod record;
+#[cfg(feature = "samples")]
+pub mod samples;
+pub mod sign;
+pub mod style;
+pub mod validate;
+
+pub use diff::{FieldChange, FieldDiff, diff_fields, render_diff};
+pub use inspect::InspectReport;
+pub use output::OutputFormat;
+pub use record::{AnyRecord, RecordKind};
+pub use style::{ColorChoice, Style};
+pub use validate::{RecordValidator, SchemaSelector, ValidationReport};
@@ -0,0 +1,387 @@
+//! The `cep` command-line tool.
+
+use cep_cli::output::{ErrorOutput, to_json};
+use cep_cli::sign::{default_verification_method, sign, sign_manifest, verify};
+use cep_cli::{
+    AnyRecord, ColorChoice, InspectReport, OutputFormat, RecordValidator, SchemaSelector, Style,
+    diff_fields, render_diff,
+};
+use cep_core::manifest::{MANIFEST_FILE, MANIFEST_KEY_FILE, PROJECT_MANIFEST_KEY};
+use cep_core::{
+    Canonicalize, CepError, CepResult, CepResultExt, FreshnessPolicy, KeyFile, ProofType,
+    SchemaRegistry, SigningPolicy, SystemClock, find_repo_root,
+};
+use chrono::Duration;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use serde_json::json;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "cep", version, about = "Civic Exchange Protocol tools")]
+struct Cli {
+    /// When to color output.
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto, global = true)]
+    color: ColorChoice,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text, global = true)]
+    output: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Show a record's type, schema version, hash and attestation status.
+    Inspect {
+        /// Record file (JSON).
+        file: PathBuf,
+
+        /// Compare canonical fields with another record.
+        #[arg(long, value_name = "OTHER")]
+        diff: Option<PathBuf>,
+    },
+
+    /// Generate a signing key.
+    Keygen {
+        /// Key algorithm.
+        #[arg(long = "type", value_enum, default_value_t = KeyType::Ed25519)]
+        key_type: KeyType,
+
+        /// Where to write the key pair.
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Where to write the public key alone, for distribution to verifiers.
+        #[arg(long)]
+        public: Option<PathBuf>,
+    },
+
+    /// Sign a record, replacing its attestation.
+    Sign {
+        /// Record file (JSON).
+        file: PathBuf,
+
+        /// Key pair file from `cep keygen`.
+        #[arg(long)]
+        key: PathBuf,
+
+        /// Attestor id, e.g. did:web:example.gov.
+        #[arg(long)]
+        attestor: String,
+
+        /// Verification method URI [default: <attestor>#key-1].
+        #[arg(long)]
+        verification_method: Option<String>,
+
+        /// Proof type: Ed25519Signature2020 or JsonWebSignature2020 (detached JWS).
+        #[arg(long, default_value = "Ed25519Signature2020")]
+        proof_type: String,
+
+        /// Signing policy file; refuse proof types it does not allow.
+        #[arg(long)]
+        policy: Option<PathBuf>,
+
+        /// Where to write the signed record [default: stdout].
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Verify a record's Ed25519 or detached JWS attestation.
+    Verify {
+        /// Record file (JSON).
+        file: PathBuf,
+
+        /// Public key or key pair file.
+        #[arg(long)]
+        key: PathBuf,
+
+        /// Signing policy file; reject proof types it does not allow.
+        #[arg(long)]
+        policy: Option<PathBuf>,
+
+        /// Warn if the record was attested more than this many days ago.
+        #[arg(long, value_name = "DAYS")]
+        max_age: Option<u32>,
+    },
+
+    /// Check record files against JSON Schema and business rules.
+    ///
+    /// Exits with status 1 if any file fails.
+    Validate {
+        /// Record files or directories (searched recursively for *.json).
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Schema to require, e.g. entity-record@1.0.0 [default: detect per file].
+        #[arg(long)]
+        schema: Option<SchemaSelector>,
+    },
+
+    /// Start a record from a minimal template that passes schema validation.
+    New {
+        /// Schema to scaffold, e.g. entity-record or exchange-record@1.0.0.
+        schema: SchemaSelector,
+
+        /// Where to write the template [default: stdout].
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Hash and sign the schema and vocabulary files into the asset manifest.
+    ///
+    /// Writes the manifest and the public key to the repository root, where
+    /// the schema registry checks them at load.
+    Manifest {
+        /// Project key pair file.
+        #[arg(long)]
+        key: PathBuf,
+
+        /// Repository root [default: detected].
+        #[arg(long)]
+        root: Option<PathBuf>,
+    },
+
+    /// Print a shell completion script.
+    Completions {
+        /// Target shell.
+        shell: Shell,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum KeyType {
+    Ed25519,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let style = Style::new(cli.color);
+    match run(cli.command, cli.output, &style) {
+        Ok(code) => code,
+        Err(e) => {
+            match cli.output {
+                OutputFormat::Text => eprintln!("error: {}", e),
+                OutputFormat::Json => {
+                    println!("{}", to_json(&ErrorOutput::new(&e)).unwrap_or_default());
+                }
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command, output: OutputFormat, style: &Style) -> CepResult<ExitCode> {
+    let json = output == OutputFormat::Json;
+    match command {
+        Command::Inspect { file, diff } => {
+            let record = AnyRecord::read(&file)?;
+            let report = InspectReport::new(&record);
+            let diffs = match diff {
+                Some(other) => {
+                    let other = AnyRecord::read(&other)?;
+                    Some(diff_fields(
+                        &record.canonical_fields(),
+                        &other.canonical_fields(),
+                    ))
+                }
+                None => None,
+            };
+            if json {
+                let mut value = serde_json::to_value(&report)?;
+                if let Some(diffs) = diffs {
+                    value["diff"] = serde_json::to_value(diffs)?;
+                }
+                println!("{}", to_json(&value)?);
+            } else {
+                print!("{}", report.render(style));
+                if let Some(diffs) = diffs {
+                    println!();
+                    print!("{}", render_diff(&diffs, style));
+                }
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Keygen {
+            key_type: KeyType::Ed25519,
+            out,
+            public,
+        } => {
+            let keys = KeyFile::generate_ed25519();
+            keys.save(&out)?;
+            if let Some(public) = &public {
+                keys.public().save(public)?;
+            }
+            if json {
+                let value = json!({
+                    "publicKeyMultibase": keys.public_key_multibase,
+                    "keyFile": out,
+                    "publicKeyFile": public,
+                });
+                println!("{}", to_json(&value)?);
+            } else {
+                println!("{}", keys.public_key_multibase);
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Sign {
+            file,
+            key,
+            attestor,
+            verification_method,
+            proof_type,
+            policy,
+            out,
+        } => {
+            let mut record = AnyRecord::read(&file)?;
+            let policy = policy.as_deref().map(SigningPolicy::load).transpose()?;
+            let method =
+                verification_method.unwrap_or_else(|| default_verification_method(&attestor));
+            sign(
+                &mut record,
+                &KeyFile::load(&key)?,
+                &ProofType::from(proof_type),
+                &attestor,
+                &method,
+                &SystemClock,
+                policy.as_ref(),
+            )?;
+            let signed = record.to_json_pretty()?;
+            match out {
+                Some(path) => {
+                    write_file(&path, &signed)?;
+                    if json {
+                        let value = json!({
+                            "file": path,
+                            "hash": record.calculate_hash(),
+                        });
+                        println!("{}", to_json(&value)?);
+                    }
+                    Ok(ExitCode::SUCCESS)
+                }
+                // The signed record is already JSON.
+                None => {
+                    println!("{}", signed);
+                    Ok(ExitCode::SUCCESS)
+                }
+            }
+        }
+        Command::Verify {
+            file,
+            key,
+            policy,
+            max_age,
+        } => {
+            let record = AnyRecord::read(&file)?;
+            let signing_policy = policy.as_deref().map(SigningPolicy::load).transpose()?;
+            verify(&record, &KeyFile::load(&key)?, signing_policy.as_ref())
+                .with_record(&record.calculate_hash())?;
+            let class = record.kind().as_str();
+            let mut policy = FreshnessPolicy::new();
+            if let Some(days) = max_age {
+                policy = policy.max_age(class, Duration::days(days.into()));
+            }
+            let warnings = policy.check(
+                class,
+                record.attestation(),
+                record.freshness(),
+                &SystemClock,
+            );
+            if json {
+                println!(
+                    "{}",
+                    to_json(&json!({ "valid": true, "warnings": warnings }))?
+                );
+            } else {
+                println!("{}", style.green("signature valid"));
+                for warning in &warnings {
+                    println!("{} {}", style.yellow("warning:"), warning);
+                }
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Validate { paths, schema } => {
+            let registry = SchemaRegistry::global()?;
+            let report =
+                RecordValidator::new(&registry, schema.as_ref())?.validate_paths(&paths)?;
+            if json {
+                println!("{}", to_json(&report)?);
+            } else {
+                print!("{}", report.render(style));
+            }
+            Ok(if report.is_valid() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+        Command::New { schema, out } => {
+            let registry = SchemaRegistry::global()?;
+            let template = to_json(&registry.scaffold(&schema.schema_id())?)?;
+            match out {
+                Some(path) => {
+                    write_file(&path, &template)?;
+                    if json {
+                        println!("{}", to_json(&json!({ "file": path }))?);
+                    }
+                }
+                // The template is already JSON.
+                None => println!("{}", template),
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Manifest { key, root } => {
+            let root = match root {
+                Some(root) => root,
+                None => find_repo_root()?,
+            };
+            // Re-encode so a legacy headerless key file still publishes a
+            // headered public key.
+            let keys = KeyFile::from_signing_key(&KeyFile::load(&key)?.signing_key()?);
+            let signed = sign_manifest(&root, &keys)?;
+            let manifest_path = root.join(MANIFEST_FILE);
+            let key_path = root.join(MANIFEST_KEY_FILE);
+            signed.save(&manifest_path)?;
+            keys.public().save(&key_path)?;
+            let snapshot_hash = signed.manifest.snapshot_hash();
+            let project_key = keys.did_key()? == PROJECT_MANIFEST_KEY;
+            if json {
+                let value = json!({
+                    "manifestFile": manifest_path,
+                    "keyFile": key_path,
+                    "assets": signed.manifest.assets.len(),
+                    "snapshotHash": snapshot_hash,
+                    "projectKey": project_key,
+                });
+                println!("{}", to_json(&value)?);
+            } else {
+                println!(
+                    "signed {} assets, snapshot {}",
+                    signed.manifest.assets.len(),
+                    snapshot_hash
+                );
+                if !project_key {
+                    println!(
+                        "{} the key is not the project manifest key; the registry will refuse this manifest",
+                        style.yellow("warning:")
+                    );
+                }
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "cep", &mut io::stdout());
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+fn write_file(path: &Path, content: &str) -> CepResult<()> {
+    fs::write(path, content)
+        .map_err(|e| CepError::Configuration(format!("Failed to write {}: {}", path.display(), e)))
+}
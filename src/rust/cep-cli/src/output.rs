@@ -0,0 +1,80 @@
+//! Output formats shared by all commands.
+//!
+//! With `--output json` every command prints exactly one JSON document on
+//! stdout, including failures (`{"error": "..."}`, exit status 1), so
+//! pipelines can parse the result without scraping text. A failure that
+//! knows which record or field it arose in adds a `context` object.
+
+use cep_core::{CepError, CepResult, ErrorContext};
+use serde::Serialize;
+
+/// How command results are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// One JSON document per invocation.
+    Json,
+}
+
+/// Renders `value` as pretty-printed JSON.
+pub fn to_json<T: Serialize + ?Sized>(value: &T) -> CepResult<String> {
+    Ok(serde_json::to_string_pretty(value)?)
+}
+
+/// The JSON document printed when a command fails.
+#[derive(Debug, Serialize)]
+pub struct ErrorOutput {
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<ErrorContext>,
+}
+
+impl ErrorOutput {
+    pub fn new(error: &CepError) -> Self {
+        Self {
+            error: error.root().to_string(),
+            context: error.context().cloned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::{FieldChange, FieldDiff};
+
+    #[test]
+    fn test_diff_json_shape() {
+        let diff = FieldDiff {
+            field: "legalName".to_string(),
+            change: FieldChange::Changed {
+                old: "a".to_string(),
+                new: "b".to_string(),
+            },
+        };
+        let value: serde_json::Value = serde_json::from_str(&to_json(&[diff]).unwrap()).unwrap();
+        assert_eq!(value[0]["field"], "legalName");
+        assert_eq!(value[0]["change"]["changed"]["new"], "b");
+    }
+
+    #[test]
+    fn test_error_json_shape() {
+        let json = to_json(&ErrorOutput {
+            error: "boom".to_string(),
+            context: None,
+        })
+        .unwrap();
+        assert_eq!(json, "{\n  \"error\": \"boom\"\n}");
+    }
+
+    #[test]
+    fn test_error_json_keeps_context() {
+        let err = CepError::MissingField("lei".to_string()).with_field_path("identifiers");
+        let value: serde_json::Value =
+            serde_json::from_str(&to_json(&ErrorOutput::new(&err)).unwrap()).unwrap();
+        assert_eq!(value["error"], "missing required field: lei");
+        assert_eq!(value["context"]["fieldPath"], "identifiers");
+    }
+}
@@ -0,0 +1,227 @@
+//! Records of any CEP type, detected from their JSON shape.
+
+use cep_core::{
+    Attestation, Authority, Canonicalize, CepError, CepResult, Classification, Classified,
+    Freshness, HashDomain,
+};
+use cep_entity::EntityRecord;
+use cep_exchange::ExchangeRecord;
+use cep_relationship::RelationshipRecord;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// The three CEP record types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordKind {
+    Entity,
+    Relationship,
+    Exchange,
+}
+
+impl RecordKind {
+    /// Detects the record type from the fields only that type has.
+    pub fn detect(value: &Value) -> Option<Self> {
+        let object = value.as_object()?;
+        if object.contains_key("exchangeTypeUri") {
+            Some(Self::Exchange)
+        } else if object.contains_key("relationshipTypeUri") {
+            Some(Self::Relationship)
+        } else if object.contains_key("legalName") {
+            Some(Self::Entity)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Entity => "entity",
+            Self::Relationship => "relationship",
+            Self::Exchange => "exchange",
+        }
+    }
+}
+
+impl fmt::Display for RecordKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A parsed record of any type.
+#[derive(Debug, Clone)]
+pub enum AnyRecord {
+    Entity(Box<EntityRecord>),
+    Relationship(Box<RelationshipRecord>),
+    Exchange(Box<ExchangeRecord>),
+}
+
+impl AnyRecord {
+    /// Parses a record, detecting its type.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::UnknownSchema` if the type cannot be detected, or
+    /// `CepError::Serialization` if the JSON does not match the detected type.
+    pub fn from_json(json: &str) -> CepResult<Self> {
+        let value: Value = serde_json::from_str(json)?;
+        let kind = RecordKind::detect(&value)
+            .ok_or_else(|| CepError::UnknownSchema("cannot detect the record type".to_string()))?;
+        Ok(match kind {
+            RecordKind::Entity => Self::Entity(Box::new(serde_json::from_value(value)?)),
+            RecordKind::Relationship => {
+                Self::Relationship(Box::new(serde_json::from_value(value)?))
+            }
+            RecordKind::Exchange => Self::Exchange(Box::new(serde_json::from_value(value)?)),
+        })
+    }
+
+    /// Reads and parses a record file.
+    pub fn read(path: &Path) -> CepResult<Self> {
+        let json = fs::read_to_string(path).map_err(|e| {
+            CepError::Configuration(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_json(&json)
+    }
+
+    pub fn kind(&self) -> RecordKind {
+        match self {
+            Self::Entity(_) => RecordKind::Entity,
+            Self::Relationship(_) => RecordKind::Relationship,
+            Self::Exchange(_) => RecordKind::Exchange,
+        }
+    }
+
+    pub fn verifiable_id(&self) -> &str {
+        match self {
+            Self::Entity(r) => &r.verifiable_id,
+            Self::Relationship(r) => &r.verifiable_id,
+            Self::Exchange(r) => &r.verifiable_id,
+        }
+    }
+
+    pub fn schema_version(&self) -> &str {
+        match self {
+            Self::Entity(r) => &r.schema_version,
+            Self::Relationship(r) => &r.schema_version,
+            Self::Exchange(r) => &r.schema_version,
+        }
+    }
+
+    pub fn attestation(&self) -> &Attestation {
+        match self {
+            Self::Entity(r) => &r.attestation,
+            Self::Relationship(r) => &r.attestation,
+            Self::Exchange(r) => &r.attestation,
+        }
+    }
+
+    pub fn authority(&self) -> Option<&Authority> {
+        match self {
+            Self::Entity(r) => r.authority.as_ref(),
+            Self::Relationship(r) => r.authority.as_ref(),
+            Self::Exchange(r) => r.authority.as_ref(),
+        }
+    }
+
+    pub fn freshness(&self) -> Option<&Freshness> {
+        match self {
+            Self::Entity(r) => r.freshness.as_ref(),
+            Self::Relationship(r) => r.freshness.as_ref(),
+            Self::Exchange(r) => r.freshness.as_ref(),
+        }
+    }
+
+    pub fn attestation_mut(&mut self) -> &mut Attestation {
+        match self {
+            Self::Entity(r) => &mut r.attestation,
+            Self::Relationship(r) => &mut r.attestation,
+            Self::Exchange(r) => &mut r.attestation,
+        }
+    }
+
+    /// Serializes the record as pretty-printed JSON.
+    pub fn to_json_pretty(&self) -> CepResult<String> {
+        Ok(match self {
+            Self::Entity(r) => serde_json::to_string_pretty(r)?,
+            Self::Relationship(r) => serde_json::to_string_pretty(r)?,
+            Self::Exchange(r) => serde_json::to_string_pretty(r)?,
+        })
+    }
+}
+
+impl Canonicalize for AnyRecord {
+    fn canonical_fields(&self) -> BTreeMap<String, String> {
+        match self {
+            Self::Entity(r) => r.canonical_fields(),
+            Self::Relationship(r) => r.canonical_fields(),
+            Self::Exchange(r) => r.canonical_fields(),
+        }
+    }
+
+    fn hash_domain(&self) -> Option<HashDomain> {
+        match self {
+            Self::Entity(r) => r.hash_domain(),
+            Self::Relationship(r) => r.hash_domain(),
+            Self::Exchange(r) => r.hash_domain(),
+        }
+    }
+}
+
+impl Classified for AnyRecord {
+    fn classification(&self) -> Classification {
+        match self {
+            Self::Entity(r) => r.classification(),
+            Self::Relationship(r) => r.classification(),
+            Self::Exchange(r) => r.classification(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cep_core::find_repo_root;
+
+    #[test]
+    fn test_detect_kind() {
+        let detect = |json: &str| RecordKind::detect(&serde_json::from_str(json).unwrap());
+        assert_eq!(
+            detect(r#"{"exchangeTypeUri":"x"}"#),
+            Some(RecordKind::Exchange)
+        );
+        assert_eq!(
+            detect(r#"{"relationshipTypeUri":"x"}"#),
+            Some(RecordKind::Relationship)
+        );
+        assert_eq!(detect(r#"{"legalName":"x"}"#), Some(RecordKind::Entity));
+        assert_eq!(detect(r#"{"other":1}"#), None);
+        assert_eq!(detect("[]"), None);
+    }
+
+    #[test]
+    fn test_reads_example_records() {
+        let root = find_repo_root().unwrap();
+        for (path, kind) in [
+            ("examples/entity/municipality_01.json", RecordKind::Entity),
+            ("examples/exchange/grant_01.json", RecordKind::Exchange),
+        ] {
+            let record = AnyRecord::read(&root.join(path)).unwrap();
+            assert_eq!(record.kind(), kind);
+            assert_eq!(record.schema_version(), "1.0.0");
+        }
+    }
+
+    #[test]
+    fn test_unknown_record_type() {
+        assert!(matches!(
+            AnyRecord::from_json(r#"{"foo":"bar"}"#),
+            Err(CepError::UnknownSchema(_))
+        ));
+    }
+}
@@ -0,0 +1,461 @@
+//! A small, realistic sample dataset for examples and tests.
+//!
+//! The dataset describes the fictional Lincoln County, Illinois: 30 entities
+//! (the county, its towns, school and special districts, state and federal
+//! funders, nonprofits and vendors), 16 relationships between them and 32
+//! payments under those relationships.
+//!
+//! Records are generated from embedded tables with the regular builders, so
+//! SNFEIs and identifiers come out exactly as they would for real data.
+//! [`SampleDataset::seal`] then signs them with one of two [`SampleAttestor`]
+//! test keys: the county data office attests to entities and local awards,
+//! the state comptroller to most state and federal awards.
+//!
+//! The test keys are published in this file. Never use them outside tests.
+//!
+//! ```rust
+//! use cep_cli::samples::SampleDataset;
+//!
+//! let dataset = SampleDataset::sealed().unwrap();
+//! assert_eq!(dataset.entities.len(), 30);
+//! dataset.verify().unwrap();
+//! ```
+//!
+//! Enable with the `samples` feature. The programs in `examples/` use it to
+//! walk through generation, sealing, bundling and verification.
+
+use crate::record::AnyRecord;
+use crate::sign::default_verification_method;
+use cep_core::keys::{ED25519_KEY_TYPE, signing_input};
+use cep_core::{
+    Attestation, CepError, CepResult, Ed25519Signer, Ed25519Verifier, KeyFile, ProofType, Signer,
+};
+use cep_entity::{
+    AttestationInput as EntityAttestationInput, EntityBuilderInput, EntityRecord, build_entity,
+};
+use cep_exchange::ExchangeRecord;
+use cep_exchange::builder::{
+    AttestationInput as ExchangeAttestationInput, ExchangeBuilderInput, build_exchange,
+};
+use cep_relationship::RelationshipRecord;
+use cep_relationship::builder::{
+    AttestationInput as RelationshipAttestationInput, FinancialTermsInput,
+    RelationshipBuilderInput, build_relationship,
+};
+use std::collections::BTreeMap;
+
+/// When every sample record is attested.
+pub const SAMPLE_ATTESTATION_TIMESTAMP: &str = "2025-01-15T09:00:00.000000Z";
+
+/// Who attests to a sample record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SampleAttestor {
+    /// Lincoln County data office: entities and local awards.
+    County,
+    /// Illinois comptroller: most state and federal awards.
+    State,
+}
+
+impl SampleAttestor {
+    pub const ALL: [Self; 2] = [Self::County, Self::State];
+
+    /// The attestor's DID.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::County => "did:web:data.lincolncounty.example.gov",
+            Self::State => "did:web:comptroller.illinois.example.gov",
+        }
+    }
+
+    /// The attestor's test key pair.
+    pub fn keys(&self) -> KeyFile {
+        let (public, private) = match self {
+            Self::County => (
+                "z6MkrKEjEDQx42b6CNH7sE4SQm62BNsf5A9w3a97sU5aq4Gc",
+                "z3u2XX8pGSJvH1VDuNfTfio3BjM9YE8Av1UmNzis68R5v2oc",
+            ),
+            Self::State => (
+                "z6Mkrn5QAasSY1DL2GbnAiUJMoNbaDSbTAEaqLsCgCnbviPk",
+                "z3u2XH1KkY5JqGdkd8XBuM7yQ16hRbVW33aVzRkok9qtu2Rm",
+            ),
+        };
+        KeyFile {
+            key_type: ED25519_KEY_TYPE.to_string(),
+            public_key_multibase: public.to_string(),
+            private_key_multibase: Some(private.to_string()),
+        }
+    }
+
+    /// Finds the sample attestor with the given DID.
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.id() == id)
+    }
+}
+
+/// Entities: (key, legal name, entity type, jurisdiction, registration date).
+const ENTITIES: &[(&str, &str, &str, &str, &str)] = &[
+    ("county", "Lincoln County", "COUNTY", "US-IL", "1839-02-15"),
+    ("harmon", "City of Harmon", "MUNICIPALITY", "US-IL", "1857-03-04"),
+    ("oak-prairie", "Village of Oak Prairie", "MUNICIPALITY", "US-IL", "1893-06-12"),
+    ("millbrook", "City of Millbrook", "MUNICIPALITY", "US-IL", "1868-09-21"),
+    ("cedar-falls", "Town of Cedar Falls", "MUNICIPALITY", "US-IL", "1902-04-30"),
+    ("westfield", "Village of Westfield", "MUNICIPALITY", "US-IL", "1956-11-08"),
+    ("harmon-cusd", "Harmon Community Unit School District 12", "SCHOOL_DISTRICT", "US-IL", "1948-07-01"),
+    ("oak-prairie-esd", "Oak Prairie Elementary School District 41", "SCHOOL_DISTRICT", "US-IL", "1921-07-01"),
+    ("millbrook-hsd", "Millbrook High School District 205", "SCHOOL_DISTRICT", "US-IL", "1935-07-01"),
+    ("roe", "Lincoln County Regional Office of Education", "SCHOOL_DISTRICT", "US-IL", "1995-07-01"),
+    ("harmon-parks", "Harmon Park District", "SPECIAL_DISTRICT", "US-IL", "1924-05-17"),
+    ("forest-preserve", "Lincoln County Forest Preserve District", "SPECIAL_DISTRICT", "US-IL", "1931-10-02"),
+    ("millbrook-library", "Millbrook Public Library District", "SPECIAL_DISTRICT", "US-IL", "1972-01-10"),
+    ("cedar-falls-fire", "Cedar Falls Fire Protection District", "SPECIAL_DISTRICT", "US-IL", "1961-03-15"),
+    ("mosquito", "Lincoln County Mosquito Abatement District", "SPECIAL_DISTRICT", "US-IL", "1927-08-22"),
+    ("idot", "Illinois Department of Transportation", "STATE", "US-IL", "1972-01-01"),
+    ("isbe", "Illinois State Board of Education", "STATE", "US-IL", "1975-01-01"),
+    ("iepa", "Illinois Environmental Protection Agency", "STATE", "US-IL", "1970-07-01"),
+    ("usde", "U.S. Department of Education", "FEDERAL", "US", "1979-10-17"),
+    ("hud", "U.S. Department of Housing and Urban Development", "FEDERAL", "US", "1965-09-09"),
+    ("fema", "Federal Emergency Management Agency", "FEDERAL", "US", "1979-04-01"),
+    ("food-pantry", "Harmon Food Pantry", "NONPROFIT", "US-IL", "1983-02-11"),
+    ("literacy", "Lincoln County Literacy Council", "NONPROFIT", "US-IL", "1991-09-05"),
+    ("soccer", "Prairie Youth Soccer League", "NONPROFIT", "US-IL", "2004-03-19"),
+    ("historical", "Oak Prairie Historical Society", "NONPROFIT", "US-IL", "1966-05-28"),
+    ("asphalt", "Midwest Asphalt Paving LLC", "VENDOR", "US-IL", "1998-04-14"),
+    ("office-supply", "Prairie Office Supply Inc", "VENDOR", "US-IL", "1987-10-26"),
+    ("engineering", "Clearwater Engineering Group", "VENDOR", "US-IL", "2009-06-03"),
+    ("bus", "Northstar Bus Company", "VENDOR", "US-IL", "1979-08-13"),
+    ("landscaping", "Greenline Landscaping Co", "VENDOR", "US-IL", "2015-02-20"),
+];
+
+/// Relationships: (id, type, source, recipient, effective date, obligated
+/// amount, attestor).
+const RELATIONSHIPS: &[(&str, &str, &str, &str, &str, f64, SampleAttestor)] = &[
+    ("rel-2024-001", "GRANT_AGREEMENT", "usde", "harmon-cusd", "2024-07-01", 1_250_000.00, SampleAttestor::State),
+    ("rel-2024-002", "GRANT_AGREEMENT", "usde", "millbrook-hsd", "2024-07-01", 840_000.00, SampleAttestor::State),
+    ("rel-2024-003", "GRANT_AGREEMENT", "isbe", "oak-prairie-esd", "2024-07-01", 415_000.00, SampleAttestor::State),
+    ("rel-2024-004", "GRANT_AGREEMENT", "isbe", "roe", "2024-07-01", 96_000.00, SampleAttestor::State),
+    ("rel-2024-005", "GRANT_AGREEMENT", "hud", "county", "2024-10-01", 2_100_000.00, SampleAttestor::County),
+    ("rel-2024-006", "GRANT_AGREEMENT", "fema", "cedar-falls", "2024-05-20", 380_000.00, SampleAttestor::County),
+    ("rel-2024-007", "INTERGOVERNMENTAL", "idot", "county", "2024-03-01", 5_600_000.00, SampleAttestor::State),
+    ("rel-2024-008", "INTERGOVERNMENTAL", "iepa", "millbrook", "2024-04-15", 1_900_000.00, SampleAttestor::State),
+    ("rel-2024-009", "INTERGOVERNMENTAL", "county", "mosquito", "2024-01-01", 210_000.00, SampleAttestor::County),
+    ("rel-2024-010", "CONTRACT", "county", "asphalt", "2024-04-01", 3_450_000.00, SampleAttestor::County),
+    ("rel-2024-011", "CONTRACT", "county", "engineering", "2024-02-12", 640_000.00, SampleAttestor::County),
+    ("rel-2024-012", "CONTRACT", "harmon-cusd", "bus", "2024-08-01", 1_120_000.00, SampleAttestor::County),
+    ("rel-2024-013", "CONTRACT", "harmon", "landscaping", "2024-04-01", 185_000.00, SampleAttestor::County),
+    ("rel-2024-014", "CONTRACT", "millbrook-library", "office-supply", "2024-01-15", 42_000.00, SampleAttestor::County),
+    ("rel-2024-015", "GRANT_AGREEMENT", "county", "food-pantry", "2024-01-01", 150_000.00, SampleAttestor::County),
+    ("rel-2024-016", "GRANT_AGREEMENT", "harmon-parks", "soccer", "2024-03-01", 25_000.00, SampleAttestor::County),
+];
+
+/// Payments: (id, relationship id, type, amount, date, description).
+const EXCHANGES: &[(&str, &str, &str, f64, &str, &str)] = &[
+    ("exc-2024-0001", "rel-2024-001", "GRANT", 312_500.00, "2024-08-15", "Title I Part A, Q1"),
+    ("exc-2024-0002", "rel-2024-001", "GRANT", 312_500.00, "2024-11-15", "Title I Part A, Q2"),
+    ("exc-2024-0003", "rel-2024-002", "GRANT", 210_000.00, "2024-08-15", "Title I Part A, Q1"),
+    ("exc-2024-0004", "rel-2024-002", "GRANT", 210_000.00, "2024-11-15", "Title I Part A, Q2"),
+    ("exc-2024-0005", "rel-2024-003", "GRANT", 138_333.33, "2024-09-01", "Evidence-based funding"),
+    ("exc-2024-0006", "rel-2024-003", "GRANT", 138_333.33, "2024-12-01", "Evidence-based funding"),
+    ("exc-2024-0007", "rel-2024-004", "GRANT", 48_000.00, "2024-09-30", "ROE operations"),
+    ("exc-2024-0008", "rel-2024-005", "GRANT", 525_000.00, "2024-11-20", "CDBG drawdown 1"),
+    ("exc-2024-0009", "rel-2024-005", "GRANT", 410_250.75, "2024-12-18", "CDBG drawdown 2"),
+    ("exc-2024-0010", "rel-2024-006", "GRANT", 190_000.00, "2024-07-10", "Hazard mitigation"),
+    ("exc-2024-0011", "rel-2024-006", "GRANT", 95_000.00, "2024-10-22", "Hazard mitigation"),
+    ("exc-2024-0012", "rel-2024-007", "TRANSFER", 1_400_000.00, "2024-04-01", "Motor fuel tax allotment"),
+    ("exc-2024-0013", "rel-2024-007", "TRANSFER", 1_400_000.00, "2024-07-01", "Motor fuel tax allotment"),
+    ("exc-2024-0014", "rel-2024-007", "TRANSFER", 1_400_000.00, "2024-10-01", "Motor fuel tax allotment"),
+    ("exc-2024-0015", "rel-2024-008", "TRANSFER", 950_000.00, "2024-06-03", "Water revolving fund loan"),
+    ("exc-2024-0016", "rel-2024-009", "TRANSFER", 105_000.00, "2024-02-01", "Levy distribution"),
+    ("exc-2024-0017", "rel-2024-009", "TRANSFER", 105_000.00, "2024-08-01", "Levy distribution"),
+    ("exc-2024-0018", "rel-2024-010", "PAYMENT", 862_500.00, "2024-06-14", "County Road 9 resurfacing"),
+    ("exc-2024-0019", "rel-2024-010", "PAYMENT", 1_293_750.00, "2024-08-16", "County Road 9 resurfacing"),
+    ("exc-2024-0020", "rel-2024-010", "PAYMENT", 1_035_000.00, "2024-10-11", "County Road 9 resurfacing"),
+    ("exc-2024-0021", "rel-2024-011", "PAYMENT", 160_000.00, "2024-05-03", "Bridge inspection services"),
+    ("exc-2024-0022", "rel-2024-011", "PAYMENT", 224_000.00, "2024-09-06", "Bridge inspection services"),
+    ("exc-2024-0023", "rel-2024-012", "PAYMENT", 93_333.33, "2024-09-05", "Student transportation, August"),
+    ("exc-2024-0024", "rel-2024-012", "PAYMENT", 93_333.33, "2024-10-04", "Student transportation, September"),
+    ("exc-2024-0025", "rel-2024-012", "PAYMENT", 93_333.33, "2024-11-05", "Student transportation, October"),
+    ("exc-2024-0026", "rel-2024-013", "PAYMENT", 46_250.00, "2024-05-31", "Park mowing, spring"),
+    ("exc-2024-0027", "rel-2024-013", "PAYMENT", 46_250.00, "2024-08-30", "Park mowing, summer"),
+    ("exc-2024-0028", "rel-2024-014", "PAYMENT", 3_512.48, "2024-02-09", "Office supplies"),
+    ("exc-2024-0029", "rel-2024-014", "PAYMENT", 2_987.10, "2024-06-07", "Office supplies"),
+    ("exc-2024-0030", "rel-2024-015", "GRANT", 75_000.00, "2024-01-31", "Emergency food assistance"),
+    ("exc-2024-0031", "rel-2024-015", "GRANT", 75_000.00, "2024-07-31", "Emergency food assistance"),
+    ("exc-2024-0032", "rel-2024-016", "GRANT", 25_000.00, "2024-03-15", "Youth league field fees"),
+];
+
+/// The sample records, by type.
+#[derive(Debug, Clone)]
+pub struct SampleDataset {
+    pub entities: Vec<EntityRecord>,
+    pub relationships: Vec<RelationshipRecord>,
+    pub exchanges: Vec<ExchangeRecord>,
+}
+
+impl SampleDataset {
+    /// Builds the records from the embedded tables. Attestations name their
+    /// attestor but carry no proof yet.
+    pub fn generate() -> CepResult<Self> {
+        let mut entity_ids = BTreeMap::new();
+        let mut entities = Vec::with_capacity(ENTITIES.len());
+        for &(key, legal_name, entity_type, jurisdiction, registered) in ENTITIES {
+            let entity = build_entity(EntityBuilderInput {
+                source_id: Some(key.to_string()),
+                legal_name: legal_name.to_string(),
+                entity_type: Some(entity_type.to_string()),
+                jurisdiction: Some(jurisdiction.to_string()),
+                country_code: "US".to_string(),
+                address: None,
+                registration_date: Some(registered.to_string()),
+                status: None,
+                attestation: EntityAttestationInput {
+                    attested_by: SampleAttestor::County.id().to_string(),
+                    attestation_timestamp: SAMPLE_ATTESTATION_TIMESTAMP.to_string(),
+                    proof_type: None,
+                    proof_value: None,
+                    verification_method_uri: None,
+                    proof_purpose: None,
+                    anchor_uri: None,
+                },
+            })?
+            .entity;
+            entity_ids.insert(key, entity.verifiable_id.clone());
+            entities.push(entity);
+        }
+        let entity_id = |key: &str| {
+            entity_ids
+                .get(key)
+                .cloned()
+                .ok_or_else(|| CepError::MissingField(format!("sample entity '{}'", key)))
+        };
+
+        let mut relationships = Vec::with_capacity(RELATIONSHIPS.len());
+        for &(id, kind, source, recipient, effective, amount, attestor) in RELATIONSHIPS {
+            let (source_role, recipient_role) = match kind {
+                "CONTRACT" => ("buyer", "vendor"),
+                "INTERGOVERNMENTAL" => ("grantor", "recipient"),
+                _ => ("grantor", "grantee"),
+            };
+            relationships.push(
+                build_relationship(RelationshipBuilderInput {
+                    relationship_id: format!("cep-relationship:lincoln:{}", id),
+                    relationship_type: kind.to_string(),
+                    source_entity_id: entity_id(source)?,
+                    source_role: Some(source_role.to_string()),
+                    recipient_entity_id: entity_id(recipient)?,
+                    recipient_role: Some(recipient_role.to_string()),
+                    effective_date: effective.to_string(),
+                    termination_date: None,
+                    jurisdiction_iso: "US-IL".to_string(),
+                    attestation: RelationshipAttestationInput {
+                        attested_by: attestor.id().to_string(),
+                        attestation_timestamp: SAMPLE_ATTESTATION_TIMESTAMP.to_string(),
+                    },
+                    financial_terms: Some(FinancialTermsInput {
+                        total_obligated_amount: Some(amount),
+                        currency: "USD".to_string(),
+                        funding_instrument: None,
+                    }),
+                    source_reference: None,
+                    description: None,
+                })?
+                .relationship,
+            );
+        }
+
+        let mut exchanges = Vec::with_capacity(EXCHANGES.len());
+        for &(id, relationship, kind, amount, occurred, description) in EXCHANGES {
+            let &(_, _, source, recipient, _, _, attestor) = RELATIONSHIPS
+                .iter()
+                .find(|r| r.0 == relationship)
+                .ok_or_else(|| {
+                    CepError::MissingField(format!("sample relationship '{}'", relationship))
+                })?;
+            let mut exchange = build_exchange(ExchangeBuilderInput {
+                exchange_id: format!("cep-exchange:lincoln:{}", id),
+                exchange_type: kind.to_string(),
+                source_entity_id: entity_id(source)?,
+                recipient_entity_id: entity_id(recipient)?,
+                amount,
+                currency: "USD".to_string(),
+                occurred_date: occurred.to_string(),
+                attestation: ExchangeAttestationInput {
+                    attested_by: attestor.id().to_string(),
+                    attestation_timestamp: SAMPLE_ATTESTATION_TIMESTAMP.to_string(),
+                },
+                categorization: None,
+                source_reference: None,
+                description: Some(description.to_string()),
+            })?
+            .exchange;
+            exchange.relationship_id = format!("cep-relationship:lincoln:{}", relationship);
+            exchanges.push(exchange);
+        }
+
+        Ok(Self {
+            entities,
+            relationships,
+            exchanges,
+        })
+    }
+
+    /// Builds the records and signs them with the sample test keys.
+    pub fn sealed() -> CepResult<Self> {
+        let mut dataset = Self::generate()?;
+        dataset.seal()?;
+        Ok(dataset)
+    }
+
+    /// Signs every record with the test key of the attestor it names, in
+    /// one [`Signer::sign_input_batch`] call per attestor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidSignature` if a record names an attestor
+    /// that is not a [`SampleAttestor`].
+    pub fn seal(&mut self) -> CepResult<()> {
+        let entities = self
+            .entities
+            .iter_mut()
+            .map(|r| (signing_input(&*r), &mut r.attestation));
+        let relationships = self
+            .relationships
+            .iter_mut()
+            .map(|r| (signing_input(&*r), &mut r.attestation));
+        let exchanges = self
+            .exchanges
+            .iter_mut()
+            .map(|r| (signing_input(&*r), &mut r.attestation));
+
+        let mut batches: BTreeMap<SampleAttestor, Vec<(String, &mut Attestation)>> =
+            BTreeMap::new();
+        for (input, attestation) in entities.chain(relationships).chain(exchanges) {
+            let attestor = attestor_of(attestation)?;
+            stamp(attestation);
+            let bound = attestation.bound_input(&input);
+            batches
+                .entry(attestor)
+                .or_default()
+                .push((bound, attestation));
+        }
+
+        for (attestor, batch) in batches {
+            let signer = Ed25519Signer::new(attestor.keys().signing_key()?);
+            let inputs: Vec<String> = batch.iter().map(|(input, _)| input.clone()).collect();
+            let proofs = signer.sign_input_batch(&inputs)?;
+            for ((_, attestation), proof) in batch.into_iter().zip(proofs) {
+                attestation.proof_value = proof;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns every record, entities first.
+    pub fn records(&self) -> Vec<AnyRecord> {
+        let entities = self
+            .entities
+            .iter()
+            .map(|r| AnyRecord::Entity(Box::new(r.clone())));
+        let relationships = self
+            .relationships
+            .iter()
+            .map(|r| AnyRecord::Relationship(Box::new(r.clone())));
+        let exchanges = self
+            .exchanges
+            .iter()
+            .map(|r| AnyRecord::Exchange(Box::new(r.clone())));
+        entities.chain(relationships).chain(exchanges).collect()
+    }
+
+    /// Verifies every record's attestation against its attestor's test key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidSignature` naming the first record that
+    /// does not verify.
+    pub fn verify(&self) -> CepResult<()> {
+        self.records().iter().try_for_each(verify_sample)
+    }
+}
+
+/// Verifies one record against the test key of the sample attestor it names.
+///
+/// # Errors
+///
+/// Returns `CepError::InvalidSignature` if the attestor is not a sample
+/// attestor or the proof does not verify.
+pub fn verify_sample(record: &AnyRecord) -> CepResult<()> {
+    let attestation = record.attestation();
+    let attestor = attestor_of(attestation)?;
+    let verifier = Ed25519Verifier::new(attestor.keys().verifying_key()?);
+    attestation.verify(record, &verifier).map_err(|e| match e {
+        CepError::InvalidSignature(reason) => {
+            CepError::InvalidSignature(format!("{}: {}", record.verifiable_id(), reason))
+        }
+        other => other,
+    })
+}
+
+fn attestor_of(attestation: &Attestation) -> CepResult<SampleAttestor> {
+    SampleAttestor::from_id(&attestation.attestor_id).ok_or_else(|| {
+        CepError::InvalidSignature(format!(
+            "'{}' is not a sample attestor",
+            attestation.attestor_id
+        ))
+    })
+}
+
+// The builders already stamped the attestation time, so sealing is
+// deterministic.
+fn stamp(attestation: &mut Attestation) {
+    attestation.proof_type = ProofType::Ed25519Signature2020;
+    attestation.verification_method_uri = default_verification_method(&attestation.attestor_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::business_rules;
+    use cep_core::Canonicalize;
+
+    #[test]
+    fn test_dataset_is_consistent() {
+        let dataset = SampleDataset::generate().unwrap();
+        assert_eq!(dataset.entities.len(), ENTITIES.len());
+        assert_eq!(dataset.relationships.len(), RELATIONSHIPS.len());
+        assert_eq!(dataset.exchanges.len(), EXCHANGES.len());
+
+        let relationship_ids: Vec<&str> = dataset
+            .relationships
+            .iter()
+            .map(|r| r.verifiable_id.as_str())
+            .collect();
+        for exchange in &dataset.exchanges {
+            assert!(relationship_ids.contains(&exchange.relationship_id.as_str()));
+        }
+        for record in dataset.records() {
+            assert_eq!(business_rules(&record), Vec::<String>::new());
+        }
+    }
+
+    #[test]
+    fn test_sealed_records_verify() {
+        let dataset = SampleDataset::sealed().unwrap();
+        dataset.verify().unwrap();
+        assert_eq!(
+            dataset.records()[0].calculate_hash(),
+            SampleDataset::sealed().unwrap().records()[0].calculate_hash()
+        );
+
+        let mut tampered = dataset.clone();
+        tampered.exchanges[0].value.amount += 1.0;
+        assert!(matches!(
+            tampered.verify(),
+            Err(CepError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_unsealed_records_do_not_verify() {
+        assert!(SampleDataset::generate().unwrap().verify().is_err());
+    }
+}
@@ -0,0 +1,241 @@
+//! `cep sign` and `cep verify`: Ed25519 attestations from key files, as
+//! `Ed25519Signature2020` or detached JWS (`JsonWebSignature2020`) proofs.
+//! `cep manifest`: the signed asset manifest.
+
+use crate::record::AnyRecord;
+use cep_core::signer::{ed25519_signer, ed25519_verifier};
+use cep_core::{
+    AssetManifest, CepResult, Clock, KeyFile, ProofType, SchemaRegistry, SignedManifest,
+    SigningPolicy,
+};
+use std::path::Path;
+
+/// Signs `record` in place with a `proof_type` proof, replacing its
+/// attestation.
+///
+/// The attestation is stamped with the time of `clock` and names
+/// `verification_method` as the key to verify against. With a `policy`, the
+/// proof type must be allowed for the record's kind.
+///
+/// # Errors
+///
+/// Returns `CepError::PolicyViolation` if the policy does not allow
+/// `proof_type`, and `CepError::InvalidSignature` if it cannot be produced
+/// with an Ed25519 key.
+pub fn sign(
+    record: &mut AnyRecord,
+    keys: &KeyFile,
+    proof_type: &ProofType,
+    attestor_id: &str,
+    verification_method: &str,
+    clock: &dyn Clock,
+    policy: Option<&SigningPolicy>,
+) -> CepResult<()> {
+    if let Some(policy) = policy {
+        policy.check_record(record, proof_type)?;
+    }
+    let signer = ed25519_signer(proof_type, keys.signing_key()?)?;
+    let mut attestation = record.attestation().clone();
+    attestation.attestor_id = attestor_id.to_string();
+    attestation.attestation_timestamp = clock.now();
+    attestation.verification_method_uri = verification_method.to_string();
+    attestation.prove(record, signer.as_ref())?;
+    *record.attestation_mut() = attestation;
+    Ok(())
+}
+
+/// Verifies the record's attestation against a (public) key file and, if
+/// given, a signing policy.
+///
+/// # Errors
+///
+/// Returns `CepError::PolicyViolation` if the policy does not allow the
+/// proof type, and `CepError::InvalidSignature` if the proof type cannot be
+/// checked with an Ed25519 key or the signature does not verify.
+pub fn verify(record: &AnyRecord, keys: &KeyFile, policy: Option<&SigningPolicy>) -> CepResult<()> {
+    let attestation = record.attestation();
+    if let Some(policy) = policy {
+        policy.check_record(record, &attestation.proof_type)?;
+    }
+    let verifier = ed25519_verifier(&attestation.proof_type, keys.verifying_key()?)?;
+    attestation.verify(record, verifier.as_ref())
+}
+
+/// Hashes the schema and vocabulary files under `repo_root` into an asset
+/// manifest and signs it with `keys`, naming the key by its `did:key`
+/// verification method.
+///
+/// # Errors
+///
+/// Returns `CepError::Configuration` if an asset cannot be read, and
+/// `CepError::InvalidSignature` if `keys` has no private key.
+pub fn sign_manifest(repo_root: &Path, keys: &KeyFile) -> CepResult<SignedManifest> {
+    let manifest = AssetManifest::build(repo_root, &SchemaRegistry::asset_paths(repo_root)?)?;
+    Ok(SignedManifest::sign(
+        manifest,
+        &keys.did_key_verification_method()?,
+        &keys.signing_key()?,
+    ))
+}
+
+/// Default verification method for an attestor DID: its first key.
+pub fn default_verification_method(attestor_id: &str) -> String {
+    format!("{}#key-1", attestor_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cep_core::{CepError, FixedClock, find_repo_root};
+
+    fn example() -> AnyRecord {
+        let root = find_repo_root().unwrap();
+        AnyRecord::read(&root.join("examples/entity/municipality_01.json")).unwrap()
+    }
+
+    #[test]
+    fn test_sign_then_verify() {
+        let keys = KeyFile::generate_ed25519();
+        let clock = FixedClock::new("2025-12-01T00:00:00.000000Z".parse().unwrap());
+        let mut record = example();
+        sign(
+            &mut record,
+            &keys,
+            &ProofType::Ed25519Signature2020,
+            "did:web:example.gov",
+            "did:web:example.gov#key-1",
+            &clock,
+            None,
+        )
+        .unwrap();
+
+        let attestation = record.attestation();
+        assert_eq!(attestation.proof_type, ProofType::Ed25519Signature2020);
+        assert_eq!(attestation.attestor_id, "did:web:example.gov");
+        verify(&record, &keys.public(), None).unwrap();
+
+        // Round-trips through the written JSON.
+        let reread = AnyRecord::from_json(&record.to_json_pretty().unwrap()).unwrap();
+        verify(&reread, &keys.public(), None).unwrap();
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering_and_other_keys() {
+        let keys = KeyFile::generate_ed25519();
+        let clock = FixedClock::new("2025-12-01T00:00:00.000000Z".parse().unwrap());
+        let mut record = example();
+        sign(
+            &mut record,
+            &keys,
+            &ProofType::Ed25519Signature2020,
+            "did:web:a",
+            "did:web:a#key-1",
+            &clock,
+            None,
+        )
+        .unwrap();
+
+        assert!(verify(&record, &KeyFile::generate_ed25519(), None).is_err());
+
+        if let AnyRecord::Entity(entity) = &mut record {
+            entity.legal_name = "city of shelbyville".to_string();
+        }
+        assert!(matches!(
+            verify(&record, &keys, None),
+            Err(CepError::InvalidSignature(_))
+        ));
+
+        // The unsigned example has a manual attestation.
+        assert!(verify(&example(), &keys, None).is_err());
+    }
+
+    #[test]
+    fn test_jws_proofs() {
+        let keys = KeyFile::generate_ed25519();
+        let clock = FixedClock::new("2025-12-01T00:00:00.000000Z".parse().unwrap());
+        let mut record = example();
+        sign(
+            &mut record,
+            &keys,
+            &ProofType::JsonWebSignature2020,
+            "did:web:a",
+            "did:web:a#key-1",
+            &clock,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            record.attestation().proof_type,
+            ProofType::JsonWebSignature2020
+        );
+        let reread = AnyRecord::from_json(&record.to_json_pretty().unwrap()).unwrap();
+        verify(&reread, &keys.public(), None).unwrap();
+
+        let unsupported = sign(
+            &mut record,
+            &keys,
+            &ProofType::DataIntegrityProof,
+            "did:web:a",
+            "did:web:a#key-1",
+            &clock,
+            None,
+        );
+        assert!(matches!(unsupported, Err(CepError::InvalidSignature(_))));
+    }
+
+    #[test]
+    fn test_sign_manifest_covers_assets() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("schemas")).unwrap();
+        std::fs::create_dir_all(dir.path().join("vocabularies")).unwrap();
+        std::fs::write(dir.path().join("schemas/cep.entity.schema.json"), "{}").unwrap();
+        std::fs::write(dir.path().join("vocabularies/v.v1.0.0.json"), "{}").unwrap();
+        std::fs::write(dir.path().join("schemas/README.md"), "").unwrap();
+
+        let keys = KeyFile::generate_ed25519();
+        let signed = sign_manifest(dir.path(), &keys).unwrap();
+        signed.verify(&keys.verifying_key().unwrap()).unwrap();
+        assert_eq!(
+            signed.manifest.assets.keys().collect::<Vec<_>>(),
+            [
+                "schemas/cep.entity.schema.json",
+                "vocabularies/v.v1.0.0.json"
+            ]
+        );
+        assert!(sign_manifest(dir.path(), &keys.public()).is_err());
+    }
+
+    #[test]
+    fn test_signing_policy_is_enforced() {
+        let keys = KeyFile::generate_ed25519();
+        let clock = FixedClock::new("2025-12-01T00:00:00.000000Z".parse().unwrap());
+        let policy = SigningPolicy::new().allow("entity", ["Ed25519Signature2020"]);
+        let mut record = example();
+        let refused = sign(
+            &mut record,
+            &keys,
+            &ProofType::JsonWebSignature2020,
+            "did:web:a",
+            "did:web:a#key-1",
+            &clock,
+            Some(&policy),
+        );
+        assert!(matches!(refused, Err(CepError::PolicyViolation { .. })));
+
+        sign(
+            &mut record,
+            &keys,
+            &ProofType::JsonWebSignature2020,
+            "did:web:a",
+            "did:web:a#key-1",
+            &clock,
+            None,
+        )
+        .unwrap();
+        verify(&record, &keys, None).unwrap();
+        assert!(matches!(
+            verify(&record, &keys, Some(&policy)),
+            Err(CepError::PolicyViolation { .. })
+        ));
+    }
+}
@@ -0,0 +1,490 @@
+//! `cep validate`: check record files before publishing.
+//!
+//! Every `.json` file under the given paths is checked in three steps:
+//!
+//! 1. JSON Schema, using the schemas from the [`SchemaRegistry`]; unless a
+//!    version is selected, each file is checked against the schema version
+//!    it declares
+//! 2. parsing into the typed record
+//! 3. business rules the schema cannot express, such as supported schema
+//!    versions and the authority citation required on restricted records
+//!
+//! Problems are reported per file and summarized by category, e.g.
+//! `schema/required` or `rule`, so a failing batch shows at a glance whether
+//! one mistake is repeated or many things are wrong.
+
+use crate::record::{AnyRecord, RecordKind};
+use crate::style::Style;
+use cep_core::{
+    CURRENT_SCHEMA_VERSION, CepError, CepResult, Classified, SCHEMA_VERSION, SchemaRegistry,
+    SchemaVersion, validate_authority,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A schema named on the command line, e.g. `entity-record@1.0.0`.
+///
+/// The `-record` suffix and the version are optional; a patch version is
+/// ignored because schemas are published per major.minor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaSelector {
+    pub kind: RecordKind,
+    pub version: Option<String>,
+}
+
+impl FromStr for SchemaSelector {
+    type Err = CepError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, version) = match s.split_once('@') {
+            Some((name, version)) => (name, Some(version)),
+            None => (s, None),
+        };
+        let kind = match name.strip_suffix("-record").unwrap_or(name) {
+            "entity" => RecordKind::Entity,
+            "relationship" => RecordKind::Relationship,
+            "exchange" => RecordKind::Exchange,
+            _ => {
+                return Err(CepError::UnknownSchema(format!(
+                    "'{}' (expected entity, relationship or exchange)",
+                    name
+                )));
+            }
+        };
+        let version = version.map(|v| v.splitn(3, '.').take(2).collect::<Vec<_>>().join("."));
+        Ok(Self { kind, version })
+    }
+}
+
+impl SchemaSelector {
+    /// The selected schema as named in the registry, e.g. `entity@1.0`.
+    pub fn schema_id(&self) -> String {
+        match &self.version {
+            Some(version) => format!("{}@{}", self.kind.as_str(), version),
+            None => self.kind.as_str().to_string(),
+        }
+    }
+}
+
+/// One problem found in one file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Issue {
+    pub file: PathBuf,
+    /// `read`, `json`, `type`, `version`, `schema/<keyword>`, `parse` or
+    /// `rule`.
+    pub category: String,
+    /// JSON pointer to the offending value, for schema issues.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub message: String,
+}
+
+/// Result of validating a set of files.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+    pub files_checked: usize,
+    pub files_failed: usize,
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    /// Returns true if every file passed.
+    pub fn is_valid(&self) -> bool {
+        self.files_failed == 0
+    }
+
+    /// Counts issues per category.
+    pub fn summary(&self) -> BTreeMap<&str, usize> {
+        let mut summary = BTreeMap::new();
+        for issue in &self.issues {
+            *summary.entry(issue.category.as_str()).or_insert(0) += 1;
+        }
+        summary
+    }
+
+    /// Renders each issue on its own line, followed by the summary.
+    pub fn render(&self, style: &Style) -> String {
+        let mut out = String::new();
+        for issue in &self.issues {
+            let location = match &issue.path {
+                Some(path) if !path.is_empty() => format!(" at {}", path),
+                _ => String::new(),
+            };
+            out.push_str(&format!(
+                "{}: {}{}: {}\n",
+                issue.file.display(),
+                style.red(&issue.category),
+                location,
+                issue.message
+            ));
+        }
+        if !self.issues.is_empty() {
+            out.push('\n');
+            for (category, count) in self.summary() {
+                out.push_str(&format!(
+                    "{} {}\n",
+                    style.bold(&format!("{:<24}", category)),
+                    count
+                ));
+            }
+        }
+        let totals = format!(
+            "{} of {} files passed",
+            self.files_checked - self.files_failed,
+            self.files_checked
+        );
+        out.push_str(&if self.is_valid() {
+            style.green(&totals)
+        } else {
+            style.red(&totals)
+        });
+        out.push('\n');
+        out
+    }
+}
+
+/// Validates record files against the registry's schemas and business rules.
+pub struct RecordValidator {
+    expected: Option<RecordKind>,
+    /// The selected version, if any; otherwise each file's declared one.
+    version: Option<String>,
+    /// Validators by record type and schema major.minor.
+    validators: BTreeMap<(&'static str, String), jsonschema::Validator>,
+}
+
+impl RecordValidator {
+    /// Compiles the schemas to validate against: the selected one, or one
+    /// per record type when each file's type is detected. Without a selected
+    /// version, every loaded version of those schemas is compiled.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::UnknownSchema` if the selected schema or version is
+    /// not in the registry, or `CepError::Configuration` if a schema does not
+    /// compile.
+    pub fn new(registry: &SchemaRegistry, selector: Option<&SchemaSelector>) -> CepResult<Self> {
+        let kinds = match selector {
+            Some(selector) => vec![selector.kind],
+            None => vec![
+                RecordKind::Entity,
+                RecordKind::Relationship,
+                RecordKind::Exchange,
+            ],
+        };
+        let version = selector.and_then(|s| s.version.as_deref());
+
+        // Cross-schema `$ref`s resolve through the schemas' own `$id`s.
+        let resources = jsonschema::Registry::new()
+            .extend(
+                registry
+                    .get_registry()
+                    .iter()
+                    .map(|(id, schema)| (id.as_str(), schema)),
+            )
+            .and_then(|builder| builder.prepare())
+            .map_err(|e| CepError::Configuration(format!("Invalid schema registry: {}", e)))?;
+
+        let mut validators = BTreeMap::new();
+        for kind in kinds {
+            let versions = match version {
+                Some(version) => vec![version.to_string()],
+                None => registry.schema_versions(kind.as_str()),
+            };
+            for version in versions {
+                let schema = registry.get_schema(kind.as_str(), Some(&version))?;
+                let validator = jsonschema::options()
+                    .with_registry(&resources)
+                    .should_validate_formats(true)
+                    .build(&schema)
+                    .map_err(|e| {
+                        CepError::Configuration(format!(
+                            "Invalid {} v{} schema: {}",
+                            kind, version, e
+                        ))
+                    })?;
+                validators.insert((kind.as_str(), version), validator);
+            }
+        }
+        Ok(Self {
+            expected: selector.map(|s| s.kind),
+            version: version.map(str::to_string),
+            validators,
+        })
+    }
+
+    /// Validates every `.json` file in `paths`, descending into directories.
+    pub fn validate_paths(&self, paths: &[PathBuf]) -> CepResult<ValidationReport> {
+        let mut files = Vec::new();
+        for path in paths {
+            collect_json_files(path, &mut files)?;
+        }
+        let mut report = ValidationReport::default();
+        for file in files {
+            let issues = self.validate_file(&file);
+            report.files_checked += 1;
+            if !issues.is_empty() {
+                report.files_failed += 1;
+                report.issues.extend(issues);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Validates one file, returning its issues.
+    pub fn validate_file(&self, file: &Path) -> Vec<Issue> {
+        let issue = |category: &str, path: Option<String>, message: String| Issue {
+            file: file.to_path_buf(),
+            category: category.to_string(),
+            path,
+            message,
+        };
+
+        let json = match fs::read_to_string(file) {
+            Ok(json) => json,
+            Err(e) => return vec![issue("read", None, e.to_string())],
+        };
+        let value: Value = match serde_json::from_str(&json) {
+            Ok(value) => value,
+            Err(e) => return vec![issue("json", None, e.to_string())],
+        };
+        let kind = match (RecordKind::detect(&value), self.expected) {
+            (Some(detected), Some(expected)) if detected != expected => {
+                return vec![issue(
+                    "type",
+                    None,
+                    format!(
+                        "expected a {} record, found a {} record",
+                        expected, detected
+                    ),
+                )];
+            }
+            (Some(detected), _) => detected,
+            (None, Some(expected)) => expected,
+            (None, None) => {
+                return vec![issue(
+                    "type",
+                    None,
+                    "cannot detect the record type".to_string(),
+                )];
+            }
+        };
+
+        let version = match (&self.version, value.get("schemaVersion")) {
+            (Some(selected), _) => selected.clone(),
+            (None, Some(Value::String(declared))) => match declared.parse::<SchemaVersion>() {
+                Ok(declared) => declared.major_minor(),
+                Err(e) => return vec![issue("version", None, e.to_string())],
+            },
+            // The schema reports the missing or mistyped field.
+            (None, _) => CURRENT_SCHEMA_VERSION.major_minor(),
+        };
+        let Some(validator) = self.validators.get(&(kind.as_str(), version.clone())) else {
+            return vec![issue(
+                "version",
+                None,
+                format!("no {} schema v{} is loaded", kind, version),
+            )];
+        };
+        let schema_issues: Vec<Issue> = validator
+            .iter_errors(&value)
+            .map(|e| {
+                issue(
+                    &format!("schema/{}", e.kind().keyword()),
+                    Some(e.instance_path().to_string()),
+                    e.to_string(),
+                )
+            })
+            .collect();
+        if !schema_issues.is_empty() {
+            return schema_issues;
+        }
+
+        match AnyRecord::from_json(&json) {
+            Ok(record) => business_rules(&record)
+                .into_iter()
+                .map(|message| issue("rule", None, message))
+                .collect(),
+            Err(e) => vec![issue("parse", None, e.to_string())],
+        }
+    }
+}
+
+/// Checks the rules the JSON Schemas cannot express.
+pub fn business_rules(record: &AnyRecord) -> Vec<String> {
+    // Entity records have their own rule set, which includes the checks below.
+    if let AnyRecord::Entity(entity) = record {
+        return entity.validate().err().into_iter().collect();
+    }
+    let mut problems = Vec::new();
+    if record.schema_version() != SCHEMA_VERSION {
+        problems.push(format!(
+            "Unsupported schema version: {}",
+            record.schema_version()
+        ));
+    }
+    if let Err(e) = validate_authority(record.classification(), record.authority()) {
+        problems.push(e.to_string());
+    }
+    problems
+}
+
+fn collect_json_files(path: &Path, out: &mut Vec<PathBuf>) -> CepResult<()> {
+    if !path.is_dir() {
+        out.push(path.to_path_buf());
+        return Ok(());
+    }
+    let read_error = |e: std::io::Error| {
+        CepError::Configuration(format!("Failed to read {}: {}", path.display(), e))
+    };
+    let mut entries = fs::read_dir(path)
+        .map_err(read_error)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(read_error)?;
+    entries.sort();
+    for entry in entries {
+        if entry.is_dir() {
+            collect_json_files(&entry, out)?;
+        } else if entry.extension().is_some_and(|ext| ext == "json") {
+            out.push(entry);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cep_core::find_repo_root;
+
+    fn grant() -> Value {
+        let root = find_repo_root().unwrap();
+        serde_json::from_str(
+            &fs::read_to_string(root.join("examples/exchange/grant_01.json")).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_selector() {
+        let selector: SchemaSelector = "entity-record@1.0.0".parse().unwrap();
+        assert_eq!(selector.kind, RecordKind::Entity);
+        assert_eq!(selector.version.as_deref(), Some("1.0"));
+        let selector: SchemaSelector = "exchange".parse().unwrap();
+        assert_eq!(selector.kind, RecordKind::Exchange);
+        assert_eq!(selector.version, None);
+        assert!("ledger-record@1.0.0".parse::<SchemaSelector>().is_err());
+    }
+
+    #[test]
+    fn test_validate_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, content: &str| {
+            let path = dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write("a/valid.json", &grant().to_string());
+        let mut restricted = grant();
+        restricted["classification"] = "restricted".into();
+        write("a/restricted.json", &restricted.to_string());
+        let mut incomplete = grant();
+        incomplete
+            .as_object_mut()
+            .unwrap()
+            .remove("occurredTimestamp");
+        write("b/incomplete.json", &incomplete.to_string());
+        write("b/broken.json", "{");
+        write("notes.txt", "not a record");
+
+        let registry = SchemaRegistry::global().unwrap();
+        let validator = RecordValidator::new(&registry, None).unwrap();
+        let report = validator
+            .validate_paths(&[dir.path().to_path_buf()])
+            .unwrap();
+
+        assert_eq!(report.files_checked, 4);
+        assert_eq!(report.files_failed, 3);
+        // The schema itself requires authority on restricted records.
+        assert_eq!(
+            report.summary(),
+            BTreeMap::from([("json", 1), ("schema/required", 2)])
+        );
+        assert!(
+            report
+                .render(&Style::plain())
+                .ends_with("1 of 4 files passed\n")
+        );
+    }
+
+    #[test]
+    fn test_selected_schema_rejects_other_types() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("grant.json");
+        fs::write(&path, grant().to_string()).unwrap();
+
+        let registry = SchemaRegistry::global().unwrap();
+        let selector = "entity-record@1.0.0".parse().unwrap();
+        let issues = RecordValidator::new(&registry, Some(&selector))
+            .unwrap()
+            .validate_file(&path);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].category, "type");
+    }
+
+    #[test]
+    fn test_files_checked_against_declared_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, version: &str| {
+            let mut record = grant();
+            record["schemaVersion"] = version.into();
+            let path = dir.path().join(name);
+            fs::write(&path, record.to_string()).unwrap();
+            path
+        };
+        let current = write("current.json", SCHEMA_VERSION);
+        let next = write("next.json", "1.1.0");
+        let future = write("future.json", "2.0.0");
+
+        let mut registry = SchemaRegistry::global().unwrap();
+        registry.register_schema(
+            "exchange",
+            SchemaVersion::new(1, 1, 0),
+            serde_json::json!({"type": "object", "required": ["newInV11"]}),
+        );
+        let validator = RecordValidator::new(&registry, None).unwrap();
+        assert!(validator.validate_file(&current).is_empty());
+        let issues = validator.validate_file(&next);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].category, "schema/required");
+        assert_eq!(validator.validate_file(&future)[0].category, "version");
+    }
+
+    #[test]
+    fn test_scaffolds_pass_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = SchemaRegistry::global().unwrap();
+        let validator = RecordValidator::new(&registry, None).unwrap();
+        for name in ["entity-record", "relationship-record@1.0.0", "exchange"] {
+            let selector: SchemaSelector = name.parse().unwrap();
+            let path = dir.path().join(format!("{}.json", selector.kind));
+            let scaffold = registry.scaffold(&selector.schema_id()).unwrap();
+            fs::write(&path, scaffold.to_string()).unwrap();
+            assert_eq!(validator.validate_file(&path), [], "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_business_rules_require_authority() {
+        let mut restricted = grant();
+        restricted["classification"] = "restricted".into();
+        let record = AnyRecord::from_json(&restricted.to_string()).unwrap();
+        assert_eq!(business_rules(&record).len(), 1);
+        assert!(business_rules(&AnyRecord::from_json(&grant().to_string()).unwrap()).is_empty());
+    }
+}
@@ -31,13 +31,13 @@ fn main() {
     .unwrap();
 
     // schemas -> SCHEMAS (namespace-aware keys, e.g. \"cep.entity\")
-    generate_schemas_group(&repo_root, &out_dir, &mut out);
+    generate_schemas_group(repo_root, &out_dir, &mut out);
 
     // vocabularies -> VOCABULARIES (relative path without .json)
-    generate_simple_group(&repo_root, &out_dir, &mut out, "vocabularies", "VOCABULARIES");
+    generate_simple_group(repo_root, &out_dir, &mut out, "vocabularies", "VOCABULARIES");
 
     // test_vectors -> TEST_VECTORS (relative path without .json)
-    generate_simple_group(&repo_root, &out_dir, &mut out, "test_vectors", "TEST_VECTORS");
+    generate_simple_group(repo_root, &out_dir, &mut out, "test_vectors", "TEST_VECTORS");
 }
 
 /// Generate SCHEMAS with namespace-aware keys.
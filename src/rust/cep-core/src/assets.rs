@@ -27,6 +27,7 @@ pub use generated::{SCHEMAS, VOCABULARIES, TEST_VECTORS};
 /// - \"cep.exchange\"
 /// - \"cep.entity.identifier-scheme\"
 /// - \"cep.vocabulary\"
+/// - \"cep.snfei.canonical-input\"
 ///
 /// For any other schemas under `schemas/`, the key is the relative path
 /// without `.json`. Examples:
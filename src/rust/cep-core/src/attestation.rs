@@ -1,20 +1,51 @@
-/// Attestation and cryptographic proof types for CEP records.
-///
-/// Every CEP record includes an attestation block that proves:
-/// - Who attested to the record (attestorId)
-/// - When it was attested (attestationTimestamp)
-/// - Cryptographic proof of integrity (proofType, proofValue, verificationMethodUri)
+//! Attestation and cryptographic proof types for CEP records.
+//!
+//! Every CEP record includes an attestation block that proves:
+//! - Who attested to the record (attestorId)
+//! - When it was attested (attestationTimestamp)
+//! - Cryptographic proof of integrity (proofType, proofValue, verificationMethodUri)
+//!
+//! The proof algorithm is a [`ProofType`]. Unknown proof types round-trip as
+//! [`ProofType::Other`] so records from newer peers still parse; only
+//! [`ProofType::Ed25519Signature2020`] and [`ProofType::JsonWebSignature2020`]
+//! proofs can be created and checked by this crate.
+//!
+//! A proof covers the record's [`signing_input`] together with the
+//! attestation's own `attestationTimestamp`, `attestorId`, `proofType` and
+//! `verificationMethodUri` (see [`Attestation::bound_input`]), so none of
+//! them can be changed without invalidating it. Proofs issued before the
+//! metadata was signed cover the record alone; they still verify, but their
+//! metadata is not authenticated.
+//!
+//! A record co-signed by several parties carries an [`AttestationSet`]. A
+//! party vouching for someone else's attestation adds an [`Endorsement`] to
+//! an [`EndorsementChain`].
 
 use crate::canonical::{insert_if_present, insert_required, Canonicalize};
+use crate::clock::Clock;
+use crate::error::{CepError, CepResult};
+use crate::hash::CanonicalHash;
+use crate::keys::signing_input;
+use crate::policy::SigningPolicy;
+use crate::resolver::VerificationMethodResolver;
+use crate::signer::{Signer, Verifier};
+use crate::spec::{CanonicalSpec, FieldEncoding, TypeSpec};
 use crate::timestamp::CanonicalTimestamp;
+use crate::verification::{verify_attestation, verify_endorsement, VerificationReport};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::fmt;
+
+/// Tag opening [`Attestation::bound_input`], which no record-only signing
+/// input can start with.
+pub const BOUND_INPUT_TAG: &str = "CEP-SIG-V2:";
 
 /// The purpose of a cryptographic proof.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ProofPurpose {
     /// The proof asserts the truth of a claim.
+    #[default]
     AssertionMethod,
     /// The proof authenticates the identity of the attestor.
     Authentication,
@@ -32,9 +63,80 @@ impl ProofPurpose {
     }
 }
 
-impl Default for ProofPurpose {
-    fn default() -> Self {
-        ProofPurpose::AssertionMethod
+/// The proof algorithm of an attestation.
+///
+/// Serialized as its name, e.g. `"Ed25519Signature2020"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum ProofType {
+    /// Ed25519 signature over the record's signing hash.
+    Ed25519Signature2020,
+    /// Detached JWS over the record's signing hash (see [`crate::jws`]).
+    JsonWebSignature2020,
+    /// ECDSA over secp256k1.
+    EcdsaSecp256k1Signature2019,
+    /// ECDSA over NIST P-256.
+    EcdsaSecp256r1Signature2019,
+    /// W3C Data Integrity proof with a named cryptosuite.
+    DataIntegrityProof,
+    /// No cryptographic proof; the record was attested by hand. The builders'
+    /// default when no proof is supplied.
+    ManualAttestation,
+    /// A proof type this crate does not know.
+    Other(String),
+}
+
+impl ProofType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Ed25519Signature2020 => "Ed25519Signature2020",
+            Self::JsonWebSignature2020 => "JsonWebSignature2020",
+            Self::EcdsaSecp256k1Signature2019 => "EcdsaSecp256k1Signature2019",
+            Self::EcdsaSecp256r1Signature2019 => "EcdsaSecp256r1Signature2019",
+            Self::DataIntegrityProof => "DataIntegrityProof",
+            Self::ManualAttestation => "ManualAttestation",
+            Self::Other(name) => name,
+        }
+    }
+
+    /// Returns true if this crate can check proofs of this type.
+    pub fn is_verifiable(&self) -> bool {
+        matches!(
+            self,
+            Self::Ed25519Signature2020 | Self::JsonWebSignature2020
+        )
+    }
+}
+
+impl fmt::Display for ProofType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for ProofType {
+    fn from(name: &str) -> Self {
+        match name {
+            "Ed25519Signature2020" => Self::Ed25519Signature2020,
+            "JsonWebSignature2020" => Self::JsonWebSignature2020,
+            "EcdsaSecp256k1Signature2019" => Self::EcdsaSecp256k1Signature2019,
+            "EcdsaSecp256r1Signature2019" => Self::EcdsaSecp256r1Signature2019,
+            "DataIntegrityProof" => Self::DataIntegrityProof,
+            "ManualAttestation" => Self::ManualAttestation,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for ProofType {
+    fn from(name: String) -> Self {
+        Self::from(name.as_str())
+    }
+}
+
+impl From<ProofType> for String {
+    fn from(proof_type: ProofType) -> Self {
+        proof_type.as_str().to_string()
     }
 }
 
@@ -50,9 +152,8 @@ pub struct Attestation {
     /// When the attestation was created.
     pub attestation_timestamp: CanonicalTimestamp,
 
-    /// The proof algorithm identifier.
-    /// Examples: "Ed25519Signature2020", "EcdsaSecp256k1Signature2019", "DataIntegrityProof"
-    pub proof_type: String,
+    /// The proof algorithm.
+    pub proof_type: ProofType,
 
     /// The cryptographic signature or proof value.
     pub proof_value: String,
@@ -74,7 +175,7 @@ impl Attestation {
     pub fn new(
         attestor_id: String,
         attestation_timestamp: CanonicalTimestamp,
-        proof_type: String,
+        proof_type: ProofType,
         proof_value: String,
         verification_method_uri: String,
     ) -> Self {
@@ -89,6 +190,148 @@ impl Attestation {
         }
     }
 
+    /// Creates a new Attestation stamped with the current time of `clock`.
+    ///
+    /// Prefer this over passing `CanonicalTimestamp::now()` so callers can
+    /// substitute a fixed or deterministic clock in tests.
+    pub fn new_at(
+        clock: &dyn Clock,
+        attestor_id: String,
+        proof_type: ProofType,
+        proof_value: String,
+        verification_method_uri: String,
+    ) -> Self {
+        Self::new(
+            attestor_id,
+            clock.now(),
+            proof_type,
+            proof_value,
+            verification_method_uri,
+        )
+    }
+
+    /// Signs `record` with `signer` and returns the attestation, stamped with
+    /// the current time of `clock` and the signer's proof type.
+    ///
+    /// The record's own `attestation` field is not part of the record's
+    /// signing input, so it can be filled in with the result.
+    ///
+    /// # Errors
+    ///
+    /// Passes through the signer's errors.
+    pub fn sign<T: Canonicalize + ?Sized>(
+        record: &T,
+        signer: &dyn Signer,
+        clock: &dyn Clock,
+        attestor_id: String,
+        verification_method_uri: String,
+    ) -> CepResult<Self> {
+        Self::sign_input(
+            &signing_input(record),
+            signer,
+            clock,
+            attestor_id,
+            verification_method_uri,
+        )
+    }
+
+    /// Signs the canonical string `input` with `signer`.
+    pub(crate) fn sign_input(
+        input: &str,
+        signer: &dyn Signer,
+        clock: &dyn Clock,
+        attestor_id: String,
+        verification_method_uri: String,
+    ) -> CepResult<Self> {
+        let mut attestation = Self::new_at(
+            clock,
+            attestor_id,
+            signer.proof_type(),
+            String::new(),
+            verification_method_uri,
+        );
+        attestation.prove_input(input, signer)?;
+        Ok(attestation)
+    }
+
+    /// Fills in this attestation's proof over `record`, keeping its attestor,
+    /// timestamp and verification method. The proof type becomes the
+    /// signer's.
+    ///
+    /// # Errors
+    ///
+    /// Passes through the signer's errors.
+    pub fn prove<T: Canonicalize + ?Sized>(
+        &mut self,
+        record: &T,
+        signer: &dyn Signer,
+    ) -> CepResult<()> {
+        self.prove_input(&signing_input(record), signer)
+    }
+
+    fn prove_input(&mut self, input: &str, signer: &dyn Signer) -> CepResult<()> {
+        self.proof_type = signer.proof_type();
+        self.proof_value = signer.sign_input(&self.bound_input(input))?;
+        Ok(())
+    }
+
+    /// Returns what this attestation's proof signs for the canonical string
+    /// `input`: [`BOUND_INPUT_TAG`], the attestation's
+    /// `attestationTimestamp`, `attestorId`, `proofType` and
+    /// `verificationMethodUri` as canonical fields, a newline, then `input`.
+    pub fn bound_input(&self, input: &str) -> String {
+        let mut metadata = BTreeMap::new();
+        insert_required(
+            &mut metadata,
+            "attestationTimestamp",
+            &self.attestation_timestamp.to_canonical_string(),
+        );
+        insert_required(&mut metadata, "attestorId", &self.attestor_id);
+        insert_required(&mut metadata, "proofType", self.proof_type.as_str());
+        insert_required(&mut metadata, "verificationMethodUri", &self.verification_method_uri);
+        let metadata = metadata
+            .iter()
+            .map(|(k, v)| format!("\"{}\":\"{}\"", k, v))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{}{}\n{}", BOUND_INPUT_TAG, metadata, input)
+    }
+
+    /// Checks this attestation's proof over `record` with `verifier`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidSignature` if the proof type is not the
+    /// verifier's, or the proof does not verify.
+    pub fn verify<T: Canonicalize + ?Sized>(
+        &self,
+        record: &T,
+        verifier: &dyn Verifier,
+    ) -> CepResult<()> {
+        self.verify_input(&signing_input(record), verifier)
+    }
+
+    /// Checks this attestation's proof over the canonical string `input`.
+    ///
+    /// A proof over `input` alone, as issued before attestation metadata was
+    /// signed, is accepted too.
+    pub(crate) fn verify_input(&self, input: &str, verifier: &dyn Verifier) -> CepResult<()> {
+        if self.proof_type != verifier.proof_type() {
+            return Err(CepError::InvalidSignature(format!(
+                "proof type '{}' cannot be checked by a {} verifier",
+                self.proof_type,
+                verifier.proof_type()
+            )));
+        }
+        verifier
+            .verify_input(&self.bound_input(input), &self.proof_value)
+            .or_else(|e| {
+                verifier
+                    .verify_input(input, &self.proof_value)
+                    .map_err(|_| e)
+            })
+    }
+
     /// Sets the proof purpose.
     pub fn with_purpose(mut self, purpose: ProofPurpose) -> Self {
         self.proof_purpose = purpose;
@@ -115,7 +358,7 @@ impl Canonicalize for Attestation {
         );
         insert_required(&mut map, "attestorId", &self.attestor_id);
         insert_required(&mut map, "proofPurpose", self.proof_purpose.as_str());
-        insert_required(&mut map, "proofType", &self.proof_type);
+        insert_required(&mut map, "proofType", self.proof_type.as_str());
         insert_required(&mut map, "proofValue", &self.proof_value);
         insert_required(&mut map, "verificationMethodUri", &self.verification_method_uri);
 
@@ -123,15 +366,364 @@ impl Canonicalize for Attestation {
     }
 }
 
+impl CanonicalSpec for Attestation {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("Attestation")
+            .optional("anchorUri", FieldEncoding::String)
+            .required("attestationTimestamp", FieldEncoding::Timestamp)
+            .required("attestorId", FieldEncoding::String)
+            .required("proofPurpose", FieldEncoding::String)
+            .required("proofType", FieldEncoding::String)
+            .required("proofValue", FieldEncoding::String)
+            .required("verificationMethodUri", FieldEncoding::String)
+    }
+}
+
+/// Several attestations over the same record, e.g. a county clerk's and a
+/// state auditor's.
+///
+/// Every attestation in the set proves the same signing hash. The set holds
+/// at most one attestation per attestor and is kept sorted by `attestorId`,
+/// so it serializes and canonicalizes the same way whatever order the
+/// attestations were added in.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<Attestation>", into = "Vec<Attestation>")]
+pub struct AttestationSet {
+    attestations: Vec<Attestation>,
+}
+
+impl AttestationSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an attestation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidSignature` if its attestor has already
+    /// attested.
+    pub fn add(&mut self, attestation: Attestation) -> CepResult<()> {
+        match self
+            .attestations
+            .binary_search_by(|a| a.attestor_id.cmp(&attestation.attestor_id))
+        {
+            Ok(_) => Err(CepError::InvalidSignature(format!(
+                "{} has already attested",
+                attestation.attestor_id
+            ))),
+            Err(index) => {
+                self.attestations.insert(index, attestation);
+                Ok(())
+            }
+        }
+    }
+
+    /// Signs `record` with `signer` and adds the attestation.
+    ///
+    /// # Errors
+    ///
+    /// Passes through the signer's errors, and returns
+    /// `CepError::InvalidSignature` if the attestor has already attested.
+    pub fn sign<T: Canonicalize + ?Sized>(
+        &mut self,
+        record: &T,
+        signer: &dyn Signer,
+        clock: &dyn Clock,
+        attestor_id: String,
+        verification_method_uri: String,
+    ) -> CepResult<()> {
+        self.add(Attestation::sign(
+            record,
+            signer,
+            clock,
+            attestor_id,
+            verification_method_uri,
+        )?)
+    }
+
+    /// Returns the attestation by `attestor_id`, if any.
+    pub fn get(&self, attestor_id: &str) -> Option<&Attestation> {
+        self.attestations
+            .binary_search_by(|a| a.attestor_id.as_str().cmp(attestor_id))
+            .ok()
+            .map(|index| &self.attestations[index])
+    }
+
+    /// Attestations in `attestorId` order.
+    pub fn iter(&self) -> impl Iterator<Item = &Attestation> {
+        self.attestations.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.attestations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.attestations.is_empty()
+    }
+
+    /// Verifies every attestation over `record`, in `attestorId` order.
+    ///
+    /// See [`verify_attestation`] for the checks made.
+    pub fn verify<T: Canonicalize + ?Sized>(
+        &self,
+        record: &T,
+        expected_hash: Option<&CanonicalHash>,
+        resolver: &dyn VerificationMethodResolver,
+        clock: &dyn Clock,
+        policy: Option<&SigningPolicy>,
+    ) -> Vec<VerificationReport> {
+        self.attestations
+            .iter()
+            .map(|attestation| {
+                verify_attestation(record, attestation, expected_hash, resolver, clock, policy)
+            })
+            .collect()
+    }
+
+    /// Requires a valid attestation over `record` from each of `attestor_ids`.
+    ///
+    /// Attestations by other attestors are not checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidSignature` naming the first required
+    /// attestor that has not attested or whose attestation does not verify,
+    /// and `CepError::PolicyViolation` if `policy` refuses its proof type.
+    pub fn require<T: Canonicalize + ?Sized>(
+        &self,
+        record: &T,
+        attestor_ids: &[&str],
+        resolver: &dyn VerificationMethodResolver,
+        clock: &dyn Clock,
+        policy: Option<&SigningPolicy>,
+    ) -> CepResult<()> {
+        for attestor_id in attestor_ids {
+            let attestation = self.get(attestor_id).ok_or_else(|| {
+                CepError::InvalidSignature(format!("{} has not attested", attestor_id))
+            })?;
+            verify_attestation(record, attestation, None, resolver, clock, policy)
+                .ensure_valid()?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<Vec<Attestation>> for AttestationSet {
+    type Error = CepError;
+
+    fn try_from(attestations: Vec<Attestation>) -> CepResult<Self> {
+        let mut set = Self::new();
+        for attestation in attestations {
+            set.add(attestation)?;
+        }
+        Ok(set)
+    }
+}
+
+impl From<AttestationSet> for Vec<Attestation> {
+    fn from(set: AttestationSet) -> Self {
+        set.attestations
+    }
+}
+
+impl Canonicalize for AttestationSet {
+    /// One field per attestor, keyed by `attestorId`.
+    fn canonical_fields(&self) -> BTreeMap<String, String> {
+        self.attestations
+            .iter()
+            .map(|a| (a.attestor_id.clone(), a.to_canonical_string()))
+            .collect()
+    }
+}
+
+/// A second party's attestation over an existing attestation, e.g. a state
+/// office endorsing a county's record.
+///
+/// The endorser's proof covers the full canonical form of the endorsed
+/// attestation (or of an earlier endorsement), its own attestation included,
+/// and `endorsedHash` names that canonical form's hash so a chain can be
+/// checked link by link.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Endorsement {
+    /// Canonical hash of the endorsed attestation or endorsement.
+    pub endorsed_hash: CanonicalHash,
+
+    /// The endorser's attestation over the endorsed item.
+    pub attestation: Attestation,
+}
+
+impl Endorsement {
+    /// Endorses `endorsed` (an [`Attestation`] or an [`Endorsement`]) with
+    /// `signer`.
+    ///
+    /// # Errors
+    ///
+    /// Passes through the signer's errors.
+    pub fn sign<T: Canonicalize + ?Sized>(
+        endorsed: &T,
+        signer: &dyn Signer,
+        clock: &dyn Clock,
+        endorser_id: String,
+        verification_method_uri: String,
+    ) -> CepResult<Self> {
+        let signed = endorsed.to_canonical_string();
+        Ok(Self {
+            endorsed_hash: CanonicalHash::from_canonical_string(&signed),
+            attestation: Attestation::sign_input(
+                &signed,
+                signer,
+                clock,
+                endorser_id,
+                verification_method_uri,
+            )?,
+        })
+    }
+}
+
+impl Canonicalize for Endorsement {
+    fn canonical_fields(&self) -> BTreeMap<String, String> {
+        let mut map = BTreeMap::new();
+
+        // Fields in alphabetical order
+        insert_required(
+            &mut map,
+            "attestation",
+            &self.attestation.to_canonical_string(),
+        );
+        insert_required(&mut map, "endorsedHash", self.endorsed_hash.as_hex());
+
+        map
+    }
+}
+
+/// A record's original attestation and the endorsements stacked on it.
+///
+/// The first endorsement endorses the original attestation and each later
+/// one endorses the endorsement before it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndorsementChain {
+    pub original: Attestation,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endorsements: Vec<Endorsement>,
+}
+
+impl EndorsementChain {
+    pub fn new(original: Attestation) -> Self {
+        Self {
+            original,
+            endorsements: Vec::new(),
+        }
+    }
+
+    /// Endorses the latest link of the chain with `signer`.
+    ///
+    /// # Errors
+    ///
+    /// Passes through the signer's errors.
+    pub fn endorse(
+        &mut self,
+        signer: &dyn Signer,
+        clock: &dyn Clock,
+        endorser_id: String,
+        verification_method_uri: String,
+    ) -> CepResult<()> {
+        let endorsement = match self.endorsements.last() {
+            Some(last) => {
+                Endorsement::sign(last, signer, clock, endorser_id, verification_method_uri)
+            }
+            None => Endorsement::sign(
+                &self.original,
+                signer,
+                clock,
+                endorser_id,
+                verification_method_uri,
+            ),
+        }?;
+        self.endorsements.push(endorsement);
+        Ok(())
+    }
+
+    /// Attestor ids from the original signer to the latest endorser.
+    pub fn signers(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.original.attestor_id.as_str()).chain(
+            self.endorsements
+                .iter()
+                .map(|e| e.attestation.attestor_id.as_str()),
+        )
+    }
+
+    /// Walks the chain from the latest endorsement back to the original
+    /// signer, checking each endorsement against the link it endorses and
+    /// finally the original attestation against `record`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::PolicyViolation` if `policy` refuses the proof
+    /// type of any link for `record`'s class, `CepError::RevisionChain` if an
+    /// endorsement does not name the hash of the link before it, and
+    /// `CepError::InvalidSignature` for the first attestation that does not
+    /// verify.
+    pub fn verify<T: Canonicalize + ?Sized>(
+        &self,
+        record: &T,
+        resolver: &dyn VerificationMethodResolver,
+        clock: &dyn Clock,
+        policy: Option<&SigningPolicy>,
+    ) -> CepResult<()> {
+        if let Some(policy) = policy {
+            for endorsement in &self.endorsements {
+                policy.check_record(record, &endorsement.attestation.proof_type)?;
+            }
+        }
+        for (i, endorsement) in self.endorsements.iter().enumerate().rev() {
+            match i {
+                0 => check_endorsement(&self.original, endorsement, i, resolver, clock)?,
+                _ => check_endorsement(&self.endorsements[i - 1], endorsement, i, resolver, clock)?,
+            }
+        }
+        verify_attestation(record, &self.original, None, resolver, clock, policy).ensure_valid()
+    }
+}
+
+/// Checks the endorsement at `index` against the link it endorses.
+fn check_endorsement<T: Canonicalize>(
+    endorsed: &T,
+    endorsement: &Endorsement,
+    index: usize,
+    resolver: &dyn VerificationMethodResolver,
+    clock: &dyn Clock,
+) -> CepResult<()> {
+    let actual = CanonicalHash::from_canonical_string(&endorsed.to_canonical_string());
+    if actual != endorsement.endorsed_hash {
+        return Err(CepError::RevisionChain(format!(
+            "endorsement {} by {} names {}, but the link before it hashes to {}",
+            index + 1,
+            endorsement.attestation.attestor_id,
+            endorsement.endorsed_hash,
+            actual
+        )));
+    }
+    verify_endorsement(endorsed, endorsement, resolver, clock).ensure_valid()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::FixedClock;
+    use crate::keys::KeyFile;
+    use crate::resolver::VerificationKey;
+    use crate::signer::Ed25519Signer;
+    use ed25519_dalek::SigningKey;
 
     fn test_attestation() -> Attestation {
         Attestation::new(
             "cep-entity:sam-uei:J6H4FB3N5YK7".to_string(),
             "2025-11-28T14:30:00.000000Z".parse().unwrap(),
-            "Ed25519Signature2020".to_string(),
+            ProofType::Ed25519Signature2020,
             "z3FXQqFwbZxKBxGxqFpCD...".to_string(),
             "did:web:example.gov#key-1".to_string(),
         )
@@ -176,6 +768,247 @@ mod tests {
         assert!(fields.contains_key("anchorUri"));
     }
 
+    #[test]
+    fn test_new_at_uses_clock() {
+        let clock = FixedClock::new("2025-11-28T14:30:00.000000Z".parse().unwrap());
+        let attestation = Attestation::new_at(
+            &clock,
+            "cep-entity:sam-uei:J6H4FB3N5YK7".to_string(),
+            ProofType::Ed25519Signature2020,
+            "z3FXQqFwbZxKBxGxqFpCD...".to_string(),
+            "did:web:example.gov#key-1".to_string(),
+        );
+
+        assert_eq!(attestation, test_attestation());
+    }
+
+    #[test]
+    fn test_sign_and_verify_with_pluggable_backends() {
+        use crate::signer::{Ed25519Signer, Ed25519Verifier};
+        use ed25519_dalek::SigningKey;
+
+        let key = SigningKey::from_bytes(&[6u8; 32]);
+        let clock = FixedClock::new("2025-11-28T14:30:00.000000Z".parse().unwrap());
+        let record = test_attestation().with_anchor("https://example.gov/a".to_string());
+
+        let attestation = Attestation::sign(
+            &record,
+            &Ed25519Signer::new(key.clone()),
+            &clock,
+            "did:web:example.gov".to_string(),
+            "did:web:example.gov#key-1".to_string(),
+        )
+        .unwrap();
+        assert_eq!(attestation.proof_type, ProofType::Ed25519Signature2020);
+        assert_eq!(attestation.attestation_timestamp, clock.now());
+
+        let verifier = Ed25519Verifier::new(key.verifying_key());
+        attestation.verify(&record, &verifier).unwrap();
+        assert!(attestation.verify(&test_attestation(), &verifier).is_err());
+
+        let mut other_type = attestation.clone();
+        other_type.proof_type = ProofType::DataIntegrityProof;
+        assert!(matches!(
+            other_type.verify(&record, &verifier),
+            Err(CepError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_proof_type_names_round_trip() {
+        for name in [
+            "Ed25519Signature2020",
+            "EcdsaSecp256k1Signature2019",
+            "EcdsaSecp256r1Signature2019",
+            "DataIntegrityProof",
+            "ManualAttestation",
+            "BbsBlsSignature2020",
+        ] {
+            let json = format!("\"{}\"", name);
+            let proof_type: ProofType = serde_json::from_str(&json).unwrap();
+            assert_eq!(proof_type.as_str(), name);
+            assert_eq!(serde_json::to_string(&proof_type).unwrap(), json);
+        }
+        assert_eq!(
+            ProofType::from("BbsBlsSignature2020"),
+            ProofType::Other("BbsBlsSignature2020".to_string())
+        );
+        assert!(ProofType::Ed25519Signature2020.is_verifiable());
+        assert!(ProofType::JsonWebSignature2020.is_verifiable());
+        assert!(!ProofType::EcdsaSecp256r1Signature2019.is_verifiable());
+        assert!(!ProofType::ManualAttestation.is_verifiable());
+    }
+
+    const CLERK: &str = "did:web:clerk.county.example.gov";
+    const AUDITOR: &str = "did:web:auditor.state.example.gov";
+    const TREASURER: &str = "did:web:treasurer.state.example.gov";
+
+    /// Attestors and their keys, each published as `<attestor>#key-1`.
+    struct Keys(Vec<(&'static str, SigningKey)>);
+
+    impl Keys {
+        fn new() -> Self {
+            Self(vec![
+                (CLERK, SigningKey::from_bytes(&[1u8; 32])),
+                (AUDITOR, SigningKey::from_bytes(&[2u8; 32])),
+                (TREASURER, SigningKey::from_bytes(&[3u8; 32])),
+            ])
+        }
+
+        fn signer(&self, attestor_id: &str) -> Ed25519Signer {
+            let (_, key) = self.0.iter().find(|(id, _)| *id == attestor_id).unwrap();
+            Ed25519Signer::new(key.clone())
+        }
+    }
+
+    impl VerificationMethodResolver for Keys {
+        fn resolve(&self, attestor_id: &str) -> CepResult<Option<Vec<VerificationKey>>> {
+            Ok(self
+                .0
+                .iter()
+                .find(|(id, _)| *id == attestor_id)
+                .map(|(id, key)| {
+                    vec![KeyFile::from_signing_key(key).to_verification_key(&method(id))]
+                }))
+        }
+    }
+
+    fn method(attestor_id: &str) -> String {
+        format!("{}#key-1", attestor_id)
+    }
+
+    fn clock() -> FixedClock {
+        FixedClock::new("2025-11-28T14:30:00.000000Z".parse().unwrap())
+    }
+
+    #[test]
+    fn test_attestation_set_co_signing() {
+        let clock = clock();
+        let record = test_attestation();
+        let resolver = Keys::new();
+
+        let mut set = AttestationSet::new();
+        for id in [CLERK, AUDITOR] {
+            set.sign(
+                &record,
+                &resolver.signer(id),
+                &clock,
+                id.to_string(),
+                method(id),
+            )
+            .unwrap();
+        }
+        let ids: Vec<&str> = set.iter().map(|a| a.attestor_id.as_str()).collect();
+        assert_eq!(ids, [AUDITOR, CLERK]);
+        assert!(matches!(
+            set.add(set.get(CLERK).unwrap().clone()),
+            Err(CepError::InvalidSignature(_))
+        ));
+
+        // Order of arrival does not change the serialized or canonical form.
+        let mut reversed: Vec<Attestation> = set.clone().into();
+        reversed.reverse();
+        let json = serde_json::to_string(&reversed).unwrap();
+        let parsed: AttestationSet = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, set);
+        assert_eq!(parsed.calculate_hash(), set.calculate_hash());
+
+        let reports = set.verify(&record, None, &resolver, &clock, None);
+        assert!(reports.iter().all(|report| report.is_valid()));
+        set.require(&record, &[CLERK, AUDITOR], &resolver, &clock, None)
+            .unwrap();
+
+        assert!(matches!(
+            set.require(&record, &[CLERK, TREASURER], &resolver, &clock, None),
+            Err(CepError::InvalidSignature(_))
+        ));
+        let altered = test_attestation().with_anchor("https://example.gov/tx/1".to_string());
+        assert!(
+            set.require(&altered, &[CLERK], &resolver, &clock, None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_endorsement_chain_walks_back_to_original_signer() {
+        let clock = clock();
+        let resolver = Keys::new();
+        let record = test_attestation();
+        let original = Attestation::sign(
+            &record,
+            &resolver.signer(CLERK),
+            &clock,
+            CLERK.to_string(),
+            method(CLERK),
+        )
+        .unwrap();
+
+        let mut chain = EndorsementChain::new(original);
+        for id in [AUDITOR, TREASURER] {
+            chain
+                .endorse(&resolver.signer(id), &clock, id.to_string(), method(id))
+                .unwrap();
+        }
+        assert_eq!(
+            chain.signers().collect::<Vec<_>>(),
+            [CLERK, AUDITOR, TREASURER]
+        );
+        assert_eq!(
+            chain.endorsements[0].endorsed_hash,
+            chain.original.calculate_hash()
+        );
+        chain.verify(&record, &resolver, &clock, None).unwrap();
+
+        // A policy that refuses the chain's proof type fails it.
+        let policy = SigningPolicy::new().with_default([ProofType::JsonWebSignature2020]);
+        assert!(matches!(
+            chain.verify(&record, &resolver, &clock, Some(&policy)),
+            Err(CepError::PolicyViolation { .. })
+        ));
+
+        let json = serde_json::to_string(&chain).unwrap();
+        let parsed: EndorsementChain = serde_json::from_str(&json).unwrap();
+        parsed.verify(&record, &resolver, &clock, None).unwrap();
+
+        // Swapping in a different original breaks the first link.
+        let mut swapped = chain.clone();
+        swapped.original.anchor_uri = Some("https://example.gov/tx/1".to_string());
+        assert!(matches!(
+            swapped.verify(&record, &resolver, &clock, None),
+            Err(CepError::RevisionChain(_))
+        ));
+
+        // An endorsement whose proof does not cover the link fails.
+        let mut forged = chain.clone();
+        forged.endorsements[1].attestation.proof_value =
+            chain.endorsements[0].attestation.proof_value.clone();
+        assert!(matches!(
+            forged.verify(&record, &resolver, &clock, None),
+            Err(CepError::InvalidSignature(_))
+        ));
+
+        // Replacing an intermediate endorsement and re-pointing the next one
+        // at it fails: the next endorser signed the replaced link in full.
+        let mut relinked = chain.clone();
+        relinked.endorsements[0] = Endorsement::sign(
+            &chain.original,
+            &resolver.signer(TREASURER),
+            &clock,
+            TREASURER.to_string(),
+            method(TREASURER),
+        )
+        .unwrap();
+        relinked.endorsements[1].endorsed_hash = relinked.endorsements[0].calculate_hash();
+        assert!(matches!(
+            relinked.verify(&record, &resolver, &clock, None),
+            Err(CepError::InvalidSignature(_))
+        ));
+
+        // The endorsements hold, but the original does not cover this record.
+        let other = test_attestation().with_anchor("https://example.gov/tx/2".to_string());
+        assert!(chain.verify(&other, &resolver, &clock, None).is_err());
+    }
+
     #[test]
     fn test_hash_stability() {
         let a1 = test_attestation();
@@ -0,0 +1,219 @@
+//! Legal authority under which a record is shared.
+//!
+//! A record may cite the statute, regulation or data-sharing agreement that
+//! permits its publication. The citation is part of the canonical form, so it
+//! is covered by the record's attestation like any other field.
+//!
+//! Restricted records must cite an authority; see [`validate_authority`].
+
+use crate::canonical::{Canonicalize, insert_if_present, insert_required};
+use crate::classification::Classification;
+use crate::error::{CepError, CepResult};
+use crate::spec::{CanonicalSpec, FieldEncoding, TypeSpec};
+use crate::timestamp::CanonicalTimestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Citation of the statutory authority or agreement a record is shared under.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Authority {
+    /// URI of the statute, regulation or agreement text.
+    pub citation_uri: String,
+
+    /// Identifier of the data-sharing agreement, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agreement_id: Option<String>,
+
+    /// When the authority took effect.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective_timestamp: Option<CanonicalTimestamp>,
+
+    /// When the authority lapses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_timestamp: Option<CanonicalTimestamp>,
+}
+
+impl Authority {
+    /// Creates a citation of the given statute or agreement URI.
+    pub fn new(citation_uri: String) -> Self {
+        Self {
+            citation_uri,
+            agreement_id: None,
+            effective_timestamp: None,
+            expiration_timestamp: None,
+        }
+    }
+
+    /// Sets the data-sharing agreement identifier.
+    pub fn with_agreement(mut self, agreement_id: String) -> Self {
+        self.agreement_id = Some(agreement_id);
+        self
+    }
+
+    /// Sets the dates the authority is in force.
+    pub fn with_effective_period(
+        mut self,
+        effective: CanonicalTimestamp,
+        expiration: Option<CanonicalTimestamp>,
+    ) -> Self {
+        self.effective_timestamp = Some(effective);
+        self.expiration_timestamp = expiration;
+        self
+    }
+
+    /// Returns true if the authority is in force at `at`. Open-ended bounds
+    /// are treated as unlimited.
+    pub fn is_in_force(&self, at: &CanonicalTimestamp) -> bool {
+        self.effective_timestamp
+            .as_ref()
+            .is_none_or(|from| from <= at)
+            && self
+                .expiration_timestamp
+                .as_ref()
+                .is_none_or(|until| at < until)
+    }
+
+    /// Checks that the citation is usable.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::MissingField` if the citation URI is empty, or
+    /// `CepError::InvalidTimestamp` if the authority expires before it takes
+    /// effect.
+    pub fn validate(&self) -> CepResult<()> {
+        if self.citation_uri.trim().is_empty() {
+            return Err(CepError::MissingField("authority.citationUri".to_string()));
+        }
+        if let (Some(from), Some(until)) = (&self.effective_timestamp, &self.expiration_timestamp)
+            && until <= from
+        {
+            return Err(CepError::InvalidTimestamp(format!(
+                "authority expires ({}) before it takes effect ({})",
+                until, from
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Checks a record's authority citation against its classification.
+///
+/// Restricted records must cite an authority; any citation present must be
+/// valid.
+///
+/// # Errors
+///
+/// Returns `CepError::MissingField` if a restricted record has no authority,
+/// or the error from [`Authority::validate`].
+pub fn validate_authority(
+    classification: Classification,
+    authority: Option<&Authority>,
+) -> CepResult<()> {
+    match authority {
+        Some(authority) => authority.validate(),
+        None if classification == Classification::Restricted => Err(CepError::MissingField(
+            "authority (required for restricted records)".to_string(),
+        )),
+        None => Ok(()),
+    }
+}
+
+impl Canonicalize for Authority {
+    fn canonical_fields(&self) -> BTreeMap<String, String> {
+        let mut map = BTreeMap::new();
+
+        // Fields in alphabetical order
+        insert_if_present(&mut map, "agreementId", self.agreement_id.as_deref());
+        insert_required(&mut map, "citationUri", &self.citation_uri);
+        insert_if_present(
+            &mut map,
+            "effectiveTimestamp",
+            self.effective_timestamp
+                .as_ref()
+                .map(|t| t.to_canonical_string())
+                .as_deref(),
+        );
+        insert_if_present(
+            &mut map,
+            "expirationTimestamp",
+            self.expiration_timestamp
+                .as_ref()
+                .map(|t| t.to_canonical_string())
+                .as_deref(),
+        );
+
+        map
+    }
+}
+
+impl CanonicalSpec for Authority {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("Authority")
+            .optional("agreementId", FieldEncoding::String)
+            .required("citationUri", FieldEncoding::String)
+            .optional("effectiveTimestamp", FieldEncoding::Timestamp)
+            .optional("expirationTimestamp", FieldEncoding::Timestamp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(s: &str) -> CanonicalTimestamp {
+        s.parse().unwrap()
+    }
+
+    fn test_authority() -> Authority {
+        Authority::new("https://www.law.cornell.edu/uscode/text/31/6102".to_string())
+            .with_agreement("DSA-2025-014".to_string())
+            .with_effective_period(ts("2025-01-01T00:00:00.000000Z"), None)
+    }
+
+    #[test]
+    fn test_canonical_fields() {
+        let fields = test_authority().canonical_fields();
+        let keys: Vec<&String> = fields.keys().collect();
+        assert_eq!(
+            keys,
+            vec!["agreementId", "citationUri", "effectiveTimestamp"]
+        );
+        assert_eq!(fields["effectiveTimestamp"], "2025-01-01T00:00:00.000000Z");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(test_authority().validate().is_ok());
+        assert!(matches!(
+            Authority::new(" ".to_string()).validate(),
+            Err(CepError::MissingField(_))
+        ));
+        let inverted = Authority::new("https://example.gov/dsa".to_string()).with_effective_period(
+            ts("2025-06-01T00:00:00.000000Z"),
+            Some(ts("2025-01-01T00:00:00.000000Z")),
+        );
+        assert!(matches!(
+            inverted.validate(),
+            Err(CepError::InvalidTimestamp(_))
+        ));
+    }
+
+    #[test]
+    fn test_restricted_requires_authority() {
+        assert!(validate_authority(Classification::Public, None).is_ok());
+        assert!(validate_authority(Classification::Internal, None).is_ok());
+        assert!(matches!(
+            validate_authority(Classification::Restricted, None),
+            Err(CepError::MissingField(_))
+        ));
+        assert!(validate_authority(Classification::Restricted, Some(&test_authority())).is_ok());
+    }
+
+    #[test]
+    fn test_is_in_force() {
+        let authority = test_authority();
+        assert!(!authority.is_in_force(&ts("2024-12-31T23:59:59.000000Z")));
+        assert!(authority.is_in_force(&ts("2025-01-01T00:00:00.000000Z")));
+    }
+}
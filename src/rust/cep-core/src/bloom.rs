@@ -0,0 +1,293 @@
+//! Bloom filter summaries of record hash sets.
+//!
+//! Before a full sync, two nodes exchange a compact [`BloomFilter`] over the
+//! canonical hashes they hold. Comparing summaries is enough to:
+//!
+//! - skip the sync when both sides hold the same set ([`BloomFilter::matches`]),
+//! - estimate how many records differ ([`BloomFilter::estimate_difference`]),
+//! - find local records the peer certainly lacks ([`BloomFilter::missing_from`]).
+//!
+//! Both sides must build their filters with the same parameters; a node
+//! sizes its filter, sends it, and the peer builds a matching one with
+//! [`BloomFilter::with_params_of`].
+//!
+//! ```rust
+//! use cep_core::bloom::BloomFilter;
+//! use cep_core::CanonicalHash;
+//!
+//! let ours: Vec<_> = (0..100)
+//!     .map(|i| CanonicalHash::from_canonical_string(&i.to_string()))
+//!     .collect();
+//! let summary = BloomFilter::from_hashes(&ours, 0.01);
+//!
+//! let mut theirs = BloomFilter::with_params_of(&summary);
+//! for hash in &ours[..90] {
+//!     theirs.insert(hash);
+//! }
+//! assert!(!summary.matches(&theirs));
+//! assert_eq!(theirs.missing_from(&ours).len(), 10);
+//! ```
+
+use crate::error::{CepError, CepResult};
+use crate::hash::CanonicalHash;
+use serde::{Deserialize, Serialize};
+
+/// A Bloom filter over canonical record hashes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "BloomFilterRepr", into = "BloomFilterRepr")]
+pub struct BloomFilter {
+    bits: Vec<u8>,
+    bit_count: u64,
+    hash_count: u32,
+    item_count: u64,
+}
+
+/// Wire form of [`BloomFilter`], checked before it becomes one.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BloomFilterRepr {
+    /// Filter bits as lowercase hex, least significant bit first.
+    bits: String,
+    bit_count: u64,
+    hash_count: u32,
+    item_count: u64,
+}
+
+impl TryFrom<BloomFilterRepr> for BloomFilter {
+    type Error = CepError;
+
+    fn try_from(repr: BloomFilterRepr) -> CepResult<Self> {
+        let bits = hex::decode(&repr.bits)
+            .map_err(|e| CepError::Configuration(format!("invalid bloom filter bits: {}", e)))?;
+        if repr.bit_count == 0
+            || repr.hash_count == 0
+            || bits.len() as u64 != repr.bit_count.div_ceil(8)
+        {
+            return Err(CepError::Configuration(format!(
+                "inconsistent bloom filter: {} bytes for {} bits, {} hashes",
+                bits.len(),
+                repr.bit_count,
+                repr.hash_count
+            )));
+        }
+        Ok(Self {
+            bits,
+            bit_count: repr.bit_count,
+            hash_count: repr.hash_count,
+            item_count: repr.item_count,
+        })
+    }
+}
+
+impl From<BloomFilter> for BloomFilterRepr {
+    fn from(filter: BloomFilter) -> Self {
+        Self {
+            bits: hex::encode(&filter.bits),
+            bit_count: filter.bit_count,
+            hash_count: filter.hash_count,
+            item_count: filter.item_count,
+        }
+    }
+}
+
+impl BloomFilter {
+    /// Creates an empty filter sized for `expected_items` at the given false
+    /// positive rate.
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        let n = expected_items.max(1) as f64;
+        let p = false_positive_rate.clamp(1e-9, 0.5);
+        let ln2 = std::f64::consts::LN_2;
+        let bit_count = ((-n * p.ln() / (ln2 * ln2)).ceil() as u64).max(64);
+        let hash_count = ((bit_count as f64 / n * ln2).round() as u32).max(1);
+        Self::with_params(bit_count, hash_count)
+    }
+
+    /// Creates an empty filter with the same size and hash count as `other`,
+    /// so the two can be compared.
+    pub fn with_params_of(other: &BloomFilter) -> Self {
+        Self::with_params(other.bit_count, other.hash_count)
+    }
+
+    fn with_params(bit_count: u64, hash_count: u32) -> Self {
+        Self {
+            bits: vec![0; bit_count.div_ceil(8) as usize],
+            bit_count,
+            hash_count,
+            item_count: 0,
+        }
+    }
+
+    /// Builds a filter over `hashes`, sized for their number.
+    pub fn from_hashes(hashes: &[CanonicalHash], false_positive_rate: f64) -> Self {
+        let mut filter = Self::new(hashes.len(), false_positive_rate);
+        for hash in hashes {
+            filter.insert(hash);
+        }
+        filter
+    }
+
+    /// Adds a hash.
+    pub fn insert(&mut self, hash: &CanonicalHash) {
+        for bit in self.bit_positions(hash) {
+            self.bits[(bit / 8) as usize] |= 1 << (bit % 8);
+        }
+        self.item_count += 1;
+    }
+
+    /// Returns false if `hash` is certainly not in the set, true if it
+    /// probably is.
+    pub fn contains(&self, hash: &CanonicalHash) -> bool {
+        self.bit_positions(hash)
+            .all(|bit| self.bits[(bit / 8) as usize] & (1 << (bit % 8)) != 0)
+    }
+
+    /// Number of hashes inserted.
+    pub fn item_count(&self) -> u64 {
+        self.item_count
+    }
+
+    /// Size of the filter in bits.
+    pub fn bit_count(&self) -> u64 {
+        self.bit_count
+    }
+
+    /// Number of hash functions.
+    pub fn hash_count(&self) -> u32 {
+        self.hash_count
+    }
+
+    /// Returns true if both filters were built with the same parameters and
+    /// hold the same bits and count, i.e. the sets very likely match and the
+    /// sync can be skipped.
+    pub fn matches(&self, other: &BloomFilter) -> bool {
+        self == other
+    }
+
+    /// Estimates the number of hashes in exactly one of the two sets.
+    ///
+    /// Uses the standard cardinality estimate `-(m/k) ln(1 - X/m)` for each
+    /// filter and for their union; the symmetric difference is
+    /// `2|A ∪ B| - |A| - |B|`. The estimate degrades as the filters fill up.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::Configuration` if the filters' parameters differ.
+    pub fn estimate_difference(&self, other: &BloomFilter) -> CepResult<f64> {
+        self.check_compatible(other)?;
+        let union = Self {
+            bits: self
+                .bits
+                .iter()
+                .zip(&other.bits)
+                .map(|(a, b)| a | b)
+                .collect(),
+            ..self.clone()
+        };
+        let difference = 2.0 * union.estimated_len() - self.estimated_len() - other.estimated_len();
+        Ok(difference.max(0.0))
+    }
+
+    /// Returns the hashes in `hashes` that are certainly not in this filter:
+    /// records the filter's owner is missing.
+    pub fn missing_from<'a>(&self, hashes: &'a [CanonicalHash]) -> Vec<&'a CanonicalHash> {
+        hashes.iter().filter(|h| !self.contains(h)).collect()
+    }
+
+    /// Estimated number of distinct hashes inserted, from the bits set.
+    pub fn estimated_len(&self) -> f64 {
+        let set: u64 = self.bits.iter().map(|b| b.count_ones() as u64).sum();
+        let m = self.bit_count as f64;
+        if set as f64 >= m {
+            return f64::INFINITY;
+        }
+        -(m / self.hash_count as f64) * (1.0 - set as f64 / m).ln()
+    }
+
+    fn check_compatible(&self, other: &BloomFilter) -> CepResult<()> {
+        if self.bit_count != other.bit_count || self.hash_count != other.hash_count {
+            return Err(CepError::Configuration(format!(
+                "bloom filters differ: {} bits/{} hashes vs {} bits/{} hashes",
+                self.bit_count, self.hash_count, other.bit_count, other.hash_count
+            )));
+        }
+        Ok(())
+    }
+
+    /// Bit positions for `hash` by double hashing. Canonical hashes are
+    /// already uniform, so their bytes are used directly.
+    fn bit_positions(&self, hash: &CanonicalHash) -> impl Iterator<Item = u64> + use<> {
+        let bytes = hash.as_bytes();
+        let h1 = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let h2 = u64::from_le_bytes(bytes[8..16].try_into().unwrap()) | 1;
+        let m = self.bit_count;
+        (0..self.hash_count as u64).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hashes(range: std::ops::Range<u32>) -> Vec<CanonicalHash> {
+        range
+            .map(|i| CanonicalHash::from_canonical_string(&format!("record-{}", i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_membership_and_false_positive_rate() {
+        let filter = BloomFilter::from_hashes(&hashes(0..10_000), 0.01);
+        assert!(hashes(0..10_000).iter().all(|h| filter.contains(h)));
+        let false_positives = hashes(10_000..20_000)
+            .iter()
+            .filter(|h| filter.contains(h))
+            .count();
+        assert!(false_positives < 200, "{} false positives", false_positives);
+    }
+
+    #[test]
+    fn test_difference_estimate_and_skip() {
+        let ours = BloomFilter::from_hashes(&hashes(0..5_000), 0.01);
+        let mut theirs = BloomFilter::with_params_of(&ours);
+        for hash in &hashes(0..5_000) {
+            theirs.insert(hash);
+        }
+        assert!(ours.matches(&theirs));
+        assert!(ours.estimate_difference(&theirs).unwrap() < 1.0);
+
+        // 100 records only we have, 50 only they have.
+        let mut ours = BloomFilter::with_params_of(&ours);
+        for hash in &hashes(0..5_100) {
+            ours.insert(hash);
+        }
+        let mut theirs = BloomFilter::with_params_of(&ours);
+        for hash in &hashes(100..5_150) {
+            theirs.insert(hash);
+        }
+        assert!(!ours.matches(&theirs));
+        let estimate = ours.estimate_difference(&theirs).unwrap();
+        assert!((120.0..180.0).contains(&estimate), "estimate {}", estimate);
+
+        let ours_hashes = hashes(0..5_100);
+        let missing = theirs.missing_from(&ours_hashes);
+        assert!(missing.len() <= 100 && missing.len() >= 95);
+    }
+
+    #[test]
+    fn test_wire_form() {
+        let filter = BloomFilter::from_hashes(&hashes(0..10), 0.01);
+        let json = serde_json::to_value(&filter).unwrap();
+        assert_eq!(json["itemCount"], 10);
+        let back: BloomFilter = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(back, filter);
+
+        let mut truncated = json;
+        truncated["bits"] = serde_json::Value::String("00".to_string());
+        assert!(serde_json::from_value::<BloomFilter>(truncated).is_err());
+        assert!(
+            filter
+                .estimate_difference(&BloomFilter::new(1_000, 0.01))
+                .is_err()
+        );
+    }
+}
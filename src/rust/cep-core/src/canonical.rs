@@ -1,31 +1,116 @@
-/// Canonical serialization for CEP records.
-///
-/// This module provides the core trait and utilities for generating
-/// deterministic canonical strings from CEP records. The canonical string
-/// is the input to SHA-256 hashing for record integrity verification.
-///
-/// # Canonicalization Rules
-///
-/// 1. **Field Order**: Fields MUST be serialized in a defined, alphabetical order.
-///    Use `BTreeMap` for key-value pairs and `BTreeSet` for collections.
-///
-/// 2. **Null/Empty Omission**: Fields with null, None, or empty string values
-///    MUST be omitted entirely from the canonical string.
-///
-/// 3. **Timestamp Format**: All timestamps MUST use `YYYY-MM-DDTHH:MM:SS.ffffffZ`
-///    with exactly 6 decimal places for microseconds.
-///
-/// 4. **Numeric Format**: Monetary amounts MUST use exactly 2 decimal places.
-///    Integers MUST NOT have decimal points.
-///
-/// 5. **String Escaping**: Strings are NOT JSON-escaped in the canonical form.
-///    The canonical string is a simple key:value concatenation.
-///
-/// 6. **Encoding**: The canonical string MUST be UTF-8 encoded.
+//! Canonical serialization for CEP records.
+//!
+//! This module provides the core trait and utilities for generating
+//! deterministic canonical strings from CEP records. The canonical string
+//! is the input to SHA-256 hashing for record integrity verification.
+//!
+//! # Canonicalization Rules
+//!
+//! 1. **Field Order**: Fields MUST be serialized in a defined, alphabetical order.
+//!    Use `BTreeMap` for key-value pairs and `BTreeSet` for collections.
+//!
+//! 2. **Null/Empty Omission**: Fields with null, None, or empty string values
+//!    MUST be omitted entirely from the canonical string.
+//!
+//! 3. **Timestamp Format**: All timestamps MUST use `YYYY-MM-DDTHH:MM:SS.ffffffZ`
+//!    with exactly 6 decimal places for microseconds.
+//!
+//! 4. **Numeric Format**: Monetary amounts MUST use exactly 2 decimal places.
+//!    Integers MUST NOT have decimal points, leading zeros, or a `+` sign.
+//!    Booleans are `true` or `false`. Lists are `[` + the comma-joined
+//!    canonical strings of their elements + `]`, in the caller's order.
+//!    Use [`insert_number`], [`insert_bool`] and [`insert_list`] rather than
+//!    formatting these by hand.
+//!
+//! 5. **String Escaping**: Strings are NOT JSON-escaped in the canonical form.
+//!    The canonical string is a simple key:value concatenation.
+//!
+//! 6. **Encoding**: The canonical string MUST be UTF-8 encoded.
+//!
+//! A machine-readable version of these rules, with per-type field lists, is
+//! available from [`crate::spec`].
+//!
+//! # Hash Modes
+//!
+//! [`Canonicalize::calculate_hash`] hashes the canonical string alone
+//! ([`HashMode::Plain`]), as every hash issued so far does. Nothing in the
+//! string says which record type produced it, so in principle an entity and
+//! a relationship could share one. [`HashMode::DomainSeparated`] prepends
+//! the record's schema id and version ([`HashDomain`]) before hashing:
+//!
+//! ```text
+//!     CEP-HASH-V2:cep.entity@1.0.0\n"attestation":"...",...
+//! ```
+//!
+//! [`Canonicalize::hash_mode_of`] tells which mode an existing hash was
+//! computed under, so plain hashes stay verifiable.
+//!
+//! The default stays plain even for types that declare a domain. A record's
+//! hash is not private to this crate: the Python implementation's parity
+//! tests pin the same plain hashes, `previousRecordHash` links and receipts
+//! name them, and peers recompute them to check what they received. If only
+//! this crate switched its default, its hashes would stop matching every
+//! other implementation's. Moving to domain separation therefore needs a
+//! protocol version in which all implementations switch together. Until
+//! then, use [`Canonicalize::calculate_hash_with`] where both sides have
+//! agreed on [`HashMode::DomainSeparated`], for example through
+//! [`RecordEnvelope::seal_with`](crate::envelope::RecordEnvelope::seal_with).
+//!
+//! # JSON Export
+//!
+//! [`Canonicalize::to_ordered_json`] and [`Canonicalize::to_compact_json`]
+//! emit ordinary JSON with object keys in the same alphabetical order as the
+//! canonical string, so exported files diff cleanly. They use standard JSON
+//! escaping and are for people and tools, not for hashing.
 
+use crate::error::CepResult;
 use crate::hash::CanonicalHash;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::collections::BTreeMap;
 
+/// Tag opening the hash input in [`HashMode::DomainSeparated`].
+pub const DOMAIN_SEPARATION_TAG: &str = "CEP-HASH-V2:";
+
+/// How a canonical string becomes a hash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HashMode {
+    /// SHA-256 of the canonical string.
+    #[default]
+    Plain,
+    /// SHA-256 of the record's [`HashDomain`] prefix and the canonical string.
+    DomainSeparated,
+}
+
+/// The schema id and version that separate one record type's hashes from
+/// another's.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashDomain {
+    /// Schema id, e.g. `cep.entity`.
+    pub schema_id: String,
+    /// The record's declared schema version.
+    pub version: String,
+}
+
+impl HashDomain {
+    pub fn new(schema_id: &str, version: &str) -> Self {
+        Self {
+            schema_id: schema_id.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    /// The line prepended to the canonical string:
+    /// `CEP-HASH-V2:<schema_id>@<version>` and a newline.
+    pub fn prefix(&self) -> String {
+        format!(
+            "{}{}@{}\n",
+            DOMAIN_SEPARATION_TAG, self.schema_id, self.version
+        )
+    }
+}
+
 /// Trait for types that can be serialized to a canonical string for hashing.
 pub trait Canonicalize {
     /// Returns the ordered map of field names to their canonical string values.
@@ -47,10 +132,75 @@ pub trait Canonicalize {
             .join(",")
     }
 
-    /// Computes the SHA-256 hash of the canonical string.
+    /// Computes the SHA-256 hash of the canonical string
+    /// ([`HashMode::Plain`]), the hash every implementation agrees on; see
+    /// [Hash Modes](self#hash-modes).
     fn calculate_hash(&self) -> CanonicalHash {
         CanonicalHash::from_canonical_string(&self.to_canonical_string())
     }
+
+    /// The domain this type's hashes are separated under.
+    ///
+    /// Top-level records return their schema id and declared version. Nested
+    /// types return `None` and hash the same in every mode.
+    fn hash_domain(&self) -> Option<HashDomain> {
+        None
+    }
+
+    /// Computes the hash under `mode`.
+    fn calculate_hash_with(&self, mode: HashMode) -> CanonicalHash {
+        match (mode, self.hash_domain()) {
+            (HashMode::DomainSeparated, Some(domain)) => CanonicalHash::from_canonical_string(
+                &format!("{}{}", domain.prefix(), self.to_canonical_string()),
+            ),
+            _ => self.calculate_hash(),
+        }
+    }
+
+    /// Returns the mode under which `hash` is this record's hash, trying the
+    /// domain-separated mode first, or `None` if it matches neither.
+    fn hash_mode_of(&self, hash: &CanonicalHash) -> Option<HashMode> {
+        [HashMode::DomainSeparated, HashMode::Plain]
+            .into_iter()
+            .find(|mode| self.calculate_hash_with(*mode) == *hash)
+    }
+
+    /// Serializes to pretty-printed JSON with keys in canonical order.
+    fn to_ordered_json(&self) -> CepResult<String>
+    where
+        Self: Serialize,
+    {
+        Ok(serde_json::to_string_pretty(&ordered_value(self)?)?)
+    }
+
+    /// Serializes to single-line JSON with keys in canonical order.
+    fn to_compact_json(&self) -> CepResult<String>
+    where
+        Self: Serialize,
+    {
+        Ok(serde_json::to_string(&ordered_value(self)?)?)
+    }
+}
+
+/// Converts `value` to JSON with every object's keys sorted.
+///
+/// Sorting explicitly keeps the order stable even if `serde_json`'s
+/// `preserve_order` feature is enabled elsewhere in the build.
+fn ordered_value<T: Serialize + ?Sized>(value: &T) -> CepResult<Value> {
+    fn sort(value: Value) -> Value {
+        match value {
+            Value::Object(map) => {
+                let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+                let sorted: Map<String, Value> =
+                    entries.into_iter().map(|(k, v)| (k, sort(v))).collect();
+                Value::Object(sorted)
+            }
+            Value::Array(items) => Value::Array(items.into_iter().map(sort).collect()),
+            other => other,
+        }
+    }
+    Ok(sort(serde_json::to_value(value)?))
 }
 
 /// Helper function to format a monetary amount with exactly 2 decimal places.
@@ -65,10 +215,10 @@ pub fn format_amount(amount: f64) -> String {
 
 /// Helper function to add a field to a BTreeMap only if the value is Some and non-empty.
 pub fn insert_if_present(map: &mut BTreeMap<String, String>, key: &str, value: Option<&str>) {
-    if let Some(v) = value {
-        if !v.is_empty() {
-            map.insert(key.to_string(), v.to_string());
-        }
+    if let Some(v) = value
+        && !v.is_empty()
+    {
+        map.insert(key.to_string(), v.to_string());
     }
 }
 
@@ -77,10 +227,40 @@ pub fn insert_required(map: &mut BTreeMap<String, String>, key: &str, value: &st
     map.insert(key.to_string(), value.to_string());
 }
 
+/// Helper function to add an integer field in base 10.
+///
+/// - 7 becomes "7"
+/// - -42 becomes "-42"
+pub fn insert_number(map: &mut BTreeMap<String, String>, key: &str, value: impl Into<i128>) {
+    map.insert(key.to_string(), value.into().to_string());
+}
+
+/// Helper function to add a boolean field as "true" or "false".
+pub fn insert_bool(map: &mut BTreeMap<String, String>, key: &str, value: bool) {
+    map.insert(key.to_string(), value.to_string());
+}
+
+/// Helper function to add a list of nested values.
+///
+/// The value is `[` + the comma-joined canonical strings of the items + `]`.
+/// Items are joined in iteration order, so callers must sort them first if
+/// the list has no inherent order. Empty lists are omitted.
+pub fn insert_list<'a, T, I>(map: &mut BTreeMap<String, String>, key: &str, items: I)
+where
+    T: Canonicalize + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    let parts: Vec<String> = items.into_iter().map(|i| i.to_canonical_string()).collect();
+    if !parts.is_empty() {
+        map.insert(key.to_string(), format!("[{}]", parts.join(",")));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[derive(Serialize)]
     struct TestRecord {
         alpha: String,
         beta: Option<String>,
@@ -146,6 +326,74 @@ mod tests {
         assert_eq!(format_amount(1234567.89), "1234567.89");
     }
 
+    #[test]
+    fn test_typed_helpers() {
+        let mut map = BTreeMap::new();
+        insert_number(&mut map, "count", 7u32);
+        insert_number(&mut map, "delta", -42i64);
+        insert_bool(&mut map, "flag", false);
+        assert_eq!(map["count"], "7");
+        assert_eq!(map["delta"], "-42");
+        assert_eq!(map["flag"], "false");
+    }
+
+    #[test]
+    fn test_insert_list() {
+        let item = |alpha: &str| TestRecord {
+            alpha: alpha.to_string(),
+            beta: None,
+            gamma: "g".to_string(),
+        };
+        let mut map = BTreeMap::new();
+        insert_list(&mut map, "items", &[item("a"), item("b")]);
+        insert_list(&mut map, "empty", &Vec::<TestRecord>::new());
+        assert_eq!(
+            map["items"],
+            r#"["alpha":"a","gamma":"g","alpha":"b","gamma":"g"]"#
+        );
+        assert!(!map.contains_key("empty"));
+    }
+
+    #[test]
+    fn test_ordered_json_export() {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Exported {
+            zeta: String,
+            nested: TestRecord,
+        }
+
+        impl Canonicalize for Exported {
+            fn canonical_fields(&self) -> BTreeMap<String, String> {
+                let mut map = BTreeMap::new();
+                insert_required(&mut map, "nested", &self.nested.to_canonical_string());
+                insert_required(&mut map, "zeta", &self.zeta);
+                map
+            }
+        }
+
+        let record = Exported {
+            zeta: "say \"hi\"".to_string(),
+            nested: TestRecord {
+                gamma: "c".to_string(),
+                beta: None,
+                alpha: "a".to_string(),
+            },
+        };
+
+        assert_eq!(
+            record.to_compact_json().unwrap(),
+            r#"{"nested":{"alpha":"a","beta":null,"gamma":"c"},"zeta":"say \"hi\""}"#
+        );
+        let pretty = record.to_ordered_json().unwrap();
+        assert!(pretty.starts_with("{\n  \"nested\": {\n    \"alpha\""));
+        // The hash is still taken from the canonical string.
+        assert_ne!(
+            record.calculate_hash(),
+            CanonicalHash::from_canonical_string(&record.to_compact_json().unwrap())
+        );
+    }
+
     #[test]
     fn test_hash_determinism() {
         let record1 = TestRecord {
@@ -162,4 +410,57 @@ mod tests {
 
         assert_eq!(record1.calculate_hash(), record2.calculate_hash());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_domain_separated_hash() {
+        struct Typed(TestRecord, &'static str);
+
+        impl Canonicalize for Typed {
+            fn canonical_fields(&self) -> BTreeMap<String, String> {
+                self.0.canonical_fields()
+            }
+
+            fn hash_domain(&self) -> Option<HashDomain> {
+                Some(HashDomain::new(self.1, "1.0.0"))
+            }
+        }
+
+        let fields = || TestRecord {
+            alpha: "a".to_string(),
+            beta: None,
+            gamma: "c".to_string(),
+        };
+        let entity = Typed(fields(), "cep.entity");
+        let relationship = Typed(fields(), "cep.relationship");
+
+        // Same canonical string: only the domain tells the two apart.
+        assert_eq!(entity.calculate_hash(), relationship.calculate_hash());
+        let separated = entity.calculate_hash_with(HashMode::DomainSeparated);
+        assert_ne!(
+            separated,
+            relationship.calculate_hash_with(HashMode::DomainSeparated)
+        );
+        assert_eq!(
+            separated,
+            CanonicalHash::from_canonical_string(
+                "CEP-HASH-V2:cep.entity@1.0.0\n\"alpha\":\"a\",\"gamma\":\"c\""
+            )
+        );
+
+        assert_eq!(
+            entity.hash_mode_of(&separated),
+            Some(HashMode::DomainSeparated)
+        );
+        assert_eq!(
+            entity.hash_mode_of(&entity.calculate_hash()),
+            Some(HashMode::Plain)
+        );
+        assert_eq!(relationship.hash_mode_of(&separated), None);
+
+        // Types without a domain hash the same in both modes.
+        assert_eq!(
+            fields().calculate_hash_with(HashMode::DomainSeparated),
+            fields().calculate_hash()
+        );
+    }
+}
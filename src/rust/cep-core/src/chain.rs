@@ -0,0 +1,262 @@
+//! Revision chains.
+//!
+//! Each revision of a record links to its predecessor through
+//! `previousRecordHash`. A chain is verifiable when revision numbers run
+//! 1..=n without gaps and every link's previous hash equals the hash of the
+//! link before it.
+//!
+//! Links may be full records, [`HashStub`]s or [`Tombstone`]s. A stub keeps
+//! only what chain verification needs, so old revisions can be dropped (see
+//! [`crate::retention`]) without breaking the chain. A tombstone is an
+//! attested stub recording why a revision was deleted. The hash a stub or
+//! tombstone carries is still authenticated by the next revision, which
+//! commits to it.
+
+use crate::canonical::Canonicalize;
+use crate::error::{CepError, CepResult};
+use crate::hash::CanonicalHash;
+use crate::timestamp::CanonicalTimestamp;
+use crate::tombstone::Tombstone;
+use serde::{Deserialize, Serialize};
+
+/// A record that takes part in a revision chain.
+pub trait Revisioned: Canonicalize {
+    /// The identifier shared by every revision of the record.
+    fn verifiable_id(&self) -> &str;
+
+    /// The revision number, starting at 1.
+    fn revision_number(&self) -> u32;
+
+    /// Hash of the previous revision; `None` for the first.
+    fn previous_record_hash(&self) -> Option<&CanonicalHash>;
+
+    /// When the record was revoked, if its status ends its life (dissolved,
+    /// terminated, reversed, ...).
+    fn revoked_at(&self) -> Option<CanonicalTimestamp> {
+        None
+    }
+}
+
+/// What remains of a revision after its content has been dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashStub {
+    /// Identifier of the record.
+    pub verifiable_id: String,
+
+    /// Revision number of the dropped revision.
+    pub revision_number: u32,
+
+    /// Canonical hash of the dropped revision.
+    pub record_hash: CanonicalHash,
+
+    /// The dropped revision's `previousRecordHash`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_record_hash: Option<CanonicalHash>,
+}
+
+impl HashStub {
+    /// Creates the stub for `record`.
+    pub fn of<T: Revisioned>(record: &T) -> Self {
+        Self {
+            verifiable_id: record.verifiable_id().to_string(),
+            revision_number: record.revision_number(),
+            record_hash: record.calculate_hash(),
+            previous_record_hash: record.previous_record_hash().cloned(),
+        }
+    }
+}
+
+/// One revision in a chain: the full record, its stub, or its tombstone.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChainLink<T> {
+    Record(T),
+    Stub(HashStub),
+    Tombstone(Tombstone),
+}
+
+impl<T: Revisioned> ChainLink<T> {
+    /// Identifier of the record.
+    pub fn verifiable_id(&self) -> &str {
+        match self {
+            ChainLink::Record(record) => record.verifiable_id(),
+            ChainLink::Stub(stub) => &stub.verifiable_id,
+            ChainLink::Tombstone(tombstone) => &tombstone.verifiable_id,
+        }
+    }
+
+    /// Revision number of this link.
+    pub fn revision_number(&self) -> u32 {
+        match self {
+            ChainLink::Record(record) => record.revision_number(),
+            ChainLink::Stub(stub) => stub.revision_number,
+            ChainLink::Tombstone(tombstone) => tombstone.revision_number,
+        }
+    }
+
+    /// Hash of this revision: computed for records, stored for stubs and
+    /// tombstones.
+    pub fn record_hash(&self) -> CanonicalHash {
+        match self {
+            ChainLink::Record(record) => record.calculate_hash(),
+            ChainLink::Stub(stub) => stub.record_hash.clone(),
+            ChainLink::Tombstone(tombstone) => tombstone.record_hash.clone(),
+        }
+    }
+
+    /// The link's `previousRecordHash`.
+    pub fn previous_record_hash(&self) -> Option<&CanonicalHash> {
+        match self {
+            ChainLink::Record(record) => record.previous_record_hash(),
+            ChainLink::Stub(stub) => stub.previous_record_hash.as_ref(),
+            ChainLink::Tombstone(tombstone) => tombstone.previous_record_hash.as_ref(),
+        }
+    }
+
+    /// Replaces a full record with its stub. Stubs and tombstones are
+    /// returned unchanged.
+    pub fn into_stub(self) -> Self {
+        match self {
+            ChainLink::Record(record) => ChainLink::Stub(HashStub::of(&record)),
+            stub => stub,
+        }
+    }
+
+    /// Returns the full record, if this link still has one.
+    pub fn record(&self) -> Option<&T> {
+        match self {
+            ChainLink::Record(record) => Some(record),
+            ChainLink::Stub(_) | ChainLink::Tombstone(_) => None,
+        }
+    }
+}
+
+/// Verifies a revision chain, ordered from first revision to head.
+///
+/// # Errors
+///
+/// Returns `CepError::RevisionChain` if the chain is empty, mixes record ids,
+/// skips or repeats a revision number, or has a link whose previous hash does
+/// not match the preceding revision.
+pub fn verify_chain<T: Revisioned>(links: &[ChainLink<T>]) -> CepResult<()> {
+    let Some(first) = links.first() else {
+        return Err(CepError::RevisionChain("chain is empty".to_string()));
+    };
+    let id = first.verifiable_id();
+    let mut previous: Option<CanonicalHash> = None;
+    for (expected, link) in (1u32..).zip(links) {
+        if link.verifiable_id() != id {
+            return Err(CepError::RevisionChain(format!(
+                "revision {} belongs to {}, not {}",
+                link.revision_number(),
+                link.verifiable_id(),
+                id
+            )));
+        }
+        if link.revision_number() != expected {
+            return Err(CepError::RevisionChain(format!(
+                "{}: expected revision {}, found {}",
+                id,
+                expected,
+                link.revision_number()
+            )));
+        }
+        if link.previous_record_hash() != previous.as_ref() {
+            return Err(CepError::RevisionChain(format!(
+                "{}: revision {} does not link to revision {}",
+                id,
+                expected,
+                expected - 1
+            )));
+        }
+        previous = Some(link.record_hash());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::canonical::insert_required;
+    use std::collections::BTreeMap;
+
+    /// A minimal revisioned record for chain tests.
+    #[derive(Debug, Clone, PartialEq)]
+    pub(crate) struct Revision {
+        pub number: u32,
+        pub previous: Option<CanonicalHash>,
+        pub revoked_at: Option<CanonicalTimestamp>,
+    }
+
+    impl Canonicalize for Revision {
+        fn canonical_fields(&self) -> BTreeMap<String, String> {
+            let mut map = BTreeMap::new();
+            if let Some(ref hash) = self.previous {
+                insert_required(&mut map, "previousRecordHash", hash.as_hex());
+            }
+            insert_required(&mut map, "revisionNumber", &self.number.to_string());
+            map
+        }
+    }
+
+    impl Revisioned for Revision {
+        fn verifiable_id(&self) -> &str {
+            "cep-test:record:1"
+        }
+        fn revision_number(&self) -> u32 {
+            self.number
+        }
+        fn previous_record_hash(&self) -> Option<&CanonicalHash> {
+            self.previous.as_ref()
+        }
+        fn revoked_at(&self) -> Option<CanonicalTimestamp> {
+            self.revoked_at
+        }
+    }
+
+    /// Builds a correctly linked chain of `n` revisions.
+    pub(crate) fn chain(n: u32) -> Vec<ChainLink<Revision>> {
+        let mut links = Vec::new();
+        let mut previous = None;
+        for number in 1..=n {
+            let revision = Revision {
+                number,
+                previous: previous.take(),
+                revoked_at: None,
+            };
+            previous = Some(revision.calculate_hash());
+            links.push(ChainLink::Record(revision));
+        }
+        links
+    }
+
+    #[test]
+    fn test_verify_chain() {
+        assert!(verify_chain(&chain(4)).is_ok());
+        assert!(matches!(
+            verify_chain::<Revision>(&[]),
+            Err(CepError::RevisionChain(_))
+        ));
+
+        let mut gap = chain(4);
+        gap.remove(2);
+        assert!(matches!(
+            verify_chain(&gap),
+            Err(CepError::RevisionChain(_))
+        ));
+    }
+
+    #[test]
+    fn test_stubs_preserve_chain() {
+        let mut links = chain(4);
+        links[0] = links[0].clone().into_stub();
+        links[1] = links[1].clone().into_stub();
+        assert!(verify_chain(&links).is_ok());
+
+        // A stub with a forged hash no longer matches its successor.
+        if let ChainLink::Stub(ref mut stub) = links[1] {
+            stub.record_hash = CanonicalHash::from_canonical_string("forged");
+        }
+        assert!(verify_chain(&links).is_err());
+    }
+}
@@ -0,0 +1,259 @@
+//! Record-level access classification.
+//!
+//! Every record may carry a `classification` drawn from the
+//! `access-classification` vocabulary: `public`, `internal` or `restricted`.
+//! A record without one is public. Classifications are ordered, and each
+//! level above public needs a scope the requester must hold:
+//!
+//! | Classification | Required scope |
+//! |---|---|
+//! | `public` | none |
+//! | `internal` | `cep:read:internal` |
+//! | `restricted` | `cep:read:restricted` |
+//!
+//! Layers that hand records out, such as servers and exporters, call
+//! [`AccessScopes::filter`] with the scopes granted to the requester, for
+//! example from a trust registry. Everything the requester may not see is
+//! dropped before serialization. [`write_bundle`](crate::stream::write_bundle)
+//! takes the recipient's scopes and does this itself.
+//!
+//! ```rust
+//! use cep_core::classification::{AccessScopes, Classification};
+//!
+//! let partner = AccessScopes::new(["cep:read:internal"]);
+//! assert!(partner.permits(Classification::Internal));
+//! assert!(!partner.permits(Classification::Restricted));
+//! assert!(AccessScopes::public().permits(Classification::Public));
+//! ```
+
+use crate::error::{CepError, CepResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// URI of the access classification vocabulary.
+pub const CLASSIFICATION_VOCABULARY_URI: &str = "https://raw.githubusercontent.com/civic-interconnect/civic-exchange-protocol/main/vocabulary/access-classification.json";
+
+/// Who may receive a record, from least to most sensitive.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Classification {
+    #[default]
+    Public,
+    Internal,
+    Restricted,
+}
+
+impl Classification {
+    /// All classifications, from least to most sensitive.
+    pub const ALL: [Self; 3] = [Self::Public, Self::Internal, Self::Restricted];
+
+    /// Returns the vocabulary code, which is also the canonical form.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Internal => "internal",
+            Self::Restricted => "restricted",
+        }
+    }
+
+    /// Returns the vocabulary term URI.
+    pub fn term_uri(&self) -> String {
+        format!("{}#{}", CLASSIFICATION_VOCABULARY_URI, self.as_str())
+    }
+
+    /// Returns the scope a requester needs to receive records of this
+    /// classification, or `None` for public records.
+    pub fn required_scope(&self) -> Option<&'static str> {
+        match self {
+            Self::Public => None,
+            Self::Internal => Some("cep:read:internal"),
+            Self::Restricted => Some("cep:read:restricted"),
+        }
+    }
+}
+
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Classification {
+    type Err = CepError;
+
+    /// Accepts a vocabulary code or term URI.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = s
+            .strip_prefix(CLASSIFICATION_VOCABULARY_URI)
+            .and_then(|rest| rest.strip_prefix('#'))
+            .unwrap_or(s);
+        Self::ALL
+            .into_iter()
+            .find(|c| c.as_str() == code)
+            .ok_or_else(|| {
+                CepError::UnknownTerm(format!("'{}' is not an access classification", s))
+            })
+    }
+}
+
+/// A record that carries an access classification.
+pub trait Classified {
+    /// Returns the record's classification; unclassified records are public.
+    fn classification(&self) -> Classification;
+}
+
+/// Scopes granted to a requester.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccessScopes {
+    granted: BTreeSet<String>,
+}
+
+impl AccessScopes {
+    /// Creates a scope set from the requester's granted scopes.
+    pub fn new<I, S>(granted: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            granted: granted.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Scopes of an anonymous requester, who may only see public records.
+    pub fn public() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if records of `classification` may be released.
+    pub fn permits(&self, classification: Classification) -> bool {
+        classification
+            .required_scope()
+            .is_none_or(|scope| self.granted.contains(scope))
+    }
+
+    /// Keeps only the records the requester may receive.
+    pub fn filter<'a, T, I>(&self, records: I) -> Vec<&'a T>
+    where
+        T: Classified + 'a,
+        I: IntoIterator<Item = &'a T>,
+    {
+        records
+            .into_iter()
+            .filter(|r| self.permits(r.classification()))
+            .collect()
+    }
+
+    /// Fails if the requester may not receive `record`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::AccessDenied` naming the missing scope.
+    pub fn check<T: Classified + ?Sized>(&self, record: &T) -> CepResult<()> {
+        let classification = record.classification();
+        if self.permits(classification) {
+            Ok(())
+        } else {
+            Err(CepError::AccessDenied {
+                classification: classification.to_string(),
+                required_scope: classification
+                    .required_scope()
+                    .unwrap_or_default()
+                    .to_string(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema_registry::SchemaRegistry;
+
+    struct Doc(Option<Classification>);
+
+    impl Classified for Doc {
+        fn classification(&self) -> Classification {
+            self.0.unwrap_or_default()
+        }
+    }
+
+    #[test]
+    fn test_codes_match_vocabulary() {
+        let vocab = SchemaRegistry::new()
+            .unwrap()
+            .get_vocabulary("access-classification.v1.0.0")
+            .unwrap();
+        assert_eq!(vocab["vocabularyUri"], CLASSIFICATION_VOCABULARY_URI);
+        let terms: Vec<(&str, &str)> = vocab["terms"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| (t["code"].as_str().unwrap(), t["termUri"].as_str().unwrap()))
+            .collect();
+        let expected: Vec<(&str, String)> = Classification::ALL
+            .iter()
+            .map(|c| (c.as_str(), c.term_uri()))
+            .collect();
+        assert_eq!(terms.len(), expected.len());
+        for ((code, uri), (expected_code, expected_uri)) in terms.iter().zip(&expected) {
+            assert_eq!(code, expected_code);
+            assert_eq!(uri, expected_uri);
+        }
+    }
+
+    #[test]
+    fn test_parse_code_and_uri() {
+        assert_eq!(
+            "internal".parse::<Classification>().unwrap(),
+            Classification::Internal
+        );
+        assert_eq!(
+            Classification::Restricted
+                .term_uri()
+                .parse::<Classification>()
+                .unwrap(),
+            Classification::Restricted
+        );
+        assert!("secret".parse::<Classification>().is_err());
+        assert_eq!(
+            serde_json::to_string(&Classification::Restricted).unwrap(),
+            "\"restricted\""
+        );
+    }
+
+    #[test]
+    fn test_filter_by_scopes() {
+        let docs = [
+            Doc(None),
+            Doc(Some(Classification::Internal)),
+            Doc(Some(Classification::Restricted)),
+        ];
+        let visible = |scopes: &AccessScopes| {
+            scopes
+                .filter(&docs)
+                .iter()
+                .map(|d| d.classification())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(visible(&AccessScopes::public()), [Classification::Public]);
+        assert_eq!(
+            visible(&AccessScopes::new(["cep:read:internal"])),
+            [Classification::Public, Classification::Internal]
+        );
+        // Scopes are not hierarchical: restricted access does not imply internal.
+        assert_eq!(
+            visible(&AccessScopes::new(["cep:read:restricted"])),
+            [Classification::Public, Classification::Restricted]
+        );
+
+        assert!(matches!(
+            AccessScopes::public().check(&docs[1]),
+            Err(CepError::AccessDenied { .. })
+        ));
+    }
+}
@@ -0,0 +1,157 @@
+//! Time sources for CEP record creation.
+//!
+//! Code that stamps records (attestation creation, audit entries) takes a
+//! [`Clock`] instead of calling [`CanonicalTimestamp::now`] directly, so tests
+//! and reproducible builds can control the time that ends up in a hash.
+//!
+//! - [`SystemClock`]: wall-clock UTC time
+//! - [`FixedClock`]: always returns the same instant
+//! - [`OffsetClock`]: starts at a fixed instant and advances by a fixed step
+//!   on every call, giving distinct, strictly increasing timestamps
+
+use crate::timestamp::CanonicalTimestamp;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A source of canonical timestamps.
+pub trait Clock: Send + Sync {
+    /// Returns the current time according to this clock.
+    fn now(&self) -> CanonicalTimestamp;
+}
+
+/// Wall-clock UTC time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> CanonicalTimestamp {
+        CanonicalTimestamp::now()
+    }
+}
+
+/// A clock frozen at a single instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(CanonicalTimestamp);
+
+impl FixedClock {
+    /// Creates a clock that always returns `timestamp`.
+    pub fn new(timestamp: CanonicalTimestamp) -> Self {
+        Self(timestamp)
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> CanonicalTimestamp {
+        self.0
+    }
+}
+
+/// A deterministic, monotonic clock.
+///
+/// The first call returns `start`; each later call returns the previous value
+/// plus `step`. Safe to share across threads: every caller gets a distinct tick.
+///
+/// Once the next value would fall outside the range chrono can represent,
+/// the clock saturates at the latest (or, for a negative step, earliest)
+/// representable instant instead of panicking.
+#[derive(Debug)]
+pub struct OffsetClock {
+    start: CanonicalTimestamp,
+    step: Duration,
+    ticks: AtomicU64,
+}
+
+impl OffsetClock {
+    /// Creates a clock starting at `start` and advancing by `step` per call.
+    pub fn new(start: CanonicalTimestamp, step: Duration) -> Self {
+        Self {
+            start,
+            step,
+            ticks: AtomicU64::new(0),
+        }
+    }
+
+    /// Creates a clock advancing by one microsecond per call, the smallest
+    /// step that still yields distinct canonical timestamps.
+    pub fn starting_at(start: CanonicalTimestamp) -> Self {
+        Self::new(start, Duration::microseconds(1))
+    }
+}
+
+impl Clock for OffsetClock {
+    fn now(&self) -> CanonicalTimestamp {
+        let tick = self.ticks.fetch_add(1, Ordering::SeqCst);
+        let limit = if self.step < Duration::zero() {
+            DateTime::<Utc>::MIN_UTC
+        } else {
+            DateTime::<Utc>::MAX_UTC
+        };
+        let now = i32::try_from(tick)
+            .ok()
+            .and_then(|tick| self.step.checked_mul(tick))
+            .and_then(|offset| self.start.as_datetime().checked_add_signed(offset))
+            .unwrap_or(limit);
+        CanonicalTimestamp::new(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start() -> CanonicalTimestamp {
+        "2025-11-28T14:30:00.000000Z".parse().unwrap()
+    }
+
+    #[test]
+    fn test_fixed_clock() {
+        let clock = FixedClock::new(start());
+        assert_eq!(clock.now(), start());
+        assert_eq!(clock.now(), start());
+    }
+
+    #[test]
+    fn test_offset_clock_advances() {
+        let clock = OffsetClock::new(start(), Duration::seconds(1));
+        assert_eq!(clock.now().to_canonical_string(), "2025-11-28T14:30:00.000000Z");
+        assert_eq!(clock.now().to_canonical_string(), "2025-11-28T14:30:01.000000Z");
+        assert_eq!(clock.now().to_canonical_string(), "2025-11-28T14:30:02.000000Z");
+    }
+
+    #[test]
+    fn test_offset_clock_is_strictly_increasing() {
+        let clock = OffsetClock::starting_at(start());
+        let a = clock.now();
+        let b = clock.now();
+        assert!(a < b);
+        assert_eq!(b.to_canonical_string(), "2025-11-28T14:30:00.000001Z");
+    }
+
+    #[test]
+    fn test_offset_clock_saturates() {
+        let latest = CanonicalTimestamp::new(DateTime::<Utc>::MAX_UTC);
+
+        let clock = OffsetClock::new(start(), Duration::MAX);
+        assert_eq!(clock.now(), start());
+        assert_eq!(clock.now(), latest);
+
+        let clock = OffsetClock::starting_at(start());
+        clock.ticks.store(u64::from(u32::MAX), Ordering::SeqCst);
+        assert_eq!(clock.now(), latest);
+
+        let clock = OffsetClock::new(start(), Duration::MIN);
+        clock.now();
+        assert_eq!(
+            clock.now(),
+            CanonicalTimestamp::new(DateTime::<Utc>::MIN_UTC)
+        );
+    }
+
+    #[test]
+    fn test_system_clock_is_usable_as_trait_object() {
+        let clock: &dyn Clock = &SystemClock;
+        let a = clock.now();
+        let b = clock.now();
+        assert!(a <= b);
+    }
+}
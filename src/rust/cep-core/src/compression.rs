@@ -0,0 +1,123 @@
+//! Compression of bundles and exports.
+//!
+//! Bundles compress roughly tenfold with zstd. Compression only wraps the
+//! bytes on disk or on the wire; record hashes are always computed over the
+//! uncompressed canonical strings, so a record has the same hash whether it
+//! travelled compressed or not.
+//!
+//! Readers detect zstd by its frame magic number, so callers never need to
+//! know how a bundle was stored. Resource limits apply to the decompressed
+//! bytes, which keeps a small compressed bundle from expanding past
+//! [`ResourceLimits`](crate::ResourceLimits).
+//!
+//! zstd support is behind the default `zstd` feature. Without it, compressed
+//! input is rejected with a clear error instead of a JSON parse failure.
+
+use crate::error::{CepError, CepResult};
+use std::io::{BufRead, BufReader, Read, Write};
+
+/// The four bytes every zstd frame starts with.
+pub const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// How a bundle or export is written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    /// Plain JSON.
+    #[default]
+    None,
+    /// zstd at the given level (1-22; 3 is zstd's own default).
+    #[cfg(feature = "zstd")]
+    Zstd { level: i32 },
+}
+
+/// Returns true if `bytes` starts with the zstd frame magic number.
+pub fn is_zstd(bytes: &[u8]) -> bool {
+    bytes.starts_with(&ZSTD_MAGIC)
+}
+
+/// Wraps `reader` so that zstd-compressed input is decompressed and plain
+/// input passes through unchanged.
+///
+/// # Errors
+///
+/// Returns `CepError::Configuration` if the input is compressed and the
+/// `zstd` feature is disabled, or if reading the first bytes fails.
+pub fn decompressing_reader<'a, R: Read + 'a>(reader: R) -> CepResult<Box<dyn BufRead + 'a>> {
+    let mut reader = BufReader::new(reader);
+    let compressed = is_zstd(reader.fill_buf().map_err(io_error)?);
+    if !compressed {
+        return Ok(Box::new(reader));
+    }
+    #[cfg(feature = "zstd")]
+    {
+        let decoder = zstd::stream::read::Decoder::with_buffer(reader).map_err(io_error)?;
+        Ok(Box::new(BufReader::new(decoder)))
+    }
+    #[cfg(not(feature = "zstd"))]
+    Err(CepError::Configuration(
+        "input is zstd-compressed; enable the `zstd` feature of cep-core".to_string(),
+    ))
+}
+
+/// Writes to `writer` through `write`, compressing as requested, and returns
+/// `writer` once the output is complete.
+pub fn write_compressed<W, F>(writer: W, compression: Compression, write: F) -> CepResult<W>
+where
+    W: Write,
+    F: FnOnce(&mut dyn Write) -> CepResult<()>,
+{
+    match compression {
+        Compression::None => {
+            let mut writer = writer;
+            write(&mut writer)?;
+            writer.flush().map_err(io_error)?;
+            Ok(writer)
+        }
+        #[cfg(feature = "zstd")]
+        Compression::Zstd { level } => {
+            let mut encoder = zstd::stream::write::Encoder::new(writer, level).map_err(io_error)?;
+            write(&mut encoder)?;
+            encoder.finish().map_err(io_error)
+        }
+    }
+}
+
+fn io_error(e: std::io::Error) -> CepError {
+    CepError::Configuration(format!("compression error: {}", e))
+}
+
+#[cfg(all(test, feature = "zstd"))]
+mod tests {
+    use super::*;
+
+    fn round_trip(data: &[u8], compression: Compression) -> (Vec<u8>, Vec<u8>) {
+        let stored = write_compressed(Vec::new(), compression, |w| {
+            w.write_all(data).map_err(io_error)
+        })
+        .unwrap();
+
+        let mut restored = Vec::new();
+        decompressing_reader(&stored[..])
+            .unwrap()
+            .read_to_end(&mut restored)
+            .unwrap();
+        (stored, restored)
+    }
+
+    #[test]
+    fn test_zstd_round_trip_is_detected() {
+        let data = br#"[{"legalName":"Springfield"}]"#.repeat(100);
+        let (stored, restored) = round_trip(&data, Compression::Zstd { level: 3 });
+        assert!(is_zstd(&stored));
+        assert!(stored.len() < data.len() / 10);
+        assert_eq!(restored, data);
+    }
+
+    #[test]
+    fn test_plain_passes_through() {
+        let data = br#"[{"legalName":"Springfield"}]"#;
+        let (stored, restored) = round_trip(data, Compression::None);
+        assert_eq!(stored, data);
+        assert_eq!(restored, data);
+    }
+}
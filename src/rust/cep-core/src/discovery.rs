@@ -0,0 +1,257 @@
+//! Node discovery documents.
+//!
+//! Each node publishes a [`DiscoveryDocument`] at
+//! [`WELL_KNOWN_PATH`](crate::negotiation::WELL_KNOWN_PATH) describing itself:
+//! its DID, the protocol and schema versions it speaks, the record types it
+//! serves, its API endpoints, its public keys and where its bundles can be
+//! downloaded. A federation member needs only the node's base URL; everything
+//! else is read from the document.
+//!
+//! A discovery document is also a [`VerificationMethodResolver`] for the node
+//! it describes, so its keys can be used to verify the node's records.
+//!
+//! ```rust
+//! use cep_core::discovery::{discovery_url, BundleLocation, DiscoveryDocument};
+//!
+//! let doc = DiscoveryDocument::new("did:web:data.example.gov")
+//!     .with_record_types(["entity", "exchange"])
+//!     .with_endpoint("records", "https://data.example.gov/cep/records")
+//!     .with_bundle(BundleLocation::new(
+//!         "exchange",
+//!         "https://data.example.gov/bundles/2025.json.zst",
+//!     ));
+//!
+//! let json = doc.to_json_pretty().unwrap();
+//! assert_eq!(DiscoveryDocument::from_json(&json).unwrap(), doc);
+//! assert_eq!(
+//!     discovery_url("https://data.example.gov/"),
+//!     "https://data.example.gov/.well-known/cep"
+//! );
+//! ```
+
+use crate::error::{CepError, CepResult};
+use crate::negotiation::{SupportedVersions, WELL_KNOWN_PATH};
+use crate::resolver::{VerificationKey, VerificationMethodResolver};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Returns the discovery document URL for a node's base URL.
+pub fn discovery_url(base_url: &str) -> String {
+    format!("{}{}", base_url.trim_end_matches('/'), WELL_KNOWN_PATH)
+}
+
+/// Where a node publishes a bundle of records.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleLocation {
+    /// Record type the bundle contains (`"entity"`, `"exchange"`, ...).
+    pub record_type: String,
+
+    /// Download URI. Compression is detected from the content.
+    pub uri: String,
+}
+
+impl BundleLocation {
+    /// Creates a bundle location.
+    pub fn new(record_type: &str, uri: &str) -> Self {
+        Self {
+            record_type: record_type.to_string(),
+            uri: uri.to_string(),
+        }
+    }
+}
+
+/// The document a node serves at `/.well-known/cep`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveryDocument {
+    /// The node's DID.
+    pub node_id: String,
+
+    /// Protocol and schema versions the node supports.
+    #[serde(flatten)]
+    pub versions: SupportedVersions,
+
+    /// Record types the node serves.
+    #[serde(default)]
+    pub record_types: Vec<String>,
+
+    /// API endpoints by name (e.g. `"records"`, `"submissions"`).
+    #[serde(default)]
+    pub endpoints: BTreeMap<String, String>,
+
+    /// Public keys the node attests with.
+    #[serde(default)]
+    pub verification_methods: Vec<VerificationKey>,
+
+    /// Published record bundles.
+    #[serde(default)]
+    pub bundles: Vec<BundleLocation>,
+}
+
+impl DiscoveryDocument {
+    /// Creates a document for `node_id` advertising this implementation's
+    /// versions.
+    pub fn new(node_id: &str) -> Self {
+        Self {
+            node_id: node_id.to_string(),
+            versions: SupportedVersions::default(),
+            record_types: Vec::new(),
+            endpoints: BTreeMap::new(),
+            verification_methods: Vec::new(),
+            bundles: Vec::new(),
+        }
+    }
+
+    /// Sets the record types the node serves.
+    pub fn with_record_types<I, S>(mut self, record_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.record_types = record_types.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Adds an API endpoint.
+    pub fn with_endpoint(mut self, name: &str, url: &str) -> Self {
+        self.endpoints.insert(name.to_string(), url.to_string());
+        self
+    }
+
+    /// Adds a public key.
+    pub fn with_key(mut self, key: VerificationKey) -> Self {
+        self.verification_methods.push(key);
+        self
+    }
+
+    /// Adds a bundle location.
+    pub fn with_bundle(mut self, bundle: BundleLocation) -> Self {
+        self.bundles.push(bundle);
+        self
+    }
+
+    /// Returns true if the node serves `record_type` records.
+    pub fn serves(&self, record_type: &str) -> bool {
+        self.record_types.iter().any(|t| t == record_type)
+    }
+
+    /// Checks that the document is usable.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidIdentifier` if the node id is not a DID or a
+    /// key does not belong to the node, or `CepError::MissingField` if no
+    /// protocol or schema versions are advertised.
+    pub fn validate(&self) -> CepResult<()> {
+        if !self.node_id.starts_with("did:") {
+            return Err(CepError::InvalidIdentifier(format!(
+                "node id '{}' is not a DID",
+                self.node_id
+            )));
+        }
+        if self.versions.protocol_versions.is_empty() {
+            return Err(CepError::MissingField("protocolVersions".to_string()));
+        }
+        if self.versions.schema_versions.is_empty() {
+            return Err(CepError::MissingField("schemaVersions".to_string()));
+        }
+        if let Some(key) = self
+            .verification_methods
+            .iter()
+            .find(|k| !k.id.starts_with(&format!("{}#", self.node_id)))
+        {
+            return Err(CepError::InvalidIdentifier(format!(
+                "key '{}' does not belong to node {}",
+                key.id, self.node_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Parses and validates a discovery document.
+    pub fn from_json(json: &str) -> CepResult<Self> {
+        let doc: Self = serde_json::from_str(json)?;
+        doc.validate()?;
+        Ok(doc)
+    }
+
+    /// Serializes the document as pretty-printed JSON.
+    pub fn to_json_pretty(&self) -> CepResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl VerificationMethodResolver for DiscoveryDocument {
+    fn resolve(&self, attestor_id: &str) -> CepResult<Option<Vec<VerificationKey>>> {
+        Ok((attestor_id == self.node_id).then(|| self.verification_methods.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keys::KeyFile;
+
+    const NODE: &str = "did:web:data.lincolncounty.example.gov";
+
+    fn document() -> DiscoveryDocument {
+        let keys = KeyFile::generate_ed25519();
+        DiscoveryDocument::new(NODE)
+            .with_record_types(["entity", "relationship", "exchange"])
+            .with_endpoint(
+                "records",
+                "https://data.lincolncounty.example.gov/cep/records",
+            )
+            .with_key(keys.to_verification_key(&format!("{}#key-1", NODE)))
+            .with_bundle(BundleLocation::new(
+                "exchange",
+                "https://data.lincolncounty.example.gov/bundles/exchanges-2025.json.zst",
+            ))
+    }
+
+    #[test]
+    fn test_round_trip_and_shape() {
+        let doc = document();
+        let json: serde_json::Value = serde_json::from_str(&doc.to_json_pretty().unwrap()).unwrap();
+        assert_eq!(json["nodeId"], NODE);
+        assert!(json["protocolVersions"].is_array());
+        assert_eq!(
+            json["verificationMethods"][0]["type"],
+            "Ed25519VerificationKey2020"
+        );
+        assert_eq!(
+            DiscoveryDocument::from_json(&json.to_string()).unwrap(),
+            doc
+        );
+        assert!(doc.serves("exchange"));
+        assert!(!doc.serves("audit"));
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(matches!(
+            DiscoveryDocument::from_json(
+                r#"{"nodeId":"data.example.gov","protocolVersions":["1.0.0"],"schemaVersions":["1.0.0"]}"#
+            ),
+            Err(CepError::InvalidIdentifier(_))
+        ));
+        assert!(matches!(
+            DiscoveryDocument::from_json(
+                r#"{"nodeId":"did:web:data.example.gov","protocolVersions":[],"schemaVersions":["1.0.0"]}"#
+            ),
+            Err(CepError::MissingField(_))
+        ));
+
+        let mut foreign_key = document();
+        foreign_key.verification_methods[0].id = "did:web:other.example.gov#key-1".to_string();
+        assert!(foreign_key.validate().is_err());
+    }
+
+    #[test]
+    fn test_resolves_node_keys() {
+        let doc = document();
+        assert_eq!(doc.resolve(NODE).unwrap().unwrap().len(), 1);
+        assert_eq!(doc.resolve("did:web:other.example.gov").unwrap(), None);
+    }
+}
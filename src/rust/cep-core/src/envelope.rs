@@ -0,0 +1,197 @@
+//! Record envelopes: a record carried together with its canonical hash.
+//!
+//! A sender wraps each record in a [`RecordEnvelope`] naming the hash it
+//! computed. Deserializing an envelope does not look at the hash unless asked
+//! to: with [`EnvelopeCheck::Verify`] the hash is recomputed as soon as the
+//! record has been parsed, and a corrupted record is refused with
+//! `CepError::HashMismatch` at the edge of the pipeline instead of failing
+//! somewhere deep inside it.
+//!
+//! The embedded hash may be in either [`HashMode`]; both are accepted.
+//!
+//! Envelopes also record the version of the library that sealed them and the
+//! schema version the record declares, so a receiver can tell which
+//! implementation and schema produced a record without parsing it first.
+//!
+//! ```rust
+//! use cep_core::envelope::{EnvelopeCheck, RecordEnvelope};
+//! # use cep_core::Canonicalize;
+//! # use serde::{Deserialize, Serialize};
+//! # use std::collections::BTreeMap;
+//! # #[derive(Serialize, Deserialize)]
+//! # struct Note { n: u32 }
+//! # impl Canonicalize for Note {
+//! #     fn canonical_fields(&self) -> BTreeMap<String, String> {
+//! #         BTreeMap::from([("n".to_string(), self.n.to_string())])
+//! #     }
+//! # }
+//!
+//! let json = serde_json::to_string(&RecordEnvelope::seal(Note { n: 7 })).unwrap();
+//! let envelope: RecordEnvelope<Note> =
+//!     RecordEnvelope::from_json(&json, EnvelopeCheck::Verify).unwrap();
+//! assert_eq!(envelope.record.n, 7);
+//!
+//! let tampered = json.replace("\"n\":7", "\"n\":8");
+//! assert!(RecordEnvelope::<Note>::from_json(&tampered, EnvelopeCheck::Verify).is_err());
+//! ```
+
+use crate::canonical::{Canonicalize, HashMode};
+use crate::error::{CepError, CepResult};
+use crate::hash::CanonicalHash;
+use crate::version::{LIBRARY_VERSION, SchemaVersion};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+
+/// Whether deserializing an envelope checks the embedded hash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EnvelopeCheck {
+    /// Take the embedded hash on trust.
+    #[default]
+    Skip,
+    /// Recompute the record's hash and fail with `CepError::HashMismatch`
+    /// if it differs from the embedded one.
+    Verify,
+}
+
+/// A record and the canonical hash its sender computed for it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordEnvelope<T> {
+    /// Canonical hash of `record`, in either hash mode.
+    pub record_hash: CanonicalHash,
+
+    /// Version of the library that sealed the envelope.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub library_version: Option<String>,
+
+    /// Schema version the record declares, if its type has a schema.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<SchemaVersion>,
+
+    pub record: T,
+}
+
+impl<T: Canonicalize> RecordEnvelope<T> {
+    /// Wraps `record` with its plain canonical hash.
+    pub fn seal(record: T) -> Self {
+        Self::seal_with(record, HashMode::Plain)
+    }
+
+    /// Wraps `record` with its canonical hash in `mode`.
+    pub fn seal_with(record: T, mode: HashMode) -> Self {
+        Self {
+            record_hash: record.calculate_hash_with(mode),
+            library_version: Some(LIBRARY_VERSION.to_string()),
+            schema_version: record
+                .hash_domain()
+                .and_then(|domain| domain.version.parse().ok()),
+            record,
+        }
+    }
+
+    /// Recomputes the record's hash and returns the mode it matched in.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::HashMismatch` if the embedded hash matches the
+    /// record in neither mode.
+    pub fn check(&self) -> CepResult<HashMode> {
+        self.record
+            .hash_mode_of(&self.record_hash)
+            .ok_or_else(|| CepError::HashMismatch {
+                expected: self.record_hash.to_string(),
+                actual: self.record.calculate_hash().to_string(),
+            })
+    }
+
+    /// Unwraps the record.
+    pub fn into_record(self) -> T {
+        self.record
+    }
+}
+
+impl<T: Canonicalize + DeserializeOwned> RecordEnvelope<T> {
+    /// Parses an envelope from JSON, checking its hash if `check` asks to.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::Serialization` for malformed JSON and
+    /// `CepError::HashMismatch` if verification is requested and fails.
+    pub fn from_json(json: &str, check: EnvelopeCheck) -> CepResult<Self> {
+        Self::checked(serde_json::from_str(json)?, check)
+    }
+
+    /// Reads an envelope from `reader`, checking its hash if `check` asks to.
+    ///
+    /// # Errors
+    ///
+    /// As for [`from_json`](Self::from_json).
+    pub fn from_reader<R: Read>(reader: R, check: EnvelopeCheck) -> CepResult<Self> {
+        Self::checked(serde_json::from_reader(reader)?, check)
+    }
+
+    fn checked(envelope: Self, check: EnvelopeCheck) -> CepResult<Self> {
+        if check == EnvelopeCheck::Verify {
+            envelope.check()?;
+        }
+        Ok(envelope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canonical::HashDomain;
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Note {
+        text: String,
+    }
+
+    impl Canonicalize for Note {
+        fn canonical_fields(&self) -> BTreeMap<String, String> {
+            BTreeMap::from([("text".to_string(), self.text.clone())])
+        }
+
+        fn hash_domain(&self) -> Option<HashDomain> {
+            Some(HashDomain::new("cep.note", "1.0.0"))
+        }
+    }
+
+    fn note(text: &str) -> Note {
+        Note {
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_verify_accepts_both_hash_modes() {
+        for mode in [HashMode::Plain, HashMode::DomainSeparated] {
+            let json = serde_json::to_string(&RecordEnvelope::seal_with(note("a"), mode)).unwrap();
+            let envelope = RecordEnvelope::<Note>::from_json(&json, EnvelopeCheck::Verify).unwrap();
+            assert_eq!(envelope.check().unwrap(), mode);
+            assert_eq!(envelope.library_version.as_deref(), Some(LIBRARY_VERSION));
+            assert_eq!(envelope.schema_version, Some(SchemaVersion::new(1, 0, 0)));
+            assert_eq!(envelope.into_record(), note("a"));
+        }
+    }
+
+    #[test]
+    fn test_corrupted_record_refused_only_when_verifying() {
+        let json = serde_json::to_string(&RecordEnvelope::seal(note("a"))).unwrap();
+        let corrupted = json.replace("\"text\":\"a\"", "\"text\":\"b\"");
+
+        let trusted = RecordEnvelope::<Note>::from_json(&corrupted, EnvelopeCheck::Skip).unwrap();
+        assert_eq!(trusted.record, note("b"));
+
+        match RecordEnvelope::<Note>::from_reader(corrupted.as_bytes(), EnvelopeCheck::Verify) {
+            Err(CepError::HashMismatch { expected, actual }) => {
+                assert_eq!(expected, note("a").calculate_hash().to_string());
+                assert_eq!(actual, note("b").calculate_hash().to_string());
+            }
+            other => panic!("expected a hash mismatch, got {:?}", other),
+        }
+    }
+}
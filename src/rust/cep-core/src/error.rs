@@ -1,5 +1,26 @@
-/// Error types for CEP operations.
+//! Error types for CEP operations.
+//!
+//! Errors raised deep inside a pipeline rarely know which record they came
+//! from. [`CepResultExt`] lets callers attach that as they propagate:
+//!
+//! ```rust
+//! use cep_core::{CanonicalHash, CepError, CepResult, CepResultExt};
+//!
+//! fn lei() -> CepResult<()> {
+//!     Err(CepError::MissingField("lei".to_string()))
+//! }
+//!
+//! let hash = CanonicalHash::from_canonical_string("record");
+//! let err = lei().with_field_path("identifiers").with_record(&hash).unwrap_err();
+//! let context = err.context().unwrap();
+//! assert_eq!(context.field_path.as_deref(), Some("identifiers"));
+//! assert_eq!(context.record_hash.as_ref(), Some(&hash));
+//! assert!(matches!(err.root(), CepError::MissingField(_)));
+//! ```
 
+use crate::hash::CanonicalHash;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use thiserror::Error;
 
 /// Errors that can occur during CEP operations.
@@ -44,7 +65,291 @@ pub enum CepError {
     /// Configuration or environment error.
     #[error("Configuration error: {0}")]
     Configuration(String),
+
+    /// Record is well-formed but violates the signing policy.
+    #[error("signing policy violation: proof type '{proof_type}' not allowed for {record_class} records (allowed: {allowed:?})")]
+    PolicyViolation {
+        record_class: String,
+        proof_type: String,
+        allowed: Vec<String>,
+    },
+
+    /// Asset file or manifest failed integrity verification.
+    #[error("asset integrity check failed: {0}")]
+    AssetIntegrity(String),
+
+    /// Untrusted input exceeded a configured resource limit.
+    #[error("resource limit exceeded: {limit} is {actual}, maximum is {max}")]
+    ResourceLimit {
+        limit: String,
+        actual: usize,
+        max: usize,
+    },
+
+    /// Key material is malformed or a signature does not verify.
+    #[error("signature verification failed: {0}")]
+    InvalidSignature(String),
+
+    /// Value is not a term of its controlled vocabulary.
+    #[error("unknown vocabulary term: {0}")]
+    UnknownTerm(String),
+
+    /// Requester lacks the scope needed to receive a classified record.
+    #[error("access denied: {classification} records require scope '{required_scope}'")]
+    AccessDenied {
+        classification: String,
+        required_scope: String,
+    },
+
+    /// Two parties share no supported protocol or schema version.
+    #[error("no common {kind} version: we support {local:?}, peer supports {remote:?}")]
+    NoCommonVersion {
+        kind: String,
+        local: Vec<String>,
+        remote: Vec<String>,
+    },
+
+    /// A hardware signing token failed or needs operator action.
+    #[error("hardware token error: {0}")]
+    HardwareToken(#[from] crate::token::TokenError),
+
+    /// Another error, with the record and field it arose in.
+    #[error("{error} ({context})")]
+    WithContext {
+        context: ErrorContext,
+        error: Box<CepError>,
+    },
+}
+
+/// Broad class of an error, for deciding how to respond to it.
+///
+/// A service maps [`Input`](Self::Input) and
+/// [`Verification`](Self::Verification) to 4xx responses and
+/// [`System`](Self::System) to 5xx; a batch pipeline retries system errors
+/// and dead-letters the rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorClass {
+    /// The input is malformed, incomplete or not acceptable.
+    Input,
+    /// The input is well-formed but its hashes, signatures or chain do not
+    /// check out.
+    Verification,
+    /// Configuration, environment or I/O failed; the same input may succeed
+    /// later.
+    System,
+}
+
+impl ErrorClass {
+    /// Returns true if retrying the same input may succeed.
+    pub fn is_retryable(self) -> bool {
+        self == ErrorClass::System
+    }
+}
+
+impl CepError {
+    /// Returns a stable code naming the kind of error, e.g. `missing_field`.
+    ///
+    /// Errors with context report the code of the error they wrap.
+    pub fn code(&self) -> &'static str {
+        match self {
+            CepError::InvalidTimestamp(_) => "invalid_timestamp",
+            CepError::InvalidHash(_) => "invalid_hash",
+            CepError::InvalidIdentifier(_) => "invalid_identifier",
+            CepError::MissingField(_) => "missing_field",
+            CepError::UnsupportedVersion(_) => "unsupported_version",
+            CepError::HashMismatch { .. } => "hash_mismatch",
+            CepError::Serialization(_) => "serialization",
+            CepError::RevisionChain(_) => "revision_chain",
+            CepError::UnknownSchema(_) => "unknown_schema",
+            CepError::Configuration(_) => "configuration",
+            CepError::PolicyViolation { .. } => "policy_violation",
+            CepError::AssetIntegrity(_) => "asset_integrity",
+            CepError::ResourceLimit { .. } => "resource_limit",
+            CepError::InvalidSignature(_) => "invalid_signature",
+            CepError::UnknownTerm(_) => "unknown_term",
+            CepError::AccessDenied { .. } => "access_denied",
+            CepError::NoCommonVersion { .. } => "no_common_version",
+            CepError::HardwareToken(_) => "hardware_token",
+            CepError::WithContext { error, .. } => error.code(),
+        }
+    }
+
+    /// Returns the broad class of this error.
+    pub fn class(&self) -> ErrorClass {
+        match self {
+            CepError::InvalidTimestamp(_)
+            | CepError::InvalidHash(_)
+            | CepError::InvalidIdentifier(_)
+            | CepError::MissingField(_)
+            | CepError::UnsupportedVersion(_)
+            | CepError::UnknownSchema(_)
+            | CepError::ResourceLimit { .. }
+            | CepError::UnknownTerm(_)
+            | CepError::AccessDenied { .. }
+            | CepError::NoCommonVersion { .. } => ErrorClass::Input,
+            CepError::Serialization(e) if e.is_io() => ErrorClass::System,
+            CepError::Serialization(_) => ErrorClass::Input,
+            CepError::HashMismatch { .. }
+            | CepError::RevisionChain(_)
+            | CepError::PolicyViolation { .. }
+            | CepError::InvalidSignature(_) => ErrorClass::Verification,
+            CepError::Configuration(_) | CepError::AssetIntegrity(_) => ErrorClass::System,
+            CepError::HardwareToken(_) => ErrorClass::System,
+            CepError::WithContext { error, .. } => error.class(),
+        }
+    }
+
+    /// Returns the context attached to this error, if any.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            CepError::WithContext { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Returns the error without its context.
+    pub fn root(&self) -> &CepError {
+        match self {
+            CepError::WithContext { error, .. } => error,
+            other => other,
+        }
+    }
+
+    /// Attaches the hash of the record this error arose in. A hash already
+    /// attached is kept.
+    pub fn with_record(self, hash: &CanonicalHash) -> Self {
+        self.with_context(|context| {
+            context.record_hash.get_or_insert_with(|| hash.clone());
+        })
+    }
+
+    /// Attaches the path of the field this error arose in.
+    ///
+    /// Paths nest: a path already attached is appended to `path`, so
+    /// `"lei"` and then `"identifiers"` give `"identifiers.lei"`.
+    pub fn with_field_path(self, path: &str) -> Self {
+        self.with_context(|context| {
+            context.field_path = Some(match context.field_path.take() {
+                Some(inner) => format!("{}.{}", path, inner),
+                None => path.to_string(),
+            });
+        })
+    }
+
+    fn with_context(self, update: impl FnOnce(&mut ErrorContext)) -> Self {
+        let (mut context, error) = match self {
+            CepError::WithContext { context, error } => (context, error),
+            other => (ErrorContext::default(), Box::new(other)),
+        };
+        update(&mut context);
+        CepError::WithContext { context, error }
+    }
+}
+
+/// Where an error arose: the record and the field within it.
+///
+/// Serializable so the context survives being reported as JSON.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorContext {
+    /// Canonical hash of the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub record_hash: Option<CanonicalHash>,
+
+    /// Dotted path of the field, e.g. `identifiers.lei`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_path: Option<String>,
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(path) = &self.field_path {
+            parts.push(format!("field {}", path));
+        }
+        if let Some(hash) = &self.record_hash {
+            parts.push(format!("record {}", hash));
+        }
+        f.write_str(&parts.join(", "))
+    }
 }
 
 /// Result type for CEP operations.
-pub type CepResult<T> = Result<T, CepError>;
\ No newline at end of file
+pub type CepResult<T> = Result<T, CepError>;
+
+/// Context helpers for [`CepResult`].
+pub trait CepResultExt<T> {
+    /// See [`CepError::with_record`].
+    fn with_record(self, hash: &CanonicalHash) -> CepResult<T>;
+
+    /// See [`CepError::with_field_path`].
+    fn with_field_path(self, path: &str) -> CepResult<T>;
+}
+
+impl<T> CepResultExt<T> for CepResult<T> {
+    fn with_record(self, hash: &CanonicalHash) -> CepResult<T> {
+        self.map_err(|e| e.with_record(hash))
+    }
+
+    fn with_field_path(self, path: &str) -> CepResult<T> {
+        self.map_err(|e| e.with_field_path(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_nests_and_serializes() {
+        let first = CanonicalHash::from_canonical_string("first");
+        let second = CanonicalHash::from_canonical_string("second");
+        let err = Err::<(), _>(CepError::MissingField("lei".to_string()))
+            .with_field_path("lei")
+            .with_record(&first)
+            .with_field_path("identifiers")
+            .with_record(&second)
+            .unwrap_err();
+
+        let context = err.context().unwrap();
+        assert_eq!(context.field_path.as_deref(), Some("identifiers.lei"));
+        assert_eq!(context.record_hash, Some(first.clone()));
+        assert!(matches!(err.root(), CepError::MissingField(_)));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "missing required field: lei (field identifiers.lei, record {})",
+                first
+            )
+        );
+
+        let json = serde_json::to_string(context).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ErrorContext>(&json).unwrap(),
+            *context
+        );
+        assert_eq!(
+            serde_json::to_string(&ErrorContext::default()).unwrap(),
+            "{}"
+        );
+    }
+
+    #[test]
+    fn test_error_classes() {
+        let hash = CanonicalHash::from_canonical_string("record");
+        let missing = CepError::MissingField("lei".to_string()).with_record(&hash);
+        assert_eq!(missing.class(), ErrorClass::Input);
+        assert!(!missing.class().is_retryable());
+
+        let forged = CepError::InvalidSignature("does not verify".to_string());
+        assert_eq!(forged.class(), ErrorClass::Verification);
+
+        let config = CepError::Configuration("no schemas directory".to_string());
+        assert_eq!(config.class(), ErrorClass::System);
+        assert!(config.class().is_retryable());
+
+        let malformed = serde_json::from_str::<ErrorContext>("{").unwrap_err();
+        assert_eq!(CepError::from(malformed).class(), ErrorClass::Input);
+    }
+}
\ No newline at end of file
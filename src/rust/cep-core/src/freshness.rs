@@ -0,0 +1,364 @@
+//! Record freshness.
+//!
+//! Some records stop being trustworthy as a statement of the present long
+//! before they are revoked: a roster attested two years ago says little about
+//! who is on the board today. Two mechanisms flag such records:
+//!
+//! - The publisher may set a [`Freshness`] block with `refreshBy` (when a
+//!   newer revision should have been published) and `expiresAt` (after which
+//!   the record should not be relied on at all).
+//! - The consumer may set a [`FreshnessPolicy`] with a maximum age per record
+//!   class, measured from the attestation timestamp. The timestamp is covered
+//!   by the attestation's proof (see [`Attestation::bound_input`]), so a
+//!   re-dated record fails verification; check freshness only on records
+//!   that verified.
+//!
+//! Staleness is reported as [`FreshnessWarning`]s rather than errors: a stale
+//! record is still authentic, and whether to use it is the consumer's call.
+
+use crate::attestation::Attestation;
+use crate::canonical::{Canonicalize, insert_if_present};
+use crate::clock::Clock;
+use crate::error::{CepError, CepResult};
+use crate::spec::{CanonicalSpec, FieldEncoding, TypeSpec};
+use crate::timestamp::CanonicalTimestamp;
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Publisher-declared freshness of a record.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Freshness {
+    /// When a newer revision should have been published.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_by: Option<CanonicalTimestamp>,
+
+    /// When the record stops being valid.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<CanonicalTimestamp>,
+}
+
+impl Freshness {
+    /// Creates an empty freshness block.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the time by which a newer revision is due.
+    pub fn with_refresh_by(mut self, refresh_by: CanonicalTimestamp) -> Self {
+        self.refresh_by = Some(refresh_by);
+        self
+    }
+
+    /// Sets the time the record expires.
+    pub fn with_expires_at(mut self, expires_at: CanonicalTimestamp) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Checks that the dates are consistent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidTimestamp` if the record expires before its
+    /// refresh is due.
+    pub fn validate(&self) -> CepResult<()> {
+        if let (Some(refresh_by), Some(expires_at)) = (&self.refresh_by, &self.expires_at)
+            && expires_at < refresh_by
+        {
+            return Err(CepError::InvalidTimestamp(format!(
+                "record expires ({}) before its refresh is due ({})",
+                expires_at, refresh_by
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Canonicalize for Freshness {
+    fn canonical_fields(&self) -> BTreeMap<String, String> {
+        let mut map = BTreeMap::new();
+
+        // Fields in alphabetical order
+        insert_if_present(
+            &mut map,
+            "expiresAt",
+            self.expires_at
+                .as_ref()
+                .map(|t| t.to_canonical_string())
+                .as_deref(),
+        );
+        insert_if_present(
+            &mut map,
+            "refreshBy",
+            self.refresh_by
+                .as_ref()
+                .map(|t| t.to_canonical_string())
+                .as_deref(),
+        );
+
+        map
+    }
+}
+
+impl CanonicalSpec for Freshness {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("Freshness")
+            .optional("expiresAt", FieldEncoding::Timestamp)
+            .optional("refreshBy", FieldEncoding::Timestamp)
+    }
+}
+
+/// Why a record is not fresh.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum FreshnessWarning {
+    /// The record's `expiresAt` has passed.
+    #[serde(rename_all = "camelCase")]
+    Expired { expires_at: CanonicalTimestamp },
+
+    /// The record's `refreshBy` has passed without a newer revision.
+    #[serde(rename_all = "camelCase")]
+    RefreshOverdue { refresh_by: CanonicalTimestamp },
+
+    /// The record is older than the policy allows for its class.
+    #[serde(rename_all = "camelCase")]
+    TooOld {
+        attested_at: CanonicalTimestamp,
+        max_age_days: i64,
+    },
+}
+
+impl fmt::Display for FreshnessWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Expired { expires_at } => write!(f, "record expired at {}", expires_at),
+            Self::RefreshOverdue { refresh_by } => {
+                write!(f, "record was due for refresh by {}", refresh_by)
+            }
+            Self::TooOld {
+                attested_at,
+                max_age_days,
+            } => write!(
+                f,
+                "record was attested at {}, more than {} days ago",
+                attested_at, max_age_days
+            ),
+        }
+    }
+}
+
+/// Per-record-class maximum ages.
+///
+/// Record classes are the same names [`SigningPolicy`](crate::SigningPolicy)
+/// uses (`"entity"`, `"relationship"`, `"exchange"`, ...).
+///
+/// # Example
+///
+/// ```rust
+/// use cep_core::freshness::FreshnessPolicy;
+/// use chrono::Duration;
+///
+/// let policy = FreshnessPolicy::new().max_age("entity", Duration::days(365));
+///
+/// assert_eq!(policy.max_age_for("entity"), Some(Duration::days(365)));
+/// assert_eq!(policy.max_age_for("exchange"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FreshnessPolicy {
+    rules: BTreeMap<String, Duration>,
+    default: Option<Duration>,
+}
+
+impl FreshnessPolicy {
+    /// Creates a policy with no maximum ages; only publisher-declared
+    /// freshness is checked.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum age of `record_class` records.
+    pub fn max_age(mut self, record_class: &str, age: Duration) -> Self {
+        self.rules.insert(record_class.to_string(), age);
+        self
+    }
+
+    /// Sets the maximum age for record classes without an explicit rule.
+    pub fn with_default(mut self, age: Duration) -> Self {
+        self.default = Some(age);
+        self
+    }
+
+    /// Returns the maximum age that applies to `record_class`, if any.
+    pub fn max_age_for(&self, record_class: &str) -> Option<Duration> {
+        self.rules
+            .get(record_class)
+            .or(self.default.as_ref())
+            .copied()
+    }
+
+    /// Checks a record's freshness at the current time of `clock`.
+    ///
+    /// Call this when consuming a record, alongside signature verification.
+    /// Returns every warning that applies; an empty list means fresh.
+    pub fn check(
+        &self,
+        record_class: &str,
+        attestation: &Attestation,
+        freshness: Option<&Freshness>,
+        clock: &dyn Clock,
+    ) -> Vec<FreshnessWarning> {
+        let now = clock.now();
+        let mut warnings = Vec::new();
+        if let Some(freshness) = freshness {
+            if let Some(expires_at) = freshness.expires_at
+                && expires_at <= now
+            {
+                warnings.push(FreshnessWarning::Expired { expires_at });
+            }
+            if let Some(refresh_by) = freshness.refresh_by
+                && refresh_by <= now
+            {
+                warnings.push(FreshnessWarning::RefreshOverdue { refresh_by });
+            }
+        }
+        if let Some(max_age) = self.max_age_for(record_class) {
+            let attested_at = attestation.attestation_timestamp;
+            if attested_at.as_datetime() + max_age < now.as_datetime() {
+                warnings.push(FreshnessWarning::TooOld {
+                    attested_at,
+                    max_age_days: max_age.num_days(),
+                });
+            }
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::ProofType;
+    use crate::clock::FixedClock;
+    use crate::keys::signing_input;
+    use crate::signer::{Ed25519Signer, Ed25519Verifier, Signer};
+    use ed25519_dalek::SigningKey;
+
+    fn ts(s: &str) -> CanonicalTimestamp {
+        s.parse().unwrap()
+    }
+
+    fn attestation() -> Attestation {
+        Attestation::new(
+            "did:web:example.gov".to_string(),
+            ts("2025-01-15T00:00:00.000000Z"),
+            ProofType::Ed25519Signature2020,
+            "z3FXQqFwbZxKBxGxqFpCD...".to_string(),
+            "did:web:example.gov#key-1".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_declared_freshness() {
+        let freshness = Freshness::new()
+            .with_refresh_by(ts("2026-01-15T00:00:00.000000Z"))
+            .with_expires_at(ts("2027-01-15T00:00:00.000000Z"));
+        let policy = FreshnessPolicy::new();
+
+        let fresh = FixedClock::new(ts("2025-06-01T00:00:00.000000Z"));
+        assert!(
+            policy
+                .check("entity", &attestation(), Some(&freshness), &fresh)
+                .is_empty()
+        );
+
+        let overdue = FixedClock::new(ts("2026-06-01T00:00:00.000000Z"));
+        assert_eq!(
+            policy.check("entity", &attestation(), Some(&freshness), &overdue),
+            vec![FreshnessWarning::RefreshOverdue {
+                refresh_by: ts("2026-01-15T00:00:00.000000Z")
+            }]
+        );
+
+        let expired = FixedClock::new(ts("2027-06-01T00:00:00.000000Z"));
+        assert_eq!(
+            policy
+                .check("entity", &attestation(), Some(&freshness), &expired)
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_max_age_per_class() {
+        let policy = FreshnessPolicy::new()
+            .max_age("entity", Duration::days(365))
+            .with_default(Duration::days(3650));
+        let clock = FixedClock::new(ts("2026-06-01T00:00:00.000000Z"));
+
+        let warnings = policy.check("entity", &attestation(), None, &clock);
+        assert_eq!(
+            warnings,
+            vec![FreshnessWarning::TooOld {
+                attested_at: ts("2025-01-15T00:00:00.000000Z"),
+                max_age_days: 365
+            }]
+        );
+        assert!(warnings[0].to_string().contains("more than 365 days ago"));
+        assert!(
+            policy
+                .check("exchange", &attestation(), None, &clock)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_redated_record_fails_verification() {
+        let key = SigningKey::from_bytes(&[6u8; 32]);
+        let signer = Ed25519Signer::new(key.clone());
+        let verifier = Ed25519Verifier::new(key.verifying_key());
+        let record = Freshness::new().with_expires_at(ts("2030-01-01T00:00:00.000000Z"));
+        let mut attestation = Attestation::sign(
+            &record,
+            &signer,
+            &FixedClock::new(ts("2025-01-15T00:00:00.000000Z")),
+            "did:web:example.gov".to_string(),
+            "did:web:example.gov#key-1".to_string(),
+        )
+        .unwrap();
+        attestation.verify(&record, &verifier).unwrap();
+
+        // Moving the timestamp forward would hide the record's age.
+        let policy = FreshnessPolicy::new().with_default(Duration::days(365));
+        let clock = FixedClock::new(ts("2026-06-01T00:00:00.000000Z"));
+        assert_eq!(policy.check("entity", &attestation, None, &clock).len(), 1);
+        attestation.attestation_timestamp = ts("2026-05-01T00:00:00.000000Z");
+        assert!(
+            policy
+                .check("entity", &attestation, None, &clock)
+                .is_empty()
+        );
+        assert!(matches!(
+            attestation.verify(&record, &verifier),
+            Err(CepError::InvalidSignature(_))
+        ));
+
+        // Proofs over the record alone predate signed metadata and still verify.
+        attestation.proof_value = signer.sign_input(&signing_input(&record)).unwrap();
+        attestation.verify(&record, &verifier).unwrap();
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Freshness::new().validate().is_ok());
+        let inverted = Freshness::new()
+            .with_refresh_by(ts("2026-01-15T00:00:00.000000Z"))
+            .with_expires_at(ts("2025-01-15T00:00:00.000000Z"));
+        assert!(matches!(
+            inverted.validate(),
+            Err(CepError::InvalidTimestamp(_))
+        ));
+    }
+}
@@ -1,12 +1,14 @@
-/// Cryptographic hashing utilities for CEP records.
-///
-/// All CEP hashes are SHA-256, represented as lowercase hexadecimal strings.
+//! Cryptographic hashing utilities for CEP records.
+//!
+//! All CEP hashes are SHA-256, represented as lowercase hexadecimal strings.
 
+use crate::error::CepError;
 use sha2::{Digest, Sha256};
 use std::fmt;
+use std::str::FromStr;
 
 /// A SHA-256 hash value represented as a 64-character lowercase hex string.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct CanonicalHash(String);
 
 impl CanonicalHash {
@@ -51,6 +53,22 @@ impl fmt::Display for CanonicalHash {
     }
 }
 
+impl FromStr for CanonicalHash {
+    type Err = CepError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s).ok_or_else(|| CepError::InvalidHash(format!("'{}'", s)))
+    }
+}
+
+impl TryFrom<&str> for CanonicalHash {
+    type Error = CepError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
 impl serde::Serialize for CanonicalHash {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -66,9 +84,7 @@ impl<'de> serde::Deserialize<'de> for CanonicalHash {
         D: serde::Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        Self::from_hex(&s).ok_or_else(|| {
-            serde::de::Error::custom("invalid hash: must be 64 lowercase hex characters")
-        })
+        s.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -121,4 +137,20 @@ mod tests {
             "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_and_deserialize_validate() {
+        let hex = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        let hash: CanonicalHash = hex.parse().unwrap();
+        assert_eq!(CanonicalHash::try_from(hex).unwrap(), hash);
+        assert!(matches!(
+            "abc".parse::<CanonicalHash>(),
+            Err(CepError::InvalidHash(_))
+        ));
+
+        let json = serde_json::to_string(&hash).unwrap();
+        assert_eq!(serde_json::from_str::<CanonicalHash>(&json).unwrap(), hash);
+        let err = serde_json::from_str::<CanonicalHash>("\"abc\"").unwrap_err();
+        assert!(err.to_string().contains("invalid hash"));
+    }
+}
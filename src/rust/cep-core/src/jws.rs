@@ -0,0 +1,255 @@
+//! Detached JWS proofs (`JsonWebSignature2020`).
+//!
+//! Some partners only accept proofs in JSON Web Signature form. With
+//! [`JwsSigner`] the `proofValue` is a detached, unencoded-payload JWS
+//! (RFC 7515 appendix F, RFC 7797): `<header>..<signature>`, where the
+//! header is `{"alg":"EdDSA","b64":false,"crit":["b64"]}` and the payload
+//! left out of the middle is the record's canonical signing input (see
+//! [`signing_input`](crate::keys::signing_input)). The JWS signing input is
+//! therefore `BASE64URL(header) || "." || canonical string`, and any JWS
+//! library can check the proof given the record's canonical form.
+//! [`JwsVerifier`] checks such proofs; both use the same Ed25519 keys as
+//! [`Ed25519Signature2020`](crate::ProofType::Ed25519Signature2020).
+//!
+//! A JWS proof needs the canonical string rather than its hash, so only
+//! [`Signer::sign_input`] and [`Verifier::verify_input`] work with these
+//! backends; the hash-based methods, and so batch signing, return an error.
+//!
+//! ```rust
+//! use cep_core::jws::{JwsSigner, JwsVerifier};
+//! use cep_core::{Attestation, FixedClock, KeyFile, ProofType};
+//! # use cep_core::Canonicalize;
+//! # use std::collections::BTreeMap;
+//! # struct Note(u32);
+//! # impl Canonicalize for Note {
+//! #     fn canonical_fields(&self) -> BTreeMap<String, String> {
+//! #         BTreeMap::from([("n".to_string(), self.0.to_string())])
+//! #     }
+//! # }
+//!
+//! let keys = KeyFile::generate_ed25519();
+//! let clock = FixedClock::new("2025-11-28T14:30:00.000000Z".parse().unwrap());
+//! let attestation = Attestation::sign(
+//!     &Note(7),
+//!     &JwsSigner::new(keys.signing_key().unwrap()),
+//!     &clock,
+//!     "did:web:example.gov".to_string(),
+//!     "did:web:example.gov#key-1".to_string(),
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(attestation.proof_type, ProofType::JsonWebSignature2020);
+//! assert!(attestation.proof_value.contains(".."));
+//! let verifier = JwsVerifier::new(keys.verifying_key().unwrap());
+//! attestation.verify(&Note(7), &verifier).unwrap();
+//! ```
+
+use crate::attestation::ProofType;
+use crate::error::{CepError, CepResult};
+use crate::hash::CanonicalHash;
+use crate::signer::{Signer, Verifier};
+use base64ct::{Base64UrlUnpadded, Encoding};
+use ed25519_dalek::{Signature, Signer as _, SigningKey, Verifier as _, VerifyingKey};
+use serde::Deserialize;
+
+/// Protected header of every JWS this module produces.
+pub const JWS_HEADER: &str = r#"{"alg":"EdDSA","b64":false,"crit":["b64"]}"#;
+
+/// The header members a verifier looks at.
+#[derive(Deserialize)]
+struct Header {
+    alg: String,
+    #[serde(default = "default_b64")]
+    b64: bool,
+    #[serde(default)]
+    crit: Vec<String>,
+}
+
+fn default_b64() -> bool {
+    true
+}
+
+/// Signs detached JWS proofs with an Ed25519 key held in memory.
+#[derive(Debug, Clone)]
+pub struct JwsSigner {
+    key: SigningKey,
+}
+
+impl JwsSigner {
+    pub fn new(key: SigningKey) -> Self {
+        Self { key }
+    }
+}
+
+impl Signer for JwsSigner {
+    fn proof_type(&self) -> ProofType {
+        ProofType::JsonWebSignature2020
+    }
+
+    fn sign(&self, _hash: &CanonicalHash) -> CepResult<String> {
+        Err(needs_input())
+    }
+
+    fn sign_input(&self, input: &str) -> CepResult<String> {
+        let header = Base64UrlUnpadded::encode_string(JWS_HEADER.as_bytes());
+        let signature = self.key.sign(&jws_signing_input(&header, input));
+        Ok(format!(
+            "{}..{}",
+            header,
+            Base64UrlUnpadded::encode_string(&signature.to_bytes())
+        ))
+    }
+
+    fn sign_input_batch(&self, inputs: &[String]) -> CepResult<Vec<String>> {
+        inputs.iter().map(|input| self.sign_input(input)).collect()
+    }
+}
+
+/// Verifies detached JWS proofs with an Ed25519 public key held in memory.
+#[derive(Debug, Clone)]
+pub struct JwsVerifier {
+    key: VerifyingKey,
+}
+
+impl JwsVerifier {
+    pub fn new(key: VerifyingKey) -> Self {
+        Self { key }
+    }
+}
+
+impl Verifier for JwsVerifier {
+    fn proof_type(&self) -> ProofType {
+        ProofType::JsonWebSignature2020
+    }
+
+    fn verify(&self, _hash: &CanonicalHash, _proof_value: &str) -> CepResult<()> {
+        Err(needs_input())
+    }
+
+    /// Accepts only `EdDSA` JWSs with a detached, unencoded payload and no
+    /// critical header parameter other than `b64`.
+    fn verify_input(&self, input: &str, proof_value: &str) -> CepResult<()> {
+        let malformed =
+            |reason: &str| CepError::InvalidSignature(format!("malformed JWS: {}", reason));
+        let (header, signature) = match proof_value.split('.').collect::<Vec<_>>()[..] {
+            [header, "", signature] => (header, signature),
+            [_, _, _] => return Err(malformed("payload is not detached")),
+            _ => return Err(malformed("expected three parts")),
+        };
+        let decoded = Base64UrlUnpadded::decode_vec(header)
+            .map_err(|_| malformed("header is not base64url"))?;
+        let parsed: Header =
+            serde_json::from_slice(&decoded).map_err(|e| malformed(&e.to_string()))?;
+        if parsed.alg != "EdDSA" {
+            return Err(CepError::InvalidSignature(format!(
+                "unsupported JWS algorithm '{}'",
+                parsed.alg
+            )));
+        }
+        if let Some(name) = parsed.crit.iter().find(|name| *name != "b64") {
+            return Err(CepError::InvalidSignature(format!(
+                "unsupported critical JWS header parameter '{}'",
+                name
+            )));
+        }
+        if parsed.b64 || !parsed.crit.iter().any(|name| name == "b64") {
+            return Err(malformed("payload must be unencoded (b64 false, critical)"));
+        }
+        let bytes = Base64UrlUnpadded::decode_vec(signature)
+            .map_err(|_| malformed("signature is not base64url"))?;
+        let signature = Signature::from_slice(&bytes).map_err(|e| malformed(&e.to_string()))?;
+        self.key
+            .verify(&jws_signing_input(header, input), &signature)
+            .map_err(|_| CepError::InvalidSignature("proof value does not verify".to_string()))
+    }
+}
+
+/// `<header>.<payload>` with the unencoded canonical string as payload
+/// (RFC 7797 section 3).
+fn jws_signing_input(header: &str, canonical: &str) -> Vec<u8> {
+    format!("{}.{}", header, canonical).into_bytes()
+}
+
+fn needs_input() -> CepError {
+    CepError::InvalidSignature(
+        "JsonWebSignature2020 proofs cover the canonical signing input, not its hash".to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = SigningKey::from_bytes(&[6u8; 32]);
+        let proof = JwsSigner::new(key.clone())
+            .sign_input(r#""n":"1""#)
+            .unwrap();
+        let (header, signature) = proof.split_once("..").unwrap();
+        assert_eq!(
+            Base64UrlUnpadded::decode_vec(header).unwrap(),
+            JWS_HEADER.as_bytes()
+        );
+        assert_eq!(signature.len(), 86);
+
+        // RFC 7797: the signature covers the header and the raw payload.
+        let bytes = Base64UrlUnpadded::decode_vec(signature).unwrap();
+        key.verifying_key()
+            .verify(
+                format!("{}.\"n\":\"1\"", header).as_bytes(),
+                &Signature::from_slice(&bytes).unwrap(),
+            )
+            .unwrap();
+
+        let verifier = JwsVerifier::new(key.verifying_key());
+        verifier.verify_input(r#""n":"1""#, &proof).unwrap();
+        assert!(verifier.verify_input(r#""n":"2""#, &proof).is_err());
+        let other = JwsVerifier::new(SigningKey::from_bytes(&[7u8; 32]).verifying_key());
+        assert!(other.verify_input(r#""n":"1""#, &proof).is_err());
+
+        // Hash-only calls cannot produce or check a JWS over the payload.
+        let hash = CanonicalHash::from_canonical_string(r#""n":"1""#);
+        assert!(JwsSigner::new(key).sign(&hash).is_err());
+        assert!(verifier.verify(&hash, &proof).is_err());
+    }
+
+    #[test]
+    fn test_rejects_other_jws_forms() {
+        let key = SigningKey::from_bytes(&[6u8; 32]);
+        let verifier = JwsVerifier::new(key.verifying_key());
+        let proof = JwsSigner::new(key.clone()).sign_input("a").unwrap();
+
+        // Each header is properly signed, so only the header is at fault.
+        let with_header = |header: &str| {
+            let header = Base64UrlUnpadded::encode_string(header.as_bytes());
+            let signature = key.sign(&jws_signing_input(&header, "a"));
+            format!(
+                "{}..{}",
+                header,
+                Base64UrlUnpadded::encode_string(&signature.to_bytes())
+            )
+        };
+        verifier
+            .verify_input("a", &with_header(JWS_HEADER))
+            .unwrap();
+        for header in [
+            r#"{"alg":"ES256","b64":false,"crit":["b64"]}"#,
+            r#"{"alg":"EdDSA"}"#,
+            r#"{"alg":"EdDSA","b64":false}"#,
+            r#"{"alg":"EdDSA","b64":false,"crit":["b64","exp"],"exp":1}"#,
+            r#"{"alg":"EdDSA","b64":false,"crit":["urn:example:x","b64"]}"#,
+            "not json",
+        ] {
+            assert!(
+                verifier.verify_input("a", &with_header(header)).is_err(),
+                "{}",
+                header
+            );
+        }
+
+        let attached = proof.replace("..", ".cGF5bG9hZA.");
+        assert!(verifier.verify_input("a", &attached).is_err());
+        assert!(verifier.verify_input("a", "z3abc").is_err());
+    }
+}
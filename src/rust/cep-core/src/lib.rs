@@ -1,43 +1,112 @@
-/// # CEP Core
-///
-/// Core primitives for the Civic Exchange Protocol (CEP).
-///
-/// This crate provides the foundational types and traits used by all CEP record types:
-///
-/// - [`CanonicalTimestamp`]: Microsecond-precision UTC timestamps
-/// - [`CanonicalHash`]: SHA-256 hash values
-/// - [`Canonicalize`]: Trait for deterministic serialization
-/// - [`Attestation`]: Cryptographic proof of record integrity
-///
-/// ## Canonicalization
-///
-/// All CEP records must be serializable to a deterministic canonical string for hashing.
-/// This ensures that the same logical record produces the same hash across all implementations
-/// (Rust, Python, Java, C#, TypeScript, Go).
-///
-/// ```rust
-/// use cep_core::canonical::Canonicalize;
-/// use cep_core::hash::CanonicalHash;
-///
-/// // Any type implementing Canonicalize can be hashed
-/// // let hash = my_record.calculate_hash();
-/// ```
-///
+//! # CEP Core
+//!
+//! Core primitives for the Civic Exchange Protocol (CEP).
+//!
+//! This crate provides the foundational types and traits used by all CEP record types:
+//!
+//! - [`CanonicalTimestamp`]: Microsecond-precision UTC timestamps
+//! - [`CanonicalHash`]: SHA-256 hash values
+//! - [`Canonicalize`]: Trait for deterministic serialization
+//! - [`Attestation`]: Cryptographic proof of record integrity
+//! - [`Clock`]: Injectable time source for stamping records
+//!
+//! ## Canonicalization
+//!
+//! All CEP records must be serializable to a deterministic canonical string for hashing.
+//! This ensures that the same logical record produces the same hash across all implementations
+//! (Rust, Python, Java, C#, TypeScript, Go).
+//!
+//! ```rust
+//! use cep_core::canonical::Canonicalize;
+//! use cep_core::hash::CanonicalHash;
+//!
+//! // Any type implementing Canonicalize can be hashed
+//! // let hash = my_record.calculate_hash();
+//! ```
+//!
 pub mod assets;
 pub mod attestation;
+pub mod authority;
+pub mod bloom;
 pub mod canonical;
+pub mod chain;
+pub mod classification;
+pub mod clock;
+pub mod compression;
+pub mod discovery;
+pub mod envelope;
 pub mod error;
+pub mod freshness;
 pub mod hash;
+pub mod jws;
+pub mod keys;
+pub mod limits;
+pub mod manifest;
+pub mod messages;
+pub mod negotiation;
+pub mod parallel;
+#[cfg(feature = "piv")]
+pub mod piv;
+pub mod policy;
+pub mod receipt;
+pub mod reconcile;
+pub mod resolver;
+pub mod retention;
+pub mod sampling;
+pub mod scaffold;
 pub mod schema_registry;
+pub mod signer;
+pub mod snapshot;
+pub mod spec;
+pub mod stream;
 pub mod timestamp;
+pub mod token;
+pub mod tombstone;
+pub mod verification;
 pub mod version;
 
 // Re-export primary types
 pub use assets::{get_schema, get_vocab, get_test_vector};
-pub use attestation::{Attestation, ProofPurpose};
-pub use canonical::Canonicalize;
-pub use error::{CepError, CepResult};
+pub use attestation::{
+    Attestation, AttestationSet, Endorsement, EndorsementChain, ProofPurpose, ProofType,
+};
+pub use authority::{validate_authority, Authority};
+pub use bloom::BloomFilter;
+pub use canonical::{Canonicalize, HashDomain, HashMode};
+pub use chain::{verify_chain, ChainLink, HashStub, Revisioned};
+pub use classification::{AccessScopes, Classification, Classified};
+pub use clock::{Clock, FixedClock, OffsetClock, SystemClock};
+pub use discovery::{BundleLocation, DiscoveryDocument};
+pub use envelope::{EnvelopeCheck, RecordEnvelope};
+pub use error::{CepError, CepResult, CepResultExt, ErrorClass, ErrorContext};
+pub use freshness::{Freshness, FreshnessPolicy, FreshnessWarning};
 pub use hash::CanonicalHash;
+pub use jws::{JwsSigner, JwsVerifier};
+pub use keys::{Jwk, KeyFile};
+pub use limits::ResourceLimits;
+pub use manifest::{AssetManifest, IntegrityMode, SignedManifest};
+pub use messages::Locale;
+pub use negotiation::{NegotiatedVersions, SupportedVersions};
+pub use parallel::{verify_parallel, CancellationToken, ParallelOptions, VerificationRun};
+pub use policy::SigningPolicy;
+pub use receipt::Receipt;
+pub use reconcile::{PrefixTree, Reconciliation};
+pub use resolver::{
+    CachingResolver, ResolverCacheConfig, VerificationKey, VerificationMethodResolver,
+};
+pub use retention::{Compaction, RetentionPolicy};
+pub use sampling::{SamplingPlan, SpotCheckReport};
 pub use schema_registry::{find_repo_root, SchemaRegistry};
-pub use timestamp::CanonicalTimestamp;
-pub use version::SCHEMA_VERSION;
+pub use signer::{Ed25519Signer, Ed25519Verifier, Signer, Verifier};
+pub use snapshot::SnapshotStore;
+pub use spec::{CanonicalSpec, CanonicalizationSpec, FieldEncoding, TypeSpec};
+pub use stream::{stream_bundle, write_bundle};
+pub use timestamp::{CanonicalTimestamp, OffsetTimestamp};
+pub use tombstone::{DeletionReason, Tombstone};
+pub use verification::{
+    verify_attestation, verify_endorsement, Check, CheckOutcome, VerificationReport,
+};
+pub use version::{
+    SchemaVersion, VersionRange, CURRENT_SCHEMA_VERSION, LIBRARY_VERSION, PROTOCOL_VERSION,
+    SCHEMA_VERSION,
+};
@@ -0,0 +1,289 @@
+//! Resource limits for parsing untrusted CEP input.
+//!
+//! Records and bundles arrive from other nodes and must not be trusted to be
+//! reasonably sized. [`ResourceLimits`] bounds:
+//!
+//! - the size of a single serialized record,
+//! - the length of any string (value or key) inside a record,
+//! - the number of entries in a bundle (a JSON array of records).
+//!
+//! Limits are checked before a record is converted into its typed form, and
+//! bundles are counted entry by entry without copying the entries, so an
+//! oversized input is rejected with [`CepError::ResourceLimit`] instead of
+//! being allocated.
+//!
+//! For bundles too large to hold in memory, see [`crate::stream::stream_bundle`].
+
+use crate::error::{CepError, CepResult};
+use serde::Deserializer;
+use serde::de::{DeserializeOwned, SeqAccess, Visitor};
+use serde_json::Value;
+use serde_json::value::RawValue;
+use std::cell::Cell;
+use std::fmt;
+
+/// Size limits applied when parsing untrusted records and bundles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Maximum length in bytes of any string value or object key.
+    pub max_field_length: usize,
+    /// Maximum size in bytes of one serialized record.
+    pub max_record_size: usize,
+    /// Maximum number of records in a bundle.
+    pub max_bundle_entries: usize,
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self {
+            max_field_length: 64 * 1024,
+            max_record_size: 1024 * 1024,
+            max_bundle_entries: 100_000,
+        }
+    }
+}
+
+impl ResourceLimits {
+    /// Limits that never reject anything. Only for trusted input.
+    pub fn unlimited() -> Self {
+        Self {
+            max_field_length: usize::MAX,
+            max_record_size: usize::MAX,
+            max_bundle_entries: usize::MAX,
+        }
+    }
+
+    /// Sets the maximum string length.
+    pub fn with_max_field_length(mut self, max: usize) -> Self {
+        self.max_field_length = max;
+        self
+    }
+
+    /// Sets the maximum serialized record size.
+    pub fn with_max_record_size(mut self, max: usize) -> Self {
+        self.max_record_size = max;
+        self
+    }
+
+    /// Sets the maximum number of bundle entries.
+    pub fn with_max_bundle_entries(mut self, max: usize) -> Self {
+        self.max_bundle_entries = max;
+        self
+    }
+
+    /// Fails if a serialized record of `size` bytes is too large.
+    pub fn check_record_size(&self, size: usize) -> CepResult<()> {
+        check("record size", size, self.max_record_size)
+    }
+
+    /// Fails if the string at `field` is too long.
+    pub fn check_field_length(&self, field: &str, length: usize) -> CepResult<()> {
+        check(
+            &format!("length of field '{}'", field),
+            length,
+            self.max_field_length,
+        )
+    }
+
+    /// Fails if a bundle holds too many entries.
+    pub fn check_bundle_entries(&self, count: usize) -> CepResult<()> {
+        check("bundle entries", count, self.max_bundle_entries)
+    }
+
+    /// Parses one record from JSON, enforcing the record and field limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::ResourceLimit` if a limit is exceeded, or
+    /// `CepError::Serialization` if the JSON does not match `T`.
+    pub fn parse_record<T: DeserializeOwned>(&self, json: &str) -> CepResult<T> {
+        self.check_record_size(json.len())?;
+        let value: Value = serde_json::from_str(json)?;
+        self.check_value("", &value)?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Parses a bundle (a JSON array of records), enforcing all limits.
+    ///
+    /// Entries are counted as they are read, so an array with too many
+    /// entries is rejected as soon as the limit is passed.
+    pub fn parse_bundle<T: DeserializeOwned>(&self, json: &str) -> CepResult<Vec<T>> {
+        let entries = self.split_bundle(json)?;
+        entries
+            .into_iter()
+            .map(|raw| self.parse_record(raw.get()))
+            .collect()
+    }
+
+    /// Splits a bundle into its raw entries without parsing them.
+    fn split_bundle<'a>(&self, json: &'a str) -> CepResult<Vec<&'a RawValue>> {
+        let exceeded = Cell::new(None);
+        let visitor = BundleVisitor {
+            max_entries: self.max_bundle_entries,
+            exceeded: &exceeded,
+        };
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        match deserializer.deserialize_seq(visitor) {
+            Ok(entries) => {
+                deserializer.end()?;
+                Ok(entries)
+            }
+            Err(e) => match exceeded.get() {
+                Some(count) => Err(limit_error(
+                    "bundle entries",
+                    count,
+                    self.max_bundle_entries,
+                )),
+                None => Err(e.into()),
+            },
+        }
+    }
+
+    fn check_value(&self, path: &str, value: &Value) -> CepResult<()> {
+        match value {
+            Value::String(s) => self.check_field_length(display_path(path), s.len()),
+            Value::Array(items) => items
+                .iter()
+                .enumerate()
+                .try_for_each(|(i, item)| self.check_value(&format!("{}[{}]", path, i), item)),
+            Value::Object(map) => map.iter().try_for_each(|(key, item)| {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                self.check_field_length(&child, key.len())?;
+                self.check_value(&child, item)
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() { "<root>" } else { path }
+}
+
+fn check(limit: &str, actual: usize, max: usize) -> CepResult<()> {
+    if actual > max {
+        return Err(limit_error(limit, actual, max));
+    }
+    Ok(())
+}
+
+fn limit_error(limit: &str, actual: usize, max: usize) -> CepError {
+    CepError::ResourceLimit {
+        limit: limit.to_string(),
+        actual,
+        max,
+    }
+}
+
+/// Collects borrowed bundle entries, stopping once `max_entries` is passed.
+struct BundleVisitor<'c> {
+    max_entries: usize,
+    exceeded: &'c Cell<Option<usize>>,
+}
+
+impl<'de> Visitor<'de> for BundleVisitor<'_> {
+    type Value = Vec<&'de RawValue>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a JSON array of records")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = seq.next_element::<&'de RawValue>()? {
+            if entries.len() == self.max_entries {
+                self.exceeded.set(Some(entries.len() + 1));
+                return Err(serde::de::Error::custom("too many bundle entries"));
+            }
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    struct Named {
+        legal_name: String,
+    }
+
+    fn limits() -> ResourceLimits {
+        ResourceLimits::default()
+            .with_max_field_length(16)
+            .with_max_record_size(64)
+            .with_max_bundle_entries(2)
+    }
+
+    fn assert_limit(result: CepResult<impl fmt::Debug>, expected: &str) {
+        match result {
+            Err(CepError::ResourceLimit { limit, .. }) => assert_eq!(limit, expected),
+            other => panic!("Expected ResourceLimit, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_record_within_limits() {
+        let named: Named = limits().parse_record(r#"{"legalName":"Acme"}"#).unwrap();
+        assert_eq!(named.legal_name, "Acme");
+    }
+
+    #[test]
+    fn test_record_size_limit() {
+        let json = format!(r#"{{"legalName":"{}"}}"#, "a".repeat(100));
+        assert_limit(limits().parse_record::<Named>(&json), "record size");
+    }
+
+    #[test]
+    fn test_field_length_limit() {
+        let json = format!(r#"{{"legalName":"{}"}}"#, "a".repeat(17));
+        assert_limit(
+            limits().parse_record::<Named>(&json),
+            "length of field 'legalName'",
+        );
+    }
+
+    #[test]
+    fn test_nested_field_path() {
+        let json = format!(r#"{{"a":{{"b":["x","{}"]}}}}"#, "y".repeat(17));
+        assert_limit(
+            limits().parse_record::<Value>(&json),
+            "length of field 'a.b[1]'",
+        );
+    }
+
+    #[test]
+    fn test_bundle_entry_limit() {
+        let ok: Vec<Named> = limits()
+            .parse_bundle(r#"[{"legalName":"A"},{"legalName":"B"}]"#)
+            .unwrap();
+        assert_eq!(ok.len(), 2);
+
+        let json = r#"[{"legalName":"A"},{"legalName":"B"},{"legalName":"C"}]"#;
+        assert_limit(limits().parse_bundle::<Named>(json), "bundle entries");
+    }
+
+    #[test]
+    fn test_bundle_syntax_error_is_serialization() {
+        let result = limits().parse_bundle::<Named>(r#"[{"legalName":"A"}"#);
+        assert!(matches!(result, Err(CepError::Serialization(_))));
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let json = format!(r#"{{"legalName":"{}"}}"#, "a".repeat(10_000));
+        assert!(
+            ResourceLimits::unlimited()
+                .parse_record::<Named>(&json)
+                .is_ok()
+        );
+    }
+}
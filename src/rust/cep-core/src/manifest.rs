@@ -0,0 +1,271 @@
+//! Signed manifests for schema and vocabulary assets.
+//!
+//! Schemas and vocabularies loaded from disk (or fetched over HTTP and cached
+//! on disk) could be tampered with. An [`AssetManifest`] lists the SHA-256 of
+//! every asset file; a [`SignedManifest`] adds an Ed25519 signature by the
+//! project key over the manifest's canonical form.
+//!
+//! The canonical form is one line per asset, sorted by path, in the same
+//! layout as `sha256sum` output:
+//!
+//! ```text
+//! <64 hex chars><two spaces><relative/path>\n
+//! ```
+//!
+//! [`SchemaRegistry::with_verified_root`](crate::SchemaRegistry::with_verified_root)
+//! checks the signature and every schema file against the manifest at load.
+//!
+//! The repository root holds the project's signed manifest
+//! ([`MANIFEST_FILE`]) and public key ([`MANIFEST_KEY_FILE`]).
+//! [`SchemaRegistry::new`](crate::SchemaRegistry::new), and so the global
+//! registry, verifies the assets against that manifest whenever it is
+//! present, trusting only [`PROJECT_MANIFEST_KEY`]. After changing a schema
+//! or vocabulary, regenerate the manifest with
+//! `cep manifest --key <project key file>`.
+
+use crate::error::{CepError, CepResult};
+use crate::hash::CanonicalHash;
+use crate::keys::KeyFile;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Default file name of the signed manifest at the repository root.
+pub const MANIFEST_FILE: &str = "asset-manifest.json";
+
+/// File name of the project's public manifest key at the repository root.
+pub const MANIFEST_KEY_FILE: &str = "asset-manifest.key.json";
+
+/// The project key that signs [`MANIFEST_FILE`], as a `did:key`.
+///
+/// This is the trust anchor: a manifest signed with any other key is
+/// refused, even if a matching key file sits next to it.
+pub const PROJECT_MANIFEST_KEY: &str = "did:key:z6MkkDrxzfvESkixB4gDSBhPspRjJgHUYqQoqEqvo11s894f";
+
+/// Returns [`PROJECT_MANIFEST_KEY`] as a verifying key.
+pub fn project_manifest_key() -> CepResult<VerifyingKey> {
+    KeyFile::from_did_key(PROJECT_MANIFEST_KEY)?.verifying_key()
+}
+
+/// How the registry reacts to a failed integrity check.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IntegrityMode {
+    /// Record a warning and keep loading.
+    Warn,
+    /// Fail with `CepError::AssetIntegrity`.
+    #[default]
+    Refuse,
+}
+
+/// SHA-256 digests of asset files, keyed by path relative to the repository root.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetManifest {
+    pub assets: BTreeMap<String, String>,
+}
+
+impl AssetManifest {
+    /// Hashes the given files under `root`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::Configuration` if a file cannot be read.
+    pub fn build<S: AsRef<str>>(root: &Path, relative_paths: &[S]) -> CepResult<Self> {
+        let mut assets = BTreeMap::new();
+        for relative in relative_paths {
+            let relative = relative.as_ref();
+            let bytes = read_asset(root, relative)?;
+            assets.insert(relative.to_string(), sha256_hex(&bytes));
+        }
+        Ok(Self { assets })
+    }
+
+    /// Returns the bytes covered by the signature.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        self.canonical_text().into_bytes()
+    }
+
+    /// Returns the SHA-256 of [`canonical_bytes`](Self::canonical_bytes).
+    ///
+    /// This identifies the exact set of asset contents, and is what records
+    /// carry as `schemaSnapshotHash` (see [`crate::snapshot`]).
+    pub fn snapshot_hash(&self) -> CanonicalHash {
+        CanonicalHash::from_canonical_string(&self.canonical_text())
+    }
+
+    /// Checks `content` against the recorded digest for `relative_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::AssetIntegrity` if the path is not listed or the
+    /// digest differs.
+    pub fn verify_bytes(&self, relative_path: &str, content: &[u8]) -> CepResult<()> {
+        let expected = self.assets.get(relative_path).ok_or_else(|| {
+            CepError::AssetIntegrity(format!("{} is not listed in the manifest", relative_path))
+        })?;
+        let actual = sha256_hex(content);
+        if *expected != actual {
+            return Err(CepError::AssetIntegrity(format!(
+                "{} has digest {}, manifest expects {}",
+                relative_path, actual, expected
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reads `relative_path` under `root` and checks it against the manifest.
+    pub fn verify_file(&self, root: &Path, relative_path: &str) -> CepResult<()> {
+        self.verify_bytes(relative_path, &read_asset(root, relative_path)?)
+    }
+
+    fn canonical_text(&self) -> String {
+        self.assets
+            .iter()
+            .map(|(path, digest)| format!("{}  {}\n", digest, path))
+            .collect()
+    }
+}
+
+/// An [`AssetManifest`] signed with the project's Ed25519 key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedManifest {
+    pub manifest: AssetManifest,
+    /// Identifier of the signing key (e.g. a DID URL).
+    pub key_id: String,
+    /// Hex-encoded Ed25519 signature over [`AssetManifest::canonical_bytes`].
+    pub signature: String,
+}
+
+impl SignedManifest {
+    /// Signs `manifest` with `key`.
+    pub fn sign(manifest: AssetManifest, key_id: &str, key: &SigningKey) -> Self {
+        let signature = key.sign(&manifest.canonical_bytes());
+        Self {
+            manifest,
+            key_id: key_id.to_string(),
+            signature: hex::encode(signature.to_bytes()),
+        }
+    }
+
+    /// Verifies the signature with `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::AssetIntegrity` if the signature is malformed or
+    /// does not match.
+    pub fn verify(&self, key: &VerifyingKey) -> CepResult<()> {
+        let bytes = hex::decode(&self.signature).map_err(|e| {
+            CepError::AssetIntegrity(format!("malformed manifest signature: {}", e))
+        })?;
+        let signature = Signature::from_slice(&bytes).map_err(|e| {
+            CepError::AssetIntegrity(format!("malformed manifest signature: {}", e))
+        })?;
+        key.verify(&self.manifest.canonical_bytes(), &signature)
+            .map_err(|_| CepError::AssetIntegrity("manifest signature does not verify".to_string()))
+    }
+
+    /// Loads a signed manifest from a JSON file.
+    pub fn load(path: &Path) -> CepResult<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            CepError::Configuration(format!("Failed to read manifest {}: {}", path.display(), e))
+        })?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Writes the signed manifest as pretty-printed JSON.
+    pub fn save(&self, path: &Path) -> CepResult<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json).map_err(|e| {
+            CepError::Configuration(format!(
+                "Failed to write manifest {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+}
+
+fn read_asset(root: &Path, relative_path: &str) -> CepResult<Vec<u8>> {
+    let path = root.join(relative_path);
+    fs::read(&path).map_err(|e| {
+        CepError::Configuration(format!("Failed to read asset {}: {}", path.display(), e))
+    })
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    fn fixture() -> (tempfile::TempDir, AssetManifest) {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("schemas")).unwrap();
+        fs::write(dir.path().join("schemas/a.json"), r#"{"a":1}"#).unwrap();
+        fs::write(dir.path().join("schemas/b.json"), r#"{"b":2}"#).unwrap();
+        let manifest =
+            AssetManifest::build(dir.path(), &["schemas/b.json", "schemas/a.json"]).unwrap();
+        (dir, manifest)
+    }
+
+    #[test]
+    fn test_canonical_bytes_are_sorted_sha256sum_lines() {
+        let (_dir, manifest) = fixture();
+        let text = String::from_utf8(manifest.canonical_bytes()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("  schemas/a.json"));
+        assert_eq!(lines[0].find("  "), Some(64));
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let (dir, manifest) = fixture();
+        let signed = SignedManifest::sign(manifest, "did:web:example.org#key-1", &key());
+        signed.verify(&key().verifying_key()).unwrap();
+
+        let path = dir.path().join(MANIFEST_FILE);
+        signed.save(&path).unwrap();
+        assert_eq!(SignedManifest::load(&path).unwrap(), signed);
+    }
+
+    #[test]
+    fn test_tampered_manifest_fails_verification() {
+        let (_dir, manifest) = fixture();
+        let mut signed = SignedManifest::sign(manifest, "key", &key());
+        signed
+            .manifest
+            .assets
+            .insert("schemas/a.json".to_string(), "0".repeat(64));
+        assert!(matches!(
+            signed.verify(&key().verifying_key()),
+            Err(CepError::AssetIntegrity(_))
+        ));
+
+        let other = SigningKey::from_bytes(&[8u8; 32]).verifying_key();
+        let signed = SignedManifest::sign(AssetManifest::default(), "key", &key());
+        assert!(signed.verify(&other).is_err());
+    }
+
+    #[test]
+    fn test_verify_file() {
+        let (dir, manifest) = fixture();
+        manifest.verify_file(dir.path(), "schemas/a.json").unwrap();
+
+        fs::write(dir.path().join("schemas/a.json"), r#"{"a":2}"#).unwrap();
+        assert!(matches!(
+            manifest.verify_file(dir.path(), "schemas/a.json"),
+            Err(CepError::AssetIntegrity(_))
+        ));
+        assert!(manifest.verify_bytes("schemas/c.json", b"{}").is_err());
+    }
+}
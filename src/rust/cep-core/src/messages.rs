@@ -0,0 +1,368 @@
+//! Localized error messages.
+//!
+//! `Display` on [`CepError`] is always English, for logs. Steward-facing
+//! tools call [`CepError::localized_message`] with a [`Locale`] instead,
+//! which looks the error's [`code`](CepError::code) up in a message catalog
+//! and fills in the error's details:
+//!
+//! ```rust
+//! use cep_core::messages::Locale;
+//! use cep_core::CepError;
+//!
+//! let err = CepError::MissingField("legalName".to_string());
+//! let locale: Locale = "fr-CA".parse().unwrap();
+//! assert_eq!(err.localized_message(locale), "champ obligatoire manquant : legalName");
+//! assert_eq!(err.to_string(), "missing required field: legalName");
+//! ```
+//!
+//! Details that come from outside this crate (serde and hardware token
+//! messages) are passed through in English.
+
+use crate::error::{CepError, ErrorContext};
+use std::fmt;
+use std::str::FromStr;
+
+/// A language the message catalog covers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    English,
+    French,
+    Spanish,
+}
+
+impl Locale {
+    /// Returns the ISO 639-1 language code.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::French => "fr",
+            Locale::Spanish => "es",
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = CepError;
+
+    /// Parses a BCP 47 tag by its language subtag, so `fr-CA` and `es-PR`
+    /// select French and Spanish.
+    fn from_str(tag: &str) -> Result<Self, Self::Err> {
+        let language = tag.split(['-', '_']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "en" => Ok(Locale::English),
+            "fr" => Ok(Locale::French),
+            "es" => Ok(Locale::Spanish),
+            _ => Err(CepError::Configuration(format!(
+                "no error messages for locale '{}'",
+                tag
+            ))),
+        }
+    }
+}
+
+/// One message in English, French and Spanish. `{0}`, `{1}`, ... are
+/// replaced with the error's details in order.
+struct Message {
+    code: &'static str,
+    en: &'static str,
+    fr: &'static str,
+    es: &'static str,
+}
+
+const CATALOG: &[Message] = &[
+    Message {
+        code: "invalid_timestamp",
+        en: "invalid timestamp: {0}",
+        fr: "horodatage invalide : {0}",
+        es: "marca de tiempo no válida: {0}",
+    },
+    Message {
+        code: "invalid_hash",
+        en: "invalid hash: expected 64 hex characters, got {0}",
+        fr: "empreinte invalide : 64 caractères hexadécimaux attendus, reçu {0}",
+        es: "hash no válido: se esperaban 64 caracteres hexadecimales, se recibió {0}",
+    },
+    Message {
+        code: "invalid_identifier",
+        en: "invalid identifier: {0}",
+        fr: "identifiant invalide : {0}",
+        es: "identificador no válido: {0}",
+    },
+    Message {
+        code: "missing_field",
+        en: "missing required field: {0}",
+        fr: "champ obligatoire manquant : {0}",
+        es: "falta el campo obligatorio: {0}",
+    },
+    Message {
+        code: "unsupported_version",
+        en: "unsupported schema version: {0}",
+        fr: "version de schéma non prise en charge : {0}",
+        es: "versión de esquema no admitida: {0}",
+    },
+    Message {
+        code: "hash_mismatch",
+        en: "hash verification failed: expected {0}, got {1}",
+        fr: "échec de la vérification de l'empreinte : {0} attendue, {1} obtenue",
+        es: "falló la verificación del hash: se esperaba {0}, se obtuvo {1}",
+    },
+    Message {
+        code: "serialization",
+        en: "serialization error: {0}",
+        fr: "erreur de sérialisation : {0}",
+        es: "error de serialización: {0}",
+    },
+    Message {
+        code: "revision_chain",
+        en: "revision chain error: {0}",
+        fr: "erreur dans la chaîne de révisions : {0}",
+        es: "error en la cadena de revisiones: {0}",
+    },
+    Message {
+        code: "unknown_schema",
+        en: "Unknown schema: {0}",
+        fr: "schéma inconnu : {0}",
+        es: "esquema desconocido: {0}",
+    },
+    Message {
+        code: "configuration",
+        en: "Configuration error: {0}",
+        fr: "erreur de configuration : {0}",
+        es: "error de configuración: {0}",
+    },
+    Message {
+        code: "policy_violation",
+        en: "signing policy violation: proof type '{0}' not allowed for {1} records (allowed: {2})",
+        fr: "violation de la politique de signature : type de preuve « {0} » non autorisé pour les enregistrements {1} (autorisés : {2})",
+        es: "infracción de la política de firma: el tipo de prueba '{0}' no está permitido para registros {1} (permitidos: {2})",
+    },
+    Message {
+        code: "asset_integrity",
+        en: "asset integrity check failed: {0}",
+        fr: "échec du contrôle d'intégrité des ressources : {0}",
+        es: "falló la comprobación de integridad de los recursos: {0}",
+    },
+    Message {
+        code: "resource_limit",
+        en: "resource limit exceeded: {0} is {1}, maximum is {2}",
+        fr: "limite de ressources dépassée : {0} vaut {1}, le maximum est {2}",
+        es: "límite de recursos superado: {0} es {1}, el máximo es {2}",
+    },
+    Message {
+        code: "invalid_signature",
+        en: "signature verification failed: {0}",
+        fr: "échec de la vérification de la signature : {0}",
+        es: "falló la verificación de la firma: {0}",
+    },
+    Message {
+        code: "unknown_term",
+        en: "unknown vocabulary term: {0}",
+        fr: "terme de vocabulaire inconnu : {0}",
+        es: "término de vocabulario desconocido: {0}",
+    },
+    Message {
+        code: "access_denied",
+        en: "access denied: {0} records require scope '{1}'",
+        fr: "accès refusé : les enregistrements {0} exigent la portée « {1} »",
+        es: "acceso denegado: los registros {0} requieren el ámbito '{1}'",
+    },
+    Message {
+        code: "no_common_version",
+        en: "no common {0} version: we support {1}, peer supports {2}",
+        fr: "aucune version {0} commune : nous prenons en charge {1}, le pair prend en charge {2}",
+        es: "ninguna versión de {0} en común: admitimos {1}, el par admite {2}",
+    },
+    Message {
+        code: "hardware_token",
+        en: "hardware token error: {0}",
+        fr: "erreur du jeton matériel : {0}",
+        es: "error del token de hardware: {0}",
+    },
+    Message {
+        code: "context_field",
+        en: "field {0}",
+        fr: "champ {0}",
+        es: "campo {0}",
+    },
+    Message {
+        code: "context_record",
+        en: "record {0}",
+        fr: "enregistrement {0}",
+        es: "registro {0}",
+    },
+];
+
+impl CepError {
+    /// Returns this error's message in `locale`.
+    pub fn localized_message(&self, locale: Locale) -> String {
+        if let CepError::WithContext { context, error } = self {
+            return format!(
+                "{} ({})",
+                error.localized_message(locale),
+                localized_context(context, locale)
+            );
+        }
+        render(self.code(), &self.message_args(), locale)
+    }
+
+    /// The details filled into this error's catalog message, in order.
+    fn message_args(&self) -> Vec<String> {
+        match self {
+            CepError::InvalidTimestamp(detail)
+            | CepError::InvalidHash(detail)
+            | CepError::InvalidIdentifier(detail)
+            | CepError::MissingField(detail)
+            | CepError::UnsupportedVersion(detail)
+            | CepError::RevisionChain(detail)
+            | CepError::UnknownSchema(detail)
+            | CepError::Configuration(detail)
+            | CepError::AssetIntegrity(detail)
+            | CepError::InvalidSignature(detail)
+            | CepError::UnknownTerm(detail) => vec![detail.clone()],
+            CepError::HashMismatch { expected, actual } => vec![expected.clone(), actual.clone()],
+            CepError::Serialization(e) => vec![e.to_string()],
+            CepError::PolicyViolation {
+                record_class,
+                proof_type,
+                allowed,
+            } => vec![
+                proof_type.clone(),
+                record_class.clone(),
+                format!("{:?}", allowed),
+            ],
+            CepError::ResourceLimit { limit, actual, max } => {
+                vec![limit.clone(), actual.to_string(), max.to_string()]
+            }
+            CepError::AccessDenied {
+                classification,
+                required_scope,
+            } => vec![classification.clone(), required_scope.clone()],
+            CepError::NoCommonVersion {
+                kind,
+                local,
+                remote,
+            } => vec![
+                kind.clone(),
+                format!("{:?}", local),
+                format!("{:?}", remote),
+            ],
+            CepError::HardwareToken(e) => vec![e.to_string()],
+            CepError::WithContext { error, .. } => error.message_args(),
+        }
+    }
+}
+
+fn localized_context(context: &ErrorContext, locale: Locale) -> String {
+    let mut parts = Vec::new();
+    if let Some(path) = &context.field_path {
+        parts.push(render("context_field", std::slice::from_ref(path), locale));
+    }
+    if let Some(hash) = &context.record_hash {
+        parts.push(render("context_record", &[hash.to_string()], locale));
+    }
+    parts.join(", ")
+}
+
+fn render(code: &str, args: &[String], locale: Locale) -> String {
+    let message = CATALOG
+        .iter()
+        .find(|message| message.code == code)
+        .expect("every error code has a catalog message");
+    let template = match locale {
+        Locale::English => message.en,
+        Locale::French => message.fr,
+        Locale::Spanish => message.es,
+    };
+    args.iter()
+        .enumerate()
+        .fold(template.to_string(), |text, (i, arg)| {
+            text.replace(&format!("{{{}}}", i), arg)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::CanonicalHash;
+
+    fn samples() -> Vec<CepError> {
+        vec![
+            CepError::MissingField("legalName".to_string()),
+            CepError::HashMismatch {
+                expected: "aa".to_string(),
+                actual: "bb".to_string(),
+            },
+            CepError::PolicyViolation {
+                record_class: "exchange".to_string(),
+                proof_type: "ManualAttestation".to_string(),
+                allowed: vec!["Ed25519Signature2020".to_string()],
+            },
+            CepError::ResourceLimit {
+                limit: "bundle entries".to_string(),
+                actual: 11,
+                max: 10,
+            },
+            CepError::AccessDenied {
+                classification: "restricted".to_string(),
+                required_scope: "cep:restricted".to_string(),
+            },
+            CepError::NoCommonVersion {
+                kind: "schema".to_string(),
+                local: vec!["1.0.0".to_string()],
+                remote: vec!["2.0.0".to_string()],
+            },
+            CepError::Configuration("no schemas".to_string())
+                .with_field_path("identifiers.lei")
+                .with_record(&CanonicalHash::from_canonical_string("record")),
+        ]
+    }
+
+    #[test]
+    fn test_english_catalog_matches_display() {
+        for err in samples() {
+            assert_eq!(err.localized_message(Locale::English), err.to_string());
+        }
+    }
+
+    #[test]
+    fn test_french_and_spanish_messages() {
+        let missing = CepError::MissingField("legalName".to_string());
+        assert_eq!(
+            missing.localized_message(Locale::Spanish),
+            "falta el campo obligatorio: legalName"
+        );
+
+        let limit = &samples()[3];
+        assert_eq!(
+            limit.localized_message(Locale::French),
+            "limite de ressources dépassée : bundle entries vaut 11, le maximum est 10"
+        );
+
+        let with_context = &samples()[6];
+        assert!(
+            with_context.localized_message(Locale::Spanish).starts_with(
+                "error de configuración: no schemas (campo identifiers.lei, registro "
+            )
+        );
+        for err in samples() {
+            for locale in [Locale::French, Locale::Spanish] {
+                assert!(!err.localized_message(locale).contains('{'));
+            }
+        }
+    }
+
+    #[test]
+    fn test_locale_tags() {
+        assert_eq!("fr-CA".parse::<Locale>().unwrap(), Locale::French);
+        assert_eq!("es_PR".parse::<Locale>().unwrap(), Locale::Spanish);
+        assert_eq!("EN".parse::<Locale>().unwrap(), Locale::English);
+        assert!("de-DE".parse::<Locale>().is_err());
+    }
+}
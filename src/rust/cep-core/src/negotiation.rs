@@ -0,0 +1,146 @@
+//! Protocol and schema version negotiation.
+//!
+//! Every node advertises the protocol and schema versions it supports at
+//! [`WELL_KNOWN_PATH`]. Before exchanging records, a client fetches the
+//! server's [`SupportedVersions`] and calls [`SupportedVersions::negotiate`]
+//! to pick the highest version of each that both sides support.
+//!
+//! ```rust
+//! use cep_core::negotiation::SupportedVersions;
+//!
+//! let client = SupportedVersions::new(["1.0.0", "1.1.0"], ["1.0.0"]);
+//! let server = SupportedVersions::new(["1.0.0"], ["1.0.0", "1.2.0"]);
+//!
+//! let agreed = client.negotiate(&server).unwrap();
+//! assert_eq!(agreed.protocol_version, "1.0.0");
+//! assert_eq!(agreed.schema_version, "1.0.0");
+//! ```
+
+use crate::error::{CepError, CepResult};
+use crate::version::{PROTOCOL_VERSION, SCHEMA_VERSION, SchemaVersion};
+use serde::{Deserialize, Serialize};
+
+/// Path at which nodes publish their discovery document.
+pub const WELL_KNOWN_PATH: &str = "/.well-known/cep";
+
+/// Versions a node supports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportedVersions {
+    /// Supported protocol versions (major.minor.patch).
+    pub protocol_versions: Vec<String>,
+
+    /// Supported record schema versions (major.minor.patch).
+    pub schema_versions: Vec<String>,
+}
+
+/// The versions two parties agreed on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NegotiatedVersions {
+    pub protocol_version: String,
+    pub schema_version: String,
+}
+
+impl Default for SupportedVersions {
+    /// The versions this implementation supports.
+    fn default() -> Self {
+        Self::new([PROTOCOL_VERSION], [SCHEMA_VERSION])
+    }
+}
+
+impl SupportedVersions {
+    /// Creates a version list.
+    pub fn new<P, S>(protocol_versions: P, schema_versions: S) -> Self
+    where
+        P: IntoIterator,
+        P::Item: Into<String>,
+        S: IntoIterator,
+        S::Item: Into<String>,
+    {
+        Self {
+            protocol_versions: protocol_versions.into_iter().map(Into::into).collect(),
+            schema_versions: schema_versions.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Selects the highest protocol and schema versions supported by both
+    /// `self` and `remote`.
+    ///
+    /// Versions are compared numerically; entries that are not
+    /// `major.minor.patch` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::NoCommonVersion` if the two sides share no protocol
+    /// version or no schema version.
+    pub fn negotiate(&self, remote: &SupportedVersions) -> CepResult<NegotiatedVersions> {
+        Ok(NegotiatedVersions {
+            protocol_version: highest_common(
+                "protocol",
+                &self.protocol_versions,
+                &remote.protocol_versions,
+            )?,
+            schema_version: highest_common(
+                "schema",
+                &self.schema_versions,
+                &remote.schema_versions,
+            )?,
+        })
+    }
+}
+
+fn highest_common(kind: &str, local: &[String], remote: &[String]) -> CepResult<String> {
+    let parse = |v: &str| v.parse::<SchemaVersion>().ok();
+    local
+        .iter()
+        .filter_map(|v| parse(v).map(|parsed| (parsed, v)))
+        .filter(|(parsed, _)| remote.iter().any(|r| parse(r) == Some(*parsed)))
+        .max_by_key(|(parsed, _)| *parsed)
+        .map(|(_, v)| v.clone())
+        .ok_or_else(|| CepError::NoCommonVersion {
+            kind: kind.to_string(),
+            local: local.to_vec(),
+            remote: remote.to_vec(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_picks_highest_mutual_version() {
+        let ours = SupportedVersions::new(["1.0.0", "1.2.0", "2.0.0"], ["1.0.0", "1.10.0"]);
+        let theirs = SupportedVersions::new(["1.2.0", "1.0.0"], ["1.10.0", "1.9.0", "1.0.0"]);
+        let agreed = ours.negotiate(&theirs).unwrap();
+        assert_eq!(agreed.protocol_version, "1.2.0");
+        // Numeric, not lexical: 1.10.0 > 1.9.0.
+        assert_eq!(agreed.schema_version, "1.10.0");
+        assert_eq!(theirs.negotiate(&ours).unwrap(), agreed);
+    }
+
+    #[test]
+    fn test_no_overlap_is_a_clear_error() {
+        let ours = SupportedVersions::default();
+        let theirs = SupportedVersions::new(["2.0.0"], [SCHEMA_VERSION]);
+        let err = ours.negotiate(&theirs).unwrap_err();
+        assert!(matches!(err, CepError::NoCommonVersion { ref kind, .. } if kind == "protocol"));
+        assert_eq!(
+            err.to_string(),
+            r#"no common protocol version: we support ["1.0.0"], peer supports ["2.0.0"]"#
+        );
+    }
+
+    #[test]
+    fn test_advertised_json_shape() {
+        let json = serde_json::to_value(SupportedVersions::default()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "protocolVersions": [PROTOCOL_VERSION],
+                "schemaVersions": [SCHEMA_VERSION],
+            })
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! Parallel verification with cancellation.
+//!
+//! [`verify_parallel`] spreads verification of a batch of records over a
+//! fixed number of worker threads. An operator can abort a long run through a
+//! [`CancellationToken`]; workers stop picking up records as soon as it is
+//! cancelled, and the [`VerificationRun`] returned reports what was checked
+//! before the stop and flags the run as cancelled.
+//!
+//! ```rust
+//! use cep_core::parallel::{verify_parallel, CancellationToken, ParallelOptions};
+//!
+//! let records: Vec<u32> = (0..1000).collect();
+//! let token = CancellationToken::new();
+//! let options = ParallelOptions::new(4).with_cancellation(token.clone());
+//!
+//! let run = verify_parallel(&records, &options, |_record| Ok(()));
+//! assert!(!run.cancelled);
+//! assert_eq!(run.checked, 1000);
+//! ```
+
+use crate::error::CepResult;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+
+/// A shared flag that asks a running verification to stop.
+///
+/// Clones share the flag, so one clone can be handed to the run and another
+/// kept by whoever may cancel it (a signal handler, an admin endpoint, ...).
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation. Idempotent.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true once cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Worker count and cancellation for [`verify_parallel`].
+#[derive(Debug, Clone)]
+pub struct ParallelOptions {
+    /// Number of worker threads (at least 1).
+    pub workers: usize,
+    /// Token checked before each record.
+    pub cancellation: CancellationToken,
+}
+
+impl Default for ParallelOptions {
+    /// One worker per available CPU.
+    fn default() -> Self {
+        Self::new(thread::available_parallelism().map_or(1, |n| n.get()))
+    }
+}
+
+impl ParallelOptions {
+    /// Creates options with `workers` threads and a fresh token.
+    pub fn new(workers: usize) -> Self {
+        Self {
+            workers: workers.max(1),
+            cancellation: CancellationToken::new(),
+        }
+    }
+
+    /// Uses `token` to cancel the run.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+}
+
+/// A record that failed verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationFailure {
+    /// Position of the record in the batch.
+    pub index: usize,
+    /// Why verification failed.
+    pub error: String,
+}
+
+/// The outcome of a (possibly cancelled) parallel verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationRun {
+    /// Number of records in the batch.
+    pub total: usize,
+    /// Number of records verified before the run ended.
+    pub checked: usize,
+    /// Records that failed, in batch order.
+    pub failures: Vec<VerificationFailure>,
+    /// True if the run stopped early because it was cancelled.
+    pub cancelled: bool,
+}
+
+impl VerificationRun {
+    /// Returns true if every record was checked and none failed.
+    pub fn is_complete_success(&self) -> bool {
+        !self.cancelled && self.checked == self.total && self.failures.is_empty()
+    }
+}
+
+/// Verifies `records` on `options.workers` threads.
+///
+/// Records are handed out one at a time, so slow records do not hold up a
+/// whole partition. When the token is cancelled, records already being
+/// verified finish and no new ones are started.
+pub fn verify_parallel<T, F>(records: &[T], options: &ParallelOptions, verify: F) -> VerificationRun
+where
+    T: Sync,
+    F: Fn(&T) -> CepResult<()> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let workers = options.workers.clamp(1, records.len().max(1));
+    let token = &options.cancellation;
+
+    let mut checked = 0;
+    let mut failures = Vec::new();
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut checked = 0;
+                    let mut failures = Vec::new();
+                    while !token.is_cancelled() {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(record) = records.get(index) else {
+                            break;
+                        };
+                        if let Err(e) = verify(record) {
+                            failures.push(VerificationFailure {
+                                index,
+                                error: e.to_string(),
+                            });
+                        }
+                        checked += 1;
+                    }
+                    (checked, failures)
+                })
+            })
+            .collect();
+        for handle in handles {
+            let (worker_checked, worker_failures) =
+                handle.join().expect("verification worker panicked");
+            checked += worker_checked;
+            failures.extend(worker_failures);
+        }
+    });
+    failures.sort_by_key(|f| f.index);
+
+    VerificationRun {
+        total: records.len(),
+        checked,
+        failures,
+        cancelled: checked < records.len() && token.is_cancelled(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::CepError;
+
+    fn check(record: &u32) -> CepResult<()> {
+        if record % 100 == 7 {
+            Err(CepError::InvalidSignature(format!("record {}", record)))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_matches_sequential_result() {
+        let records: Vec<u32> = (0..10_000).collect();
+        let run = verify_parallel(&records, &ParallelOptions::new(8), check);
+        assert_eq!(run.checked, 10_000);
+        assert!(!run.cancelled);
+        assert_eq!(run.failures.len(), 100);
+        assert_eq!(run.failures[0].index, 7);
+        assert!(run.failures.windows(2).all(|w| w[0].index < w[1].index));
+        assert!(!run.is_complete_success());
+
+        let empty: Vec<u32> = Vec::new();
+        assert!(verify_parallel(&empty, &ParallelOptions::new(4), check).is_complete_success());
+    }
+
+    #[test]
+    fn test_cancellation_returns_partial_results() {
+        let records: Vec<u32> = (0..100_000).collect();
+        let token = CancellationToken::new();
+        let options = ParallelOptions::new(4).with_cancellation(token.clone());
+        let run = verify_parallel(&records, &options, |record| {
+            if *record == 500 {
+                token.cancel();
+            }
+            check(record)
+        });
+        assert!(run.cancelled);
+        assert!(run.checked > 0 && run.checked < records.len());
+        assert!(!run.is_complete_success());
+    }
+
+    #[test]
+    fn test_cancelled_before_start() {
+        let records: Vec<u32> = (0..10).collect();
+        let token = CancellationToken::new();
+        token.cancel();
+        let options = ParallelOptions::new(2).with_cancellation(token);
+        let run = verify_parallel(&records, &options, check);
+        assert_eq!(run.checked, 0);
+        assert!(run.cancelled);
+    }
+}
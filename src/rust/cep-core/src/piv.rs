@@ -0,0 +1,336 @@
+//! Signing with a key held on a PIV hardware token (YubiKey and similar).
+//!
+//! Small jurisdictions often keep their attestation key on a token that never
+//! releases it. [`PivSigner`] implements [`Signer`] over a [`PivCard`], the
+//! narrow set of card operations signing needs: PIN verification, signing a
+//! digest in a slot, and reading the slot's public key. No PC/SC transport
+//! ships with this crate yet: an application implements [`PivCard`] over its
+//! own smart-card stack, and tests use an in-memory card.
+//!
+//! The PIN is verified once per session, before the first signature. A
+//! rejected PIN is never retried automatically, since each retry brings the
+//! token closer to locking itself; the signer refuses to sign until
+//! [`PivSigner::set_pin`] supplies a new one. Slots configured to require a
+//! touch wait up to the configured timeout for the operator, and report
+//! [`TokenError::TouchTimeout`] rather than hanging a batch indefinitely.
+//!
+//! Enable with the `piv` feature.
+
+use crate::attestation::ProofType;
+use crate::error::{CepError, CepResult};
+use crate::hash::CanonicalHash;
+use crate::keys::encode_multibase;
+use crate::signer::Signer;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+pub use crate::token::{PivSlot, TokenError};
+
+/// How long to wait for a touch by default.
+pub const DEFAULT_TOUCH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// A PIV PIN. Debug output is redacted.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PivPin(String);
+
+impl PivPin {
+    /// Creates a PIN.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::Configuration` unless the PIN is 6 to 8 ASCII
+    /// characters, as PIV requires.
+    pub fn new(pin: &str) -> CepResult<Self> {
+        if !(6..=8).contains(&pin.len()) || !pin.is_ascii() {
+            return Err(CepError::Configuration(
+                "PIV PIN must be 6 to 8 ASCII characters".to_string(),
+            ));
+        }
+        Ok(Self(pin.to_string()))
+    }
+
+    /// The PIN's bytes, as sent to the card.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl fmt::Debug for PivPin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PivPin(<redacted>)")
+    }
+}
+
+/// The card operations a [`PivSigner`] needs.
+pub trait PivCard: Send {
+    /// Verifies the PIN for this session.
+    fn verify_pin(&mut self, pin: &PivPin) -> Result<(), TokenError>;
+
+    /// Returns true if signing with `slot` needs a touch.
+    fn touch_required(&mut self, slot: PivSlot) -> Result<bool, TokenError>;
+
+    /// Signs `digest` with the Ed25519 key in `slot`, waiting up to
+    /// `touch_timeout` for a touch if the slot needs one.
+    fn sign(
+        &mut self,
+        slot: PivSlot,
+        digest: &[u8; 32],
+        touch_timeout: Duration,
+    ) -> Result<[u8; 64], TokenError>;
+
+    /// Returns the Ed25519 public key in `slot`.
+    fn public_key(&mut self, slot: PivSlot) -> Result<[u8; 32], TokenError>;
+}
+
+/// Session state guarded together with the card.
+struct Session<C> {
+    card: C,
+    pin: Option<PivPin>,
+    verified: bool,
+}
+
+/// Signs with an Ed25519 key on a PIV token.
+pub struct PivSigner<C: PivCard> {
+    session: Mutex<Session<C>>,
+    slot: PivSlot,
+    touch_timeout: Option<Duration>,
+}
+
+impl<C: PivCard> fmt::Debug for PivSigner<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PivSigner")
+            .field("slot", &self.slot)
+            .field("touch_timeout", &self.touch_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: PivCard> PivSigner<C> {
+    /// Creates a signer for the digital signature slot (9C), waiting
+    /// [`DEFAULT_TOUCH_TIMEOUT`] for touches.
+    pub fn new(card: C, pin: PivPin) -> Self {
+        Self {
+            session: Mutex::new(Session {
+                card,
+                pin: Some(pin),
+                verified: false,
+            }),
+            slot: PivSlot::Signature,
+            touch_timeout: Some(DEFAULT_TOUCH_TIMEOUT),
+        }
+    }
+
+    /// Signs with `slot` instead of 9C.
+    pub fn with_slot(mut self, slot: PivSlot) -> Self {
+        self.slot = slot;
+        self
+    }
+
+    /// Waits up to `timeout` for a touch, or fails at once with
+    /// [`TokenError::TouchRequired`] if `None` (for unattended batch jobs).
+    pub fn with_touch_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.touch_timeout = timeout;
+        self
+    }
+
+    /// Replaces the PIN, e.g. after the operator re-enters a rejected one.
+    /// The new PIN is verified before the next signature.
+    pub fn set_pin(&self, pin: PivPin) {
+        let mut session = self.lock();
+        session.pin = Some(pin);
+        session.verified = false;
+    }
+
+    /// Returns the slot's public key, to publish as the verification method.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::HardwareToken` if the card fails.
+    pub fn public_key(&self) -> CepResult<[u8; 32]> {
+        Ok(self.lock().card.public_key(self.slot)?)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Session<C>> {
+        self.session
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Verifies the PIN if this session has not yet, and checks the touch
+    /// policy.
+    fn unlock(&self, session: &mut Session<C>) -> Result<Duration, TokenError> {
+        if !session.verified && self.slot != PivSlot::CardAuthentication {
+            // Take the PIN so a rejected one is not tried again.
+            let pin = session.pin.take().ok_or(TokenError::PinRequired)?;
+            session.card.verify_pin(&pin)?;
+            session.pin = Some(pin);
+            session.verified = true;
+        }
+        match self.touch_timeout {
+            Some(timeout) => Ok(timeout),
+            None if session.card.touch_required(self.slot)? => {
+                Err(TokenError::TouchRequired(self.slot))
+            }
+            None => Ok(Duration::ZERO),
+        }
+    }
+
+    fn sign_all(&self, hashes: &[CanonicalHash]) -> Result<Vec<String>, TokenError> {
+        let mut session = self.lock();
+        let timeout = self.unlock(&mut session)?;
+        hashes
+            .iter()
+            .map(|hash| {
+                let signature = session.card.sign(self.slot, &hash.as_bytes(), timeout)?;
+                Ok(encode_multibase(&signature))
+            })
+            .collect()
+    }
+}
+
+impl<C: PivCard> Signer for PivSigner<C> {
+    fn proof_type(&self) -> ProofType {
+        ProofType::Ed25519Signature2020
+    }
+
+    fn sign(&self, hash: &CanonicalHash) -> CepResult<String> {
+        let mut proofs = self.sign_all(std::slice::from_ref(hash))?;
+        Ok(proofs.remove(0))
+    }
+
+    /// Holds the card for the whole batch: one PIN check, then one signature
+    /// per hash. Cards sign serially, so there is nothing to parallelize.
+    fn sign_batch(&self, hashes: &[CanonicalHash]) -> CepResult<Vec<String>> {
+        Ok(self.sign_all(hashes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer as _, SigningKey, Verifier, VerifyingKey};
+
+    /// An in-memory card with a three-attempt PIN counter.
+    struct MockCard {
+        key: SigningKey,
+        pin: &'static str,
+        retries_left: u8,
+        touch: bool,
+        touched: bool,
+        pin_checks: usize,
+    }
+
+    fn card(touch: bool) -> MockCard {
+        MockCard {
+            key: SigningKey::from_bytes(&[8u8; 32]),
+            pin: "123456",
+            retries_left: 3,
+            touch,
+            touched: true,
+            pin_checks: 0,
+        }
+    }
+
+    impl PivCard for MockCard {
+        fn verify_pin(&mut self, pin: &PivPin) -> Result<(), TokenError> {
+            self.pin_checks += 1;
+            if self.retries_left == 0 {
+                return Err(TokenError::PinLocked);
+            }
+            if pin.as_bytes() != self.pin.as_bytes() {
+                self.retries_left -= 1;
+                return Err(TokenError::PinRejected {
+                    retries_left: self.retries_left,
+                });
+            }
+            self.retries_left = 3;
+            Ok(())
+        }
+
+        fn touch_required(&mut self, _slot: PivSlot) -> Result<bool, TokenError> {
+            Ok(self.touch)
+        }
+
+        fn sign(
+            &mut self,
+            _slot: PivSlot,
+            digest: &[u8; 32],
+            touch_timeout: Duration,
+        ) -> Result<[u8; 64], TokenError> {
+            if self.touch && !self.touched {
+                return Err(TokenError::TouchTimeout(touch_timeout));
+            }
+            Ok(self.key.sign(digest).to_bytes())
+        }
+
+        fn public_key(&mut self, _slot: PivSlot) -> Result<[u8; 32], TokenError> {
+            Ok(self.key.verifying_key().to_bytes())
+        }
+    }
+
+    fn pin(pin: &str) -> PivPin {
+        PivPin::new(pin).unwrap()
+    }
+
+    fn hashes(n: usize) -> Vec<CanonicalHash> {
+        (0..n)
+            .map(|i| CanonicalHash::from_canonical_string(&i.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_batch_verifies_pin_once() {
+        let signer = PivSigner::new(card(false), pin("123456"));
+        let key = VerifyingKey::from_bytes(&signer.public_key().unwrap()).unwrap();
+        let proofs = signer.sign_batch(&hashes(20)).unwrap();
+        signer.sign(&hashes(1)[0]).unwrap();
+        assert_eq!(signer.lock().card.pin_checks, 1);
+
+        let signature = bs58::decode(&proofs[3][1..]).into_vec().unwrap();
+        let signature = ed25519_dalek::Signature::from_slice(&signature).unwrap();
+        key.verify(&hashes(4)[3].as_bytes(), &signature).unwrap();
+    }
+
+    #[test]
+    fn test_rejected_pin_is_not_retried() {
+        let signer = PivSigner::new(card(false), pin("000000"));
+        assert!(matches!(
+            signer.sign_batch(&hashes(2)),
+            Err(CepError::HardwareToken(TokenError::PinRejected {
+                retries_left: 2
+            }))
+        ));
+        assert!(matches!(
+            signer.sign_batch(&hashes(2)),
+            Err(CepError::HardwareToken(TokenError::PinRequired))
+        ));
+        assert_eq!(signer.lock().card.pin_checks, 1);
+
+        signer.set_pin(pin("123456"));
+        assert_eq!(signer.sign_batch(&hashes(2)).unwrap().len(), 2);
+        assert!(PivPin::new("123").is_err());
+        assert_eq!(format!("{:?}", pin("123456")), "PivPin(<redacted>)");
+    }
+
+    #[test]
+    fn test_touch_policy() {
+        let unattended = PivSigner::new(card(true), pin("123456")).with_touch_timeout(None);
+        assert!(matches!(
+            unattended.sign(&hashes(1)[0]),
+            Err(CepError::HardwareToken(TokenError::TouchRequired(
+                PivSlot::Signature
+            )))
+        ));
+
+        let mut untouched = card(true);
+        untouched.touched = false;
+        let signer = PivSigner::new(untouched, pin("123456"))
+            .with_touch_timeout(Some(Duration::from_secs(5)));
+        let err = signer.sign(&hashes(1)[0]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "hardware token error: token touch not received within 5s"
+        );
+    }
+}
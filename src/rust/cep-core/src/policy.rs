@@ -0,0 +1,389 @@
+//! Signing policy enforcement for CEP records.
+//!
+//! A federation can restrict which proof types are acceptable for each record
+//! class (e.g. EntityRecords MUST use `Ed25519Signature2020`, audit entries MAY
+//! use `DataIntegrityProof`). The same [`SigningPolicy`] is evaluated when a
+//! record is sealed and again when it is verified: [`SigningPolicy::sign`]
+//! and [`SigningPolicy::verify`] refuse a disallowed proof type before any
+//! cryptography runs.
+//!
+//! [`verify_attestation`](crate::verify_attestation),
+//! [`AttestationSet`](crate::AttestationSet) and
+//! [`EndorsementChain`](crate::EndorsementChain) take an optional policy too,
+//! and apply it to the class named by the record's hash domain (see
+//! [`record_class`]).
+//!
+//! Policy failures are reported as [`CepError::PolicyViolation`], which is
+//! deliberately separate from cryptographic failures such as
+//! [`CepError::HashMismatch`]: a record can be perfectly intact and still be
+//! unacceptable under a federation's rules.
+//!
+//! Policies are stored as JSON:
+//!
+//! ```json
+//! {
+//!   "rules": { "entity": ["Ed25519Signature2020"] },
+//!   "default": ["Ed25519Signature2020", "JsonWebSignature2020"]
+//! }
+//! ```
+
+use crate::attestation::{Attestation, ProofType};
+use crate::canonical::Canonicalize;
+use crate::clock::Clock;
+use crate::error::{CepError, CepResult};
+use crate::signer::{Signer, Verifier};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+/// Record class of records without a hash domain. Only the policy's default
+/// allow-list applies to them, unless a rule names this class.
+pub const UNCLASSIFIED: &str = "unclassified";
+
+/// Returns the record class a policy applies to `record` as: the schema id of
+/// its hash domain without the `cep.` prefix, e.g. `"entity"` for
+/// `cep.entity`, or [`UNCLASSIFIED`] for types without a hash domain.
+pub fn record_class<T: Canonicalize + ?Sized>(record: &T) -> String {
+    match record.hash_domain() {
+        Some(domain) => domain
+            .schema_id
+            .strip_prefix("cep.")
+            .unwrap_or(&domain.schema_id)
+            .to_string(),
+        None => UNCLASSIFIED.to_string(),
+    }
+}
+
+/// Per-record-class allow-lists of proof types.
+///
+/// Record classes are the logical schema names used by the registry
+/// (`"entity"`, `"relationship"`, `"exchange"`, ...).
+///
+/// # Example
+///
+/// ```rust
+/// use cep_core::policy::SigningPolicy;
+/// use cep_core::ProofType;
+///
+/// let policy = SigningPolicy::new()
+///     .allow("entity", ["Ed25519Signature2020"])
+///     .allow("audit", ["Ed25519Signature2020", "DataIntegrityProof"]);
+///
+/// assert!(policy.is_allowed("entity", &ProofType::Ed25519Signature2020));
+/// assert!(!policy.is_allowed("entity", &ProofType::DataIntegrityProof));
+/// assert!(policy.is_allowed("audit", &ProofType::DataIntegrityProof));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningPolicy {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    rules: BTreeMap<String, BTreeSet<ProofType>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default: Option<BTreeSet<ProofType>>,
+}
+
+impl SigningPolicy {
+    /// Creates an empty policy that allows any proof type for any class.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts `record_class` to the given proof types.
+    ///
+    /// Calling this again for the same class adds to its allow-list.
+    pub fn allow<I, P>(mut self, record_class: &str, proof_types: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<ProofType>,
+    {
+        self.rules
+            .entry(record_class.to_string())
+            .or_default()
+            .extend(proof_types.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the allow-list used for record classes without an explicit rule.
+    ///
+    /// Without a default, unlisted classes accept any proof type.
+    pub fn with_default<I, P>(mut self, proof_types: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<ProofType>,
+    {
+        self.default = Some(proof_types.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Returns the allow-list that applies to `record_class`, if any.
+    pub fn allowed_proof_types(&self, record_class: &str) -> Option<&BTreeSet<ProofType>> {
+        self.rules.get(record_class).or(self.default.as_ref())
+    }
+
+    /// Loads a policy file.
+    pub fn load(path: &Path) -> CepResult<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            CepError::Configuration(format!(
+                "Failed to read policy file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Returns true if `proof_type` is acceptable for `record_class`.
+    pub fn is_allowed(&self, record_class: &str, proof_type: &ProofType) -> bool {
+        self.allowed_proof_types(record_class)
+            .is_none_or(|allowed| allowed.contains(proof_type))
+    }
+
+    /// Checks an attestation against the policy.
+    ///
+    /// Call this when sealing a record (before signing) and when verifying
+    /// one (alongside the cryptographic checks).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::PolicyViolation` if the attestation's proof type is
+    /// not allowed for `record_class`.
+    pub fn check(&self, record_class: &str, attestation: &Attestation) -> CepResult<()> {
+        self.check_proof_type(record_class, &attestation.proof_type)
+    }
+
+    /// Checks a proof type against the policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::PolicyViolation` if `proof_type` is not allowed for
+    /// `record_class`.
+    pub fn check_proof_type(&self, record_class: &str, proof_type: &ProofType) -> CepResult<()> {
+        if self.is_allowed(record_class, proof_type) {
+            return Ok(());
+        }
+        let allowed = self
+            .allowed_proof_types(record_class)
+            .map(|set| set.iter().map(ToString::to_string).collect())
+            .unwrap_or_default();
+        Err(CepError::PolicyViolation {
+            record_class: record_class.to_string(),
+            proof_type: proof_type.to_string(),
+            allowed,
+        })
+    }
+
+    /// Checks a proof type against the policy for `record`'s
+    /// [`record_class`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::PolicyViolation` if `proof_type` is not allowed.
+    pub fn check_record<T: Canonicalize + ?Sized>(
+        &self,
+        record: &T,
+        proof_type: &ProofType,
+    ) -> CepResult<()> {
+        self.check_proof_type(&record_class(record), proof_type)
+    }
+
+    /// Signs a `record_class` record like [`Attestation::sign`], if the
+    /// policy allows the signer's proof type.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::PolicyViolation` without signing if the proof type
+    /// is not allowed, and passes through the signer's errors.
+    pub fn sign<T: Canonicalize + ?Sized>(
+        &self,
+        record_class: &str,
+        record: &T,
+        signer: &dyn Signer,
+        clock: &dyn Clock,
+        attestor_id: String,
+        verification_method_uri: String,
+    ) -> CepResult<Attestation> {
+        self.check_proof_type(record_class, &signer.proof_type())?;
+        Attestation::sign(record, signer, clock, attestor_id, verification_method_uri)
+    }
+
+    /// Verifies a `record_class` record's attestation like
+    /// [`Attestation::verify`], if the policy allows its proof type.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::PolicyViolation` if the proof type is not allowed,
+    /// whether or not the proof itself is valid, and otherwise the errors of
+    /// [`Attestation::verify`].
+    pub fn verify<T: Canonicalize + ?Sized>(
+        &self,
+        record_class: &str,
+        record: &T,
+        attestation: &Attestation,
+        verifier: &dyn Verifier,
+    ) -> CepResult<()> {
+        self.check(record_class, attestation)?;
+        attestation.verify(record, verifier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FixedClock;
+    use crate::jws::{JwsSigner, JwsVerifier};
+    use crate::signer::{Ed25519Signer, Ed25519Verifier};
+    use ed25519_dalek::SigningKey;
+
+    fn attestation(proof_type: &str) -> Attestation {
+        Attestation::new(
+            "cep-entity:sam-uei:J6H4FB3N5YK7".to_string(),
+            "2025-11-28T14:30:00.000000Z".parse().unwrap(),
+            ProofType::from(proof_type),
+            "z3FXQqFwbZxKBxGxqFpCD...".to_string(),
+            "did:web:example.gov#key-1".to_string(),
+        )
+    }
+
+    fn federation_policy() -> SigningPolicy {
+        SigningPolicy::new()
+            .allow("entity", ["Ed25519Signature2020"])
+            .allow("audit", ["Ed25519Signature2020", "DataIntegrityProof"])
+    }
+
+    #[test]
+    fn test_empty_policy_allows_everything() {
+        let policy = SigningPolicy::new();
+        assert!(
+            policy
+                .check("entity", &attestation("DataIntegrityProof"))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_allowed_proof_type() {
+        let policy = federation_policy();
+        assert!(
+            policy
+                .check("entity", &attestation("Ed25519Signature2020"))
+                .is_ok()
+        );
+        assert!(
+            policy
+                .check("audit", &attestation("DataIntegrityProof"))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_violation_is_distinct_error() {
+        let policy = federation_policy();
+        let result = policy.check("entity", &attestation("DataIntegrityProof"));
+        match result {
+            Err(CepError::PolicyViolation {
+                record_class,
+                proof_type,
+                allowed,
+            }) => {
+                assert_eq!(record_class, "entity");
+                assert_eq!(proof_type, "DataIntegrityProof");
+                assert_eq!(allowed, vec!["Ed25519Signature2020".to_string()]);
+            }
+            other => panic!("Expected PolicyViolation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unlisted_class_uses_default() {
+        let policy = federation_policy().with_default(["Ed25519Signature2020"]);
+        assert!(
+            policy
+                .check("exchange", &attestation("DataIntegrityProof"))
+                .is_err()
+        );
+        assert!(
+            policy
+                .check("exchange", &attestation("Ed25519Signature2020"))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_unlisted_class_without_default_is_open() {
+        let policy = federation_policy();
+        assert!(
+            policy
+                .check("exchange", &attestation("DataIntegrityProof"))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_policy_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.json");
+        let json = r#"{
+            "rules": { "entity": ["Ed25519Signature2020"] },
+            "default": ["JsonWebSignature2020"]
+        }"#;
+        fs::write(&path, json).unwrap();
+        let policy = SigningPolicy::load(&path).unwrap();
+        assert_eq!(
+            policy,
+            SigningPolicy::new()
+                .allow("entity", [ProofType::Ed25519Signature2020])
+                .with_default([ProofType::JsonWebSignature2020])
+        );
+        let reparsed: SigningPolicy =
+            serde_json::from_str(&serde_json::to_string(&policy).unwrap()).unwrap();
+        assert_eq!(reparsed, policy);
+        assert!(SigningPolicy::load(&dir.path().join("missing.json")).is_err());
+    }
+
+    #[test]
+    fn test_sign_refuses_disallowed_proof_type() {
+        struct Note;
+        impl Canonicalize for Note {
+            fn canonical_fields(&self) -> BTreeMap<String, String> {
+                BTreeMap::from([("n".to_string(), "1".to_string())])
+            }
+        }
+        let key = SigningKey::from_bytes(&[3u8; 32]);
+        let clock = FixedClock::new("2025-11-28T14:30:00.000000Z".parse().unwrap());
+        let policy = federation_policy();
+        let sign = |signer: &dyn Signer| {
+            policy.sign(
+                "entity",
+                &Note,
+                signer,
+                &clock,
+                "did:web:example.gov".to_string(),
+                "did:web:example.gov#key-1".to_string(),
+            )
+        };
+
+        let jws = JwsSigner::new(key.clone());
+        assert!(matches!(sign(&jws), Err(CepError::PolicyViolation { .. })));
+        let attestation = sign(&Ed25519Signer::new(key.clone())).unwrap();
+        let verifier = Ed25519Verifier::new(key.verifying_key());
+        policy
+            .verify("entity", &Note, &attestation, &verifier)
+            .unwrap();
+
+        // A valid proof of a disallowed type is still refused.
+        let attestation = Attestation::sign(
+            &Note,
+            &jws,
+            &clock,
+            "did:web:example.gov".to_string(),
+            "did:web:example.gov#key-1".to_string(),
+        )
+        .unwrap();
+        let verifier = JwsVerifier::new(key.verifying_key());
+        attestation.verify(&Note, &verifier).unwrap();
+        assert!(matches!(
+            policy.verify("entity", &Note, &attestation, &verifier),
+            Err(CepError::PolicyViolation { .. })
+        ));
+    }
+}
@@ -0,0 +1,228 @@
+//! Receipts: signed acknowledgements of accepted records.
+//!
+//! When a node accepts a submitted record it returns a [`Receipt`] naming the
+//! record's canonical hash, the accepting node and the time of acceptance,
+//! attested with the node's key. The submitter can later show the receipt,
+//! together with the record and the node's public key, as proof that the
+//! node accepted exactly that record. No contact with the node is needed.
+
+use crate::attestation::Attestation;
+use crate::canonical::{Canonicalize, insert_required};
+use crate::clock::Clock;
+use crate::error::{CepError, CepResult};
+use crate::hash::CanonicalHash;
+use crate::signer::{Signer, Verifier};
+use crate::spec::{CanonicalSpec, FieldEncoding, TypeSpec};
+use crate::timestamp::CanonicalTimestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A node's signed acknowledgement that it accepted a record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Receipt {
+    /// Canonical hash of the accepted record.
+    pub submission_hash: CanonicalHash,
+
+    /// Identifier (typically a DID) of the node that accepted the record.
+    pub acceptor_node_id: String,
+
+    /// When the node accepted the record.
+    pub accepted_timestamp: CanonicalTimestamp,
+
+    /// The accepting node's attestation over this receipt.
+    pub attestation: Attestation,
+}
+
+impl Receipt {
+    /// Issues a receipt for `record`, signed with `signer`.
+    ///
+    /// The receipt is stamped with the time of `clock` and names
+    /// `verification_method` as the key to verify against.
+    ///
+    /// # Errors
+    ///
+    /// Passes through the signer's errors.
+    pub fn issue<T: Canonicalize + ?Sized>(
+        record: &T,
+        acceptor_node_id: &str,
+        verification_method: &str,
+        signer: &dyn Signer,
+        clock: &dyn Clock,
+    ) -> CepResult<Self> {
+        let accepted_timestamp = clock.now();
+        let mut receipt = Self {
+            submission_hash: record.calculate_hash(),
+            acceptor_node_id: acceptor_node_id.to_string(),
+            accepted_timestamp,
+            attestation: Attestation::new(
+                acceptor_node_id.to_string(),
+                accepted_timestamp,
+                signer.proof_type(),
+                String::new(),
+                verification_method.to_string(),
+            ),
+        };
+        let mut attestation = receipt.attestation.clone();
+        attestation.prove(&receipt, signer)?;
+        receipt.attestation = attestation;
+        Ok(receipt)
+    }
+
+    /// Verifies the receipt's signature with `verifier`, which holds the
+    /// acceptor's public key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidSignature` if the receipt was not attested by
+    /// the acceptor node, its proof type is not the verifier's, or the
+    /// signature does not verify.
+    pub fn verify_signature(&self, verifier: &dyn Verifier) -> CepResult<()> {
+        if self.attestation.attestor_id != self.acceptor_node_id {
+            return Err(CepError::InvalidSignature(format!(
+                "receipt from {} is attested by {}",
+                self.acceptor_node_id, self.attestation.attestor_id
+            )));
+        }
+        self.attestation.verify(self, verifier)
+    }
+
+    /// Verifies that this receipt acknowledges `record` and is validly signed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::HashMismatch` if the receipt is for a different
+    /// record, or the error from [`verify_signature`](Self::verify_signature).
+    pub fn verify<T: Canonicalize + ?Sized>(
+        &self,
+        record: &T,
+        verifier: &dyn Verifier,
+    ) -> CepResult<()> {
+        let actual = record.calculate_hash();
+        if actual != self.submission_hash {
+            return Err(CepError::HashMismatch {
+                expected: self.submission_hash.to_string(),
+                actual: actual.to_string(),
+            });
+        }
+        self.verify_signature(verifier)
+    }
+}
+
+impl Canonicalize for Receipt {
+    fn canonical_fields(&self) -> BTreeMap<String, String> {
+        let mut map = BTreeMap::new();
+
+        // Fields in alphabetical order
+        insert_required(
+            &mut map,
+            "acceptedTimestamp",
+            &self.accepted_timestamp.to_canonical_string(),
+        );
+        insert_required(&mut map, "acceptorNodeId", &self.acceptor_node_id);
+        insert_required(
+            &mut map,
+            "attestation",
+            &self.attestation.to_canonical_string(),
+        );
+        insert_required(&mut map, "submissionHash", self.submission_hash.as_hex());
+
+        map
+    }
+}
+
+impl CanonicalSpec for Receipt {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("Receipt")
+            .required("acceptedTimestamp", FieldEncoding::Timestamp)
+            .required("acceptorNodeId", FieldEncoding::String)
+            .required("attestation", FieldEncoding::nested("Attestation"))
+            .required("submissionHash", FieldEncoding::Hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::ProofType;
+    use crate::clock::FixedClock;
+    use crate::jws::{JwsSigner, JwsVerifier};
+    use crate::keys::KeyFile;
+    use crate::signer::{Ed25519Signer, Ed25519Verifier};
+
+    struct Submission(&'static str);
+
+    impl Canonicalize for Submission {
+        fn canonical_fields(&self) -> BTreeMap<String, String> {
+            let mut map = BTreeMap::new();
+            insert_required(&mut map, "name", self.0);
+            map
+        }
+    }
+
+    fn issue(signer: &dyn Signer) -> Receipt {
+        let clock = FixedClock::new("2026-02-01T12:00:00.000000Z".parse().unwrap());
+        Receipt::issue(
+            &Submission("Acme"),
+            "did:web:node.example.gov",
+            "did:web:node.example.gov#key-1",
+            signer,
+            &clock,
+        )
+        .unwrap()
+    }
+
+    fn verifier(keys: &KeyFile) -> Ed25519Verifier {
+        Ed25519Verifier::new(keys.public().verifying_key().unwrap())
+    }
+
+    #[test]
+    fn test_issue_and_verify_offline() {
+        let keys = KeyFile::generate_ed25519();
+        let receipt = issue(&Ed25519Signer::new(keys.signing_key().unwrap()));
+        assert_eq!(receipt.submission_hash, Submission("Acme").calculate_hash());
+
+        // Only the receipt, the record and the public key are needed.
+        let json = serde_json::to_string(&receipt).unwrap();
+        let received: Receipt = serde_json::from_str(&json).unwrap();
+        received
+            .verify(&Submission("Acme"), &verifier(&keys))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_any_signer_backend() {
+        let keys = KeyFile::generate_ed25519();
+        let receipt = issue(&JwsSigner::new(keys.signing_key().unwrap()));
+        assert_eq!(
+            receipt.attestation.proof_type,
+            ProofType::JsonWebSignature2020
+        );
+        let jws = JwsVerifier::new(keys.verifying_key().unwrap());
+        receipt.verify(&Submission("Acme"), &jws).unwrap();
+        assert!(matches!(
+            receipt.verify(&Submission("Acme"), &verifier(&keys)),
+            Err(CepError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_other_record_and_tampering() {
+        let keys = KeyFile::generate_ed25519();
+        let receipt = issue(&Ed25519Signer::new(keys.signing_key().unwrap()));
+        assert!(matches!(
+            receipt.verify(&Submission("Other"), &verifier(&keys)),
+            Err(CepError::HashMismatch { .. })
+        ));
+
+        let mut backdated = receipt.clone();
+        backdated.accepted_timestamp = "2026-01-01T12:00:00.000000Z".parse().unwrap();
+        assert!(matches!(
+            backdated.verify_signature(&verifier(&keys)),
+            Err(CepError::InvalidSignature(_))
+        ));
+
+        let other_node = KeyFile::generate_ed25519();
+        assert!(receipt.verify_signature(&verifier(&other_node)).is_err());
+    }
+}
@@ -0,0 +1,382 @@
+//! Set reconciliation over Merkle prefix trees.
+//!
+//! A [`PrefixTree`] arranges a node's record hashes by their hex prefixes.
+//! Each subtree has a digest over the hashes it holds, so two nodes whose
+//! subtrees have equal digests hold the same records there and need not look
+//! further. Subtrees with at most [`LEAF_SIZE`] hashes are leaves and list
+//! their hashes outright.
+//!
+//! Reconciliation is interactive. The initiator asks for the root, then only
+//! for the children whose digests differ from its own, until it reaches
+//! leaves and can name the differing hashes. The number of summaries
+//! exchanged grows with the size of the difference and the depth of the
+//! tree, not with the size of the stores.
+//!
+//! ```rust
+//! use cep_core::reconcile::{PrefixTree, Reconciliation};
+//! use cep_core::CanonicalHash;
+//!
+//! let hash = |i: u32| CanonicalHash::from_canonical_string(&i.to_string());
+//! let ours = PrefixTree::from_hashes((0..1000).map(hash));
+//! let theirs = PrefixTree::from_hashes((1..1001).map(hash));
+//!
+//! let mut session = Reconciliation::new();
+//! while let Some(prefixes) = session.next_request() {
+//!     // In a real sync this request goes over the wire.
+//!     let response = theirs.respond(&prefixes).unwrap();
+//!     session.apply(&ours, &response);
+//! }
+//! assert_eq!(session.missing_locally(), [hash(1000)]);
+//! assert_eq!(session.missing_remotely(), [hash(0)]);
+//! ```
+
+use crate::error::{CepError, CepResult};
+use crate::hash::CanonicalHash;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Subtrees with at most this many hashes are leaves. Both sides of a
+/// reconciliation must agree on it.
+pub const LEAF_SIZE: usize = 32;
+
+/// Maximum number of prefixes answered in one request.
+pub const MAX_PREFIXES_PER_REQUEST: usize = 4096;
+
+const HEX_DIGITS: &str = "0123456789abcdef";
+
+/// A subtree as described to a peer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeSummary {
+    /// The subtree's hex prefix (empty for the root).
+    pub prefix: String,
+
+    /// Number of hashes in the subtree.
+    pub count: u64,
+
+    /// Digest over the subtree.
+    pub digest: CanonicalHash,
+
+    /// For leaves, every hash in the subtree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hashes: Option<Vec<CanonicalHash>>,
+
+    /// For inner nodes, the digest of each non-empty child, keyed by the
+    /// next hex digit.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub children: BTreeMap<String, CanonicalHash>,
+}
+
+#[derive(Debug, Clone)]
+struct InnerNode {
+    count: u64,
+    digest: CanonicalHash,
+    children: BTreeMap<String, CanonicalHash>,
+}
+
+/// A Merkle prefix tree over a set of record hashes.
+#[derive(Debug, Clone, Default)]
+pub struct PrefixTree {
+    hashes: BTreeSet<String>,
+    inner: HashMap<String, InnerNode>,
+}
+
+impl PrefixTree {
+    /// Builds the tree over `hashes`. Duplicates are ignored.
+    pub fn from_hashes<I>(hashes: I) -> Self
+    where
+        I: IntoIterator<Item = CanonicalHash>,
+    {
+        let mut tree = Self {
+            hashes: hashes.into_iter().map(|h| h.as_hex().to_string()).collect(),
+            inner: HashMap::new(),
+        };
+        let sorted: Vec<String> = tree.hashes.iter().cloned().collect();
+        tree.build("", &sorted);
+        tree
+    }
+
+    /// Number of hashes in the tree.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Returns true if the tree holds no hashes.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// The root digest. Equal roots mean equal sets.
+    pub fn root(&self) -> CanonicalHash {
+        self.summary("").digest
+    }
+
+    /// Answers a peer's request for the subtrees at `prefixes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidHash` if a prefix is not lowercase hex of at
+    /// most 64 characters, or `CepError::ResourceLimit` if more than
+    /// [`MAX_PREFIXES_PER_REQUEST`] prefixes are requested.
+    pub fn respond(&self, prefixes: &[String]) -> CepResult<Vec<NodeSummary>> {
+        if prefixes.len() > MAX_PREFIXES_PER_REQUEST {
+            return Err(CepError::ResourceLimit {
+                limit: "prefixes per request".to_string(),
+                actual: prefixes.len(),
+                max: MAX_PREFIXES_PER_REQUEST,
+            });
+        }
+        prefixes
+            .iter()
+            .map(|prefix| {
+                if prefix.len() > 64 || !prefix.chars().all(|c| HEX_DIGITS.contains(c)) {
+                    return Err(CepError::InvalidHash(format!("prefix '{}'", prefix)));
+                }
+                Ok(self.summary(prefix))
+            })
+            .collect()
+    }
+
+    /// Describes the subtree at `prefix`.
+    pub fn summary(&self, prefix: &str) -> NodeSummary {
+        if let Some(node) = self.inner.get(prefix) {
+            return NodeSummary {
+                prefix: prefix.to_string(),
+                count: node.count,
+                digest: node.digest.clone(),
+                hashes: None,
+                children: node.children.clone(),
+            };
+        }
+        // Not an inner node, so the subtree is small enough to be a leaf.
+        let hashes: Vec<String> = self.hashes_under(prefix).cloned().collect();
+        NodeSummary {
+            prefix: prefix.to_string(),
+            count: hashes.len() as u64,
+            digest: leaf_digest(&hashes),
+            hashes: Some(
+                hashes
+                    .iter()
+                    .filter_map(|h| CanonicalHash::from_hex(h))
+                    .collect(),
+            ),
+            children: BTreeMap::new(),
+        }
+    }
+
+    fn hashes_under<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a String> + 'a {
+        self.hashes
+            .range(prefix.to_string()..)
+            .take_while(move |h| h.starts_with(prefix))
+    }
+
+    fn build(&mut self, prefix: &str, hashes: &[String]) -> CanonicalHash {
+        if hashes.len() <= LEAF_SIZE {
+            return leaf_digest(hashes);
+        }
+        let depth = prefix.len();
+        let mut children = BTreeMap::new();
+        let mut start = 0;
+        while start < hashes.len() {
+            let digit = &hashes[start][depth..=depth];
+            let end = start
+                + hashes[start..]
+                    .iter()
+                    .take_while(|h| &h[depth..=depth] == digit)
+                    .count();
+            let child = format!("{}{}", prefix, digit);
+            let digest = self.build(&child, &hashes[start..end]);
+            children.insert(digit.to_string(), digest);
+            start = end;
+        }
+        let digest = inner_digest(&children);
+        self.inner.insert(
+            prefix.to_string(),
+            InnerNode {
+                count: hashes.len() as u64,
+                digest: digest.clone(),
+                children,
+            },
+        );
+        digest
+    }
+}
+
+fn leaf_digest(hashes: &[String]) -> CanonicalHash {
+    CanonicalHash::from_canonical_string(&format!("leaf:{}", hashes.join(",")))
+}
+
+fn inner_digest(children: &BTreeMap<String, CanonicalHash>) -> CanonicalHash {
+    let parts: Vec<String> = children
+        .iter()
+        .map(|(digit, digest)| format!("{}={}", digit, digest))
+        .collect();
+    CanonicalHash::from_canonical_string(&format!("node:{}", parts.join(",")))
+}
+
+/// The initiator's side of a reconciliation.
+#[derive(Debug, Clone)]
+pub struct Reconciliation {
+    pending: Vec<String>,
+    missing_locally: BTreeSet<CanonicalHash>,
+    missing_remotely: BTreeSet<CanonicalHash>,
+    rounds: usize,
+    summaries_received: usize,
+}
+
+impl Default for Reconciliation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Reconciliation {
+    /// Starts a reconciliation at the root.
+    pub fn new() -> Self {
+        Self {
+            pending: vec![String::new()],
+            missing_locally: BTreeSet::new(),
+            missing_remotely: BTreeSet::new(),
+            rounds: 0,
+            summaries_received: 0,
+        }
+    }
+
+    /// The prefixes to request from the peer next, or `None` when the
+    /// reconciliation is complete.
+    pub fn next_request(&self) -> Option<Vec<String>> {
+        (!self.pending.is_empty()).then(|| self.pending.clone())
+    }
+
+    /// Compares the peer's `response` against `local` and queues the
+    /// differing children for the next round.
+    pub fn apply(&mut self, local: &PrefixTree, response: &[NodeSummary]) {
+        self.rounds += 1;
+        self.summaries_received += response.len();
+        self.pending.clear();
+        for remote in response {
+            let mine = local.summary(&remote.prefix);
+            if mine.digest == remote.digest {
+                continue;
+            }
+            if let Some(theirs) = &remote.hashes {
+                let mine: BTreeSet<&str> = local
+                    .hashes_under(&remote.prefix)
+                    .map(String::as_str)
+                    .collect();
+                let theirs_set: BTreeSet<&str> = theirs.iter().map(|h| h.as_hex()).collect();
+                self.missing_locally.extend(
+                    theirs
+                        .iter()
+                        .filter(|h| !mine.contains(h.as_hex()))
+                        .cloned(),
+                );
+                self.missing_remotely.extend(
+                    mine.iter()
+                        .filter(|h| !theirs_set.contains(*h))
+                        .filter_map(|h| CanonicalHash::from_hex(h)),
+                );
+                continue;
+            }
+            for digit in HEX_DIGITS.chars() {
+                let child = format!("{}{}", remote.prefix, digit);
+                match remote.children.get(&digit.to_string()) {
+                    Some(digest) => {
+                        if local.summary(&child).digest != *digest {
+                            self.pending.push(child);
+                        }
+                    }
+                    None => self.missing_remotely.extend(
+                        local
+                            .hashes_under(&child)
+                            .filter_map(|h| CanonicalHash::from_hex(h)),
+                    ),
+                }
+            }
+        }
+    }
+
+    /// Hashes the peer holds and we do not.
+    pub fn missing_locally(&self) -> Vec<CanonicalHash> {
+        self.missing_locally.iter().cloned().collect()
+    }
+
+    /// Hashes we hold and the peer does not.
+    pub fn missing_remotely(&self) -> Vec<CanonicalHash> {
+        self.missing_remotely.iter().cloned().collect()
+    }
+
+    /// Number of request/response rounds so far.
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// Number of subtree summaries received so far.
+    pub fn summaries_received(&self) -> usize {
+        self.summaries_received
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(i: u32) -> CanonicalHash {
+        CanonicalHash::from_canonical_string(&format!("record-{}", i))
+    }
+
+    fn reconcile(local: &PrefixTree, remote: &PrefixTree) -> Reconciliation {
+        let mut session = Reconciliation::new();
+        while let Some(prefixes) = session.next_request() {
+            session.apply(local, &remote.respond(&prefixes).unwrap());
+        }
+        session
+    }
+
+    #[test]
+    fn test_equal_sets_stop_at_root() {
+        let a = PrefixTree::from_hashes((0..5_000).map(hash));
+        let b = PrefixTree::from_hashes((0..5_000).rev().map(hash));
+        assert_eq!(a.root(), b.root());
+        let session = reconcile(&a, &b);
+        assert_eq!(session.rounds(), 1);
+        assert!(session.missing_locally().is_empty());
+        assert!(session.missing_remotely().is_empty());
+    }
+
+    #[test]
+    fn test_finds_small_difference_in_large_sets() {
+        let local = PrefixTree::from_hashes((0..50_000).filter(|i| i % 10_000 != 3).map(hash));
+        let remote = PrefixTree::from_hashes((0..50_000).filter(|i| *i != 42).map(hash));
+        let session = reconcile(&local, &remote);
+
+        let mut expected: Vec<_> = (0..5).map(|k| hash(k * 10_000 + 3)).collect();
+        expected.sort();
+        assert_eq!(session.missing_locally(), expected);
+        assert_eq!(session.missing_remotely(), [hash(42)]);
+        // O(diff · depth), not O(total).
+        assert!(
+            session.summaries_received() < 100,
+            "{}",
+            session.summaries_received()
+        );
+    }
+
+    #[test]
+    fn test_empty_sides_and_bad_prefixes() {
+        let empty = PrefixTree::default();
+        let full = PrefixTree::from_hashes((0..100).map(hash));
+        assert_eq!(reconcile(&empty, &full).missing_locally().len(), 100);
+        assert_eq!(reconcile(&full, &empty).missing_remotely().len(), 100);
+
+        assert!(matches!(
+            full.respond(&["xyz".to_string()]),
+            Err(CepError::InvalidHash(_))
+        ));
+        let too_many = vec![String::new(); MAX_PREFIXES_PER_REQUEST + 1];
+        assert!(matches!(
+            full.respond(&too_many),
+            Err(CepError::ResourceLimit { .. })
+        ));
+    }
+}
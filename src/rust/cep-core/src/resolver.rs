@@ -0,0 +1,276 @@
+//! Verification method resolution and caching.
+//!
+//! Verifying an attestation requires the attestor's public keys, which are
+//! usually obtained by resolving a DID document. Resolution is slow, so
+//! [`CachingResolver`] wraps any [`VerificationMethodResolver`] with a
+//! time-to-live cache:
+//!
+//! - Successful lookups are cached for [`ResolverCacheConfig::ttl`].
+//! - "Not found" results are cached for [`ResolverCacheConfig::negative_ttl`],
+//!   so repeated lookups of unknown attestors do not hammer the resolver.
+//! - Resolver errors are never cached.
+//!
+//! `CachingResolver` itself implements `VerificationMethodResolver`, so a
+//! single `Arc<CachingResolver<_>>` can be shared by every verification path:
+//! [`verify_attestation`](crate::verify_attestation),
+//! [`AttestationSet`](crate::AttestationSet) and
+//! [`EndorsementChain`](crate::EndorsementChain) all take the resolver as a
+//! `&dyn VerificationMethodResolver`.
+
+use crate::clock::{Clock, SystemClock};
+use crate::error::CepResult;
+use crate::timestamp::CanonicalTimestamp;
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A public key published by an attestor.
+///
+/// Serializes as a DID-document verification method
+/// (`id`, `type`, `publicKeyMultibase`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationKey {
+    /// Verification method URI (e.g. "did:web:example.gov#key-1").
+    pub id: String,
+    /// Key type (e.g. "Ed25519VerificationKey2020").
+    #[serde(rename = "type")]
+    pub key_type: String,
+    /// Multibase-encoded public key.
+    pub public_key_multibase: String,
+}
+
+/// Resolves an attestor id to its published verification keys.
+pub trait VerificationMethodResolver: Send + Sync {
+    /// Returns the attestor's keys, `Ok(None)` if the attestor is unknown, or
+    /// an error if resolution failed and may succeed on retry.
+    fn resolve(&self, attestor_id: &str) -> CepResult<Option<Vec<VerificationKey>>>;
+}
+
+/// Cache settings for [`CachingResolver`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolverCacheConfig {
+    /// How long a successful lookup stays cached.
+    pub ttl: Duration,
+    /// How long a "not found" result stays cached.
+    pub negative_ttl: Duration,
+    /// Maximum number of cached attestors.
+    pub max_entries: usize,
+}
+
+impl Default for ResolverCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::minutes(5),
+            negative_ttl: Duration::seconds(30),
+            max_entries: 1024,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    keys: Option<Vec<VerificationKey>>,
+    expires_at: CanonicalTimestamp,
+}
+
+/// A [`VerificationMethodResolver`] that caches results of another resolver.
+pub struct CachingResolver<R> {
+    inner: R,
+    config: ResolverCacheConfig,
+    clock: Arc<dyn Clock>,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl<R: VerificationMethodResolver> CachingResolver<R> {
+    /// Wraps `inner` with a cache using the system clock.
+    pub fn new(inner: R, config: ResolverCacheConfig) -> Self {
+        Self {
+            inner,
+            config,
+            clock: Arc::new(SystemClock),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Uses `clock` to decide when entries expire.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Returns the cache settings.
+    pub fn config(&self) -> &ResolverCacheConfig {
+        &self.config
+    }
+
+    /// Drops the cached entry for `attestor_id`, e.g. after a key rotation.
+    pub fn invalidate(&self, attestor_id: &str) {
+        self.entries.lock().unwrap().remove(attestor_id);
+    }
+
+    /// Drops all cached entries.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// Returns the number of cached entries, including expired ones not yet evicted.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Returns true if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn insert(
+        &self,
+        attestor_id: &str,
+        keys: Option<Vec<VerificationKey>>,
+        now: CanonicalTimestamp,
+    ) {
+        if self.config.max_entries == 0 {
+            return;
+        }
+        let ttl = if keys.is_some() {
+            self.config.ttl
+        } else {
+            self.config.negative_ttl
+        };
+        let expires_at = CanonicalTimestamp::new(now.as_datetime() + ttl);
+
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.config.max_entries && !entries.contains_key(attestor_id) {
+            entries.retain(|_, entry| entry.expires_at > now);
+            if entries.len() >= self.config.max_entries
+                && let Some(oldest) = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.expires_at)
+                    .map(|(id, _)| id.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(attestor_id.to_string(), CacheEntry { keys, expires_at });
+    }
+}
+
+impl<R: VerificationMethodResolver> VerificationMethodResolver for CachingResolver<R> {
+    fn resolve(&self, attestor_id: &str) -> CepResult<Option<Vec<VerificationKey>>> {
+        let now = self.clock.now();
+        if let Some(entry) = self.entries.lock().unwrap().get(attestor_id)
+            && entry.expires_at > now
+        {
+            return Ok(entry.keys.clone());
+        }
+
+        let keys = self.inner.resolve(attestor_id)?;
+        self.insert(attestor_id, keys.clone(), now);
+        Ok(keys)
+    }
+}
+
+impl<T: VerificationMethodResolver + ?Sized> VerificationMethodResolver for Arc<T> {
+    fn resolve(&self, attestor_id: &str) -> CepResult<Option<Vec<VerificationKey>>> {
+        (**self).resolve(attestor_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::OffsetClock;
+    use crate::error::CepError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Resolver that knows one attestor and counts calls.
+    #[derive(Default)]
+    struct CountingResolver {
+        calls: AtomicUsize,
+        fail: bool,
+    }
+
+    impl VerificationMethodResolver for CountingResolver {
+        fn resolve(&self, attestor_id: &str) -> CepResult<Option<Vec<VerificationKey>>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.fail {
+                return Err(CepError::Configuration("resolver offline".to_string()));
+            }
+            if attestor_id != "did:web:example.gov" {
+                return Ok(None);
+            }
+            Ok(Some(vec![VerificationKey {
+                id: "did:web:example.gov#key-1".to_string(),
+                key_type: "Ed25519VerificationKey2020".to_string(),
+                public_key_multibase: "z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"
+                    .to_string(),
+            }]))
+        }
+    }
+
+    /// A clock that advances 10 seconds per call.
+    fn clock() -> Arc<dyn Clock> {
+        Arc::new(OffsetClock::new(
+            "2025-11-28T14:30:00.000000Z".parse().unwrap(),
+            Duration::seconds(10),
+        ))
+    }
+
+    fn config() -> ResolverCacheConfig {
+        ResolverCacheConfig {
+            ttl: Duration::seconds(25),
+            negative_ttl: Duration::seconds(5),
+            max_entries: 2,
+        }
+    }
+
+    #[test]
+    fn test_positive_hits_are_cached_until_ttl() {
+        let cache = CachingResolver::new(CountingResolver::default(), config()).with_clock(clock());
+        for _ in 0..3 {
+            assert!(cache.resolve("did:web:example.gov").unwrap().is_some());
+        }
+        assert_eq!(cache.inner.calls.load(Ordering::SeqCst), 1);
+
+        // Fourth call is 30s after the first: past the 25s TTL.
+        cache.resolve("did:web:example.gov").unwrap();
+        assert_eq!(cache.inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_negative_results_use_negative_ttl() {
+        let cache = CachingResolver::new(CountingResolver::default(), config()).with_clock(clock());
+        assert!(cache.resolve("did:web:unknown.gov").unwrap().is_none());
+        // 10s later: the 5s negative entry has expired.
+        assert!(cache.resolve("did:web:unknown.gov").unwrap().is_none());
+        assert_eq!(cache.inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_errors_are_not_cached() {
+        let resolver = CountingResolver {
+            fail: true,
+            ..Default::default()
+        };
+        let cache = CachingResolver::new(resolver, config()).with_clock(clock());
+        assert!(cache.resolve("did:web:example.gov").is_err());
+        assert!(cache.resolve("did:web:example.gov").is_err());
+        assert_eq!(cache.inner.calls.load(Ordering::SeqCst), 2);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_invalidate_and_capacity() {
+        let cache = CachingResolver::new(CountingResolver::default(), config()).with_clock(clock());
+        cache.resolve("did:web:example.gov").unwrap();
+        cache.invalidate("did:web:example.gov");
+        assert!(cache.is_empty());
+
+        cache.resolve("did:web:a.gov").unwrap();
+        cache.resolve("did:web:b.gov").unwrap();
+        cache.resolve("did:web:c.gov").unwrap();
+        assert!(cache.len() <= 2);
+    }
+}
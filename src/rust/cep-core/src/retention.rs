@@ -0,0 +1,233 @@
+//! Retention policies for superseded revisions.
+//!
+//! A [`RetentionPolicy`] decides which revisions of a chain keep their full
+//! content. Everything else is replaced by a [`HashStub`], so the compacted
+//! chain still passes [`verify_chain`]. The rules:
+//!
+//! - The head (latest revision) is always kept.
+//! - At most [`keep_revisions`](RetentionPolicy::keep_revisions) superseded
+//!   revisions are kept, newest first.
+//! - Revoked records keep their whole history for
+//!   [`keep_revoked_for`](RetentionPolicy::keep_revoked_for) after
+//!   revocation, then are compacted down to the head.
+//! - [Pinned](RetentionPolicy::with_pinned) chains, such as federation roots
+//!   and trust registries, are never compacted.
+//!
+//! The default policy keeps everything.
+
+use crate::chain::{ChainLink, HashStub, Revisioned, verify_chain};
+use crate::clock::Clock;
+use crate::error::CepResult;
+use chrono::Duration;
+use std::collections::BTreeSet;
+
+/// Which superseded revisions keep their content.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Superseded revisions to keep per chain; `None` keeps all.
+    pub keep_revisions: Option<usize>,
+
+    /// How long a revoked chain keeps its full history; `None` applies
+    /// `keep_revisions` to revoked chains like any other.
+    pub keep_revoked_for: Option<Duration>,
+
+    /// Verifiable IDs whose chains are kept in full regardless of the other
+    /// rules.
+    pub pinned: BTreeSet<String>,
+}
+
+/// The result of compacting one chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Compaction<T> {
+    /// The compacted chain, first revision to head.
+    pub chain: Vec<ChainLink<T>>,
+
+    /// Number of revisions replaced by stubs in this pass.
+    pub stubbed: usize,
+}
+
+impl RetentionPolicy {
+    /// A policy that keeps every revision.
+    pub fn keep_all() -> Self {
+        Self::default()
+    }
+
+    /// Keeps at most `n` superseded revisions per chain.
+    pub fn with_keep_revisions(mut self, n: usize) -> Self {
+        self.keep_revisions = Some(n);
+        self
+    }
+
+    /// Keeps the full history of revoked chains for `period` after revocation.
+    pub fn with_keep_revoked_for(mut self, period: Duration) -> Self {
+        self.keep_revoked_for = Some(period);
+        self
+    }
+
+    /// Pins the chain of `verifiable_id` so it is never compacted.
+    pub fn with_pinned(mut self, verifiable_id: &str) -> Self {
+        self.pinned.insert(verifiable_id.to_string());
+        self
+    }
+
+    /// Returns true if the chain of `verifiable_id` is pinned.
+    pub fn is_pinned(&self, verifiable_id: &str) -> bool {
+        self.pinned.contains(verifiable_id)
+    }
+
+    /// Returns how many superseded revisions of a chain with `head` to keep.
+    fn superseded_to_keep<T: Revisioned>(&self, head: &T, clock: &dyn Clock) -> Option<usize> {
+        if let (Some(revoked_at), Some(period)) = (head.revoked_at(), self.keep_revoked_for) {
+            let expires = revoked_at.as_datetime() + period;
+            return if clock.now().as_datetime() < expires {
+                None
+            } else {
+                Some(0)
+            };
+        }
+        self.keep_revisions
+    }
+
+    /// Compacts a chain ordered from first revision to head.
+    ///
+    /// The chain is verified before and after compaction, so a broken chain is
+    /// never silently rewritten.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::RevisionChain` if the chain does not verify.
+    pub fn compact<T: Revisioned>(
+        &self,
+        chain: Vec<ChainLink<T>>,
+        clock: &dyn Clock,
+    ) -> CepResult<Compaction<T>> {
+        verify_chain(&chain)?;
+        if chain
+            .first()
+            .is_some_and(|link| self.is_pinned(link.verifiable_id()))
+        {
+            return Ok(Compaction { chain, stubbed: 0 });
+        }
+
+        // If the head is a stub or tombstone there is no status to read, so
+        // only `keep_revisions` applies.
+        let keep = match chain.last().and_then(ChainLink::record) {
+            Some(head) => self.superseded_to_keep(head, clock),
+            None => self.keep_revisions,
+        };
+        let superseded = chain.len() - 1;
+        let cutoff = keep.map_or(0, |keep| superseded.saturating_sub(keep));
+
+        let mut stubbed = 0;
+        let chain: Vec<ChainLink<T>> = chain
+            .into_iter()
+            .enumerate()
+            .map(|(i, link)| match link {
+                ChainLink::Record(record) if i < cutoff => {
+                    stubbed += 1;
+                    ChainLink::Stub(HashStub::of(&record))
+                }
+                link => link,
+            })
+            .collect();
+
+        verify_chain(&chain)?;
+        Ok(Compaction { chain, stubbed })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::tests::{Revision, chain};
+    use crate::clock::FixedClock;
+    use crate::timestamp::CanonicalTimestamp;
+
+    fn ts(s: &str) -> CanonicalTimestamp {
+        s.parse().unwrap()
+    }
+
+    fn kept(compaction: &Compaction<Revision>) -> Vec<u32> {
+        compaction
+            .chain
+            .iter()
+            .filter_map(|link| link.record().map(|r| r.number))
+            .collect()
+    }
+
+    #[test]
+    fn test_keep_revisions() {
+        let clock = FixedClock::new(ts("2030-01-01T00:00:00.000000Z"));
+
+        let all = RetentionPolicy::keep_all()
+            .compact(chain(5), &clock)
+            .unwrap();
+        assert_eq!(kept(&all), vec![1, 2, 3, 4, 5]);
+        assert_eq!(all.stubbed, 0);
+
+        let policy = RetentionPolicy::keep_all().with_keep_revisions(2);
+        let compacted = policy.compact(chain(5), &clock).unwrap();
+        assert_eq!(kept(&compacted), vec![3, 4, 5]);
+        assert_eq!(compacted.stubbed, 2);
+        assert_eq!(compacted.chain.len(), 5);
+
+        // Compacting again is a no-op.
+        let again = policy.compact(compacted.chain, &clock).unwrap();
+        assert_eq!(again.stubbed, 0);
+
+        // The head survives even when no history is kept.
+        let head_only = RetentionPolicy::keep_all()
+            .with_keep_revisions(0)
+            .compact(chain(3), &clock)
+            .unwrap();
+        assert_eq!(kept(&head_only), vec![3]);
+    }
+
+    #[test]
+    fn test_keep_revoked_for() {
+        let mut links = chain(3);
+        if let Some(ChainLink::Record(head)) = links.last_mut() {
+            head.revoked_at = Some(ts("2025-01-01T00:00:00.000000Z"));
+        }
+        let policy = RetentionPolicy::keep_all()
+            .with_keep_revisions(1)
+            .with_keep_revoked_for(Duration::days(365 * 7));
+
+        let within = FixedClock::new(ts("2031-06-01T00:00:00.000000Z"));
+        let compacted = policy.compact(links.clone(), &within).unwrap();
+        assert_eq!(kept(&compacted), vec![1, 2, 3]);
+
+        let after = FixedClock::new(ts("2032-01-01T00:00:00.000000Z"));
+        let compacted = policy.compact(links, &after).unwrap();
+        assert_eq!(kept(&compacted), vec![3]);
+    }
+
+    #[test]
+    fn test_pinned_chain_is_never_compacted() {
+        let clock = FixedClock::new(ts("2030-01-01T00:00:00.000000Z"));
+        let links = chain(4);
+        let id = links[0].verifiable_id().to_string();
+        let policy = RetentionPolicy::keep_all()
+            .with_keep_revisions(0)
+            .with_pinned(&id);
+        assert!(policy.is_pinned(&id));
+
+        let compacted = policy.compact(links.clone(), &clock).unwrap();
+        assert_eq!(kept(&compacted), vec![1, 2, 3, 4]);
+        assert_eq!(compacted.stubbed, 0);
+
+        let unpinned = RetentionPolicy::keep_all().with_keep_revisions(0);
+        assert_eq!(unpinned.compact(links, &clock).unwrap().stubbed, 3);
+    }
+
+    #[test]
+    fn test_broken_chain_is_not_compacted() {
+        let mut links = chain(3);
+        links.swap(0, 1);
+        assert!(
+            RetentionPolicy::keep_all()
+                .compact(links, &FixedClock::new(ts("2030-01-01T00:00:00.000000Z")))
+                .is_err()
+        );
+    }
+}
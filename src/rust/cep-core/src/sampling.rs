@@ -0,0 +1,333 @@
+//! Sampling-based spot-check verification.
+//!
+//! Verifying every record of a large store is too slow to run daily. A
+//! [`SamplingPlan`] instead verifies a random sample just large enough to
+//! estimate the corruption rate within a chosen margin at a chosen
+//! confidence, and reports the estimate with its confidence interval:
+//!
+//! ```rust
+//! use cep_core::sampling::SamplingPlan;
+//!
+//! let plan = SamplingPlan::new(0.95, 0.01).unwrap();
+//! assert_eq!(plan.sample_size(50_000_000), 9_602);
+//!
+//! let report = plan.spot_check(50_000_000, 42, |_index| Ok(()));
+//! assert_eq!(report.failures.len(), 0);
+//! assert!(report.upper_bound < 0.001);
+//! ```
+//!
+//! The sample is drawn from a seeded generator and the seed is part of the
+//! [`SpotCheckReport`], so an auditor can re-verify exactly the same records.
+
+use crate::error::{CepError, CepResult};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// How large a sample to verify.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SamplingPlan {
+    /// Confidence level of the reported interval, e.g. `0.95`.
+    pub confidence: f64,
+    /// Largest acceptable half-width of the interval, e.g. `0.01`.
+    pub margin: f64,
+}
+
+impl Default for SamplingPlan {
+    /// 95% confidence, ±1%.
+    fn default() -> Self {
+        Self {
+            confidence: 0.95,
+            margin: 0.01,
+        }
+    }
+}
+
+/// One sampled record that failed verification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SampleFailure {
+    /// Position of the record in the population.
+    pub index: usize,
+    /// Why verification failed.
+    pub error: String,
+}
+
+/// The outcome of a spot check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotCheckReport {
+    /// The plan the sample was sized by.
+    pub plan: SamplingPlan,
+    /// Number of records in the population.
+    pub population: usize,
+    /// Seed the sample was drawn with.
+    pub seed: u64,
+    /// Number of records verified.
+    pub sample_size: usize,
+    /// Sampled records that failed verification.
+    pub failures: Vec<SampleFailure>,
+    /// Failures as a fraction of the sample.
+    pub estimated_rate: f64,
+    /// Lower bound of the corruption rate at `plan.confidence`.
+    pub lower_bound: f64,
+    /// Upper bound of the corruption rate at `plan.confidence`.
+    pub upper_bound: f64,
+}
+
+impl SamplingPlan {
+    /// Creates a plan.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::Configuration` unless both `confidence` and
+    /// `margin` are strictly between 0 and 1.
+    pub fn new(confidence: f64, margin: f64) -> CepResult<Self> {
+        for (name, value) in [("confidence", confidence), ("margin", margin)] {
+            if !(value > 0.0 && value < 1.0) {
+                return Err(CepError::Configuration(format!(
+                    "sampling {} must be between 0 and 1, got {}",
+                    name, value
+                )));
+            }
+        }
+        Ok(Self { confidence, margin })
+    }
+
+    /// Number of records to verify out of `population`.
+    ///
+    /// Uses Cochran's formula at the worst-case rate of 50% with the finite
+    /// population correction, so small populations are verified (almost) in
+    /// full.
+    pub fn sample_size(&self, population: usize) -> usize {
+        if population == 0 {
+            return 0;
+        }
+        let z = self.z();
+        let n0 = z * z * 0.25 / (self.margin * self.margin);
+        let n = n0 / (1.0 + (n0 - 1.0) / population as f64);
+        (n.ceil() as usize).min(population)
+    }
+
+    /// The sorted record indices to verify, drawn with `seed`.
+    pub fn sample_indices(&self, population: usize, seed: u64) -> Vec<usize> {
+        let size = self.sample_size(population);
+        if size == population {
+            return (0..population).collect();
+        }
+        // Floyd's algorithm: `size` distinct indices without materializing
+        // the population.
+        let mut rng = SplitMix64(seed);
+        let mut chosen = BTreeSet::new();
+        for upper in (population - size)..population {
+            let candidate = rng.below(upper as u64 + 1) as usize;
+            if !chosen.insert(candidate) {
+                chosen.insert(upper);
+            }
+        }
+        chosen.into_iter().collect()
+    }
+
+    /// Verifies a sample drawn with `seed`, calling `verify` with the index
+    /// of each sampled record.
+    pub fn spot_check<F>(&self, population: usize, seed: u64, mut verify: F) -> SpotCheckReport
+    where
+        F: FnMut(usize) -> CepResult<()>,
+    {
+        let indices = self.sample_indices(population, seed);
+        let failures: Vec<SampleFailure> = indices
+            .iter()
+            .filter_map(|&index| {
+                verify(index).err().map(|e| SampleFailure {
+                    index,
+                    error: e.to_string(),
+                })
+            })
+            .collect();
+        let sample_size = indices.len();
+        let estimated_rate = if sample_size == 0 {
+            0.0
+        } else {
+            failures.len() as f64 / sample_size as f64
+        };
+        let (lower_bound, upper_bound) = if sample_size == population {
+            // Everything was verified: the rate is exact.
+            (estimated_rate, estimated_rate)
+        } else {
+            wilson_interval(failures.len(), sample_size, self.z())
+        };
+        SpotCheckReport {
+            plan: *self,
+            population,
+            seed,
+            sample_size,
+            failures,
+            estimated_rate,
+            lower_bound,
+            upper_bound,
+        }
+    }
+
+    /// Verifies a sample of `records` drawn with a fresh random seed.
+    pub fn spot_check_records<T, F>(&self, records: &[T], mut verify: F) -> SpotCheckReport
+    where
+        F: FnMut(&T) -> CepResult<()>,
+    {
+        self.spot_check(records.len(), OsRng.next_u64(), |index| {
+            verify(&records[index])
+        })
+    }
+
+    /// Two-sided standard normal critical value for the confidence level.
+    fn z(&self) -> f64 {
+        normal_quantile(1.0 - (1.0 - self.confidence) / 2.0)
+    }
+}
+
+/// Wilson score interval for `failures` out of `n`.
+fn wilson_interval(failures: usize, n: usize, z: f64) -> (f64, f64) {
+    let n = n as f64;
+    let p = failures as f64 / n;
+    let z2 = z * z;
+    let denominator = 1.0 + z2 / n;
+    let center = (p + z2 / (2.0 * n)) / denominator;
+    let half_width = z / denominator * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+    (
+        (center - half_width).max(0.0),
+        (center + half_width).min(1.0),
+    )
+}
+
+/// Inverse of the standard normal CDF (Acklam's rational approximation,
+/// relative error below 1.2e-9).
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Small seedable generator, so a sample can be reproduced from its seed.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..bound` (Lemire's multiply-shift; the bias is
+    /// negligible for sampling).
+    fn below(&mut self, bound: u64) -> u64 {
+        ((self.next() as u128 * bound as u128) >> 64) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_sizes() {
+        assert!((normal_quantile(0.975) - 1.959_964).abs() < 1e-6);
+        let plan = SamplingPlan::default();
+        assert_eq!(plan.sample_size(0), 0);
+        assert_eq!(plan.sample_size(4), 4);
+        assert_eq!(plan.sample_size(100), 99);
+        assert_eq!(plan.sample_size(50_000_000), 9_602);
+        assert!(
+            SamplingPlan::new(0.99, 0.01)
+                .unwrap()
+                .sample_size(50_000_000)
+                > 16_000
+        );
+        assert!(SamplingPlan::new(1.0, 0.01).is_err());
+        assert!(SamplingPlan::new(0.95, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_sample_is_reproducible_and_distinct() {
+        let plan = SamplingPlan::new(0.95, 0.05).unwrap();
+        let first = plan.sample_indices(1_000_000, 7);
+        assert_eq!(first.len(), plan.sample_size(1_000_000));
+        assert_eq!(first, plan.sample_indices(1_000_000, 7));
+        assert_ne!(first, plan.sample_indices(1_000_000, 8));
+        assert!(first.windows(2).all(|w| w[0] < w[1]));
+        assert!(*first.last().unwrap() < 1_000_000);
+    }
+
+    #[test]
+    fn test_interval_covers_true_corruption_rate() {
+        // Every 50th record is corrupt: a true rate of 2%.
+        let plan = SamplingPlan::default();
+        let report = plan.spot_check(10_000_000, 2026, |index| {
+            if index % 50 == 0 {
+                Err(CepError::InvalidSignature("bad".to_string()))
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(report.sample_size, 9_595);
+        assert!(!report.failures.is_empty());
+        assert!(report.lower_bound < 0.02 && 0.02 < report.upper_bound);
+        assert!(report.upper_bound - report.lower_bound < 2.0 * plan.margin);
+    }
+
+    #[test]
+    fn test_small_population_is_exact() {
+        let records = [true, false, true, true];
+        let report = SamplingPlan::default().spot_check_records(&records, |ok| match ok {
+            true => Ok(()),
+            false => Err(CepError::InvalidSignature("bad".to_string())),
+        });
+        assert_eq!(report.sample_size, 4);
+        assert_eq!(report.failures[0].index, 1);
+        assert_eq!(report.lower_bound, 0.25);
+        assert_eq!(report.upper_bound, 0.25);
+    }
+}
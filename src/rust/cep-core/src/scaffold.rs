@@ -0,0 +1,591 @@
+//! Minimal record templates generated from the schemas.
+//!
+//! [`SchemaRegistry::scaffold`] walks a schema and builds the smallest
+//! instance it accepts: every required field, and nothing else, filled with
+//! a placeholder of the right shape. Integrators start from the template
+//! instead of an empty file, and replace the placeholders.
+//!
+//! ```rust
+//! use cep_core::SchemaRegistry;
+//!
+//! let registry = SchemaRegistry::new().unwrap();
+//! let template = registry.scaffold("entity").unwrap();
+//! assert_eq!(template["schemaVersion"], "1.0.0");
+//! assert_eq!(template["status"]["statusCode"], "ACTIVE");
+//! assert!(template.get("legalNameNormalized").is_none());
+//! ```
+//!
+//! Placeholders are chosen per keyword, in this order:
+//!
+//! | Keyword | Placeholder |
+//! |---|---|
+//! | `const`, `default` | that value |
+//! | `enum` | the first member |
+//! | `format` | a sample, e.g. `https://example.org/<field>` for `uri` |
+//! | `pattern` | [`PLACEHOLDER_TIMESTAMP`] if it matches, else a string built from the pattern |
+//! | `type` | `<field>` for strings, the `minimum` or 0 for numbers, `false` |
+//!
+//! Of a `oneOf` or `anyOf`, the first branch is used. Conditional
+//! (`if`/`then`) requirements only apply to optional fields, which are left
+//! out, so they are not looked at.
+
+use crate::error::{CepError, CepResult};
+use crate::schema_registry::SchemaRegistry;
+use serde_json::{Map, Value};
+
+/// Placeholder for timestamp fields, in canonical form.
+pub const PLACEHOLDER_TIMESTAMP: &str = "2000-01-01T00:00:00.000000Z";
+
+/// How deeply `$ref`s and nested schemas are followed before giving up.
+const MAX_DEPTH: usize = 64;
+
+impl SchemaRegistry {
+    /// Builds a minimal valid instance of a schema.
+    ///
+    /// `schema_id` is a schema `$id`, a schema name (e.g. `"entity"`) for
+    /// the current version, or `name@version` (e.g. `"entity@1.0"`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::UnknownSchema` if the schema, or a schema it refers
+    /// to, is not in the registry, and `CepError::Configuration` if its
+    /// `$ref`s are nested more than 64 deep.
+    pub fn scaffold(&self, schema_id: &str) -> CepResult<Value> {
+        let schema = match self.get_registry().get(schema_id) {
+            Some(schema) => schema.clone(),
+            None => match schema_id.split_once('@') {
+                Some((name, version)) => {
+                    let major_minor: Vec<&str> = version.splitn(3, '.').take(2).collect();
+                    self.get_schema(name, Some(&major_minor.join(".")))?
+                }
+                None => self.get_schema(schema_id, None)?,
+            },
+        };
+        Scaffolder { registry: self }.instance(&schema, &schema, "value", 0)
+    }
+}
+
+struct Scaffolder<'a> {
+    registry: &'a SchemaRegistry,
+}
+
+impl Scaffolder<'_> {
+    /// Builds an instance of `schema`, a part of the document `root`, for
+    /// the field called `name`.
+    fn instance(&self, schema: &Value, root: &Value, name: &str, depth: usize) -> CepResult<Value> {
+        if depth > MAX_DEPTH {
+            return Err(CepError::Configuration(format!(
+                "schema for '{}' is nested too deeply to scaffold",
+                name
+            )));
+        }
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let (target, document) = self.resolve(reference, root)?;
+            return self.instance(&target, &document, name, depth + 1);
+        }
+        let schema = first_branch(schema);
+
+        if let Some(value) = schema.get("const").or_else(|| schema.get("default")) {
+            return Ok(value.clone());
+        }
+        if let Some(first) = schema
+            .get("enum")
+            .and_then(Value::as_array)
+            .and_then(|members| members.first())
+        {
+            return Ok(first.clone());
+        }
+
+        match schema_type(&schema) {
+            "object" => self.object(&schema, root, depth),
+            "array" => {
+                let count = schema.get("minItems").and_then(Value::as_u64).unwrap_or(0);
+                let items = schema.get("items").cloned().unwrap_or(Value::Bool(true));
+                (0..count)
+                    .map(|_| self.instance(&items, root, name, depth + 1))
+                    .collect::<CepResult<Vec<_>>>()
+                    .map(Value::Array)
+            }
+            "integer" => Ok(Value::from(minimum(&schema).ceil() as i64)),
+            "number" => Ok(Value::from(minimum(&schema))),
+            "boolean" => Ok(Value::Bool(false)),
+            "null" => Ok(Value::Null),
+            _ => Ok(Value::String(string(&schema, name))),
+        }
+    }
+
+    /// Fills in the required properties, or the first property with a
+    /// non-empty placeholder if the object only needs `minProperties`.
+    fn object(&self, schema: &Value, root: &Value, depth: usize) -> CepResult<Value> {
+        let empty = Map::new();
+        let properties = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .unwrap_or(&empty);
+        let property = |name: &str| {
+            let schema = properties.get(name).cloned().unwrap_or(Value::Bool(true));
+            self.instance(&schema, root, name, depth + 1)
+        };
+
+        let mut object = Map::new();
+        for name in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            object.insert(name.to_string(), property(name)?);
+        }
+
+        let min_properties = schema
+            .get("minProperties")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        for name in properties.keys() {
+            if object.len() as u64 >= min_properties {
+                break;
+            }
+            if object.contains_key(name) {
+                continue;
+            }
+            let value = property(name)?;
+            let is_empty = match &value {
+                Value::Array(items) => items.is_empty(),
+                Value::Object(members) => members.is_empty(),
+                _ => false,
+            };
+            if !is_empty {
+                object.insert(name.clone(), value);
+            }
+        }
+        Ok(Value::Object(object))
+    }
+
+    /// Finds the schema a `$ref` points to, and the document it is in.
+    fn resolve(&self, reference: &str, root: &Value) -> CepResult<(Value, Value)> {
+        let (base, fragment) = reference.split_once('#').unwrap_or((reference, ""));
+        let document = if base.is_empty() {
+            root.clone()
+        } else {
+            let registry = self.registry.get_registry();
+            let relative = root
+                .get("$id")
+                .and_then(Value::as_str)
+                .and_then(|id| id.rsplit_once('/'))
+                .map(|(dir, _)| format!("{}/{}", dir, base));
+            registry
+                .get(base)
+                .or_else(|| relative.and_then(|id| registry.get(&id)))
+                .cloned()
+                .ok_or_else(|| {
+                    CepError::UnknownSchema(format!("cannot resolve $ref '{}'", reference))
+                })?
+        };
+        let target = document.pointer(fragment).cloned().ok_or_else(|| {
+            CepError::UnknownSchema(format!("cannot resolve $ref '{}'", reference))
+        })?;
+        Ok((target, document))
+    }
+}
+
+/// Merges the first `oneOf`/`anyOf` branch's `properties` and `required`
+/// into `schema`.
+fn first_branch(schema: &Value) -> Value {
+    let mut merged = schema.clone();
+    let Some(branch) = ["oneOf", "anyOf"]
+        .iter()
+        .find_map(|keyword| schema.get(*keyword)?.as_array()?.first())
+    else {
+        return merged;
+    };
+    if let Some(object) = merged.as_object_mut() {
+        object.remove("oneOf");
+        object.remove("anyOf");
+        if let Some(branch_properties) = branch.get("properties").and_then(Value::as_object) {
+            let properties = object
+                .entry("properties")
+                .or_insert_with(|| Value::Object(Map::new()));
+            for (name, overlay) in branch_properties {
+                let property = &mut properties[name.as_str()];
+                match (property.as_object_mut(), overlay.as_object()) {
+                    (Some(base), Some(overlay)) => base.extend(overlay.clone()),
+                    _ => *property = overlay.clone(),
+                }
+            }
+        }
+        if let Some(branch_required) = branch.get("required").and_then(Value::as_array) {
+            let required = object
+                .entry("required")
+                .or_insert_with(|| Value::Array(Vec::new()));
+            if let Some(required) = required.as_array_mut() {
+                for name in branch_required {
+                    if !required.contains(name) {
+                        required.push(name.clone());
+                    }
+                }
+            }
+        }
+    }
+    merged
+}
+
+/// The first non-null `type`, or `object` if the schema has properties.
+fn schema_type(schema: &Value) -> &str {
+    let declared = match schema.get("type") {
+        Some(Value::String(name)) => Some(name.as_str()),
+        Some(Value::Array(names)) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|name| *name != "null")
+            .or(Some("null")),
+        _ => None,
+    };
+    declared.unwrap_or(if schema.get("properties").is_some() {
+        "object"
+    } else {
+        "string"
+    })
+}
+
+/// The smallest number the schema allows, or 0.
+fn minimum(schema: &Value) -> f64 {
+    match (
+        schema.get("minimum").and_then(Value::as_f64),
+        schema.get("exclusiveMinimum").and_then(Value::as_f64),
+    ) {
+        (Some(minimum), _) => minimum,
+        (None, Some(exclusive)) => exclusive + 1.0,
+        (None, None) => 0.0,
+    }
+}
+
+/// A placeholder string for the field called `name`.
+fn string(schema: &Value, name: &str) -> String {
+    let format = schema.get("format").and_then(Value::as_str);
+    let mut value = match format {
+        Some("uri") | Some("iri") | Some("uri-reference") => {
+            format!("https://example.org/{}", name)
+        }
+        Some("date-time") => PLACEHOLDER_TIMESTAMP.to_string(),
+        Some("date") => PLACEHOLDER_TIMESTAMP[..10].to_string(),
+        Some("email") => "steward@example.org".to_string(),
+        Some("uuid") => "00000000-0000-0000-0000-000000000000".to_string(),
+        _ => format!("<{}>", name),
+    };
+    if let Some(pattern) = schema
+        .get("pattern")
+        .and_then(Value::as_str)
+        .and_then(Pattern::parse)
+    {
+        if !pattern.matches(&value) {
+            value = if pattern.matches(PLACEHOLDER_TIMESTAMP) {
+                PLACEHOLDER_TIMESTAMP.to_string()
+            } else {
+                pattern.example()
+            };
+        }
+        return value;
+    }
+    let min_length = schema.get("minLength").and_then(Value::as_u64).unwrap_or(0) as usize;
+    while value.chars().count() < min_length {
+        value.push('_');
+    }
+    value
+}
+
+/// The subset of regular expressions used by the CEP schemas: literals,
+/// `\` escapes, character classes, groups, and `?`, `*`, `+` and `{m,n}`
+/// quantifiers. Patterns are treated as anchored.
+#[derive(Debug, Clone, PartialEq)]
+struct Pattern(Vec<Term>);
+
+#[derive(Debug, Clone, PartialEq)]
+struct Term {
+    atom: Atom,
+    min: usize,
+    max: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Atom {
+    /// Inclusive character ranges; a literal is a one-character range.
+    Class(Vec<(char, char)>),
+    Group(Pattern),
+}
+
+impl Pattern {
+    /// Parses `pattern`, or returns `None` if it uses syntax outside the
+    /// subset (alternation, negated classes, ...).
+    fn parse(pattern: &str) -> Option<Self> {
+        let pattern = pattern.strip_prefix('^').unwrap_or(pattern);
+        let pattern = pattern.strip_suffix('$').unwrap_or(pattern);
+        let mut chars = pattern.chars().peekable();
+        let parsed = Self::parse_sequence(&mut chars)?;
+        chars.next().is_none().then_some(parsed)
+    }
+
+    fn parse_sequence(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Option<Self> {
+        let mut terms = Vec::new();
+        while let Some(&c) = chars.peek() {
+            let atom = match c {
+                ')' => break,
+                '(' => {
+                    chars.next();
+                    let group = Self::parse_sequence(chars)?;
+                    (chars.next()? == ')').then_some(())?;
+                    Atom::Group(group)
+                }
+                '[' => {
+                    chars.next();
+                    let mut ranges = Vec::new();
+                    loop {
+                        let start = match chars.next()? {
+                            ']' => break,
+                            '^' if ranges.is_empty() => return None,
+                            '\\' => chars.next()?,
+                            c => c,
+                        };
+                        let end = if chars.peek() == Some(&'-') {
+                            chars.next();
+                            match chars.next()? {
+                                ']' => {
+                                    ranges.push((start, start));
+                                    ranges.push(('-', '-'));
+                                    break;
+                                }
+                                end => end,
+                            }
+                        } else {
+                            start
+                        };
+                        ranges.push((start, end));
+                    }
+                    Atom::Class(ranges)
+                }
+                '\\' => {
+                    chars.next();
+                    match chars.next()? {
+                        'd' => Atom::Class(vec![('0', '9')]),
+                        c if c.is_ascii_alphanumeric() => return None,
+                        c => Atom::Class(vec![(c, c)]),
+                    }
+                }
+                '|' | '.' | '?' | '*' | '+' | '{' => return None,
+                c => {
+                    chars.next();
+                    Atom::Class(vec![(c, c)])
+                }
+            };
+            let (min, max) = match chars.peek() {
+                Some('?') => (0, Some(1)),
+                Some('*') => (0, None),
+                Some('+') => (1, None),
+                Some('{') => {
+                    chars.next();
+                    let mut spec = String::new();
+                    loop {
+                        match chars.next()? {
+                            '}' => break,
+                            c => spec.push(c),
+                        }
+                    }
+                    let (min, max) = match spec.split_once(',') {
+                        Some((min, "")) => (min.parse().ok()?, None),
+                        Some((min, max)) => (min.parse().ok()?, Some(max.parse().ok()?)),
+                        None => (spec.parse().ok()?, Some(spec.parse().ok()?)),
+                    };
+                    terms.push(Term { atom, min, max });
+                    continue;
+                }
+                _ => (1, Some(1)),
+            };
+            if (min, max) != (1, Some(1)) {
+                chars.next();
+            }
+            terms.push(Term { atom, min, max });
+        }
+        Some(Self(terms))
+    }
+
+    /// The shortest string the pattern matches, using the first member of
+    /// each character class.
+    fn example(&self) -> String {
+        self.0
+            .iter()
+            .flat_map(|term| std::iter::repeat_n(&term.atom, term.min))
+            .map(|atom| match atom {
+                Atom::Class(ranges) => ranges.first().map_or(String::new(), |r| r.0.to_string()),
+                Atom::Group(group) => group.example(),
+            })
+            .collect()
+    }
+
+    /// Returns true if the pattern matches all of `s`.
+    fn matches(&self, s: &str) -> bool {
+        let input: Vec<char> = s.chars().collect();
+        self.ends(&input, 0).contains(&input.len())
+    }
+
+    /// Positions in `input` where a match starting at `start` can end.
+    fn ends(&self, input: &[char], start: usize) -> Vec<usize> {
+        let mut positions = vec![start];
+        for term in &self.0 {
+            let mut next = Vec::new();
+            let mut current = positions;
+            for count in 0..=input.len() + 1 {
+                if count >= term.min {
+                    next.extend(current.iter().copied());
+                }
+                if term.max == Some(count) || current.is_empty() {
+                    break;
+                }
+                current = current
+                    .iter()
+                    .flat_map(|&pos| term.atom.ends(input, pos))
+                    .collect();
+                current.sort_unstable();
+                current.dedup();
+            }
+            next.sort_unstable();
+            next.dedup();
+            positions = next;
+        }
+        positions
+    }
+}
+
+impl Atom {
+    fn ends(&self, input: &[char], pos: usize) -> Vec<usize> {
+        match self {
+            Atom::Class(ranges) => input
+                .get(pos)
+                .filter(|c| ranges.iter().any(|(lo, hi)| (lo..=hi).contains(c)))
+                .map_or(Vec::new(), |_| vec![pos + 1]),
+            Atom::Group(group) => group.ends(input, pos),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_patterns() {
+        let pattern = |p: &str| Pattern::parse(p).unwrap();
+
+        let jurisdiction = pattern("^[A-Z]{2}(-[A-Z0-9]{1,3})?$");
+        assert_eq!(jurisdiction.example(), "AA");
+        assert!(jurisdiction.matches("US-MN"));
+        assert!(!jurisdiction.matches("US-"));
+
+        let timestamp =
+            pattern(r"^[0-9]{4}-[0-9]{2}-[0-9]{2}T[0-9]{2}:[0-9]{2}:[0-9]{2}\.[0-9]{6}Z$");
+        assert!(timestamp.matches(PLACEHOLDER_TIMESTAMP));
+        assert!(!timestamp.matches("2000-01-01T00:00:00Z"));
+
+        let chain = pattern("^[A-Z_]+([>][A-Z_]+)*$");
+        assert_eq!(chain.example(), "A");
+        assert!(chain.matches("FEDERAL>STATE>LOCAL"));
+        assert!(!chain.matches("FEDERAL>"));
+
+        assert!(pattern(r"^[0-9]{2}\.[0-9]{3}$").matches("10.555"));
+        assert!(pattern("^[0-9]{2,6}$").matches("9211"));
+        assert!(Pattern::parse("^(a|b)$").is_none());
+        assert!(Pattern::parse("^[^a]$").is_none());
+    }
+
+    #[test]
+    fn test_scaffold_picks_placeholders_by_keyword() {
+        let mut registry = SchemaRegistry::with_root(PathBuf::from("/nonexistent")).unwrap();
+        registry.register_schema(
+            "widget",
+            crate::version::CURRENT_SCHEMA_VERSION,
+            json!({
+                "$id": "urn:test:widget",
+                "type": "object",
+                "required": ["kind", "size", "homepage", "code", "parts", "owner", "made"],
+                "properties": {
+                    "kind": {"type": "string", "enum": ["gear", "cog"]},
+                    "size": {"type": "integer", "minimum": 3},
+                    "homepage": {"type": "string", "format": "uri"},
+                    "code": {"type": "string", "pattern": "^[A-Z]{3}-[0-9]{2}$"},
+                    "parts": {"type": "array", "minItems": 2, "items": {"$ref": "#/$defs/part"}},
+                    "owner": {"$ref": "urn:test:owner#/$defs/owner"},
+                    "made": {"type": "string", "pattern": "^[0-9]{4}-[0-9]{2}-[0-9]{2}T.*$"},
+                    "optional": {"type": "string"}
+                },
+                "$defs": {
+                    "part": {"type": ["null", "object"], "required": ["id"], "properties": {"id": {"type": "string", "minLength": 6}}}
+                },
+                "oneOf": [{"required": ["optional"]}, {"required": ["size"]}]
+            }),
+        );
+        registry.register_schema(
+            "owner",
+            crate::version::CURRENT_SCHEMA_VERSION,
+            json!({
+                "$id": "urn:test:owner",
+                "$defs": {"owner": {"type": "object", "minProperties": 1, "properties": {
+                    "aliases": {"type": "array"},
+                    "name": {"type": "string"}
+                }}}
+            }),
+        );
+
+        let widget = registry.scaffold("widget").unwrap();
+        assert_eq!(
+            widget,
+            json!({
+                "kind": "gear",
+                "size": 3,
+                "homepage": "https://example.org/homepage",
+                "code": "AAA-00",
+                "parts": [{"id": "<id>__"}, {"id": "<id>__"}],
+                "owner": {"name": "<name>"},
+                // `.*` is outside the subset, so the pattern is not applied.
+                "made": "<made>",
+                "optional": "<optional>"
+            })
+        );
+        assert_eq!(registry.scaffold("urn:test:widget").unwrap(), widget);
+        assert!(matches!(
+            registry.scaffold("gadget"),
+            Err(CepError::UnknownSchema(_))
+        ));
+
+        registry.register_schema(
+            "loop",
+            crate::version::CURRENT_SCHEMA_VERSION,
+            json!({"$ref": "#"}),
+        );
+        assert!(matches!(
+            registry.scaffold("loop"),
+            Err(CepError::Configuration(_))
+        ));
+    }
+
+    #[test]
+    fn test_scaffold_record_schemas() {
+        let registry = SchemaRegistry::new().unwrap();
+
+        let entity = registry.scaffold("entity@1.0.0").unwrap();
+        assert_eq!(
+            entity["attestation"]["attestationTimestamp"],
+            PLACEHOLDER_TIMESTAMP
+        );
+        assert_eq!(entity["identifiers"].as_object().unwrap().len(), 1);
+        assert_eq!(entity["revisionNumber"], 1);
+
+        let relationship = registry.scaffold("relationship").unwrap();
+        assert!(relationship["bilateralParties"]["partyA"]["roleUri"].is_string());
+        assert!(relationship.get("multilateralMembers").is_none());
+
+        let exchange = registry.scaffold("exchange").unwrap();
+        assert_eq!(exchange["status"]["statusCode"], "PENDING");
+        assert_eq!(exchange["value"]["amount"], 0.0);
+    }
+}
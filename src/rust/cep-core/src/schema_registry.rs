@@ -1,53 +1,82 @@
-/// Central schema registry for CEP validation.
-///
-/// Provides schema lookup and validation registry for all CEP record types.
-/// Schema files are loaded from the repository root relative to the crate.
-///
-/// # Example
-///
-/// ```rust
-/// use cep_core::SchemaRegistry;
-///
-/// // Get the shared global registry
-/// let registry = SchemaRegistry::global()
-///     .expect("failed to load global schema registry");
-///
-/// // Get the current entity schema
-/// let schema = registry
-///     .get_schema("entity", None)
-///     .expect("failed to resolve entity schema");
-///
-/// // Basic sanity check on shape
-/// assert!(schema.is_object());
-///
-/// // List all available schemas
-/// for (name, version) in registry.list_schemas() {
-///     println!("{} v{}", name, version);
-/// }
-/// ```
-///
-/// # Custom Registry
-///
-/// ```rust
-/// use cep_core::SchemaRegistry;
-/// use std::path::PathBuf;
-///
-/// // Create a registry with a custom root path
-/// let registry = SchemaRegistry::with_root(PathBuf::from("/custom/path"))
-///     .expect("failed to create registry with custom root");
-/// // In this example we only check that construction succeeded.
-/// assert!(registry.list_schemas().len() >= 0);
-/// ```
+//! Central schema registry for CEP validation.
+//!
+//! Provides schema lookup and validation registry for all CEP record types.
+//! Schema files are loaded from the repository root relative to the crate.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cep_core::SchemaRegistry;
+//!
+//! // Get the shared global registry
+//! let registry = SchemaRegistry::global()
+//!     .expect("failed to load global schema registry");
+//!
+//! // Get the current entity schema
+//! let schema = registry
+//!     .get_schema("entity", None)
+//!     .expect("failed to resolve entity schema");
+//!
+//! // Basic sanity check on shape
+//! assert!(schema.is_object());
+//!
+//! // List all available schemas
+//! for (name, version) in registry.list_schemas() {
+//!     println!("{} v{}", name, version);
+//! }
+//! ```
+//!
+//! # Several Versions
+//!
+//! A registry can hold several versions of the same schema, e.g. 1.0 and
+//! 1.1 of `entity` while stewards migrate. Extra versions are added with
+//! [`SchemaRegistry::register_schema`], and
+//! [`SchemaRegistry::get_schema_for`] picks the one a record declares in its
+//! `schemaVersion`:
+//!
+//! ```rust
+//! use cep_core::SchemaRegistry;
+//! use cep_core::version::SchemaVersion;
+//! use serde_json::json;
+//! use std::path::PathBuf;
+//!
+//! let mut registry = SchemaRegistry::with_root(PathBuf::from("/custom/path")).unwrap();
+//! registry.register_schema("entity", SchemaVersion::new(1, 0, 0), json!({"title": "v1.0"}));
+//! registry.register_schema("entity", SchemaVersion::new(1, 1, 0), json!({"title": "v1.1"}));
+//!
+//! let record = json!({"schemaVersion": "1.1.0"});
+//! assert_eq!(registry.get_schema_for("entity", &record).unwrap()["title"], "v1.1");
+//! assert_eq!(registry.schema_versions("entity"), ["1.0", "1.1"]);
+//! ```
+//!
+//! # Custom Registry
+//!
+//! ```rust
+//! use cep_core::SchemaRegistry;
+//! use std::path::PathBuf;
+//!
+//! // Create a registry with a custom root path
+//! let registry = SchemaRegistry::with_root(PathBuf::from("/custom/path"))
+//!     .expect("failed to create registry with custom root");
+//! // In this example we only check that construction succeeded.
+//! assert!(registry.list_schemas().len() >= 0);
+//! ```
 
 use once_cell::sync::Lazy;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
-use std::path::PathBuf;
-use std::sync::RwLock;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
 
 use crate::error::{CepError, CepResult};
-use crate::version::SCHEMA_VERSION;
+use crate::hash::CanonicalHash;
+use crate::manifest::{
+    AssetManifest, IntegrityMode, MANIFEST_FILE, SignedManifest, project_manifest_key,
+};
+use crate::snapshot::SnapshotStore;
+use crate::version::{CURRENT_SCHEMA_VERSION, SchemaVersion};
+use ed25519_dalek::VerifyingKey;
 
 /// Schema key: (name, version)
 type SchemaKey = (String, String);
@@ -83,6 +112,11 @@ fn default_catalog() -> Vec<CatalogEntry> {
             version: "1.0".to_string(),
             relative_path: "test_vectors/schemas/v1.0/generation-vector-set.schema.json",
         },
+        CatalogEntry {
+            name: "snfei-canonical-input".to_string(),
+            version: "1.0".to_string(),
+            relative_path: "schemas/cep.snfei.canonical-input.schema.json",
+        },
     ]
 }
 
@@ -108,6 +142,13 @@ pub struct SchemaRegistry {
     schemas: HashMap<SchemaKey, Value>,
     /// Catalog of known schemas.
     catalog: Vec<CatalogEntry>,
+    /// Manifest every schema file is checked against, if verification is enabled.
+    integrity: Option<(AssetManifest, IntegrityMode)>,
+    /// Integrity failures tolerated under `IntegrityMode::Warn`, including
+    /// those found by later vocabulary reads. Shared by clones.
+    integrity_warnings: Arc<Mutex<Vec<String>>>,
+    /// Snapshot hash of the manifest the assets were checked against.
+    snapshot: Option<CanonicalHash>,
 }
 
 impl SchemaRegistry {
@@ -116,14 +157,61 @@ impl SchemaRegistry {
     /// Searches for `pyproject.toml` or workspace `Cargo.toml` by walking up
     /// from `CARGO_MANIFEST_DIR`, current directory, or executable location.
     ///
+    /// If the root holds a signed manifest ([`MANIFEST_FILE`]), the assets are
+    /// verified against it with the
+    /// [`PROJECT_MANIFEST_KEY`](crate::manifest::PROJECT_MANIFEST_KEY) under
+    /// `IntegrityMode::Refuse`, as by [`with_verified_root`](Self::with_verified_root).
+    ///
     /// # Errors
     ///
-    /// Returns `CepError::Configuration` if repository root cannot be found.
+    /// Returns `CepError::Configuration` if repository root cannot be found,
+    /// and `CepError::AssetIntegrity` if the manifest does not verify.
     pub fn new() -> CepResult<Self> {
         let repo_root = find_repo_root()?;
+        let manifest_path = repo_root.join(MANIFEST_FILE);
+        if manifest_path.is_file() {
+            let manifest = SignedManifest::load(&manifest_path)?;
+            return Self::with_verified_root(
+                repo_root,
+                &manifest,
+                &project_manifest_key()?,
+                IntegrityMode::Refuse,
+            );
+        }
         Self::with_root(repo_root)
     }
 
+    /// Returns the paths, relative to `repo_root`, of the assets a signed
+    /// manifest covers: every cataloged schema present and every JSON file
+    /// directly under `schemas/` and `vocabularies/`, sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::Configuration` if a directory cannot be read.
+    pub fn asset_paths(repo_root: &Path) -> CepResult<Vec<String>> {
+        let mut paths: BTreeSet<String> = default_catalog()
+            .iter()
+            .filter(|entry| repo_root.join(entry.relative_path).is_file())
+            .map(|entry| entry.relative_path.to_string())
+            .collect();
+        for dir in ["schemas", "vocabularies"] {
+            let path = repo_root.join(dir);
+            if !path.is_dir() {
+                continue;
+            }
+            let entries = fs::read_dir(&path).map_err(|e| {
+                CepError::Configuration(format!("Failed to read {}: {}", path.display(), e))
+            })?;
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if name.ends_with(".json") && entry.path().is_file() {
+                    paths.insert(format!("{}/{}", dir, name));
+                }
+            }
+        }
+        Ok(paths.into_iter().collect())
+    }
+
     /// Creates a new SchemaRegistry with a specific root path.
     ///
     /// # Arguments
@@ -140,11 +228,80 @@ impl SchemaRegistry {
             registry: HashMap::new(),
             schemas: HashMap::new(),
             catalog,
+            integrity: None,
+            integrity_warnings: Arc::default(),
+            snapshot: None,
         };
         registry.load_schemas()?;
         Ok(registry)
     }
 
+    /// Creates a SchemaRegistry that checks every schema file against a signed manifest.
+    ///
+    /// The manifest signature is verified with `key` first, then each schema
+    /// file's SHA-256 is compared with the manifest entry for its path. Under
+    /// `IntegrityMode::Warn` failures are collected in
+    /// [`integrity_warnings`](Self::integrity_warnings) and loading continues.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::AssetIntegrity` under `IntegrityMode::Refuse` if the
+    /// signature or any schema digest does not match.
+    pub fn with_verified_root(
+        repo_root: PathBuf,
+        manifest: &SignedManifest,
+        key: &VerifyingKey,
+        mode: IntegrityMode,
+    ) -> CepResult<Self> {
+        let mut registry = Self {
+            repo_root,
+            registry: HashMap::new(),
+            schemas: HashMap::new(),
+            catalog: default_catalog(),
+            integrity: Some((manifest.manifest.clone(), mode)),
+            integrity_warnings: Arc::default(),
+            snapshot: Some(manifest.manifest.snapshot_hash()),
+        };
+        registry.tolerate(manifest.verify(key))?;
+        registry.load_schemas()?;
+        Ok(registry)
+    }
+
+    /// Creates a SchemaRegistry from a pinned snapshot in `store`.
+    ///
+    /// Used to verify a record against the definitions named by its
+    /// `schemaSnapshotHash`, without network access. Every schema file is
+    /// checked against the snapshot manifest under `IntegrityMode::Refuse`.
+    ///
+    /// # Errors
+    ///
+    /// - `CepError::Configuration` if the snapshot is not in the store
+    /// - `CepError::AssetIntegrity` if the manifest or any file was altered
+    pub fn from_snapshot(store: &SnapshotStore, hash: &CanonicalHash) -> CepResult<Self> {
+        let manifest = store.manifest(hash)?;
+        let mut registry = Self {
+            repo_root: store.snapshot_dir(hash),
+            registry: HashMap::new(),
+            schemas: HashMap::new(),
+            catalog: default_catalog(),
+            integrity: Some((manifest, IntegrityMode::Refuse)),
+            integrity_warnings: Arc::default(),
+            snapshot: Some(hash.clone()),
+        };
+        registry.load_schemas()?;
+        Ok(registry)
+    }
+
+    /// Returns the snapshot hash the loaded assets were verified against, if any.
+    pub fn snapshot_hash(&self) -> Option<&CanonicalHash> {
+        self.snapshot.as_ref()
+    }
+
+    /// Returns integrity failures that were tolerated under `IntegrityMode::Warn`.
+    pub fn integrity_warnings(&self) -> Vec<String> {
+        self.integrity_warnings.lock().unwrap().clone()
+    }
+
     /// Gets the global shared registry instance.
     ///
     /// The global registry is lazily initialized on first access and cached
@@ -265,6 +422,55 @@ impl SchemaRegistry {
         }
     }
 
+    /// Gets the schema version a record declares in its `schemaVersion`.
+    ///
+    /// Records without a `schemaVersion` get the current version, so that
+    /// validation reports the missing field instead of an unknown schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::UnsupportedVersion` if `schemaVersion` is not a
+    /// `major.minor.patch` string, or `CepError::UnknownSchema` if that
+    /// version of the schema is not loaded.
+    pub fn get_schema_for(&self, name: &str, record: &Value) -> CepResult<Value> {
+        match record.get("schemaVersion") {
+            Some(Value::String(declared)) => {
+                let version: SchemaVersion = declared.parse()?;
+                self.get_schema(name, Some(&version.major_minor()))
+            }
+            Some(other) => Err(CepError::UnsupportedVersion(format!(
+                "schemaVersion must be a string, got {}",
+                other
+            ))),
+            None => self.get_schema(name, None),
+        }
+    }
+
+    /// Adds `schema` as `version` of `name`, alongside any versions already
+    /// loaded. A schema already registered for the same major.minor is
+    /// replaced.
+    ///
+    /// The schema is also made available for `$ref` resolution under its
+    /// `$id`, so versions that share a `$id` shadow each other there.
+    pub fn register_schema(&mut self, name: &str, version: SchemaVersion, schema: Value) {
+        let version = version.major_minor();
+        let schema_id = schema_id(&schema, name, &version);
+        self.schemas.insert((name.to_string(), version), schema.clone());
+        self.registry.insert(schema_id, schema);
+    }
+
+    /// Lists the loaded versions of schema `name` as major.minor, oldest first.
+    pub fn schema_versions(&self, name: &str) -> Vec<String> {
+        let mut versions: Vec<(SchemaVersion, &String)> = self
+            .schemas
+            .keys()
+            .filter(|(n, _)| n == name)
+            .filter_map(|(_, v)| Some((format!("{}.0", v).parse().ok()?, v)))
+            .collect();
+        versions.sort();
+        versions.into_iter().map(|(_, v)| v.clone()).collect()
+    }
+
     /// Gets the full registry map for JSON Schema validation.
     ///
     /// Returns a HashMap mapping schema `$id` URIs to schema `Value`.
@@ -274,6 +480,52 @@ impl SchemaRegistry {
         &self.registry
     }
 
+    /// Reads a vocabulary by key, e.g. `"entity-type.v1.0.0"`.
+    ///
+    /// Keys are file names under `vocabularies/` without `.json`, as in
+    /// [`get_vocab`](crate::get_vocab). When the registry has a manifest, the
+    /// file is checked against it; under `IntegrityMode::Warn` a mismatch is
+    /// added to [`integrity_warnings`](Self::integrity_warnings).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::UnknownSchema` if the key is not a plain file name
+    /// or the vocabulary file does not exist, or `CepError::AssetIntegrity`
+    /// under `IntegrityMode::Refuse` if it fails verification.
+    pub fn get_vocabulary(&self, key: &str) -> CepResult<Value> {
+        if key.is_empty() || key.contains(['/', '\\']) || key.contains("..") {
+            return Err(CepError::UnknownSchema(format!(
+                "Invalid vocabulary key: '{}'",
+                key
+            )));
+        }
+        let relative_path = format!("vocabularies/{}.json", key);
+        let path = self.repo_root.join(&relative_path);
+        if !path.is_file() {
+            return Err(CepError::UnknownSchema(format!(
+                "Unknown vocabulary: '{}'",
+                key
+            )));
+        }
+        let content = fs::read_to_string(&path).map_err(|e| {
+            CepError::Configuration(format!(
+                "Failed to read vocabulary file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        if let Some((manifest, _)) = &self.integrity {
+            self.tolerate(manifest.verify_bytes(&relative_path, content.as_bytes()))?;
+        }
+        serde_json::from_str(&content).map_err(|e| {
+            CepError::Configuration(format!(
+                "Failed to parse vocabulary JSON {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
     /// Lists all available (name, version) pairs in the catalog.
     ///
     /// Note: This returns all cataloged schemas, not just those with files present.
@@ -317,6 +569,11 @@ impl SchemaRegistry {
                 ))
             })?;
 
+            if let Some((manifest, _)) = &self.integrity {
+                let check = manifest.verify_bytes(entry.relative_path, content.as_bytes());
+                self.tolerate(check)?;
+            }
+
             let schema: Value = serde_json::from_str(&content).map_err(|e| {
                 CepError::Configuration(format!(
                     "Failed to parse schema JSON {}: {}",
@@ -325,13 +582,7 @@ impl SchemaRegistry {
                 ))
             })?;
 
-            // Extract $id or generate one
-            let schema_id = schema
-                .get("$id")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string())
-                .unwrap_or_else(|| format!("urn:cep:{}:{}", entry.name, entry.version));
-
+            let schema_id = schema_id(&schema, &entry.name, &entry.version);
             let key = (entry.name.clone(), entry.version.clone());
             self.schemas.insert(key, schema.clone());
             self.registry.insert(schema_id, schema);
@@ -340,6 +591,20 @@ impl SchemaRegistry {
         Ok(())
     }
 
+    /// Applies the integrity mode to the outcome of a check.
+    fn tolerate(&self, check: CepResult<()>) -> CepResult<()> {
+        match (check, self.integrity.as_ref().map(|(_, mode)| *mode)) {
+            (Err(err), Some(IntegrityMode::Warn)) => {
+                self.integrity_warnings
+                    .lock()
+                    .unwrap()
+                    .push(err.to_string());
+                Ok(())
+            }
+            (result, _) => result,
+        }
+    }
+
     /// Clears the global registry cache (useful for testing).
     #[cfg(test)]
     pub fn clear_global_cache() {
@@ -363,12 +628,16 @@ impl Default for SchemaRegistry {
 
 /// Get major.minor version for schema lookup (drop patch).
 fn schema_version() -> String {
-    let parts: Vec<&str> = SCHEMA_VERSION.split('.').collect();
-    if parts.len() >= 2 {
-        format!("{}.{}", parts[0], parts[1])
-    } else {
-        SCHEMA_VERSION.to_string()
-    }
+    CURRENT_SCHEMA_VERSION.major_minor()
+}
+
+/// Returns the schema's `$id`, or a `urn:cep:<name>:<version>` if it has none.
+fn schema_id(schema: &Value, name: &str, version: &str) -> String {
+    schema
+        .get("$id")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| format!("urn:cep:{}:{}", name, version))
 }
 
 /// Walk up from current location to find repository root.
@@ -388,12 +657,11 @@ pub fn find_repo_root() -> CepResult<PathBuf> {
             }
             // Check for workspace Cargo.toml with [workspace] section
             let cargo_path = ancestor.join("Cargo.toml");
-            if cargo_path.exists() {
-                if let Ok(content) = fs::read_to_string(&cargo_path) {
-                    if content.contains("[workspace]") {
-                        return Ok(ancestor.to_path_buf());
-                    }
-                }
+            if cargo_path.exists()
+                && let Ok(content) = fs::read_to_string(&cargo_path)
+                && content.contains("[workspace]")
+            {
+                return Ok(ancestor.to_path_buf());
             }
         }
     }
@@ -405,23 +673,22 @@ pub fn find_repo_root() -> CepResult<PathBuf> {
                 return Ok(ancestor.to_path_buf());
             }
             let cargo_path = ancestor.join("Cargo.toml");
-            if cargo_path.exists() {
-                if let Ok(content) = fs::read_to_string(&cargo_path) {
-                    if content.contains("[workspace]") {
-                        return Ok(ancestor.to_path_buf());
-                    }
-                }
+            if cargo_path.exists()
+                && let Ok(content) = fs::read_to_string(&cargo_path)
+                && content.contains("[workspace]")
+            {
+                return Ok(ancestor.to_path_buf());
             }
         }
     }
 
     // Try executable location as last resort
-    if let Ok(exe_path) = std::env::current_exe() {
-        if let Some(exe_dir) = exe_path.parent() {
-            for ancestor in exe_dir.ancestors() {
-                if ancestor.join("pyproject.toml").exists() {
-                    return Ok(ancestor.to_path_buf());
-                }
+    if let Ok(exe_path) = std::env::current_exe()
+        && let Some(exe_dir) = exe_path.parent()
+    {
+        for ancestor in exe_dir.ancestors() {
+            if ancestor.join("pyproject.toml").exists() {
+                return Ok(ancestor.to_path_buf());
             }
         }
     }
@@ -453,6 +720,7 @@ mod tests {
         assert!(names.contains(&"exchange"));
         assert!(names.contains(&"relationship"));
         assert!(names.contains(&"snfei"));
+        assert!(names.contains(&"snfei-canonical-input"));
     }
 
     #[test]
@@ -477,6 +745,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_standard_loader_verifies_committed_manifest() {
+        use crate::keys::KeyFile;
+        use crate::manifest::{MANIFEST_KEY_FILE, PROJECT_MANIFEST_KEY};
+
+        let root = find_repo_root().unwrap();
+        let signed = SignedManifest::load(&root.join(MANIFEST_FILE)).unwrap();
+        // Regenerate with `cep manifest` after changing a schema or vocabulary.
+        let listed: Vec<&String> = signed.manifest.assets.keys().collect();
+        let present = SchemaRegistry::asset_paths(&root).unwrap();
+        assert_eq!(listed, present.iter().collect::<Vec<_>>());
+
+        let key_file = KeyFile::load(&root.join(MANIFEST_KEY_FILE)).unwrap();
+        assert_eq!(key_file.did_key().unwrap(), PROJECT_MANIFEST_KEY);
+
+        let registry = SchemaRegistry::new().unwrap();
+        assert_eq!(
+            registry.snapshot_hash(),
+            Some(&signed.manifest.snapshot_hash())
+        );
+        assert!(registry.integrity_warnings().is_empty());
+        registry.get_vocabulary("entity-type.v1.0.0").unwrap();
+    }
+
     #[test]
     fn test_registry_with_nonexistent_root() {
         let result = SchemaRegistry::with_root(PathBuf::from("/nonexistent/path"));
@@ -498,6 +790,211 @@ mod tests {
         }
     }
 
+    fn signed_fixture() -> (tempfile::TempDir, SignedManifest, ed25519_dalek::SigningKey) {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("schemas")).unwrap();
+        fs::write(
+            dir.path().join("schemas/cep.entity.schema.json"),
+            r#"{"$id":"urn:test:entity","type":"object"}"#,
+        )
+        .unwrap();
+        let key = ed25519_dalek::SigningKey::from_bytes(&[3u8; 32]);
+        let manifest =
+            AssetManifest::build(dir.path(), &["schemas/cep.entity.schema.json"]).unwrap();
+        let signed = SignedManifest::sign(manifest, "project-key", &key);
+        (dir, signed, key)
+    }
+
+    #[test]
+    fn test_verified_registry_loads_matching_schemas() {
+        let (dir, signed, key) = signed_fixture();
+        let registry = SchemaRegistry::with_verified_root(
+            dir.path().to_path_buf(),
+            &signed,
+            &key.verifying_key(),
+            IntegrityMode::Refuse,
+        )
+        .unwrap();
+        assert_eq!(registry.len(), 1);
+        assert!(registry.integrity_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_verified_registry_tampered_schema() {
+        let (dir, signed, key) = signed_fixture();
+        fs::write(
+            dir.path().join("schemas/cep.entity.schema.json"),
+            r#"{"$id":"urn:test:entity","type":"string"}"#,
+        )
+        .unwrap();
+
+        let refused = SchemaRegistry::with_verified_root(
+            dir.path().to_path_buf(),
+            &signed,
+            &key.verifying_key(),
+            IntegrityMode::Refuse,
+        );
+        assert!(matches!(refused, Err(CepError::AssetIntegrity(_))));
+
+        let warned = SchemaRegistry::with_verified_root(
+            dir.path().to_path_buf(),
+            &signed,
+            &key.verifying_key(),
+            IntegrityMode::Warn,
+        )
+        .unwrap();
+        assert_eq!(warned.len(), 1);
+        assert_eq!(warned.integrity_warnings().len(), 1);
+    }
+
+    #[test]
+    fn test_verified_registry_wrong_key() {
+        let (dir, signed, _) = signed_fixture();
+        let other = ed25519_dalek::SigningKey::from_bytes(&[4u8; 32]).verifying_key();
+        let result = SchemaRegistry::with_verified_root(
+            dir.path().to_path_buf(),
+            &signed,
+            &other,
+            IntegrityMode::Refuse,
+        );
+        assert!(matches!(result, Err(CepError::AssetIntegrity(_))));
+    }
+
+    #[test]
+    fn test_registry_from_pinned_snapshot() {
+        let (dir, _, _) = signed_fixture();
+        fs::create_dir_all(dir.path().join("vocabularies")).unwrap();
+        fs::write(
+            dir.path().join("vocabularies/entity-type.v1.0.0.json"),
+            r#"{"version":"1.0.0","terms":[]}"#,
+        )
+        .unwrap();
+        let store_dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(store_dir.path().to_path_buf());
+        let hash = store
+            .pin(
+                dir.path(),
+                &[
+                    "schemas/cep.entity.schema.json",
+                    "vocabularies/entity-type.v1.0.0.json",
+                ],
+            )
+            .unwrap();
+
+        // Later edits to the live files do not affect the pinned snapshot.
+        fs::write(
+            dir.path().join("schemas/cep.entity.schema.json"),
+            r#"{"$id":"urn:test:entity","type":"string"}"#,
+        )
+        .unwrap();
+
+        let registry = SchemaRegistry::from_snapshot(&store, &hash).unwrap();
+        assert_eq!(registry.snapshot_hash(), Some(&hash));
+        assert_eq!(registry.get_schema("entity", None).unwrap()["type"], "object");
+        let vocab = registry.get_vocabulary("entity-type.v1.0.0").unwrap();
+        assert_eq!(vocab["version"], "1.0.0");
+        assert!(matches!(
+            registry.get_vocabulary("missing.v1.0.0"),
+            Err(CepError::UnknownSchema(_))
+        ));
+    }
+
+    #[test]
+    fn test_vocabulary_integrity_and_keys() {
+        let (dir, signed, key) = signed_fixture();
+        fs::create_dir_all(dir.path().join("vocabularies")).unwrap();
+        fs::write(
+            dir.path().join("vocabularies/entity-type.v1.0.0.json"),
+            r#"{"version":"1.0.0","terms":[]}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("secret.json"), "{}").unwrap();
+        let open = |mode| {
+            SchemaRegistry::with_verified_root(
+                dir.path().to_path_buf(),
+                &signed,
+                &key.verifying_key(),
+                mode,
+            )
+            .unwrap()
+        };
+
+        // The vocabulary is not in the manifest.
+        let refused = open(IntegrityMode::Refuse);
+        assert!(matches!(
+            refused.get_vocabulary("entity-type.v1.0.0"),
+            Err(CepError::AssetIntegrity(_))
+        ));
+        let warned = open(IntegrityMode::Warn);
+        assert!(warned.integrity_warnings().is_empty());
+        let vocab = warned.get_vocabulary("entity-type.v1.0.0").unwrap();
+        assert_eq!(vocab["version"], "1.0.0");
+        let warnings = warned.integrity_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("vocabularies/entity-type.v1.0.0.json"));
+
+        for key in ["../secret", "sub/entity-type", "..", "a\\b", ""] {
+            assert!(
+                matches!(warned.get_vocabulary(key), Err(CepError::UnknownSchema(_))),
+                "{}",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_registry_refuses_altered_snapshot() {
+        let (dir, _, _) = signed_fixture();
+        let store_dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(store_dir.path().to_path_buf());
+        let hash = store
+            .pin(dir.path(), &["schemas/cep.entity.schema.json"])
+            .unwrap();
+
+        fs::write(
+            store
+                .snapshot_dir(&hash)
+                .join("schemas/cep.entity.schema.json"),
+            r#"{"$id":"urn:test:entity","type":"string"}"#,
+        )
+        .unwrap();
+        let result = SchemaRegistry::from_snapshot(&store, &hash);
+        assert!(matches!(result, Err(CepError::AssetIntegrity(_))));
+    }
+
+    #[test]
+    fn test_versions_coexist() {
+        let mut registry = SchemaRegistry::with_root(PathBuf::from("/nonexistent")).unwrap();
+        let v1_1 = serde_json::json!({"$id": "urn:test:entity:1.1", "title": "1.1"});
+        registry.register_schema("entity", SchemaVersion::new(1, 1, 0), v1_1);
+        registry.register_schema(
+            "entity",
+            CURRENT_SCHEMA_VERSION,
+            serde_json::json!({"title": "1.0"}),
+        );
+        assert_eq!(registry.schema_versions("entity"), ["1.0", "1.1"]);
+        assert!(registry.get_registry().contains_key("urn:test:entity:1.1"));
+        assert!(registry.get_registry().contains_key("urn:cep:entity:1.0"));
+
+        let schema_for = |version: Value| {
+            registry.get_schema_for("entity", &serde_json::json!({ "schemaVersion": version }))
+        };
+        assert_eq!(schema_for("1.1.3".into()).unwrap()["title"], "1.1");
+        assert_eq!(schema_for("1.0.0".into()).unwrap()["title"], "1.0");
+        assert!(matches!(
+            schema_for("2.0.0".into()),
+            Err(CepError::UnknownSchema(_))
+        ));
+        assert!(matches!(
+            schema_for(1.into()),
+            Err(CepError::UnsupportedVersion(_))
+        ));
+        let undeclared = registry
+            .get_schema_for("entity", &serde_json::json!({}))
+            .unwrap();
+        assert_eq!(undeclared["title"], "1.0");
+    }
+
     #[test]
     fn test_registry_clone() {
         let registry1 = SchemaRegistry::with_root(PathBuf::from("/tmp")).unwrap();
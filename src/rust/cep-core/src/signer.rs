@@ -0,0 +1,348 @@
+//! Signing and verification backends, and batch signing.
+//!
+//! Key custody is pluggable: an organization holding its key in an HSM or a
+//! cloud KMS implements [`Signer`] (and, where it verifies its own proofs,
+//! [`Verifier`]) and passes it to [`Attestation::sign`](crate::Attestation::sign)
+//! and [`Attestation::verify`](crate::Attestation::verify). [`Ed25519Signer`]
+//! and [`Ed25519Verifier`] are the in-memory implementations shipped here.
+//!
+//! A [`Signer`] turns the SHA-256 signing hash of a record (see
+//! [`signing_hash`](crate::keys::signing_hash)) into a multibase
+//! `proofValue`. Attestations are signed through [`Signer::sign_input`],
+//! which receives the canonical string itself and by default signs its hash;
+//! proof formats that carry the payload, like detached JWS, override it.
+//! Sealing a large bundle one call at a time is dominated by
+//! per-call overhead when the key lives behind a network service or device,
+//! so [`Signer::sign_batch`] takes every hash at once and each backend
+//! batches or pipelines as suits it. [`Signer::sign_input_batch`] is its
+//! counterpart over canonical strings, and is what [`sign_records`] calls. The default implementation signs one at
+//! a time; [`Ed25519Signer`] spreads a batch over worker threads.
+//!
+//! An Ed25519 key can produce either [`ProofType::Ed25519Signature2020`] or
+//! [`ProofType::JsonWebSignature2020`] proofs; [`ed25519_signer`] and
+//! [`ed25519_verifier`] pick the backend for a proof type.
+//!
+//! ```rust
+//! use cep_core::keys::signing_input;
+//! use cep_core::signer::{Ed25519Signer, Ed25519Verifier, Verifier, sign_records};
+//! use cep_core::KeyFile;
+//! # use cep_core::Canonicalize;
+//! # use std::collections::BTreeMap;
+//! # struct Note(u32);
+//! # impl Canonicalize for Note {
+//! #     fn canonical_fields(&self) -> BTreeMap<String, String> {
+//! #         BTreeMap::from([("n".to_string(), self.0.to_string())])
+//! #     }
+//! # }
+//!
+//! let keys = KeyFile::generate_ed25519();
+//! let signer = Ed25519Signer::new(keys.signing_key().unwrap());
+//! let notes: Vec<Note> = (0..1000).map(Note).collect();
+//!
+//! let proofs = sign_records(&notes, &signer).unwrap();
+//! let verifier = Ed25519Verifier::new(keys.verifying_key().unwrap());
+//! verifier.verify_input(&signing_input(&notes[7]), &proofs[7]).unwrap();
+//! ```
+
+use crate::attestation::ProofType;
+use crate::canonical::Canonicalize;
+use crate::error::{CepError, CepResult};
+use crate::hash::CanonicalHash;
+use crate::jws::{JwsSigner, JwsVerifier};
+use crate::keys::{decode_multibase, encode_multibase, signing_input};
+use ed25519_dalek::{Signature, Signer as _, SigningKey, Verifier as _, VerifyingKey};
+use std::thread;
+
+/// Batches smaller than this are signed on the calling thread.
+const MIN_PARALLEL_BATCH: usize = 256;
+
+/// A signing key, wherever it is held.
+pub trait Signer: Send + Sync {
+    /// Proof type written into attestations this signer produces.
+    fn proof_type(&self) -> ProofType;
+
+    /// Signs one signing hash and returns the multibase `proofValue`.
+    fn sign(&self, hash: &CanonicalHash) -> CepResult<String>;
+
+    /// Signs one canonical signing input (see
+    /// [`signing_input`](crate::keys::signing_input)) and returns the
+    /// `proofValue`.
+    ///
+    /// The default signs the input's SHA-256 with [`sign`](Self::sign).
+    fn sign_input(&self, input: &str) -> CepResult<String> {
+        self.sign(&CanonicalHash::from_canonical_string(input))
+    }
+
+    /// Signs many signing hashes, returning proofs in the same order.
+    ///
+    /// Backends with per-call overhead should override this to batch or
+    /// pipeline requests. The default signs one hash at a time.
+    fn sign_batch(&self, hashes: &[CanonicalHash]) -> CepResult<Vec<String>> {
+        hashes.iter().map(|hash| self.sign(hash)).collect()
+    }
+
+    /// Signs many canonical signing inputs, returning proofs in the same
+    /// order.
+    ///
+    /// The default hashes every input and signs the hashes with
+    /// [`sign_batch`](Self::sign_batch). Backends that override
+    /// [`sign_input`](Self::sign_input) must override this too.
+    fn sign_input_batch(&self, inputs: &[String]) -> CepResult<Vec<String>> {
+        let hashes: Vec<CanonicalHash> = inputs
+            .iter()
+            .map(|input| CanonicalHash::from_canonical_string(input))
+            .collect();
+        self.sign_batch(&hashes)
+    }
+}
+
+/// Checks proofs of one proof type against a public key, wherever it is held.
+pub trait Verifier: Send + Sync {
+    /// Proof type this verifier checks.
+    fn proof_type(&self) -> ProofType;
+
+    /// Checks a multibase `proofValue` over a signing hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidSignature` if the proof is malformed or does
+    /// not verify.
+    fn verify(&self, hash: &CanonicalHash, proof_value: &str) -> CepResult<()>;
+
+    /// Checks a `proofValue` over a canonical signing input.
+    ///
+    /// The default checks it over the input's SHA-256 with
+    /// [`verify`](Self::verify).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidSignature` if the proof is malformed or does
+    /// not verify.
+    fn verify_input(&self, input: &str, proof_value: &str) -> CepResult<()> {
+        self.verify(&CanonicalHash::from_canonical_string(input), proof_value)
+    }
+}
+
+/// Signs with an Ed25519 key held in memory.
+#[derive(Debug, Clone)]
+pub struct Ed25519Signer {
+    key: SigningKey,
+    workers: usize,
+}
+
+impl Ed25519Signer {
+    /// Creates a signer using one worker per available CPU for batches.
+    pub fn new(key: SigningKey) -> Self {
+        Self {
+            key,
+            workers: thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+
+    /// Uses `workers` threads (at least 1) for batches.
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    fn sign_hash(&self, hash: &CanonicalHash) -> String {
+        encode_multibase(&self.key.sign(&hash.as_bytes()).to_bytes())
+    }
+}
+
+impl Signer for Ed25519Signer {
+    fn proof_type(&self) -> ProofType {
+        ProofType::Ed25519Signature2020
+    }
+
+    fn sign(&self, hash: &CanonicalHash) -> CepResult<String> {
+        Ok(self.sign_hash(hash))
+    }
+
+    /// Splits the batch into one contiguous chunk per worker.
+    fn sign_batch(&self, hashes: &[CanonicalHash]) -> CepResult<Vec<String>> {
+        if self.workers == 1 || hashes.len() < MIN_PARALLEL_BATCH {
+            return Ok(hashes.iter().map(|hash| self.sign_hash(hash)).collect());
+        }
+        let chunk = hashes.len().div_ceil(self.workers);
+        let proofs = thread::scope(|scope| {
+            let handles: Vec<_> = hashes
+                .chunks(chunk)
+                .map(|part| {
+                    scope.spawn(move || {
+                        part.iter()
+                            .map(|hash| self.sign_hash(hash))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("signing worker panicked"))
+                .collect()
+        });
+        Ok(proofs)
+    }
+}
+
+/// Verifies with an Ed25519 public key held in memory.
+#[derive(Debug, Clone)]
+pub struct Ed25519Verifier {
+    key: VerifyingKey,
+}
+
+impl Ed25519Verifier {
+    pub fn new(key: VerifyingKey) -> Self {
+        Self { key }
+    }
+}
+
+impl Verifier for Ed25519Verifier {
+    fn proof_type(&self) -> ProofType {
+        ProofType::Ed25519Signature2020
+    }
+
+    fn verify(&self, hash: &CanonicalHash, proof_value: &str) -> CepResult<()> {
+        let bytes = decode_multibase(proof_value)?;
+        let signature = Signature::from_slice(&bytes)
+            .map_err(|e| CepError::InvalidSignature(format!("malformed proof value: {}", e)))?;
+        self.key
+            .verify(&hash.as_bytes(), &signature)
+            .map_err(|_| CepError::InvalidSignature("proof value does not verify".to_string()))
+    }
+}
+
+/// Returns the in-memory signer producing `proof_type` proofs with an
+/// Ed25519 key.
+///
+/// # Errors
+///
+/// Returns `CepError::InvalidSignature` if Ed25519 keys cannot produce
+/// `proof_type` proofs.
+pub fn ed25519_signer(proof_type: &ProofType, key: SigningKey) -> CepResult<Box<dyn Signer>> {
+    match proof_type {
+        ProofType::Ed25519Signature2020 => Ok(Box::new(Ed25519Signer::new(key))),
+        ProofType::JsonWebSignature2020 => Ok(Box::new(JwsSigner::new(key))),
+        other => Err(unsupported(other)),
+    }
+}
+
+/// Returns the in-memory verifier checking `proof_type` proofs against an
+/// Ed25519 key.
+///
+/// # Errors
+///
+/// Returns `CepError::InvalidSignature` if Ed25519 keys cannot check
+/// `proof_type` proofs.
+pub fn ed25519_verifier(proof_type: &ProofType, key: VerifyingKey) -> CepResult<Box<dyn Verifier>> {
+    match proof_type {
+        ProofType::Ed25519Signature2020 => Ok(Box::new(Ed25519Verifier::new(key))),
+        ProofType::JsonWebSignature2020 => Ok(Box::new(JwsVerifier::new(key))),
+        other => Err(unsupported(other)),
+    }
+}
+
+fn unsupported(proof_type: &ProofType) -> CepError {
+    CepError::InvalidSignature(format!(
+        "proof type '{}' cannot be used with an Ed25519 key",
+        proof_type
+    ))
+}
+
+/// Signs every record with one [`Signer::sign_input_batch`] call and returns
+/// the proofs in order.
+///
+/// The proofs cover the records' signing inputs alone, not the metadata of
+/// the attestations they end up in; [`Attestation::prove`](crate::Attestation::prove)
+/// binds both.
+///
+/// # Errors
+///
+/// Passes through the signer's errors, and returns
+/// `CepError::InvalidSignature` if it returns the wrong number of proofs.
+pub fn sign_records<T: Canonicalize>(records: &[T], signer: &dyn Signer) -> CepResult<Vec<String>> {
+    let inputs: Vec<String> = records.iter().map(signing_input).collect();
+    let proofs = signer.sign_input_batch(&inputs)?;
+    if proofs.len() != inputs.len() {
+        return Err(CepError::InvalidSignature(format!(
+            "signer returned {} proofs for {} records",
+            proofs.len(),
+            inputs.len()
+        )));
+    }
+    Ok(proofs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Note(usize);
+
+    impl Canonicalize for Note {
+        fn canonical_fields(&self) -> BTreeMap<String, String> {
+            BTreeMap::from([("n".to_string(), self.0.to_string())])
+        }
+    }
+
+    /// Counts calls, as a remote backend would be charged per round trip.
+    struct CountingSigner {
+        inner: Ed25519Signer,
+        calls: AtomicUsize,
+    }
+
+    impl Signer for CountingSigner {
+        fn proof_type(&self) -> ProofType {
+            self.inner.proof_type()
+        }
+
+        fn sign(&self, hash: &CanonicalHash) -> CepResult<String> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            self.inner.sign(hash)
+        }
+    }
+
+    #[test]
+    fn test_batch_matches_single_signing() {
+        let key = SigningKey::from_bytes(&[3u8; 32]);
+        let notes: Vec<Note> = (0..1_000).map(Note).collect();
+        let signer = Ed25519Signer::new(key.clone()).with_workers(4);
+
+        let proofs = sign_records(&notes, &signer).unwrap();
+        assert_eq!(proofs.len(), notes.len());
+        let single = Ed25519Signer::new(key.clone());
+        for (note, proof) in notes.iter().zip(&proofs) {
+            assert_eq!(*proof, single.sign_input(&signing_input(note)).unwrap());
+        }
+        Ed25519Verifier::new(key.verifying_key())
+            .verify_input(&signing_input(&notes[999]), &proofs[999])
+            .unwrap();
+        assert!(sign_records::<Note>(&[], &signer).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_default_batch_signs_each_hash() {
+        let counting = CountingSigner {
+            inner: Ed25519Signer::new(SigningKey::from_bytes(&[4u8; 32])),
+            calls: AtomicUsize::new(0),
+        };
+        let notes: Vec<Note> = (0..10).map(Note).collect();
+        let proofs = sign_records(&notes, &counting).unwrap();
+        assert_eq!(counting.calls.load(Ordering::Relaxed), 10);
+        assert_eq!(proofs, sign_records(&notes, &counting.inner).unwrap());
+    }
+
+    #[test]
+    fn test_sign_records_with_input_signer() {
+        let key = SigningKey::from_bytes(&[6u8; 32]);
+        let notes: Vec<Note> = (0..5).map(Note).collect();
+
+        let proofs = sign_records(&notes, &JwsSigner::new(key.clone())).unwrap();
+        let verifier = JwsVerifier::new(key.verifying_key());
+        for (note, proof) in notes.iter().zip(&proofs) {
+            verifier.verify_input(&signing_input(note), proof).unwrap();
+        }
+    }
+}
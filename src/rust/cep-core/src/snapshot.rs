@@ -0,0 +1,216 @@
+//! Pinned schema and vocabulary snapshots.
+//!
+//! Schemas and vocabularies evolve, but a record verified years after it was
+//! written must be checked against the definitions it was validated with at
+//! the time. A snapshot is a frozen copy of those asset files, stored under
+//! the [`snapshot_hash`](AssetManifest::snapshot_hash) of their manifest:
+//!
+//! ```text
+//! <store>/<snapshot hash>/snapshot-manifest.json
+//! <store>/<snapshot hash>/schemas/cep.entity.schema.json
+//! <store>/<snapshot hash>/vocabularies/entity-type.v1.0.0.json
+//! ```
+//!
+//! Records declare the snapshot they were validated against in
+//! `schemaSnapshotHash`, alongside a human-readable `vocabVersion`.
+//! [`SchemaRegistry::from_snapshot`](crate::SchemaRegistry::from_snapshot)
+//! loads a pinned snapshot offline and refuses any file whose content no
+//! longer matches the manifest.
+
+use crate::error::{CepError, CepResult};
+use crate::hash::CanonicalHash;
+use crate::manifest::AssetManifest;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name of the manifest inside each snapshot directory.
+pub const SNAPSHOT_MANIFEST_FILE: &str = "snapshot-manifest.json";
+
+/// A directory of pinned snapshots, keyed by snapshot hash.
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+    root: PathBuf,
+}
+
+impl SnapshotStore {
+    /// Creates a store rooted at `root`. The directory is created on first pin.
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Returns the store root.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the directory holding the snapshot with the given hash.
+    pub fn snapshot_dir(&self, hash: &CanonicalHash) -> PathBuf {
+        self.root.join(hash.as_hex())
+    }
+
+    /// Copies the given asset files from `source_root` into a new snapshot.
+    ///
+    /// Pinning the same contents twice yields the same hash and leaves the
+    /// existing snapshot in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::Configuration` if a file cannot be read or written.
+    pub fn pin<S: AsRef<str>>(
+        &self,
+        source_root: &Path,
+        relative_paths: &[S],
+    ) -> CepResult<CanonicalHash> {
+        let manifest = AssetManifest::build(source_root, relative_paths)?;
+        let hash = manifest.snapshot_hash();
+        let dir = self.snapshot_dir(&hash);
+
+        for relative in manifest.assets.keys() {
+            let target = dir.join(relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| write_error(parent, e))?;
+            }
+            fs::copy(source_root.join(relative), &target).map_err(|e| write_error(&target, e))?;
+        }
+
+        let manifest_path = dir.join(SNAPSHOT_MANIFEST_FILE);
+        let json = serde_json::to_string_pretty(&manifest)?;
+        fs::write(&manifest_path, json).map_err(|e| write_error(&manifest_path, e))?;
+        Ok(hash)
+    }
+
+    /// Loads the manifest of a pinned snapshot.
+    ///
+    /// # Errors
+    ///
+    /// - `CepError::Configuration` if the snapshot does not exist
+    /// - `CepError::AssetIntegrity` if the stored manifest does not hash to `hash`
+    pub fn manifest(&self, hash: &CanonicalHash) -> CepResult<AssetManifest> {
+        let path = self.snapshot_dir(hash).join(SNAPSHOT_MANIFEST_FILE);
+        let content = fs::read_to_string(&path).map_err(|e| {
+            CepError::Configuration(format!("Failed to read snapshot {}: {}", path.display(), e))
+        })?;
+        let manifest: AssetManifest = serde_json::from_str(&content)?;
+        let actual = manifest.snapshot_hash();
+        if actual != *hash {
+            return Err(CepError::AssetIntegrity(format!(
+                "snapshot manifest hashes to {}, expected {}",
+                actual, hash
+            )));
+        }
+        Ok(manifest)
+    }
+
+    /// Lists the hashes of all snapshots in the store, sorted.
+    pub fn list(&self) -> CepResult<Vec<CanonicalHash>> {
+        if !self.root.is_dir() {
+            return Ok(Vec::new());
+        }
+        let entries = fs::read_dir(&self.root).map_err(|e| {
+            CepError::Configuration(format!(
+                "Failed to read snapshot store {}: {}",
+                self.root.display(),
+                e
+            ))
+        })?;
+        let mut hashes: Vec<CanonicalHash> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().join(SNAPSHOT_MANIFEST_FILE).is_file())
+            .filter_map(|entry| CanonicalHash::from_hex(&entry.file_name().to_string_lossy()))
+            .collect();
+        hashes.sort_by(|a, b| a.as_hex().cmp(b.as_hex()));
+        Ok(hashes)
+    }
+}
+
+fn write_error(path: &Path, e: std::io::Error) -> CepError {
+    CepError::Configuration(format!(
+        "Failed to write snapshot {}: {}",
+        path.display(),
+        e
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("schemas")).unwrap();
+        fs::create_dir_all(dir.path().join("vocabularies")).unwrap();
+        fs::write(dir.path().join("schemas/a.json"), r#"{"a":1}"#).unwrap();
+        fs::write(
+            dir.path().join("vocabularies/v.v1.0.0.json"),
+            r#"{"terms":[]}"#,
+        )
+        .unwrap();
+        dir
+    }
+
+    const PATHS: [&str; 2] = ["schemas/a.json", "vocabularies/v.v1.0.0.json"];
+
+    #[test]
+    fn test_pin_copies_assets_under_hash() {
+        let source = source();
+        let store_dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(store_dir.path().to_path_buf());
+
+        let hash = store.pin(source.path(), &PATHS).unwrap();
+        let dir = store.snapshot_dir(&hash);
+        assert!(dir.join("schemas/a.json").is_file());
+        assert!(dir.join("vocabularies/v.v1.0.0.json").is_file());
+
+        let manifest = store.manifest(&hash).unwrap();
+        assert_eq!(manifest.snapshot_hash(), hash);
+        assert_eq!(store.list().unwrap(), vec![hash.clone()]);
+
+        // Same contents, same snapshot.
+        assert_eq!(store.pin(source.path(), &PATHS).unwrap(), hash);
+        assert_eq!(store.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_changed_assets_get_new_snapshot() {
+        let source = source();
+        let store_dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(store_dir.path().to_path_buf());
+
+        let first = store.pin(source.path(), &PATHS).unwrap();
+        fs::write(source.path().join("schemas/a.json"), r#"{"a":2}"#).unwrap();
+        let second = store.pin(source.path(), &PATHS).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(store.list().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_tampered_manifest_is_rejected() {
+        let source = source();
+        let store_dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(store_dir.path().to_path_buf());
+        let hash = store.pin(source.path(), &PATHS).unwrap();
+
+        let path = store.snapshot_dir(&hash).join(SNAPSHOT_MANIFEST_FILE);
+        let mut manifest = store.manifest(&hash).unwrap();
+        manifest
+            .assets
+            .insert("schemas/a.json".to_string(), "0".repeat(64));
+        fs::write(&path, serde_json::to_string(&manifest).unwrap()).unwrap();
+
+        assert!(matches!(
+            store.manifest(&hash),
+            Err(CepError::AssetIntegrity(_))
+        ));
+    }
+
+    #[test]
+    fn test_missing_snapshot() {
+        let store = SnapshotStore::new(PathBuf::from("/nonexistent/store"));
+        let hash = CanonicalHash::from_canonical_string("nothing");
+        assert!(matches!(
+            store.manifest(&hash),
+            Err(CepError::Configuration(_))
+        ));
+        assert!(store.list().unwrap().is_empty());
+    }
+}
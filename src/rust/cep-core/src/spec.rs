@@ -0,0 +1,261 @@
+//! Machine-readable description of the canonicalization algorithm.
+//!
+//! Ports of CEP to other languages must reproduce the canonical string
+//! byte-for-byte. Rather than reverse-engineering the rules from prose, they
+//! can consume [`CanonicalizationSpec`] as JSON: it states the global rules
+//! (ordering, joining, escaping, omission, number and timestamp formats) and
+//! lists every canonical field of every record type with its encoding.
+//!
+//! Record crates describe their types by implementing [`CanonicalSpec`]:
+//!
+//! ```rust
+//! use cep_core::spec::{CanonicalizationSpec, CanonicalSpec};
+//! use cep_core::Attestation;
+//!
+//! let spec = CanonicalizationSpec::current().with_type(Attestation::type_spec());
+//! let json = spec.to_json().unwrap();
+//! assert!(json.contains("\"attestorId\""));
+//! ```
+
+use crate::error::CepResult;
+use serde::Serialize;
+
+/// Version of the canonicalization rules described by this module.
+///
+/// Bumped whenever a rule change would alter any canonical string.
+pub const CANONICALIZATION_SPEC_VERSION: &str = "1.0.0";
+
+/// How a field value is rendered in the canonical string.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum FieldEncoding {
+    /// The string value verbatim.
+    String,
+    /// `YYYY-MM-DDTHH:MM:SS.ffffffZ`.
+    Timestamp,
+    /// Fixed-point decimal with the given number of places (amounts use 2).
+    Decimal { places: u8 },
+    /// Base-10 integer without a decimal point.
+    Integer,
+    /// `true` or `false`.
+    Boolean,
+    /// 64 lowercase hex characters.
+    Hash,
+    /// Canonical string of a nested type, embedded without escaping.
+    Nested {
+        #[serde(rename = "type")]
+        type_name: String,
+    },
+    /// `[` + comma-joined canonical strings of a nested type + `]`.
+    NestedList {
+        #[serde(rename = "type")]
+        type_name: String,
+        /// Fields the elements are sorted by before joining (empty: input order).
+        #[serde(rename = "sortBy")]
+        sort_by: Vec<String>,
+    },
+    /// Compact JSON (`serde_json`) with object keys in sorted order.
+    Json,
+}
+
+impl FieldEncoding {
+    /// Canonical string of a nested type.
+    pub fn nested(type_name: &str) -> Self {
+        Self::Nested {
+            type_name: type_name.to_string(),
+        }
+    }
+
+    /// Bracketed list of a nested type, sorted by `sort_by`.
+    pub fn nested_list(type_name: &str, sort_by: &[&str]) -> Self {
+        Self::NestedList {
+            type_name: type_name.to_string(),
+            sort_by: sort_by.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// Monetary amount (2 decimal places).
+    pub fn amount() -> Self {
+        Self::Decimal { places: 2 }
+    }
+}
+
+/// One canonical field of a type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldSpec {
+    /// Key as it appears in the canonical string.
+    pub name: String,
+    /// Whether the field is always present.
+    pub required: bool,
+    /// How the value is rendered.
+    pub encoding: FieldEncoding,
+}
+
+/// Canonical field list of one type, in canonical (sorted) order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeSpec {
+    /// Type name, used by [`FieldEncoding::Nested`] references.
+    pub name: String,
+    /// Fields sorted by key.
+    pub fields: Vec<FieldSpec>,
+}
+
+impl TypeSpec {
+    /// Creates an empty type description.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Adds a field that is always present.
+    pub fn required(self, name: &str, encoding: FieldEncoding) -> Self {
+        self.field(name, true, encoding)
+    }
+
+    /// Adds a field that is omitted when null or empty.
+    pub fn optional(self, name: &str, encoding: FieldEncoding) -> Self {
+        self.field(name, false, encoding)
+    }
+
+    fn field(mut self, name: &str, required: bool, encoding: FieldEncoding) -> Self {
+        self.fields.push(FieldSpec {
+            name: name.to_string(),
+            required,
+            encoding,
+        });
+        self.fields.sort_by(|a, b| a.name.cmp(&b.name));
+        self
+    }
+
+    /// Returns the field names in canonical order.
+    pub fn field_names(&self) -> Vec<&str> {
+        self.fields.iter().map(|f| f.name.as_str()).collect()
+    }
+
+    /// Returns the names of required fields in canonical order.
+    pub fn required_field_names(&self) -> Vec<&str> {
+        self.fields
+            .iter()
+            .filter(|f| f.required)
+            .map(|f| f.name.as_str())
+            .collect()
+    }
+}
+
+/// Implemented by types that describe their own canonical field list.
+pub trait CanonicalSpec {
+    /// Returns the canonical field list of this type.
+    fn type_spec() -> TypeSpec;
+}
+
+/// Versioned description of the canonicalization algorithm.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CanonicalizationSpec {
+    pub spec_version: String,
+    pub schema_version: String,
+    pub hash_algorithm: String,
+    pub text_encoding: String,
+    pub field_order: String,
+    pub field_template: String,
+    pub field_separator: String,
+    pub string_escaping: String,
+    pub omitted_values: Vec<String>,
+    pub timestamp_format: String,
+    pub amount_format: String,
+    pub types: Vec<TypeSpec>,
+}
+
+impl CanonicalizationSpec {
+    /// Returns the current rules with no types listed.
+    pub fn current() -> Self {
+        Self {
+            spec_version: CANONICALIZATION_SPEC_VERSION.to_string(),
+            schema_version: crate::version::SCHEMA_VERSION.to_string(),
+            hash_algorithm: "SHA-256, lowercase hex".to_string(),
+            text_encoding: "UTF-8".to_string(),
+            field_order: "keys sorted by UTF-8 byte order".to_string(),
+            field_template: "\"{key}\":\"{value}\"".to_string(),
+            field_separator: ",".to_string(),
+            string_escaping: "none".to_string(),
+            omitted_values: vec!["null".to_string(), "empty string".to_string()],
+            timestamp_format: "%Y-%m-%dT%H:%M:%S%.6fZ".to_string(),
+            amount_format: "fixed-point, 2 decimal places; exact binary value rounded half to even"
+                .to_string(),
+            types: Vec::new(),
+        }
+    }
+
+    /// Adds a type description.
+    pub fn with_type(mut self, spec: TypeSpec) -> Self {
+        self.types.push(spec);
+        self
+    }
+
+    /// Adds several type descriptions.
+    pub fn with_types(mut self, specs: impl IntoIterator<Item = TypeSpec>) -> Self {
+        self.types.extend(specs);
+        self
+    }
+
+    /// Looks up a type description by name.
+    pub fn get_type(&self, name: &str) -> Option<&TypeSpec> {
+        self.types.iter().find(|t| t.name == name)
+    }
+
+    /// Serializes the spec as pretty-printed JSON.
+    pub fn to_json(&self) -> CepResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::{Attestation, ProofType};
+    use crate::canonical::Canonicalize;
+
+    #[test]
+    fn test_type_spec_sorts_fields() {
+        let spec = TypeSpec::new("Example")
+            .required("gamma", FieldEncoding::String)
+            .optional("alpha", FieldEncoding::Integer);
+        assert_eq!(spec.field_names(), vec!["alpha", "gamma"]);
+        assert_eq!(spec.required_field_names(), vec!["gamma"]);
+    }
+
+    #[test]
+    fn test_attestation_spec_matches_canonical_fields() {
+        let attestation = Attestation::new(
+            "cep-entity:sam-uei:J6H4FB3N5YK7".to_string(),
+            "2025-11-28T14:30:00.000000Z".parse().unwrap(),
+            ProofType::Ed25519Signature2020,
+            "z3FXQqFwbZxKBxGxqFpCD...".to_string(),
+            "did:web:example.gov#key-1".to_string(),
+        );
+        let spec = Attestation::type_spec();
+        let minimal: Vec<String> = attestation.canonical_fields().into_keys().collect();
+        assert_eq!(spec.required_field_names(), minimal);
+
+        let full = attestation.with_anchor("https://anchor.example/1".to_string());
+        let all: Vec<String> = full.canonical_fields().into_keys().collect();
+        assert_eq!(spec.field_names(), all);
+    }
+
+    #[test]
+    fn test_spec_json_shape() {
+        let spec = CanonicalizationSpec::current().with_type(Attestation::type_spec());
+        let value: serde_json::Value = serde_json::from_str(&spec.to_json().unwrap()).unwrap();
+        assert_eq!(value["specVersion"], CANONICALIZATION_SPEC_VERSION);
+        assert_eq!(value["fieldSeparator"], ",");
+        assert_eq!(value["types"][0]["name"], "Attestation");
+        assert_eq!(
+            value["types"][0]["fields"][1]["encoding"]["kind"],
+            "timestamp"
+        );
+    }
+}
@@ -0,0 +1,408 @@
+//! Streaming deserialization of record bundles.
+//!
+//! [`ResourceLimits::parse_bundle`] needs the whole bundle in memory. For
+//! large or hostile inputs, [`stream_bundle`] reads a bundle (a JSON array of
+//! records) straight from an [`io::Read`] and hands each record to a callback
+//! as soon as it has been parsed:
+//!
+//! - Each record is deserialized directly into its typed form. Fields the type
+//!   does not know are skipped with serde's `IgnoredAny`, which scans past
+//!   them without allocating, so a giant unknown field is never materialized.
+//! - Every record has a byte budget of [`ResourceLimits::max_record_size`];
+//!   the reader refuses to hand out more bytes once it is spent, so a giant
+//!   known field is cut off before it can exhaust memory.
+//! - Entries are counted against [`ResourceLimits::max_bundle_entries`].
+//!
+//! Record types used with this function should not capture unknown fields
+//! (`#[serde(flatten)]` into a map, or `serde_json::Value` catch-alls), since
+//! that would defeat the skipping.
+//!
+//! zstd-compressed bundles are decompressed transparently, and the byte
+//! budget applies to the decompressed bytes. [`write_bundle`] writes a bundle,
+//! optionally compressed, holding only the records the recipient's
+//! [`AccessScopes`] permit. See [`crate::compression`].
+
+use crate::classification::{AccessScopes, Classified};
+use crate::compression::{Compression, decompressing_reader, write_compressed};
+use crate::error::{CepError, CepResult};
+use crate::limits::ResourceLimits;
+use serde::de::{DeserializeOwned, SeqAccess, Visitor};
+use serde::{Deserializer, Serialize};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+/// Reads a bundle from `reader`, calling `on_record` for each entry.
+///
+/// Returns the number of records processed.
+///
+/// # Errors
+///
+/// - `CepError::ResourceLimit` if a record exceeds its byte budget or the
+///   bundle has too many entries (records before the violation have already
+///   been passed to `on_record`)
+/// - `CepError::Serialization` for malformed JSON or records that do not
+///   match `T`
+/// - any error returned by `on_record`, which stops the stream
+///
+/// # Example
+///
+/// ```rust
+/// use cep_core::limits::ResourceLimits;
+/// use cep_core::stream::stream_bundle;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Named { name: String }
+///
+/// let json = br#"[{"name":"a","junk":"..."},{"name":"b"}]"#;
+/// let mut names = Vec::new();
+/// let count = stream_bundle(&json[..], &ResourceLimits::default(), |n: Named| {
+///     names.push(n.name);
+///     Ok(())
+/// })
+/// .unwrap();
+/// assert_eq!(count, 2);
+/// assert_eq!(names, ["a", "b"]);
+/// ```
+pub fn stream_bundle<R, T, F>(reader: R, limits: &ResourceLimits, on_record: F) -> CepResult<usize>
+where
+    R: Read,
+    T: DeserializeOwned,
+    F: FnMut(T) -> CepResult<()>,
+{
+    let budget = Rc::new(Budget::new(limits.max_record_size));
+    let reader = BudgetReader {
+        inner: decompressing_reader(reader)?,
+        budget: Rc::clone(&budget),
+    };
+    let failure = RefCell::new(None);
+    let visitor = EntryVisitor {
+        limits,
+        budget: &budget,
+        failure: &failure,
+        on_record,
+        marker: PhantomData,
+    };
+
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let result = deserializer
+        .deserialize_seq(visitor)
+        .and_then(|count| deserializer.end().map(|_| count));
+
+    if let Some(err) = failure.into_inner() {
+        return Err(err);
+    }
+    if budget.exceeded.get() {
+        return Err(CepError::ResourceLimit {
+            limit: "record size".to_string(),
+            actual: budget.used.get() + 1,
+            max: limits.max_record_size,
+        });
+    }
+    Ok(result?)
+}
+
+/// Writes the records of `records` that `scopes` permit as a bundle (a JSON
+/// array), compressed as requested. Records the recipient may not receive
+/// are left out.
+///
+/// Returns `writer` once the bundle is complete.
+///
+/// # Example
+///
+/// ```rust
+/// use cep_core::classification::{AccessScopes, Classification, Classified};
+/// use cep_core::compression::Compression;
+/// use cep_core::limits::ResourceLimits;
+/// use cep_core::stream::{stream_bundle, write_bundle};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Note { name: String, classification: Classification }
+///
+/// impl Classified for Note {
+///     fn classification(&self) -> Classification { self.classification }
+/// }
+///
+/// let notes = vec![
+///     Note { name: "a".to_string(), classification: Classification::Public },
+///     Note { name: "b".to_string(), classification: Classification::Internal },
+/// ];
+/// let public = AccessScopes::public();
+/// let bytes = write_bundle(Vec::new(), &notes, &public, Compression::None).unwrap();
+/// let mut read = Vec::new();
+/// stream_bundle(&bytes[..], &ResourceLimits::default(), |n: Note| {
+///     read.push(n.name);
+///     Ok(())
+/// })
+/// .unwrap();
+/// assert_eq!(read, ["a"]);
+/// ```
+pub fn write_bundle<W, T>(
+    writer: W,
+    records: &[T],
+    scopes: &AccessScopes,
+    compression: Compression,
+) -> CepResult<W>
+where
+    W: Write,
+    T: Serialize + Classified,
+{
+    let permitted = scopes.filter(records);
+    write_compressed(writer, compression, |w| {
+        serde_json::to_writer(w, &permitted)?;
+        Ok(())
+    })
+}
+
+/// Per-record byte allowance shared between the reader and the visitor.
+struct Budget {
+    max: usize,
+    used: Cell<usize>,
+    exceeded: Cell<bool>,
+}
+
+impl Budget {
+    fn new(max: usize) -> Self {
+        Self {
+            max,
+            used: Cell::new(0),
+            exceeded: Cell::new(false),
+        }
+    }
+
+    fn reset(&self) {
+        self.used.set(0);
+    }
+}
+
+/// Reader that stops handing out bytes once the current record's budget is spent.
+struct BudgetReader<R> {
+    inner: R,
+    budget: Rc<Budget>,
+}
+
+impl<R: Read> Read for BudgetReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.budget.max - self.budget.used.get();
+        if remaining == 0 {
+            self.budget.exceeded.set(true);
+            return Err(io::Error::other("record size limit exceeded"));
+        }
+        let len = buf.len().min(remaining);
+        let n = self.inner.read(&mut buf[..len])?;
+        self.budget.used.set(self.budget.used.get() + n);
+        Ok(n)
+    }
+}
+
+struct EntryVisitor<'a, T, F> {
+    limits: &'a ResourceLimits,
+    budget: &'a Budget,
+    failure: &'a RefCell<Option<CepError>>,
+    on_record: F,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'de, T, F> Visitor<'de> for EntryVisitor<'_, T, F>
+where
+    T: DeserializeOwned,
+    F: FnMut(T) -> CepResult<()>,
+{
+    type Value = usize;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a JSON array of records")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<usize, A::Error> {
+        let mut count = 0;
+        loop {
+            self.budget.reset();
+            let Some(record) = seq.next_element::<T>()? else {
+                return Ok(count);
+            };
+            count += 1;
+            let outcome = self
+                .limits
+                .check_bundle_entries(count)
+                .and_then(|_| (self.on_record)(record));
+            if let Err(err) = outcome {
+                *self.failure.borrow_mut() = Some(err);
+                return Err(serde::de::Error::custom("bundle stream stopped"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classification::Classification;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    struct Named {
+        legal_name: String,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Written {
+        legal_name: String,
+        classification: Classification,
+        #[serde(skip_serializing_if = "String::is_empty")]
+        blob: String,
+    }
+
+    impl Classified for Written {
+        fn classification(&self) -> Classification {
+            self.classification
+        }
+    }
+
+    fn written(legal_name: &str, classification: Classification) -> Written {
+        Written {
+            legal_name: legal_name.to_string(),
+            classification,
+            blob: String::new(),
+        }
+    }
+
+    fn limits() -> ResourceLimits {
+        ResourceLimits::default()
+            .with_max_record_size(256)
+            .with_max_bundle_entries(3)
+    }
+
+    fn collect(json: &[u8], limits: &ResourceLimits) -> (CepResult<usize>, Vec<String>) {
+        let mut names = Vec::new();
+        let result = stream_bundle(json, limits, |n: Named| {
+            names.push(n.legal_name);
+            Ok(())
+        });
+        (result, names)
+    }
+
+    #[test]
+    fn test_streams_records_in_order() {
+        let json = br#"[ {"legalName":"A"}, {"legalName":"B", "extra": [1, {"x": "y"}]} ]"#;
+        let (result, names) = collect(json, &limits());
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(names, vec!["A", "B"]);
+    }
+
+    #[test]
+    fn test_empty_bundle() {
+        let (result, names) = collect(b"[]", &limits());
+        assert_eq!(result.unwrap(), 0);
+        assert!(names.is_empty());
+    }
+
+    #[test]
+    fn test_giant_unknown_field_hits_record_budget() {
+        let json = format!(
+            r#"[{{"legalName":"A"}},{{"legalName":"B","blob":"{}"}}]"#,
+            "x".repeat(10_000)
+        );
+        let (result, names) = collect(json.as_bytes(), &limits());
+        assert!(matches!(
+            result,
+            Err(CepError::ResourceLimit { ref limit, max: 256, .. }) if limit == "record size"
+        ));
+        assert_eq!(names, vec!["A"]);
+    }
+
+    #[test]
+    fn test_budget_is_per_record() {
+        // Total input is far larger than one record budget.
+        let entry = format!(r#"{{"legalName":"{}"}}"#, "n".repeat(200));
+        let json = format!("[{},{},{}]", entry, entry, entry);
+        let (result, _) = collect(json.as_bytes(), &limits());
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn test_entry_limit() {
+        let json = br#"[{"legalName":"A"},{"legalName":"B"},{"legalName":"C"},{"legalName":"D"}]"#;
+        let (result, names) = collect(json, &limits());
+        assert!(matches!(
+            result,
+            Err(CepError::ResourceLimit { ref limit, actual: 4, max: 3 }) if limit == "bundle entries"
+        ));
+        assert_eq!(names.len(), 3);
+    }
+
+    #[test]
+    fn test_callback_error_stops_stream() {
+        let json = br#"[{"legalName":"A"},{"legalName":"B"}]"#;
+        let result = stream_bundle(&json[..], &limits(), |_: Named| {
+            Err(CepError::MissingField("verifiableId".to_string()))
+        });
+        assert!(matches!(result, Err(CepError::MissingField(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "zstd")]
+    fn test_compressed_bundle_is_budgeted_after_decompression() {
+        let public = AccessScopes::public();
+        let records = [
+            written("A", Classification::Public),
+            written("B", Classification::Public),
+        ];
+        let bytes = write_bundle(
+            Vec::new(),
+            &records,
+            &public,
+            Compression::Zstd { level: 3 },
+        )
+        .unwrap();
+        let (result, read) = collect(&bytes, &limits());
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(read, vec!["A", "B"]);
+
+        // A record that is small compressed but large decompressed is refused.
+        let mut blob = written("A", Classification::Public);
+        blob.blob = "x".repeat(10_000);
+        let bytes =
+            write_bundle(Vec::new(), &[blob], &public, Compression::Zstd { level: 3 }).unwrap();
+        assert!(bytes.len() < 256);
+        let (result, _) = collect(&bytes, &limits());
+        assert!(matches!(result, Err(CepError::ResourceLimit { .. })));
+    }
+
+    #[test]
+    fn test_write_bundle_leaves_out_unpermitted_records() {
+        let records = [
+            written("A", Classification::Public),
+            written("B", Classification::Internal),
+            written("C", Classification::Restricted),
+        ];
+        let read = |scopes: &AccessScopes| {
+            let bytes = write_bundle(Vec::new(), &records, scopes, Compression::None).unwrap();
+            collect(&bytes, &limits()).1
+        };
+        assert_eq!(read(&AccessScopes::public()), vec!["A"]);
+        assert_eq!(
+            read(&AccessScopes::new(["cep:read:internal"])),
+            vec!["A", "B"]
+        );
+        assert_eq!(
+            read(&AccessScopes::new([
+                "cep:read:internal",
+                "cep:read:restricted"
+            ])),
+            vec!["A", "B", "C"]
+        );
+    }
+
+    #[test]
+    fn test_malformed_json() {
+        let (result, _) = collect(br#"[{"legalName":"A"}"#, &limits());
+        assert!(matches!(result, Err(CepError::Serialization(_))));
+    }
+}
@@ -1,29 +1,52 @@
-/// Canonical timestamp handling for CEP records.
-///
-/// All CEP timestamps MUST be:
-/// - UTC timezone (indicated by 'Z' suffix)
-/// - ISO 8601 format
-/// - Microsecond precision (exactly 6 decimal places)
-///
-/// Example: `2025-11-28T14:30:00.000000Z`
+//! Canonical timestamp handling for CEP records.
+//!
+//! All CEP timestamps MUST be:
+//! - UTC timezone (indicated by 'Z' suffix)
+//! - ISO 8601 format
+//! - Microsecond precision (exactly 6 decimal places)
+//!
+//! Example: `2025-11-28T14:30:00.000000Z`
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, SubsecRound, TimeZone, Utc};
 use std::fmt;
 use std::str::FromStr;
 
+/// Display format used by [`CanonicalTimestamp::to_display`].
+///
+/// Includes the offset so a displayed value is never mistaken for UTC.
+const DISPLAY_FORMAT: &str = "%Y-%m-%d %H:%M:%S %:z";
+
 /// A canonical CEP timestamp with mandatory microsecond precision.
+///
+/// Sub-microsecond precision is truncated on construction, so two timestamps
+/// compare equal exactly when their canonical strings are equal.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct CanonicalTimestamp(DateTime<Utc>);
 
 impl CanonicalTimestamp {
     /// Creates a new CanonicalTimestamp from a chrono DateTime<Utc>.
+    ///
+    /// Nanoseconds beyond microsecond precision are truncated.
     pub fn new(dt: DateTime<Utc>) -> Self {
-        Self(dt)
+        Self(dt.trunc_subsecs(6))
     }
 
-    /// Returns the current UTC time as a CanonicalTimestamp.
+    /// Returns the current UTC time as a CanonicalTimestamp,
+    /// truncated to microsecond precision.
     pub fn now() -> Self {
-        Self(Utc::now())
+        Self::new(Utc::now())
+    }
+
+    /// Creates a CanonicalTimestamp from microseconds since the Unix epoch.
+    ///
+    /// Returns None if the value is out of the representable range.
+    pub fn from_unix_micros(micros: i64) -> Option<Self> {
+        DateTime::from_timestamp_micros(micros).map(Self)
+    }
+
+    /// Returns the number of microseconds since the Unix epoch.
+    pub fn unix_micros(&self) -> i64 {
+        self.0.timestamp_micros()
     }
 
     /// Returns the underlying DateTime<Utc>.
@@ -39,6 +62,51 @@ impl CanonicalTimestamp {
     pub fn to_canonical_string(&self) -> String {
         self.0.format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string()
     }
+
+    /// Returns a human-readable representation in the given time zone.
+    ///
+    /// Format: `YYYY-MM-DD HH:MM:SS +HH:MM`
+    ///
+    /// Display strings are for UIs only and MUST NOT be used as hash input;
+    /// use [`to_canonical_string`](Self::to_canonical_string) for that.
+    pub fn to_display<Tz: TimeZone>(&self, tz: &Tz) -> String
+    where
+        Tz::Offset: fmt::Display,
+    {
+        self.0.with_timezone(tz).format(DISPLAY_FORMAT).to_string()
+    }
+
+    /// Parses an RFC 3339 string, keeping the offset it was written with.
+    ///
+    /// The returned timestamp is canonicalized to UTC as usual; the original
+    /// offset is kept alongside it as display metadata only.
+    pub fn parse_with_offset(s: &str) -> Result<OffsetTimestamp, chrono::ParseError> {
+        let dt = DateTime::parse_from_rfc3339(s)?;
+        Ok(OffsetTimestamp {
+            timestamp: Self::new(dt.with_timezone(&Utc)),
+            original_offset: *dt.offset(),
+        })
+    }
+}
+
+/// A canonical timestamp together with the offset it was originally written in.
+///
+/// Produced by [`CanonicalTimestamp::parse_with_offset`]. Only `timestamp`
+/// participates in canonicalization; `original_offset` exists so applications
+/// can show a value back to users in the zone it was entered in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OffsetTimestamp {
+    /// The UTC canonical timestamp.
+    pub timestamp: CanonicalTimestamp,
+    /// The UTC offset present in the source string.
+    pub original_offset: FixedOffset,
+}
+
+impl OffsetTimestamp {
+    /// Returns a human-readable representation in the original offset.
+    pub fn to_original_display(&self) -> String {
+        self.timestamp.to_display(&self.original_offset)
+    }
 }
 
 impl fmt::Display for CanonicalTimestamp {
@@ -53,13 +121,13 @@ impl FromStr for CanonicalTimestamp {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // Accept RFC 3339 format and convert to our canonical form
         let dt = DateTime::parse_from_rfc3339(s)?;
-        Ok(Self(dt.with_timezone(&Utc)))
+        Ok(Self::new(dt.with_timezone(&Utc)))
     }
 }
 
 impl From<DateTime<Utc>> for CanonicalTimestamp {
     fn from(dt: DateTime<Utc>) -> Self {
-        Self(dt)
+        Self::new(dt)
     }
 }
 
@@ -106,6 +174,97 @@ mod tests {
         assert_eq!(ts.to_canonical_string(), "2025-11-28T14:30:00.123456Z");
     }
 
+    #[test]
+    fn test_to_display_fixed_offset() {
+        let ts: CanonicalTimestamp = "2025-11-28T14:30:00.123456Z".parse().unwrap();
+        let eastern = FixedOffset::west_opt(5 * 3600).unwrap();
+        assert_eq!(ts.to_display(&eastern), "2025-11-28 09:30:00 -05:00");
+        assert_eq!(ts.to_display(&Utc), "2025-11-28 14:30:00 +00:00");
+    }
+
+    #[test]
+    fn test_parse_with_offset_records_original() {
+        let parsed =
+            CanonicalTimestamp::parse_with_offset("2025-11-28T20:00:00.000000+05:30").unwrap();
+        assert_eq!(
+            parsed.timestamp.to_canonical_string(),
+            "2025-11-28T14:30:00.000000Z"
+        );
+        assert_eq!(parsed.original_offset.local_minus_utc(), 5 * 3600 + 30 * 60);
+        assert_eq!(parsed.to_original_display(), "2025-11-28 20:00:00 +05:30");
+    }
+
+    #[test]
+    fn test_display_does_not_change_canonical_form() {
+        let ts: CanonicalTimestamp = "2025-11-28T14:30:00.000001Z".parse().unwrap();
+        let _ = ts.to_display(&FixedOffset::east_opt(9 * 3600).unwrap());
+        assert_eq!(ts.to_canonical_string(), "2025-11-28T14:30:00.000001Z");
+    }
+
+    #[test]
+    fn test_now_is_microsecond_truncated() {
+        let ts = CanonicalTimestamp::now();
+        assert_eq!(ts.as_datetime().timestamp_subsec_nanos() % 1_000, 0);
+    }
+
+    #[test]
+    fn test_nanoseconds_truncated_on_parse() {
+        let ts: CanonicalTimestamp = "2025-11-28T14:30:00.123456789Z".parse().unwrap();
+        let micros: CanonicalTimestamp = "2025-11-28T14:30:00.123456Z".parse().unwrap();
+        assert_eq!(ts, micros);
+        assert_eq!(ts.to_canonical_string(), "2025-11-28T14:30:00.123456Z");
+    }
+
+    #[test]
+    fn test_unix_micros_round_trip() {
+        let ts = CanonicalTimestamp::from_unix_micros(1_764_340_200_123_456).unwrap();
+        assert_eq!(ts.to_canonical_string(), "2025-11-28T14:30:00.123456Z");
+        assert_eq!(ts.unix_micros(), 1_764_340_200_123_456);
+    }
+
+    #[test]
+    fn test_unix_micros_epoch() {
+        let ts = CanonicalTimestamp::from_unix_micros(0).unwrap();
+        assert_eq!(ts.to_canonical_string(), "1970-01-01T00:00:00.000000Z");
+    }
+
+    #[test]
+    fn test_serde_matches_canonical_string() {
+        for input in [
+            "2025-11-28T14:30:00.000000Z",
+            "2025-11-28T14:30:00.123456Z",
+            "2025-11-28T09:30:00.5-05:00",
+            "2025-11-28T14:30:00.123456789Z",
+        ] {
+            let ts: CanonicalTimestamp = input.parse().unwrap();
+            let json = serde_json::to_string(&ts).unwrap();
+            assert_eq!(json, format!("\"{}\"", ts.to_canonical_string()));
+
+            let back: CanonicalTimestamp = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, ts);
+        }
+    }
+
+    #[test]
+    fn test_deserialize_rejects_invalid() {
+        for input in [
+            "\"not a timestamp\"",
+            "\"2025-11-28\"",
+            "\"2025-11-28T14:30:00\"",
+            "0",
+        ] {
+            assert!(serde_json::from_str::<CanonicalTimestamp>(input).is_err());
+        }
+    }
+
+    #[test]
+    fn test_serde_now_round_trip() {
+        let ts = CanonicalTimestamp::now();
+        let json = serde_json::to_string(&ts).unwrap();
+        let back: CanonicalTimestamp = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, ts);
+    }
+
     #[test]
     fn test_ordering() {
         let earlier: CanonicalTimestamp = "2025-11-28T14:30:00.000000Z".parse().unwrap();
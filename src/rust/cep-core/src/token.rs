@@ -0,0 +1,71 @@
+//! Hardware signing token failures.
+//!
+//! [`TokenError`] is what a token-backed signer, such as the PIV signer
+//! behind the `piv` feature, reports through `CepError::HardwareToken`. It
+//! lives outside that feature so the error type is the same whichever
+//! backends are compiled in.
+
+use std::fmt;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Failures specific to hardware tokens.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TokenError {
+    /// No token is connected, or it stopped responding.
+    #[error("token not present: {0}")]
+    NotPresent(String),
+
+    /// A signature was requested before a PIN was supplied.
+    #[error("token PIN required")]
+    PinRequired,
+
+    /// The token rejected the PIN.
+    #[error("token PIN rejected, {retries_left} attempts left before the token locks")]
+    PinRejected { retries_left: u8 },
+
+    /// The PIN is blocked; the token must be reset with its PUK.
+    #[error("token PIN is blocked")]
+    PinLocked,
+
+    /// The slot needs a touch and the signer is configured not to wait.
+    #[error("token requires a touch to sign with slot {0}")]
+    TouchRequired(PivSlot),
+
+    /// Nobody touched the token in time.
+    #[error("token touch not received within {0:?}")]
+    TouchTimeout(Duration),
+
+    /// The slot holds no key, or not an Ed25519 key.
+    #[error("token slot {slot} is unusable: {reason}")]
+    UnusableSlot { slot: PivSlot, reason: String },
+
+    /// Any other card or transport failure.
+    #[error("token error: {0}")]
+    Card(String),
+}
+
+/// PIV key slots that can hold a signing key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PivSlot {
+    /// 9A, PIV authentication.
+    Authentication,
+    /// 9C, digital signature. The usual slot for attestations.
+    Signature,
+    /// 9D, key management.
+    KeyManagement,
+    /// 9E, card authentication (no PIN).
+    CardAuthentication,
+}
+
+impl fmt::Display for PivSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = match self {
+            Self::Authentication => "9a",
+            Self::Signature => "9c",
+            Self::KeyManagement => "9d",
+            Self::CardAuthentication => "9e",
+        };
+        f.write_str(id)
+    }
+}
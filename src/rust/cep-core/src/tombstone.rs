@@ -0,0 +1,275 @@
+//! Tombstones for deleted revisions.
+//!
+//! Deleting a revision outright would break its chain: the next revision's
+//! `previousRecordHash` would point at nothing. A [`Tombstone`] takes the
+//! revision's place instead. It keeps only the deleted revision's hash and
+//! chain position, the reason for deletion from the `deletion-reason`
+//! vocabulary, and an attestation by whoever performed the deletion.
+//!
+//! [`verify_chain`](crate::chain::verify_chain) accepts a tombstone wherever
+//! the deleted revision stood, via [`ChainLink::Tombstone`]. It checks linkage
+//! only; the tombstone's attestation is verified like any record's, e.g. with
+//! [`Attestation::verify`].
+
+use crate::attestation::Attestation;
+use crate::canonical::{Canonicalize, insert_number, insert_required};
+use crate::chain::{ChainLink, HashStub, Revisioned};
+use crate::error::CepError;
+use crate::hash::CanonicalHash;
+use crate::spec::{CanonicalSpec, FieldEncoding, TypeSpec};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// URI of the deletion reason vocabulary.
+pub const DELETION_REASON_VOCABULARY_URI: &str = "https://raw.githubusercontent.com/civic-interconnect/civic-exchange-protocol/main/vocabulary/deletion-reason.json";
+
+/// Why a revision was deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeletionReason {
+    DataSubjectRequest,
+    LegalOrder,
+    RetentionExpired,
+    EnteredInError,
+}
+
+impl DeletionReason {
+    /// All deletion reasons.
+    pub const ALL: [Self; 4] = [
+        Self::DataSubjectRequest,
+        Self::LegalOrder,
+        Self::RetentionExpired,
+        Self::EnteredInError,
+    ];
+
+    /// Returns the vocabulary code, which is also the canonical form.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DataSubjectRequest => "data-subject-request",
+            Self::LegalOrder => "legal-order",
+            Self::RetentionExpired => "retention-expired",
+            Self::EnteredInError => "entered-in-error",
+        }
+    }
+
+    /// Returns the vocabulary term URI.
+    pub fn term_uri(&self) -> String {
+        format!("{}#{}", DELETION_REASON_VOCABULARY_URI, self.as_str())
+    }
+}
+
+impl fmt::Display for DeletionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DeletionReason {
+    type Err = CepError;
+
+    /// Accepts a vocabulary code or term URI.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = s
+            .strip_prefix(DELETION_REASON_VOCABULARY_URI)
+            .and_then(|rest| rest.strip_prefix('#'))
+            .unwrap_or(s);
+        Self::ALL
+            .into_iter()
+            .find(|r| r.as_str() == code)
+            .ok_or_else(|| CepError::UnknownTerm(format!("'{}' is not a deletion reason", s)))
+    }
+}
+
+/// An attested placeholder for a deleted revision.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tombstone {
+    /// Identifier of the record the deleted revision belonged to.
+    pub verifiable_id: String,
+
+    /// Revision number of the deleted revision.
+    pub revision_number: u32,
+
+    /// Canonical hash of the deleted revision.
+    pub record_hash: CanonicalHash,
+
+    /// The deleted revision's `previousRecordHash`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_record_hash: Option<CanonicalHash>,
+
+    /// Why the revision was deleted.
+    pub deletion_reason: DeletionReason,
+
+    /// Attestation by the party that performed the deletion.
+    pub attestation: Attestation,
+}
+
+impl Tombstone {
+    /// Creates the tombstone that replaces `record`.
+    pub fn of<T: Revisioned>(
+        record: &T,
+        deletion_reason: DeletionReason,
+        attestation: Attestation,
+    ) -> Self {
+        Self::from_stub(HashStub::of(record), deletion_reason, attestation)
+    }
+
+    /// Creates a tombstone for a revision that is already only a stub.
+    pub fn from_stub(
+        stub: HashStub,
+        deletion_reason: DeletionReason,
+        attestation: Attestation,
+    ) -> Self {
+        Self {
+            verifiable_id: stub.verifiable_id,
+            revision_number: stub.revision_number,
+            record_hash: stub.record_hash,
+            previous_record_hash: stub.previous_record_hash,
+            deletion_reason,
+            attestation,
+        }
+    }
+}
+
+impl Canonicalize for Tombstone {
+    fn canonical_fields(&self) -> BTreeMap<String, String> {
+        let mut map = BTreeMap::new();
+
+        // Fields in alphabetical order
+        insert_required(
+            &mut map,
+            "attestation",
+            &self.attestation.to_canonical_string(),
+        );
+        insert_required(&mut map, "deletionReason", self.deletion_reason.as_str());
+        if let Some(ref hash) = self.previous_record_hash {
+            insert_required(&mut map, "previousRecordHash", hash.as_hex());
+        }
+        insert_required(&mut map, "recordHash", self.record_hash.as_hex());
+        insert_number(&mut map, "revisionNumber", self.revision_number);
+        insert_required(&mut map, "verifiableId", &self.verifiable_id);
+
+        map
+    }
+}
+
+impl CanonicalSpec for Tombstone {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("Tombstone")
+            .required("attestation", FieldEncoding::nested("Attestation"))
+            .required("deletionReason", FieldEncoding::String)
+            .optional("previousRecordHash", FieldEncoding::Hash)
+            .required("recordHash", FieldEncoding::Hash)
+            .required("revisionNumber", FieldEncoding::Integer)
+            .required("verifiableId", FieldEncoding::String)
+    }
+}
+
+impl<T: Revisioned> ChainLink<T> {
+    /// Replaces a full record or stub with a tombstone. Tombstones are
+    /// returned unchanged.
+    pub fn into_tombstone(self, deletion_reason: DeletionReason, attestation: Attestation) -> Self {
+        match self {
+            ChainLink::Record(record) => {
+                ChainLink::Tombstone(Tombstone::of(&record, deletion_reason, attestation))
+            }
+            ChainLink::Stub(stub) => {
+                ChainLink::Tombstone(Tombstone::from_stub(stub, deletion_reason, attestation))
+            }
+            tombstone => tombstone,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::ProofType;
+    use crate::chain::tests::chain;
+    use crate::chain::verify_chain;
+    use crate::clock::FixedClock;
+    use crate::keys::KeyFile;
+    use crate::schema_registry::SchemaRegistry;
+    use crate::signer::{Ed25519Signer, Ed25519Verifier};
+    use ed25519_dalek::SigningKey;
+
+    fn test_attestation() -> Attestation {
+        Attestation::new(
+            "did:web:records.example.gov".to_string(),
+            "2026-03-01T00:00:00.000000Z".parse().unwrap(),
+            ProofType::Ed25519Signature2020,
+            String::new(),
+            "did:web:records.example.gov#key-1".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_tombstone_is_a_valid_link() {
+        let mut links = chain(3);
+        links[1] = links[1]
+            .clone()
+            .into_tombstone(DeletionReason::DataSubjectRequest, test_attestation());
+        assert!(verify_chain(&links).is_ok());
+
+        // Tombstoning the head is allowed too.
+        links[2] = links[2]
+            .clone()
+            .into_tombstone(DeletionReason::LegalOrder, test_attestation());
+        assert!(verify_chain(&links).is_ok());
+
+        // A tombstone for a different hash does not link.
+        if let ChainLink::Tombstone(ref mut tombstone) = links[1] {
+            tombstone.record_hash = CanonicalHash::from_canonical_string("other");
+        }
+        assert!(verify_chain(&links).is_err());
+    }
+
+    #[test]
+    fn test_tombstone_attestation_covers_reason() {
+        let keys = KeyFile::from_signing_key(&SigningKey::from_bytes(&[9u8; 32]));
+        let ChainLink::Record(ref revision) = chain(1)[0] else {
+            unreachable!()
+        };
+        let mut tombstone =
+            Tombstone::of(revision, DeletionReason::EnteredInError, test_attestation());
+        let signer = Ed25519Signer::new(keys.signing_key().unwrap());
+        let verifier = Ed25519Verifier::new(keys.verifying_key().unwrap());
+        tombstone.attestation = Attestation::sign(
+            &tombstone,
+            &signer,
+            &FixedClock::new("2026-03-01T00:00:00.000000Z".parse().unwrap()),
+            "did:web:records.example.gov".to_string(),
+            "did:web:records.example.gov#key-1".to_string(),
+        )
+        .unwrap();
+        let attestation = tombstone.attestation.clone();
+        assert!(attestation.verify(&tombstone, &verifier).is_ok());
+
+        tombstone.deletion_reason = DeletionReason::RetentionExpired;
+        assert!(attestation.verify(&tombstone, &verifier).is_err());
+    }
+
+    #[test]
+    fn test_reasons_match_vocabulary() {
+        let vocab = SchemaRegistry::new()
+            .unwrap()
+            .get_vocabulary("deletion-reason.v1.0.0")
+            .unwrap();
+        let codes: Vec<&str> = vocab["terms"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["code"].as_str().unwrap())
+            .collect();
+        let reasons: Vec<&str> = DeletionReason::ALL.iter().map(|r| r.as_str()).collect();
+        assert_eq!(codes, reasons);
+        assert_eq!(
+            "https://raw.githubusercontent.com/civic-interconnect/civic-exchange-protocol/main/vocabulary/deletion-reason.json#legal-order"
+                .parse::<DeletionReason>()
+                .unwrap(),
+            DeletionReason::LegalOrder
+        );
+    }
+}
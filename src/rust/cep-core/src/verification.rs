@@ -0,0 +1,544 @@
+//! Attestation verification with a per-check report.
+//!
+//! [`verify_attestation`] runs every check it can and returns a
+//! [`VerificationReport`] saying which passed, which failed and why, and
+//! which could not run, instead of stopping at the first failure:
+//!
+//! | Check | Passes when |
+//! |---|---|
+//! | [`HashMatch`](Check::HashMatch) | the record hashes to the hash it was delivered with |
+//! | [`PolicyAllowed`](Check::PolicyAllowed) | the [`SigningPolicy`] allows the proof type for the record's class |
+//! | [`TimestampSane`](Check::TimestampSane) | the attestation is not dated in the future |
+//! | [`KeyResolvable`](Check::KeyResolvable) | the attestor publishes the named verification method |
+//! | [`SignatureValid`](Check::SignatureValid) | the proof verifies under that key |
+//!
+//! The hash check is skipped when no expected hash is given, the policy
+//! check when no policy is given, and the signature check when the key could
+//! not be resolved. A failed policy check is reported by
+//! [`VerificationReport::ensure_valid`] as [`CepError::PolicyViolation`]
+//! rather than as a bad signature.
+//!
+//! [`verify_endorsement`] makes the same checks for an [`Endorsement`], whose
+//! proof covers the full canonical form of the endorsed link and whose hash
+//! check compares that form with `endorsedHash`.
+
+use crate::attestation::{Attestation, Endorsement};
+use crate::canonical::Canonicalize;
+use crate::clock::Clock;
+use crate::error::{CepError, CepResult};
+use crate::hash::CanonicalHash;
+use crate::keys::{KeyFile, signing_input};
+use crate::policy::SigningPolicy;
+use crate::resolver::{VerificationKey, VerificationMethodResolver};
+use crate::signer::ed25519_verifier;
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+/// How far in the future an attestation timestamp may lie, to allow for
+/// clock drift between attestor and verifier.
+pub const MAX_CLOCK_SKEW: Duration = Duration::minutes(5);
+
+/// One check made by [`verify_attestation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Check {
+    HashMatch,
+    PolicyAllowed,
+    TimestampSane,
+    KeyResolvable,
+    SignatureValid,
+}
+
+/// The result of one check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "detail", rename_all = "camelCase")]
+pub enum CheckOutcome {
+    Passed,
+    /// The check ran and failed, with the reason.
+    Failed(String),
+    /// The check could not run, with the reason.
+    Skipped(String),
+}
+
+/// A check and its outcome.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckResult {
+    pub check: Check,
+    #[serde(flatten)]
+    pub outcome: CheckOutcome,
+}
+
+/// What [`verify_attestation`] checked and found.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationReport {
+    pub attestor_id: String,
+    pub verification_method_uri: String,
+    /// Checks in the order they ran.
+    pub checks: Vec<CheckResult>,
+    /// The policy violation behind a failed [`Check::PolicyAllowed`].
+    #[serde(skip)]
+    violation: Option<PolicyViolation>,
+}
+
+/// The fields of a [`CepError::PolicyViolation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PolicyViolation {
+    record_class: String,
+    proof_type: String,
+    allowed: Vec<String>,
+}
+
+impl VerificationReport {
+    /// Returns the outcome of `check`, if it was made.
+    pub fn outcome(&self, check: Check) -> Option<&CheckOutcome> {
+        self.checks
+            .iter()
+            .find(|result| result.check == check)
+            .map(|result| &result.outcome)
+    }
+
+    /// Checks that ran and failed.
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks
+            .iter()
+            .filter(|result| matches!(result.outcome, CheckOutcome::Failed(_)))
+    }
+
+    /// Returns true if no check failed and the signature was verified.
+    pub fn is_valid(&self) -> bool {
+        self.failures().next().is_none()
+            && self.outcome(Check::SignatureValid) == Some(&CheckOutcome::Passed)
+    }
+
+    /// Turns the report into a result.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::PolicyViolation` if the signing policy refused the
+    /// proof type, and otherwise `CepError::InvalidSignature` naming the
+    /// attestor and the first failed check, if the report is not valid.
+    pub fn ensure_valid(&self) -> CepResult<()> {
+        if self.is_valid() {
+            return Ok(());
+        }
+        if let Some(violation) = &self.violation {
+            return Err(CepError::PolicyViolation {
+                record_class: violation.record_class.clone(),
+                proof_type: violation.proof_type.clone(),
+                allowed: violation.allowed.clone(),
+            });
+        }
+        let reason = self
+            .failures()
+            .find_map(|result| match &result.outcome {
+                CheckOutcome::Failed(reason) => Some(reason.as_str()),
+                _ => None,
+            })
+            .unwrap_or("signature was not checked");
+        Err(CepError::InvalidSignature(format!(
+            "attestation by {}: {}",
+            self.attestor_id, reason
+        )))
+    }
+
+    fn record(&mut self, check: Check, outcome: CheckOutcome) {
+        self.checks.push(CheckResult { check, outcome });
+    }
+}
+
+/// Verifies `attestation` over `record`, resolving the attestor's key with
+/// `resolver`, and reports on every check.
+///
+/// `expected_hash` is the hash the record was delivered with (from an
+/// envelope, receipt or chain link), if any. `clock` supplies the current
+/// time for the timestamp check. `policy`, if given, must allow the proof
+/// type for the record's [`record_class`](crate::policy::record_class).
+pub fn verify_attestation<T: Canonicalize + ?Sized>(
+    record: &T,
+    attestation: &Attestation,
+    expected_hash: Option<&CanonicalHash>,
+    resolver: &dyn VerificationMethodResolver,
+    clock: &dyn Clock,
+    policy: Option<&SigningPolicy>,
+) -> VerificationReport {
+    let hash_match = match expected_hash {
+        Some(expected) if record.hash_mode_of(expected).is_some() => CheckOutcome::Passed,
+        Some(expected) => CheckOutcome::Failed(format!(
+            "record hashes to {}, expected {}",
+            record.calculate_hash(),
+            expected
+        )),
+        None => CheckOutcome::Skipped("no expected hash given".to_string()),
+    };
+    let policy_check = policy.map(|policy| policy.check_record(record, &attestation.proof_type));
+    verify_input(
+        &signing_input(record),
+        hash_match,
+        policy_check,
+        attestation,
+        resolver,
+        clock,
+    )
+}
+
+/// Verifies `endorsement` over the link it endorses, resolving the
+/// endorser's key with `resolver`, and reports on every check.
+///
+/// The hash check compares the hash of `endorsed`'s full canonical form with
+/// the endorsement's `endorsedHash`. No policy applies here, since the
+/// endorsed link has no record class of its own; [`EndorsementChain::verify`](
+/// crate::EndorsementChain::verify) checks endorsements against the policy for
+/// the record.
+pub fn verify_endorsement<T: Canonicalize + ?Sized>(
+    endorsed: &T,
+    endorsement: &Endorsement,
+    resolver: &dyn VerificationMethodResolver,
+    clock: &dyn Clock,
+) -> VerificationReport {
+    let signed = endorsed.to_canonical_string();
+    let actual = CanonicalHash::from_canonical_string(&signed);
+    let hash_match = if actual == endorsement.endorsed_hash {
+        CheckOutcome::Passed
+    } else {
+        CheckOutcome::Failed(format!(
+            "endorsed link hashes to {}, endorsement names {}",
+            actual, endorsement.endorsed_hash
+        ))
+    };
+    verify_input(
+        &signed,
+        hash_match,
+        None,
+        &endorsement.attestation,
+        resolver,
+        clock,
+    )
+}
+
+/// Runs the checks after the hash check, for a proof over the canonical
+/// string `input`. `policy_check` is the result of checking the proof type
+/// against a policy, if one was given.
+fn verify_input(
+    input: &str,
+    hash_match: CheckOutcome,
+    policy_check: Option<CepResult<()>>,
+    attestation: &Attestation,
+    resolver: &dyn VerificationMethodResolver,
+    clock: &dyn Clock,
+) -> VerificationReport {
+    let mut report = VerificationReport {
+        attestor_id: attestation.attestor_id.clone(),
+        verification_method_uri: attestation.verification_method_uri.clone(),
+        checks: Vec::new(),
+        violation: None,
+    };
+    report.record(Check::HashMatch, hash_match);
+
+    let policy_allowed = match policy_check {
+        None => CheckOutcome::Skipped("no signing policy given".to_string()),
+        Some(Ok(())) => CheckOutcome::Passed,
+        Some(Err(e)) => {
+            if let CepError::PolicyViolation {
+                record_class,
+                proof_type,
+                allowed,
+            } = e.root()
+            {
+                report.violation = Some(PolicyViolation {
+                    record_class: record_class.clone(),
+                    proof_type: proof_type.clone(),
+                    allowed: allowed.clone(),
+                });
+            }
+            CheckOutcome::Failed(e.to_string())
+        }
+    };
+    report.record(Check::PolicyAllowed, policy_allowed);
+
+    let latest = clock.now().as_datetime() + MAX_CLOCK_SKEW;
+    let timestamp_sane = if attestation.attestation_timestamp.as_datetime() > latest {
+        CheckOutcome::Failed(format!(
+            "attested at {}, which is in the future",
+            attestation.attestation_timestamp
+        ))
+    } else {
+        CheckOutcome::Passed
+    };
+    report.record(Check::TimestampSane, timestamp_sane);
+
+    let key = match resolve_key(attestation, resolver) {
+        Ok(key) => {
+            report.record(Check::KeyResolvable, CheckOutcome::Passed);
+            key
+        }
+        Err(reason) => {
+            report.record(Check::KeyResolvable, CheckOutcome::Failed(reason));
+            report.record(
+                Check::SignatureValid,
+                CheckOutcome::Skipped("no key to verify against".to_string()),
+            );
+            return report;
+        }
+    };
+
+    let signature_valid = if !attestation.proof_type.is_verifiable() {
+        CheckOutcome::Failed(format!(
+            "proof type '{}' cannot be checked",
+            attestation.proof_type
+        ))
+    } else {
+        let key_file = KeyFile {
+            key_type: key.key_type,
+            public_key_multibase: key.public_key_multibase,
+            private_key_multibase: None,
+        };
+        match key_file
+            .verifying_key()
+            .and_then(|key| ed25519_verifier(&attestation.proof_type, key))
+            .and_then(|verifier| attestation.verify_input(input, verifier.as_ref()))
+        {
+            Ok(()) => CheckOutcome::Passed,
+            Err(e) => CheckOutcome::Failed(e.to_string()),
+        }
+    };
+    report.record(Check::SignatureValid, signature_valid);
+    report
+}
+
+/// Finds the attestation's verification method among the attestor's keys.
+fn resolve_key(
+    attestation: &Attestation,
+    resolver: &dyn VerificationMethodResolver,
+) -> Result<VerificationKey, String> {
+    let keys = resolver
+        .resolve(&attestation.attestor_id)
+        .map_err(|e| format!("resolving {}: {}", attestation.attestor_id, e))?
+        .ok_or_else(|| format!("attestor {} is unknown", attestation.attestor_id))?;
+    keys.into_iter()
+        .find(|key| key.id == attestation.verification_method_uri)
+        .ok_or_else(|| {
+            format!(
+                "{} does not publish {}",
+                attestation.attestor_id, attestation.verification_method_uri
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::ProofType;
+    use crate::clock::FixedClock;
+    use crate::signer::Ed25519Signer;
+    use ed25519_dalek::SigningKey;
+    use std::collections::BTreeMap;
+
+    const ATTESTOR: &str = "did:web:example.gov";
+    const METHOD: &str = "did:web:example.gov#key-1";
+
+    struct Note(&'static str);
+
+    impl Canonicalize for Note {
+        fn canonical_fields(&self) -> BTreeMap<String, String> {
+            BTreeMap::from([("text".to_string(), self.0.to_string())])
+        }
+    }
+
+    struct OneKey(KeyFile);
+
+    impl VerificationMethodResolver for OneKey {
+        fn resolve(&self, attestor_id: &str) -> CepResult<Option<Vec<VerificationKey>>> {
+            Ok((attestor_id == ATTESTOR).then(|| vec![self.0.to_verification_key(METHOD)]))
+        }
+    }
+
+    fn clock() -> FixedClock {
+        FixedClock::new("2025-11-28T14:30:00.000000Z".parse().unwrap())
+    }
+
+    fn setup() -> (Attestation, OneKey) {
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let attestation = Attestation::sign(
+            &Note("a"),
+            &Ed25519Signer::new(key.clone()),
+            &clock(),
+            ATTESTOR.to_string(),
+            METHOD.to_string(),
+        )
+        .unwrap();
+        (
+            attestation,
+            OneKey(KeyFile::from_signing_key(&key).public()),
+        )
+    }
+
+    #[test]
+    fn test_all_checks_pass() {
+        let (attestation, resolver) = setup();
+        let hash = Note("a").calculate_hash();
+        let policy = SigningPolicy::new().with_default([ProofType::Ed25519Signature2020]);
+        let report = verify_attestation(
+            &Note("a"),
+            &attestation,
+            Some(&hash),
+            &resolver,
+            &clock(),
+            Some(&policy),
+        );
+        assert!(report.is_valid());
+        assert_eq!(report.checks.len(), 5);
+        assert!(
+            report
+                .checks
+                .iter()
+                .all(|result| result.outcome == CheckOutcome::Passed)
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["checks"][0]["check"], "hashMatch");
+        assert_eq!(json["checks"][0]["status"], "passed");
+    }
+
+    #[test]
+    fn test_reports_each_failure() {
+        let (mut attestation, resolver) = setup();
+        let expected = Note("a").calculate_hash();
+        let report = verify_attestation(
+            &Note("b"),
+            &attestation,
+            Some(&expected),
+            &resolver,
+            &clock(),
+            None,
+        );
+        assert!(!report.is_valid());
+        let failed: Vec<Check> = report.failures().map(|result| result.check).collect();
+        assert_eq!(failed, [Check::HashMatch, Check::SignatureValid]);
+        assert_eq!(
+            report.outcome(Check::KeyResolvable),
+            Some(&CheckOutcome::Passed)
+        );
+        assert!(matches!(
+            report.ensure_valid(),
+            Err(CepError::InvalidSignature(reason)) if reason.contains("record hashes to")
+        ));
+
+        attestation.attestation_timestamp = "2030-01-01T00:00:00.000000Z".parse().unwrap();
+        attestation.verification_method_uri = "did:web:example.gov#key-2".to_string();
+        let report = verify_attestation(&Note("a"), &attestation, None, &resolver, &clock(), None);
+        assert!(matches!(
+            report.outcome(Check::HashMatch),
+            Some(CheckOutcome::Skipped(_))
+        ));
+        assert!(matches!(
+            report.outcome(Check::TimestampSane),
+            Some(CheckOutcome::Failed(_))
+        ));
+        assert!(matches!(
+            report.outcome(Check::KeyResolvable),
+            Some(CheckOutcome::Failed(_))
+        ));
+        assert!(matches!(
+            report.outcome(Check::SignatureValid),
+            Some(CheckOutcome::Skipped(_))
+        ));
+
+        let (_, resolver) = setup();
+        let jws = Attestation::sign(
+            &Note("a"),
+            &crate::jws::JwsSigner::new(SigningKey::from_bytes(&[9u8; 32])),
+            &clock(),
+            ATTESTOR.to_string(),
+            METHOD.to_string(),
+        )
+        .unwrap();
+        let report = verify_attestation(&Note("a"), &jws, None, &resolver, &clock(), None);
+        assert!(report.is_valid());
+        let report = verify_attestation(&Note("b"), &jws, None, &resolver, &clock(), None);
+        assert!(matches!(
+            report.outcome(Check::SignatureValid),
+            Some(CheckOutcome::Failed(_))
+        ));
+
+        let (mut manual, resolver) = setup();
+        manual.proof_type = ProofType::ManualAttestation;
+        let report = verify_attestation(&Note("a"), &manual, None, &resolver, &clock(), None);
+        assert!(matches!(
+            report.outcome(Check::SignatureValid),
+            Some(CheckOutcome::Failed(_))
+        ));
+    }
+
+    #[test]
+    fn test_policy_violation_is_its_own_check() {
+        let (attestation, resolver) = setup();
+        let report = verify_attestation(&Note("a"), &attestation, None, &resolver, &clock(), None);
+        assert!(matches!(
+            report.outcome(Check::PolicyAllowed),
+            Some(CheckOutcome::Skipped(_))
+        ));
+
+        // The signature is good, but the policy refuses its proof type.
+        let policy = SigningPolicy::new().with_default([ProofType::JsonWebSignature2020]);
+        let report = verify_attestation(
+            &Note("a"),
+            &attestation,
+            None,
+            &resolver,
+            &clock(),
+            Some(&policy),
+        );
+        let failed: Vec<Check> = report.failures().map(|result| result.check).collect();
+        assert_eq!(failed, [Check::PolicyAllowed]);
+        assert_eq!(
+            report.outcome(Check::SignatureValid),
+            Some(&CheckOutcome::Passed)
+        );
+        assert!(matches!(
+            report.ensure_valid(),
+            Err(CepError::PolicyViolation { record_class, .. }) if record_class == "unclassified"
+        ));
+
+        let mut set = crate::attestation::AttestationSet::new();
+        set.add(attestation).unwrap();
+        assert!(matches!(
+            set.require(&Note("a"), &[ATTESTOR], &resolver, &clock(), Some(&policy)),
+            Err(CepError::PolicyViolation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_shared_cache_serves_every_entry_point() {
+        use crate::attestation::AttestationSet;
+        use crate::resolver::{CachingResolver, ResolverCacheConfig};
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Counting(OneKey, AtomicUsize);
+
+        impl VerificationMethodResolver for Counting {
+            fn resolve(&self, attestor_id: &str) -> CepResult<Option<Vec<VerificationKey>>> {
+                self.1.fetch_add(1, Ordering::SeqCst);
+                self.0.resolve(attestor_id)
+            }
+        }
+
+        let (attestation, resolver) = setup();
+        let counting = Arc::new(Counting(resolver, AtomicUsize::new(0)));
+        let cache = Arc::new(
+            CachingResolver::new(Arc::clone(&counting), ResolverCacheConfig::default())
+                .with_clock(Arc::new(clock())),
+        );
+        let mut set = AttestationSet::new();
+        set.add(attestation.clone()).unwrap();
+
+        assert!(
+            verify_attestation(&Note("a"), &attestation, None, &cache, &clock(), None).is_valid()
+        );
+        assert!(set.verify(&Note("a"), None, &cache, &clock(), None)[0].is_valid());
+        set.require(&Note("a"), &[ATTESTOR], &cache, &clock(), None)
+            .unwrap();
+        assert_eq!(counting.1.load(Ordering::SeqCst), 1);
+    }
+}
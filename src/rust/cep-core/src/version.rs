@@ -1,43 +1,268 @@
-/// Version information for CEP schemas.
+//! Version information for CEP schemas.
+//!
+//! [`SchemaVersion`] is a parsed `major.minor.patch` version that compares
+//! numerically (`1.10.0 > 1.9.0`) and can be tested against a
+//! [`VersionRange`]:
+//!
+//! ```rust
+//! use cep_core::version::{SchemaVersion, VersionRange, CURRENT_SCHEMA_VERSION};
+//!
+//! let range: VersionRange = "^1.0".parse().unwrap();
+//! assert!(CURRENT_SCHEMA_VERSION.satisfies(&range));
+//!
+//! let v: SchemaVersion = "1.10.0".parse().unwrap();
+//! assert!(v > "1.9.0".parse().unwrap());
+//! assert!(!v.satisfies(&">=1.0.0, <1.10.0".parse().unwrap()));
+//! ```
+//!
+//! Each embedded schema carries its own version, listed in
+//! [`EMBEDDED_SCHEMA_VERSIONS`] and looked up with [`embedded_schema_version`].
+
+use crate::error::{CepError, CepResult};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
 /// Current schema version (major.minor.patch).
 pub const SCHEMA_VERSION: &str = "1.0.0";
 
-/// Get the major version number.
-pub fn major_version() -> u32 {
-    SCHEMA_VERSION
-        .split('.')
-        .next()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(1)
+/// [`SCHEMA_VERSION`], parsed.
+pub const CURRENT_SCHEMA_VERSION: SchemaVersion = SchemaVersion::new(1, 0, 0);
+
+/// Current node-to-node protocol version (major.minor.patch).
+pub const PROTOCOL_VERSION: &str = "1.0.0";
+
+/// Version of this library, as recorded in the envelopes it generates.
+pub const LIBRARY_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Version of each schema embedded in this library, keyed by asset key
+/// (see [`get_schema`](crate::get_schema)).
+pub const EMBEDDED_SCHEMA_VERSIONS: &[(&str, SchemaVersion)] = &[
+    ("cep.entity", SchemaVersion::new(1, 0, 0)),
+    ("cep.entity.identifier-scheme", SchemaVersion::new(1, 0, 0)),
+    ("cep.exchange", SchemaVersion::new(1, 0, 0)),
+    ("cep.relationship", SchemaVersion::new(1, 0, 0)),
+    ("cep.snfei.canonical-input", SchemaVersion::new(1, 0, 0)),
+    ("cep.vocabulary", SchemaVersion::new(1, 0, 0)),
+];
+
+/// Returns the version of the embedded schema with asset key `key`.
+pub fn embedded_schema_version(key: &str) -> Option<SchemaVersion> {
+    EMBEDDED_SCHEMA_VERSIONS
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, version)| *version)
+}
+
+/// A `major.minor.patch` version.
+///
+/// Serialized as its string form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SchemaVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl SchemaVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Returns `major.minor`, the form schema files are catalogued under.
+    pub fn major_minor(&self) -> String {
+        format!("{}.{}", self.major, self.minor)
+    }
+
+    /// Returns true if this version is within `range`.
+    pub fn satisfies(&self, range: &VersionRange) -> bool {
+        range.matches(self)
+    }
+}
+
+impl fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for SchemaVersion {
+    type Err = CepError;
+
+    /// Parses exactly `major.minor.patch`.
+    fn from_str(s: &str) -> CepResult<Self> {
+        match parse_parts(s) {
+            Some([Some(major), Some(minor), Some(patch)]) => Ok(Self::new(major, minor, patch)),
+            _ => Err(CepError::UnsupportedVersion(format!(
+                "'{}' is not major.minor.patch",
+                s
+            ))),
+        }
+    }
 }
 
-/// Get the minor version number.
-pub fn minor_version() -> u32 {
-    SCHEMA_VERSION
-        .split('.')
-        .nth(1)
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0)
+impl TryFrom<String> for SchemaVersion {
+    type Error = CepError;
+
+    fn try_from(s: String) -> CepResult<Self> {
+        s.parse()
+    }
 }
 
-/// Get the patch version number.
-pub fn patch_version() -> u32 {
-    SCHEMA_VERSION
-        .split('.')
-        .nth(2)
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0)
+impl From<SchemaVersion> for String {
+    fn from(version: SchemaVersion) -> Self {
+        version.to_string()
+    }
+}
+
+/// A set of versions, written as comma-separated comparators with Cargo's
+/// meaning:
+///
+/// - `^1.2` (or bare `1.2`): `>=1.2.0, <2.0.0`
+/// - `~1.2`: `>=1.2.0, <1.3.0`
+/// - `=1.2.3`, `>1.2`, `>=1.2`, `<2`, `<=1.2.3`
+/// - `*`: any version
+///
+/// Missing minor and patch numbers count as zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRange {
+    /// Every bound must hold.
+    bounds: Vec<(Op, SchemaVersion)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+impl VersionRange {
+    /// Returns true if `version` is within this range.
+    pub fn matches(&self, version: &SchemaVersion) -> bool {
+        self.bounds.iter().all(|(op, bound)| match op {
+            Op::Lt => version < bound,
+            Op::Le => version <= bound,
+            Op::Gt => version > bound,
+            Op::Ge => version >= bound,
+            Op::Eq => version == bound,
+        })
+    }
+}
+
+impl FromStr for VersionRange {
+    type Err = CepError;
+
+    fn from_str(s: &str) -> CepResult<Self> {
+        let invalid = || CepError::UnsupportedVersion(format!("invalid version range '{}'", s));
+        let mut bounds = Vec::new();
+        for comparator in s.split(',').map(str::trim) {
+            if comparator == "*" {
+                continue;
+            }
+            let (op, rest) = ["<=", ">=", "<", ">", "=", "^", "~"]
+                .iter()
+                .find_map(|op| comparator.strip_prefix(op).map(|rest| (*op, rest)))
+                .unwrap_or(("^", comparator));
+            let parts = parse_parts(rest.trim()).ok_or_else(invalid)?;
+            let [major, minor, patch] = parts;
+            let major = major.ok_or_else(invalid)?;
+            let low = SchemaVersion::new(major, minor.unwrap_or(0), patch.unwrap_or(0));
+            match op {
+                "<=" => bounds.push((Op::Le, low)),
+                ">=" => bounds.push((Op::Ge, low)),
+                "<" => bounds.push((Op::Lt, low)),
+                ">" => bounds.push((Op::Gt, low)),
+                "=" => bounds.push((Op::Eq, low)),
+                "~" => {
+                    bounds.push((Op::Ge, low));
+                    bounds.push((Op::Lt, SchemaVersion::new(major, low.minor + 1, 0)));
+                }
+                _ => {
+                    // Caret: the leftmost non-zero number may not change.
+                    let high = match (low.major, low.minor) {
+                        (0, 0) if patch.is_some() => SchemaVersion::new(0, 0, low.patch + 1),
+                        (0, 0) if minor.is_some() => SchemaVersion::new(0, 1, 0),
+                        (0, minor) if minor > 0 => SchemaVersion::new(0, minor + 1, 0),
+                        (major, _) => SchemaVersion::new(major + 1, 0, 0),
+                    };
+                    bounds.push((Op::Ge, low));
+                    bounds.push((Op::Lt, high));
+                }
+            }
+        }
+        Ok(Self { bounds })
+    }
+}
+
+/// Splits `major[.minor[.patch]]` into up to three numbers. Returns `None`
+/// if there are more than three parts or any part is not a number.
+fn parse_parts(s: &str) -> Option<[Option<u32>; 3]> {
+    let mut parts = [None; 3];
+    for (i, part) in s.split('.').enumerate() {
+        *parts.get_mut(i)? = Some(part.parse().ok()?);
+    }
+    Some(parts)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::schema_registry::SchemaRegistry;
 
     #[test]
     fn test_version_parsing() {
-        assert_eq!(major_version(), 1);
-        assert_eq!(minor_version(), 0);
-        assert_eq!(patch_version(), 0);
+        let current: SchemaVersion = SCHEMA_VERSION.parse().unwrap();
+        assert_eq!(current, CURRENT_SCHEMA_VERSION);
+        assert_eq!(current.to_string(), SCHEMA_VERSION);
+        assert_eq!(current.major_minor(), "1.0");
+        for bad in ["1.0", "1.0.0.0", "1.x.0", ""] {
+            assert!(bad.parse::<SchemaVersion>().is_err(), "{}", bad);
+        }
+
+        let json = serde_json::to_string(&current).unwrap();
+        assert_eq!(json, "\"1.0.0\"");
+        assert_eq!(
+            serde_json::from_str::<SchemaVersion>(&json).unwrap(),
+            current
+        );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_ranges() {
+        let v = |s: &str| s.parse::<SchemaVersion>().unwrap();
+        let r = |s: &str| s.parse::<VersionRange>().unwrap();
+
+        assert!(v("1.9.3").satisfies(&r("^1.2")));
+        assert!(!v("2.0.0").satisfies(&r("1.2")));
+        assert!(!v("0.3.0").satisfies(&r("^0.2")));
+        assert!(v("1.2.9").satisfies(&r("~1.2")));
+        assert!(!v("1.3.0").satisfies(&r("~1.2")));
+        assert!(v("1.10.0").satisfies(&r(">=1.9, <2")));
+        assert!(v("1.0.0").satisfies(&r("=1.0.0")));
+        assert!(v("7.1.0").satisfies(&r("*")));
+        assert!("1.a".parse::<VersionRange>().is_err());
+        assert!(">=".parse::<VersionRange>().is_err());
+    }
+
+    #[test]
+    fn test_embedded_schema_versions_match_schema_files() {
+        let registry = SchemaRegistry::new().unwrap();
+        for name in ["entity", "relationship", "exchange"] {
+            let schema = registry.get_schema(name, None).unwrap();
+            let declared = schema["properties"]["schemaVersion"]["const"]
+                .as_str()
+                .unwrap();
+            let embedded = embedded_schema_version(&format!("cep.{}", name)).unwrap();
+            assert_eq!(declared, embedded.to_string(), "{}", name);
+        }
+        assert!(embedded_schema_version("cep.unknown").is_none());
+    }
+}
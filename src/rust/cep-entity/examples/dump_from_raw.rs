@@ -1,5 +1,5 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 
 use cep_core::schema_registry::find_repo_root;
 use cep_core::{CepError, CepResult};
@@ -89,7 +89,7 @@ fn canonicalize_entity(entity: &EntityRecord) -> String {
 
 /// Write a pretty JSON EntityRecord and a canonical JSON text file.
 fn write_entity_outputs(
-    base_path: &PathBuf,
+    base_path: &Path,
     file_stem: &str,
     entity: &EntityRecord,
 ) -> CepResult<()> {
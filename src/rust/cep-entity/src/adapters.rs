@@ -0,0 +1,966 @@
+//! Ingestion adapters for common registry formats.
+//!
+//! An adapter reads a delimited registry file and turns each row into a
+//! [`SourceEntity`], which holds the raw fields that [`build_entity`] and
+//! SNFEI generation expect. An adapter is just an [`AdapterConfig`] that says
+//! which source column feeds which field. Supporting a new registry format
+//! therefore means writing a new config, for example one loaded from JSON,
+//! not new code.
+//!
+//! Presets cover the well-known formats:
+//!
+//! | Preset | Source |
+//! |---|---|
+//! | [`AdapterConfig::sam_gov_extract`] | SAM.gov public entity extract (V2, pipe-delimited) |
+//! | [`AdapterConfig::gleif_golden_copy`] | GLEIF LEI-CDF golden copy CSV |
+//! | [`AdapterConfig::census_government_units`] | Census of Governments unit listing |
+//! | [`AdapterConfig::state_sos_csv`] | Secretary of State business entity CSV |
+//! | [`AdapterConfig::irs_bmf`] | IRS Exempt Organizations Business Master File extract |
+//! | [`AdapterConfig::cra_charities`] | CRA list of charities |
+//!
+//! The nonprofit presets read the EIN or charity registration number as the
+//! row's `source_id`, so a [`LegacyImport`] under [`EIN_SCHEME_URI`] or
+//! [`CRA_CHARITY_SCHEME_URI`] puts charities in the same crosswalks as
+//! governments.
+//!
+//! ```rust,no_run
+//! use cep_entity::adapters::AdapterConfig;
+//! use std::path::Path;
+//!
+//! let rows = AdapterConfig::census_government_units()
+//!     .read_path(Path::new("Govt_Units_2022_Final.csv"))
+//!     .unwrap();
+//! for row in rows {
+//!     println!("{} ({:?})", row.legal_name, row.entity_type);
+//! }
+//! ```
+//!
+//! [`build_entity`]: crate::build_entity
+//! [`LegacyImport`]: crate::legacy::LegacyImport
+//! [`EIN_SCHEME_URI`]: crate::identifiers::EIN_SCHEME_URI
+//! [`CRA_CHARITY_SCHEME_URI`]: crate::identifiers::CRA_CHARITY_SCHEME_URI
+
+use cep_core::{CepError, CepResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::builder::{AttestationInput, EntityBuilderInput};
+use crate::identifiers::Ein;
+
+/// Raw entity fields read from one source row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceEntity {
+    /// The registry's own identifier (UEI, LEI, Census ID, filing number).
+    pub source_id: Option<String>,
+    pub legal_name: String,
+    /// Entity type code understood by [`EntityTypeCode`](crate::EntityTypeCode).
+    pub entity_type: Option<String>,
+    /// Jurisdiction code, e.g. "US-IL".
+    pub jurisdiction: Option<String>,
+    /// ISO 3166-1 alpha-2 country code.
+    pub country_code: String,
+    pub address: Option<String>,
+    pub registration_date: Option<String>,
+}
+
+impl SourceEntity {
+    /// Combines the source fields with an attestation into builder input.
+    pub fn into_builder_input(self, attestation: AttestationInput) -> EntityBuilderInput {
+        EntityBuilderInput {
+            source_id: self.source_id,
+            legal_name: self.legal_name,
+            entity_type: self.entity_type,
+            jurisdiction: self.jurisdiction,
+            country_code: self.country_code,
+            address: self.address,
+            registration_date: self.registration_date,
+            status: None,
+            attestation,
+        }
+    }
+}
+
+/// A source column, by header name or by zero-based position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+/// How a raw column value is turned into a field value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ValueFormat {
+    /// Used as-is after trimming.
+    #[default]
+    Text,
+    /// `YYYYMMDD`, rewritten as `YYYY-MM-DD`.
+    CompactDate,
+    /// An ISO 8601 date-time; only the date part is kept.
+    DateTime,
+    /// ISO 3166-1 alpha-3 country code, rewritten as alpha-2.
+    Alpha3Country,
+    /// A subdivision code such as "IL", prefixed with the row's country
+    /// ("US-IL") unless it already has a prefix.
+    Subdivision,
+    /// An IRS EIN, with or without the hyphen, rewritten as `XX-XXXXXXX`.
+    Ein,
+}
+
+/// Maps one source column to a field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldMapping {
+    pub column: Column,
+    #[serde(default)]
+    pub format: ValueFormat,
+    /// Lookup table applied after formatting. Values not in the table are
+    /// kept unchanged.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub values: BTreeMap<String, String>,
+}
+
+impl FieldMapping {
+    pub fn new(column: Column) -> Self {
+        Self {
+            column,
+            format: ValueFormat::Text,
+            values: BTreeMap::new(),
+        }
+    }
+
+    /// Maps a column by header name.
+    pub fn named(name: &str) -> Self {
+        Self::new(Column::Name(name.to_string()))
+    }
+
+    /// Maps a column by zero-based position.
+    pub fn index(index: usize) -> Self {
+        Self::new(Column::Index(index))
+    }
+
+    pub fn with_format(mut self, format: ValueFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_value(mut self, raw: &str, mapped: &str) -> Self {
+        self.values.insert(raw.to_string(), mapped.to_string());
+        self
+    }
+}
+
+/// Describes how to read one registry format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdapterConfig {
+    /// Name used in error messages.
+    pub name: String,
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+    #[serde(default = "default_has_headers")]
+    pub has_headers: bool,
+    /// Whether `"` quotes fields. Unquoted formats that may contain a bare
+    /// `"` in a value turn this off.
+    #[serde(default = "default_quoting")]
+    pub quoting: bool,
+    /// Rows whose first field starts with one of these are skipped, e.g. the
+    /// `BOF`/`EOF` markers in SAM.gov extracts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skip_rows_starting_with: Vec<String>,
+    pub legal_name: FieldMapping,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<FieldMapping>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_type: Option<FieldMapping>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jurisdiction: Option<FieldMapping>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<FieldMapping>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<FieldMapping>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registration_date: Option<FieldMapping>,
+    /// Country used when the row has none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_country_code: Option<String>,
+    /// Jurisdiction used when the row has none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_jurisdiction: Option<String>,
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+fn default_has_headers() -> bool {
+    true
+}
+
+fn default_quoting() -> bool {
+    true
+}
+
+impl AdapterConfig {
+    /// Creates a comma-delimited config with a header row.
+    pub fn new(name: &str, legal_name: FieldMapping) -> Self {
+        Self {
+            name: name.to_string(),
+            delimiter: default_delimiter(),
+            has_headers: default_has_headers(),
+            quoting: default_quoting(),
+            skip_rows_starting_with: Vec::new(),
+            legal_name,
+            source_id: None,
+            entity_type: None,
+            jurisdiction: None,
+            country_code: None,
+            address: None,
+            registration_date: None,
+            default_country_code: None,
+            default_jurisdiction: None,
+        }
+    }
+
+    /// SAM.gov public entity extract (V2 layout).
+    ///
+    /// The file is pipe-delimited and unquoted, with no header row. It is
+    /// bracketed by `BOF` and `EOF` marker rows. Columns are read by position: UEI (1), legal
+    /// business name (12), physical address line 1 (16), state or province
+    /// (19), country as alpha-3 (22), and entity start date as `YYYYMMDD` (24).
+    pub fn sam_gov_extract() -> Self {
+        let mut config = Self::new("SAM.gov extract", FieldMapping::index(11));
+        config.delimiter = '|';
+        config.has_headers = false;
+        config.quoting = false;
+        config.skip_rows_starting_with = vec!["BOF ".to_string(), "EOF ".to_string()];
+        config.source_id = Some(FieldMapping::index(0));
+        config.address = Some(FieldMapping::index(15));
+        config.jurisdiction = Some(FieldMapping::index(18).with_format(ValueFormat::Subdivision));
+        config.country_code = Some(FieldMapping::index(21).with_format(ValueFormat::Alpha3Country));
+        config.registration_date =
+            Some(FieldMapping::index(23).with_format(ValueFormat::CompactDate));
+        config
+    }
+
+    /// GLEIF golden copy (LEI-CDF) CSV.
+    ///
+    /// `Entity.LegalAddress.Region` is already an ISO 3166-2 code. Entity
+    /// categories (GENERAL, FUND, ...) have no CEP equivalent and are not
+    /// mapped.
+    pub fn gleif_golden_copy() -> Self {
+        let mut config = Self::new("GLEIF golden copy", FieldMapping::named("Entity.LegalName"));
+        config.source_id = Some(FieldMapping::named("LEI"));
+        config.address = Some(FieldMapping::named("Entity.LegalAddress.FirstAddressLine"));
+        config.jurisdiction = Some(
+            FieldMapping::named("Entity.LegalAddress.Region").with_format(ValueFormat::Subdivision),
+        );
+        config.country_code = Some(FieldMapping::named("Entity.LegalAddress.Country"));
+        config.registration_date = Some(
+            FieldMapping::named("Entity.EntityCreationDate").with_format(ValueFormat::DateTime),
+        );
+        config
+    }
+
+    /// Census of Governments government units listing.
+    ///
+    /// Unit types are mapped to CEP entity types. Townships count as
+    /// municipalities.
+    pub fn census_government_units() -> Self {
+        let mut config = Self::new("Census government units", FieldMapping::named("UNIT_NAME"));
+        config.source_id = Some(FieldMapping::named("CENSUS_ID_PID6"));
+        config.entity_type = Some(
+            FieldMapping::named("UNIT_TYPE")
+                .with_value("1 - COUNTY", "COUNTY")
+                .with_value("2 - MUNICIPAL", "MUNICIPALITY")
+                .with_value("3 - TOWNSHIP", "MUNICIPALITY")
+                .with_value("4 - SPECIAL DISTRICT", "SPECIAL_DISTRICT")
+                .with_value("5 - SCHOOL DISTRICT (INDEPENDENT)", "SCHOOL_DISTRICT"),
+        );
+        config.address = Some(FieldMapping::named("ADDRESS1"));
+        config.jurisdiction =
+            Some(FieldMapping::named("STATE").with_format(ValueFormat::Subdivision));
+        config.default_country_code = Some("US".to_string());
+        config
+    }
+
+    /// Business entity CSV export from a US Secretary of State.
+    ///
+    /// Every row belongs to `state` (e.g. "IL"). Column names differ between
+    /// states. This preset uses the common `Entity ID`, `Entity Name`,
+    /// `Principal Address` and `Formation Date` headers; adjust the mappings
+    /// for other layouts. Business entity types (LLC, corporation) have no
+    /// CEP equivalent and are not mapped.
+    pub fn state_sos_csv(state: &str) -> Self {
+        let mut config = Self::new(
+            &format!("{} Secretary of State export", state.to_uppercase()),
+            FieldMapping::named("Entity Name"),
+        );
+        config.source_id = Some(FieldMapping::named("Entity ID"));
+        config.address = Some(FieldMapping::named("Principal Address"));
+        config.registration_date = Some(FieldMapping::named("Formation Date"));
+        config.default_country_code = Some("US".to_string());
+        config.default_jurisdiction = Some(format!("US-{}", state.to_uppercase()));
+        config
+    }
+
+    /// IRS Exempt Organizations Business Master File extract (`eo_xx.csv`).
+    ///
+    /// The EIN is the source id. The ruling date has only month precision
+    /// (`YYYYMM`) and is not mapped; neither are subsection and NTEE codes,
+    /// which have no CEP entity type.
+    pub fn irs_bmf() -> Self {
+        let mut config = Self::new("IRS Business Master File", FieldMapping::named("NAME"));
+        config.source_id = Some(FieldMapping::named("EIN").with_format(ValueFormat::Ein));
+        config.address = Some(FieldMapping::named("STREET"));
+        config.jurisdiction =
+            Some(FieldMapping::named("STATE").with_format(ValueFormat::Subdivision));
+        config.default_country_code = Some("US".to_string());
+        config
+    }
+
+    /// CRA list of charities CSV.
+    ///
+    /// The charity registration number (a BN with program `RR`) is the
+    /// source id. The effective date is that of the current status, which
+    /// changes on revocation, so it is not used as a registration date.
+    pub fn cra_charities() -> Self {
+        let mut config = Self::new("CRA list of charities", FieldMapping::named("Legal Name"));
+        config.source_id = Some(FieldMapping::named("BN/Registration Number"));
+        config.address = Some(FieldMapping::named("Address"));
+        config.jurisdiction =
+            Some(FieldMapping::named("Province").with_format(ValueFormat::Subdivision));
+        config.country_code = Some(FieldMapping::named("Country"));
+        config.default_country_code = Some("CA".to_string());
+        config
+    }
+
+    /// Parses a config from JSON.
+    pub fn from_json(json: &str) -> CepResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Reads a registry file.
+    pub fn read_path(&self, path: &Path) -> CepResult<Vec<SourceEntity>> {
+        let file = File::open(path).map_err(|e| {
+            CepError::Configuration(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        self.read(file)
+    }
+
+    /// Reads every row of a registry file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::Configuration` if the file is malformed or a named
+    /// column is missing. Returns `CepError::MissingField` if a row has no
+    /// legal name or country. Returns `CepError::InvalidIdentifier` if a
+    /// country code cannot be converted.
+    pub fn read<R: Read>(&self, reader: R) -> CepResult<Vec<SourceEntity>> {
+        if !self.delimiter.is_ascii() {
+            return Err(CepError::Configuration(format!(
+                "{}: delimiter must be an ASCII character",
+                self.name
+            )));
+        }
+        let mut csv = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter as u8)
+            .has_headers(self.has_headers)
+            .quoting(self.quoting)
+            .flexible(true)
+            .from_reader(reader);
+        let headers = if self.has_headers {
+            Some(csv.headers().map_err(|e| self.csv_error(e))?.clone())
+        } else {
+            None
+        };
+        let columns = ResolvedColumns::new(self, headers.as_ref())?;
+
+        let mut entities = Vec::new();
+        for (index, row) in csv.records().enumerate() {
+            let row = row.map_err(|e| self.csv_error(e))?;
+            let first = row.get(0).unwrap_or("");
+            if self
+                .skip_rows_starting_with
+                .iter()
+                .any(|prefix| first.starts_with(prefix.as_str()))
+            {
+                continue;
+            }
+            // Row numbers count from 1 and include the header, as editors show them.
+            let line = index + 1 + usize::from(self.has_headers);
+            entities.push(self.read_row(&columns, &row, line)?);
+        }
+        Ok(entities)
+    }
+
+    fn read_row(
+        &self,
+        columns: &ResolvedColumns,
+        row: &csv::StringRecord,
+        line: usize,
+    ) -> CepResult<SourceEntity> {
+        let missing = |field: &str| {
+            CepError::MissingField(format!("{} (row {} of {})", field, line, self.name))
+        };
+        let legal_name = columns
+            .value(row, &self.legal_name, columns.legal_name, None)?
+            .ok_or_else(|| missing("legal_name"))?;
+        let country_code = match &self.country_code {
+            Some(mapping) => columns.value(row, mapping, columns.country_code, None)?,
+            None => None,
+        }
+        .or_else(|| self.default_country_code.clone())
+        .ok_or_else(|| missing("country_code"))?;
+
+        let optional = |mapping: &Option<FieldMapping>, index: Option<usize>| match mapping {
+            Some(mapping) => columns.value(row, mapping, index, Some(&country_code)),
+            None => Ok(None),
+        };
+        Ok(SourceEntity {
+            source_id: optional(&self.source_id, columns.source_id)?,
+            legal_name,
+            entity_type: optional(&self.entity_type, columns.entity_type)?,
+            jurisdiction: optional(&self.jurisdiction, columns.jurisdiction)?
+                .or_else(|| self.default_jurisdiction.clone()),
+            address: optional(&self.address, columns.address)?,
+            registration_date: optional(&self.registration_date, columns.registration_date)?,
+            country_code,
+        })
+    }
+
+    fn csv_error(&self, error: csv::Error) -> CepError {
+        CepError::Configuration(format!("{}: {}", self.name, error))
+    }
+}
+
+/// Column positions of a config's mappings, resolved against the header row.
+struct ResolvedColumns {
+    legal_name: Option<usize>,
+    source_id: Option<usize>,
+    entity_type: Option<usize>,
+    jurisdiction: Option<usize>,
+    country_code: Option<usize>,
+    address: Option<usize>,
+    registration_date: Option<usize>,
+}
+
+impl ResolvedColumns {
+    fn new(config: &AdapterConfig, headers: Option<&csv::StringRecord>) -> CepResult<Self> {
+        let resolve = |mapping: &Option<FieldMapping>| -> CepResult<Option<usize>> {
+            match mapping {
+                None => Ok(None),
+                Some(mapping) => match &mapping.column {
+                    Column::Index(index) => Ok(Some(*index)),
+                    Column::Name(name) => headers
+                        .and_then(|headers| headers.iter().position(|h| h.trim() == name))
+                        .map(Some)
+                        .ok_or_else(|| {
+                            CepError::Configuration(format!(
+                                "{}: no column named '{}'",
+                                config.name, name
+                            ))
+                        }),
+                },
+            }
+        };
+        Ok(Self {
+            legal_name: resolve(&Some(config.legal_name.clone()))?,
+            source_id: resolve(&config.source_id)?,
+            entity_type: resolve(&config.entity_type)?,
+            jurisdiction: resolve(&config.jurisdiction)?,
+            country_code: resolve(&config.country_code)?,
+            address: resolve(&config.address)?,
+            registration_date: resolve(&config.registration_date)?,
+        })
+    }
+
+    /// Reads and formats one field. Empty values read as `None`.
+    fn value(
+        &self,
+        row: &csv::StringRecord,
+        mapping: &FieldMapping,
+        index: Option<usize>,
+        country_code: Option<&str>,
+    ) -> CepResult<Option<String>> {
+        let raw = index.and_then(|i| row.get(i)).unwrap_or("").trim();
+        if raw.is_empty() {
+            return Ok(None);
+        }
+        let formatted = match mapping.format {
+            ValueFormat::Text => raw.to_string(),
+            ValueFormat::CompactDate => compact_date(raw),
+            ValueFormat::DateTime => raw.split('T').next().unwrap_or(raw).to_string(),
+            ValueFormat::Alpha3Country => alpha3_to_alpha2(raw)?.to_string(),
+            ValueFormat::Ein => Ein::parse(raw)?.as_str().to_string(),
+            ValueFormat::Subdivision => match country_code {
+                Some(country) if !raw.contains('-') => {
+                    format!("{}-{}", country, raw.to_uppercase())
+                }
+                _ => raw.to_uppercase(),
+            },
+        };
+        Ok(Some(
+            mapping.values.get(&formatted).cloned().unwrap_or(formatted),
+        ))
+    }
+}
+
+/// Rewrites `YYYYMMDD` as `YYYY-MM-DD`; other values are returned unchanged
+/// for the SNFEI date normalizer to handle.
+fn compact_date(raw: &str) -> String {
+    if raw.len() == 8 && raw.chars().all(|c| c.is_ascii_digit()) {
+        format!("{}-{}-{}", &raw[..4], &raw[4..6], &raw[6..])
+    } else {
+        raw.to_string()
+    }
+}
+
+/// ISO 3166-1 alpha-3 to alpha-2, for every officially assigned code.
+const ALPHA3_TO_ALPHA2: &[(&str, &str)] = &[
+    ("ABW", "AW"),
+    ("AFG", "AF"),
+    ("AGO", "AO"),
+    ("AIA", "AI"),
+    ("ALA", "AX"),
+    ("ALB", "AL"),
+    ("AND", "AD"),
+    ("ARE", "AE"),
+    ("ARG", "AR"),
+    ("ARM", "AM"),
+    ("ASM", "AS"),
+    ("ATA", "AQ"),
+    ("ATF", "TF"),
+    ("ATG", "AG"),
+    ("AUS", "AU"),
+    ("AUT", "AT"),
+    ("AZE", "AZ"),
+    ("BDI", "BI"),
+    ("BEL", "BE"),
+    ("BEN", "BJ"),
+    ("BES", "BQ"),
+    ("BFA", "BF"),
+    ("BGD", "BD"),
+    ("BGR", "BG"),
+    ("BHR", "BH"),
+    ("BHS", "BS"),
+    ("BIH", "BA"),
+    ("BLM", "BL"),
+    ("BLR", "BY"),
+    ("BLZ", "BZ"),
+    ("BMU", "BM"),
+    ("BOL", "BO"),
+    ("BRA", "BR"),
+    ("BRB", "BB"),
+    ("BRN", "BN"),
+    ("BTN", "BT"),
+    ("BVT", "BV"),
+    ("BWA", "BW"),
+    ("CAF", "CF"),
+    ("CAN", "CA"),
+    ("CCK", "CC"),
+    ("CHE", "CH"),
+    ("CHL", "CL"),
+    ("CHN", "CN"),
+    ("CIV", "CI"),
+    ("CMR", "CM"),
+    ("COD", "CD"),
+    ("COG", "CG"),
+    ("COK", "CK"),
+    ("COL", "CO"),
+    ("COM", "KM"),
+    ("CPV", "CV"),
+    ("CRI", "CR"),
+    ("CUB", "CU"),
+    ("CUW", "CW"),
+    ("CXR", "CX"),
+    ("CYM", "KY"),
+    ("CYP", "CY"),
+    ("CZE", "CZ"),
+    ("DEU", "DE"),
+    ("DJI", "DJ"),
+    ("DMA", "DM"),
+    ("DNK", "DK"),
+    ("DOM", "DO"),
+    ("DZA", "DZ"),
+    ("ECU", "EC"),
+    ("EGY", "EG"),
+    ("ERI", "ER"),
+    ("ESH", "EH"),
+    ("ESP", "ES"),
+    ("EST", "EE"),
+    ("ETH", "ET"),
+    ("FIN", "FI"),
+    ("FJI", "FJ"),
+    ("FLK", "FK"),
+    ("FRA", "FR"),
+    ("FRO", "FO"),
+    ("FSM", "FM"),
+    ("GAB", "GA"),
+    ("GBR", "GB"),
+    ("GEO", "GE"),
+    ("GGY", "GG"),
+    ("GHA", "GH"),
+    ("GIB", "GI"),
+    ("GIN", "GN"),
+    ("GLP", "GP"),
+    ("GMB", "GM"),
+    ("GNB", "GW"),
+    ("GNQ", "GQ"),
+    ("GRC", "GR"),
+    ("GRD", "GD"),
+    ("GRL", "GL"),
+    ("GTM", "GT"),
+    ("GUF", "GF"),
+    ("GUM", "GU"),
+    ("GUY", "GY"),
+    ("HKG", "HK"),
+    ("HMD", "HM"),
+    ("HND", "HN"),
+    ("HRV", "HR"),
+    ("HTI", "HT"),
+    ("HUN", "HU"),
+    ("IDN", "ID"),
+    ("IMN", "IM"),
+    ("IND", "IN"),
+    ("IOT", "IO"),
+    ("IRL", "IE"),
+    ("IRN", "IR"),
+    ("IRQ", "IQ"),
+    ("ISL", "IS"),
+    ("ISR", "IL"),
+    ("ITA", "IT"),
+    ("JAM", "JM"),
+    ("JEY", "JE"),
+    ("JOR", "JO"),
+    ("JPN", "JP"),
+    ("KAZ", "KZ"),
+    ("KEN", "KE"),
+    ("KGZ", "KG"),
+    ("KHM", "KH"),
+    ("KIR", "KI"),
+    ("KNA", "KN"),
+    ("KOR", "KR"),
+    ("KWT", "KW"),
+    ("LAO", "LA"),
+    ("LBN", "LB"),
+    ("LBR", "LR"),
+    ("LBY", "LY"),
+    ("LCA", "LC"),
+    ("LIE", "LI"),
+    ("LKA", "LK"),
+    ("LSO", "LS"),
+    ("LTU", "LT"),
+    ("LUX", "LU"),
+    ("LVA", "LV"),
+    ("MAC", "MO"),
+    ("MAF", "MF"),
+    ("MAR", "MA"),
+    ("MCO", "MC"),
+    ("MDA", "MD"),
+    ("MDG", "MG"),
+    ("MDV", "MV"),
+    ("MEX", "MX"),
+    ("MHL", "MH"),
+    ("MKD", "MK"),
+    ("MLI", "ML"),
+    ("MLT", "MT"),
+    ("MMR", "MM"),
+    ("MNE", "ME"),
+    ("MNG", "MN"),
+    ("MNP", "MP"),
+    ("MOZ", "MZ"),
+    ("MRT", "MR"),
+    ("MSR", "MS"),
+    ("MTQ", "MQ"),
+    ("MUS", "MU"),
+    ("MWI", "MW"),
+    ("MYS", "MY"),
+    ("MYT", "YT"),
+    ("NAM", "NA"),
+    ("NCL", "NC"),
+    ("NER", "NE"),
+    ("NFK", "NF"),
+    ("NGA", "NG"),
+    ("NIC", "NI"),
+    ("NIU", "NU"),
+    ("NLD", "NL"),
+    ("NOR", "NO"),
+    ("NPL", "NP"),
+    ("NRU", "NR"),
+    ("NZL", "NZ"),
+    ("OMN", "OM"),
+    ("PAK", "PK"),
+    ("PAN", "PA"),
+    ("PCN", "PN"),
+    ("PER", "PE"),
+    ("PHL", "PH"),
+    ("PLW", "PW"),
+    ("PNG", "PG"),
+    ("POL", "PL"),
+    ("PRI", "PR"),
+    ("PRK", "KP"),
+    ("PRT", "PT"),
+    ("PRY", "PY"),
+    ("PSE", "PS"),
+    ("PYF", "PF"),
+    ("QAT", "QA"),
+    ("REU", "RE"),
+    ("ROU", "RO"),
+    ("RUS", "RU"),
+    ("RWA", "RW"),
+    ("SAU", "SA"),
+    ("SDN", "SD"),
+    ("SEN", "SN"),
+    ("SGP", "SG"),
+    ("SGS", "GS"),
+    ("SHN", "SH"),
+    ("SJM", "SJ"),
+    ("SLB", "SB"),
+    ("SLE", "SL"),
+    ("SLV", "SV"),
+    ("SMR", "SM"),
+    ("SOM", "SO"),
+    ("SPM", "PM"),
+    ("SRB", "RS"),
+    ("SSD", "SS"),
+    ("STP", "ST"),
+    ("SUR", "SR"),
+    ("SVK", "SK"),
+    ("SVN", "SI"),
+    ("SWE", "SE"),
+    ("SWZ", "SZ"),
+    ("SXM", "SX"),
+    ("SYC", "SC"),
+    ("SYR", "SY"),
+    ("TCA", "TC"),
+    ("TCD", "TD"),
+    ("TGO", "TG"),
+    ("THA", "TH"),
+    ("TJK", "TJ"),
+    ("TKL", "TK"),
+    ("TKM", "TM"),
+    ("TLS", "TL"),
+    ("TON", "TO"),
+    ("TTO", "TT"),
+    ("TUN", "TN"),
+    ("TUR", "TR"),
+    ("TUV", "TV"),
+    ("TWN", "TW"),
+    ("TZA", "TZ"),
+    ("UGA", "UG"),
+    ("UKR", "UA"),
+    ("UMI", "UM"),
+    ("URY", "UY"),
+    ("USA", "US"),
+    ("UZB", "UZ"),
+    ("VAT", "VA"),
+    ("VCT", "VC"),
+    ("VEN", "VE"),
+    ("VGB", "VG"),
+    ("VIR", "VI"),
+    ("VNM", "VN"),
+    ("VUT", "VU"),
+    ("WLF", "WF"),
+    ("WSM", "WS"),
+    ("YEM", "YE"),
+    ("ZAF", "ZA"),
+    ("ZMB", "ZM"),
+    ("ZWE", "ZW"),
+];
+
+fn alpha3_to_alpha2(code: &str) -> CepResult<&'static str> {
+    let upper = code.to_uppercase();
+    ALPHA3_TO_ALPHA2
+        .iter()
+        .find(|(alpha3, _)| *alpha3 == upper)
+        .map(|(_, alpha2)| *alpha2)
+        .ok_or_else(|| {
+            CepError::InvalidIdentifier(format!(
+                "unknown ISO 3166-1 alpha-3 country code '{}'",
+                code
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_entity;
+    use cep_core::find_repo_root;
+    use std::path::PathBuf;
+
+    fn sample(name: &str) -> PathBuf {
+        find_repo_root()
+            .unwrap()
+            .join("examples-raw/adapters")
+            .join(name)
+    }
+
+    fn attestation() -> AttestationInput {
+        AttestationInput {
+            attested_by: "cep-entity:demo:attestor-1".to_string(),
+            attestation_timestamp: "2025-11-28T14:30:00.000000Z".to_string(),
+            proof_type: None,
+            proof_value: None,
+            verification_method_uri: None,
+            proof_purpose: None,
+            anchor_uri: None,
+        }
+    }
+
+    #[test]
+    fn test_sam_gov_extract() {
+        let rows = AdapterConfig::sam_gov_extract()
+            .read_path(&sample("sam_gov_extract.dat"))
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0],
+            SourceEntity {
+                source_id: Some("J6H4FB3N5YK7".to_string()),
+                legal_name: "ACME CONSULTING LLC".to_string(),
+                entity_type: None,
+                jurisdiction: Some("US-IL".to_string()),
+                country_code: "US".to_string(),
+                address: Some("100 MAIN ST".to_string()),
+                registration_date: Some("2019-03-01".to_string()),
+            }
+        );
+        assert_eq!(rows[1].country_code, "CA");
+        assert_eq!(rows[1].jurisdiction.as_deref(), Some("CA-ON"));
+        assert_eq!(rows[1].registration_date, None);
+
+        // Quotes in names are literal, and any assigned country converts.
+        let row = "X|||||||||||\"BEST\" PAVING LLC||||||||||VIR";
+        let rows = AdapterConfig::sam_gov_extract()
+            .read(row.as_bytes())
+            .unwrap();
+        assert_eq!(rows[0].legal_name, "\"BEST\" PAVING LLC");
+        assert_eq!(rows[0].country_code, "VI");
+    }
+
+    #[test]
+    fn test_gleif_golden_copy() {
+        let rows = AdapterConfig::gleif_golden_copy()
+            .read_path(&sample("gleif_golden_copy.csv"))
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].source_id.as_deref(), Some("5493001KJTIIGC8Y1R12"));
+        assert_eq!(rows[0].address.as_deref(), Some("1 Capitol Plaza, Floor 3"));
+        assert_eq!(rows[0].jurisdiction.as_deref(), Some("US-IL"));
+        assert_eq!(rows[0].registration_date.as_deref(), Some("1998-06-30"));
+        assert_eq!(rows[1].country_code, "DE");
+        assert_eq!(rows[1].registration_date, None);
+    }
+
+    #[test]
+    fn test_census_government_units() {
+        let rows = AdapterConfig::census_government_units()
+            .read_path(&sample("census_government_units.csv"))
+            .unwrap();
+        let types: Vec<_> = rows.iter().map(|r| r.entity_type.as_deref()).collect();
+        assert_eq!(
+            types,
+            vec![
+                Some("MUNICIPALITY"),
+                Some("SCHOOL_DISTRICT"),
+                Some("COUNTY")
+            ]
+        );
+        assert!(rows.iter().all(|r| r.country_code == "US"));
+        assert!(
+            rows.iter()
+                .all(|r| r.jurisdiction.as_deref() == Some("US-IL"))
+        );
+
+        let result = build_entity(rows[0].clone().into_builder_input(attestation())).unwrap();
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.entity.jurisdiction_iso, "US-IL");
+    }
+
+    #[test]
+    fn test_state_sos_csv() {
+        let rows = AdapterConfig::state_sos_csv("il")
+            .read_path(&sample("state_sos_export.csv"))
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].jurisdiction.as_deref(), Some("US-IL"));
+        assert_eq!(rows[0].registration_date.as_deref(), Some("03/15/2011"));
+        assert_eq!(rows[1].legal_name, "Lincoln Home Services, Inc.");
+        assert_eq!(rows[1].address, None);
+    }
+
+    #[test]
+    fn test_irs_bmf() {
+        let rows = AdapterConfig::irs_bmf()
+            .read_path(&sample("irs_bmf.csv"))
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].source_id.as_deref(), Some("37-1234567"));
+        assert_eq!(rows[0].legal_name, "SPRINGFIELD COMMUNITY FOOD BANK");
+        assert_eq!(rows[0].jurisdiction.as_deref(), Some("US-IL"));
+        assert_eq!(rows[0].registration_date, None);
+
+        let bad = "EIN,NAME,STREET,STATE\n071234567,Acme,1 Main St,IL\n";
+        assert!(matches!(
+            AdapterConfig::irs_bmf().read(bad.as_bytes()),
+            Err(CepError::InvalidIdentifier(_))
+        ));
+    }
+
+    #[test]
+    fn test_cra_charities() {
+        let rows = AdapterConfig::cra_charities()
+            .read_path(&sample("cra_charities.csv"))
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].source_id.as_deref(), Some("118830553RR0001"));
+        assert_eq!(rows[0].country_code, "CA");
+        assert_eq!(rows[1].jurisdiction.as_deref(), Some("CA-BC"));
+
+        let result = build_entity(rows[0].clone().into_builder_input(attestation())).unwrap();
+        assert_eq!(result.entity.jurisdiction_iso, "CA-ON");
+    }
+
+    #[test]
+    fn test_config_from_json() {
+        let json = r#"{
+            "name": "county export",
+            "delimiter": ";",
+            "legalName": {"column": "Name"},
+            "registrationDate": {"column": 2, "format": "compactDate"},
+            "defaultCountryCode": "US"
+        }"#;
+        let config = AdapterConfig::from_json(json).unwrap();
+        let rows = config
+            .read("Name;Other;Date\nSpringfield Water District;x;20010102\n".as_bytes())
+            .unwrap();
+        assert_eq!(rows[0].legal_name, "Springfield Water District");
+        assert_eq!(rows[0].registration_date.as_deref(), Some("2001-01-02"));
+    }
+
+    #[test]
+    fn test_missing_columns_and_fields() {
+        let config = AdapterConfig::gleif_golden_copy();
+        assert!(matches!(
+            config.read("LEI,Name\n1,a\n".as_bytes()),
+            Err(CepError::Configuration(_))
+        ));
+
+        let config = AdapterConfig::new("test", FieldMapping::named("Name"));
+        let err = config.read("Name\nAcme\n".as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("country_code (row 2 of test)"));
+
+        let config = AdapterConfig::sam_gov_extract();
+        let row = "X|||||||||||Acme||||||||||ZZZ";
+        assert!(matches!(
+            config.read(row.as_bytes()),
+            Err(CepError::InvalidIdentifier(_))
+        ));
+    }
+}
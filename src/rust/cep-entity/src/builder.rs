@@ -1,9 +1,10 @@
-/// Entity builder: raw data -> canonical CEP Entity.
-///
-/// Transforms heterogeneous input data into fully-formed EntityRecord
-/// with SNFEI generation.
-use cep_core::{Attestation, CanonicalTimestamp, CepError, CepResult};
-use cep_snfei::{SnfeiResult, apply_localization, generate_snfei};
+//! Entity builder: raw data -> canonical CEP Entity.
+//!
+//! Transforms heterogeneous input data into fully-formed EntityRecord
+//! with SNFEI generation.
+use cep_core::{Attestation, CanonicalTimestamp, CepError, CepResult, ProofType};
+use cep_snfei::{IdentifierVerification, SnfeiResult, apply_localization, generate_snfei};
+use serde::{Deserialize, Serialize};
 
 use crate::{EntityIdentifiers, EntityRecord, EntityStatus, EntityStatusCode};
 
@@ -97,6 +98,61 @@ pub struct EntityBuildResult {
     pub warnings: Vec<String>,
 }
 
+impl EntityBuildResult {
+    /// Re-tiers the SNFEI result against the entity's current identifiers,
+    /// e.g. after an LEI was added to an entity built at Tier 3.
+    /// `verification` says how far those identifiers were checked against
+    /// their registries.
+    ///
+    /// Returns the change, or `None` if the tier did not improve.
+    pub fn retier(&mut self, verification: IdentifierVerification) -> Option<TierChange> {
+        let upgraded = self
+            .snfei_result
+            .upgrade_with(&self.entity.identifiers, verification);
+        if upgraded.tier == self.snfei_result.tier {
+            return None;
+        }
+        let change = TierChange {
+            verifiable_id: self.entity.verifiable_id.clone(),
+            previous_tier: self.snfei_result.tier,
+            tier: upgraded.tier,
+            previous_confidence: self.snfei_result.confidence_score,
+            confidence: upgraded.confidence_score,
+        };
+        self.snfei_result = upgraded;
+        Some(change)
+    }
+}
+
+/// An entity whose tier rose because new identifiers arrived.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TierChange {
+    pub verifiable_id: String,
+    pub previous_tier: u8,
+    pub tier: u8,
+    pub previous_confidence: f64,
+    pub confidence: f64,
+}
+
+/// Re-tiers every result against its entity's current identifiers and
+/// returns one [`TierChange`] per upgraded entity, in input order.
+///
+/// `verify` checks an entity's identifiers against their registries (or
+/// accepts them offline).
+pub fn recompute_tiers(
+    results: &mut [EntityBuildResult],
+    verify: impl Fn(&EntityIdentifiers) -> IdentifierVerification,
+) -> Vec<TierChange> {
+    results
+        .iter_mut()
+        .filter_map(|result| {
+            let verification = verify(&result.entity.identifiers);
+            result.retier(verification)
+        })
+        .collect()
+}
+
 /// Normalized entity type codes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EntityTypeCode {
@@ -111,6 +167,7 @@ pub enum EntityTypeCode {
 
 impl EntityTypeCode {
     /// Parse from raw string.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         match s.to_uppercase().as_str() {
             "MUNICIPALITY" => Self::Municipality,
@@ -225,10 +282,12 @@ pub fn build_entity(input: EntityBuilderInput) -> CepResult<EntityBuildResult> {
     );
 
     // Add warning for unknown entity type
-    if input.entity_type.is_some() && entity_type == EntityTypeCode::Other {
+    if let Some(ref raw_type) = input.entity_type
+        && entity_type == EntityTypeCode::Other
+    {
         warnings.push(format!(
             "Unknown entity type '{}' normalized to 'other'",
-            input.entity_type.as_ref().unwrap()
+            raw_type
         ));
     }
 
@@ -268,8 +327,8 @@ fn build_attestation(input: &AttestationInput) -> CepResult<Attestation> {
     // Sensible defaults for examples and non-crypto environments
     let proof_type = input
         .proof_type
-        .clone()
-        .unwrap_or_else(|| "ManualAttestation".to_string());
+        .as_deref()
+        .map_or(ProofType::ManualAttestation, ProofType::from);
 
     let proof_value = input.proof_value.clone().unwrap_or_default();
 
@@ -399,4 +458,32 @@ mod tests {
             result2.snfei_result.snfei.value
         );
     }
+
+    #[test]
+    fn test_recompute_tiers_after_lei_arrives() {
+        let mut results = vec![
+            build_entity(sample_input()).unwrap(),
+            build_entity(sample_input()).unwrap(),
+        ];
+        let offline = |_: &EntityIdentifiers| IdentifierVerification::OfflineAccepted;
+        assert!(recompute_tiers(&mut results, offline).is_empty());
+
+        results[1].entity.identifiers = results[1]
+            .entity
+            .identifiers
+            .clone()
+            .with_lei(crate::Lei::new("5493001KJTIIGC8Y1R12").unwrap());
+        let unverified = |_: &EntityIdentifiers| IdentifierVerification::Unverified;
+        assert!(recompute_tiers(&mut results.clone(), unverified).is_empty());
+        let changes = recompute_tiers(&mut results, offline);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].verifiable_id, results[1].entity.verifiable_id);
+        assert_eq!((changes[0].previous_tier, changes[0].tier), (3, 1));
+        assert_eq!(changes[0].confidence, 1.0);
+        assert_eq!(results[1].snfei_result.tier, 1);
+        assert_eq!(results[0].snfei_result.tier, 3);
+
+        // Running again finds nothing new.
+        assert!(recompute_tiers(&mut results, offline).is_empty());
+    }
 }
@@ -0,0 +1,264 @@
+//! Data completeness scoring per jurisdiction.
+//!
+//! [`Completeness`] tallies, for each jurisdiction, how many entities are
+//! known and how many of them have a Tier 1 or Tier 2 identifier, an address
+//! and a registration date. Compared against expected entity counts (for
+//! example from the Census of Governments), [`Completeness::report`] ranks
+//! jurisdictions from least to most complete, so data collection effort goes
+//! where the gaps are largest.
+//!
+//! Addresses and registration dates are not part of an entity record; they
+//! are read from the SNFEI [`CanonicalInput`] the record was built from.
+//! Records added without one count as having neither.
+
+use crate::builder::EntityBuildResult;
+use crate::entity::EntityRecord;
+use cep_core::{CepError, CepResult};
+use cep_snfei::CanonicalInput;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Read;
+
+/// Expected entity counts by jurisdiction.
+pub type ExpectedCounts = BTreeMap<String, u64>;
+
+/// Reads expected counts from CSV with `jurisdiction` and `expected` columns.
+///
+/// # Errors
+///
+/// Returns `CepError::Configuration` if the CSV is malformed.
+pub fn read_expected_counts<R: Read>(reader: R) -> CepResult<ExpectedCounts> {
+    #[derive(Deserialize)]
+    struct Row {
+        jurisdiction: String,
+        expected: u64,
+    }
+
+    let mut counts = ExpectedCounts::new();
+    for row in csv::Reader::from_reader(reader).deserialize() {
+        let row: Row = row.map_err(|e| {
+            CepError::Configuration(format!("Failed to read expected counts: {}", e))
+        })?;
+        *counts.entry(row.jurisdiction).or_default() += row.expected;
+    }
+    Ok(counts)
+}
+
+/// Raw counts for one jurisdiction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletenessTally {
+    /// Entities known.
+    pub entities: u64,
+    /// Entities with an LEI or SAM UEI.
+    pub tier1_or_2: u64,
+    /// Entities with an address.
+    pub with_address: u64,
+    /// Entities with a registration date.
+    pub with_registration_date: u64,
+}
+
+/// One jurisdiction's completeness, as reported by [`Completeness::report`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JurisdictionScore {
+    /// ISO 3166-2 jurisdiction code.
+    pub jurisdiction: String,
+    /// Expected entity count, if one was supplied.
+    pub expected: Option<u64>,
+    /// The jurisdiction's counts.
+    #[serde(flatten)]
+    pub tally: CompletenessTally,
+    /// Expected entities not yet known.
+    pub missing: u64,
+    /// Known entities as a fraction of expected ones, capped at 1.
+    pub coverage: f64,
+    /// Fraction of known entities with a Tier 1 or Tier 2 identifier.
+    pub identifier_rate: f64,
+    /// Fraction of known entities with an address.
+    pub address_rate: f64,
+    /// Fraction of known entities with a registration date.
+    pub registration_date_rate: f64,
+    /// `coverage` times the mean of the three rates, from 0 to 1.
+    pub score: f64,
+}
+
+/// Completeness tallies against expected counts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Completeness {
+    expected: ExpectedCounts,
+    tallies: BTreeMap<String, CompletenessTally>,
+}
+
+impl Completeness {
+    /// Creates an empty tally against `expected` counts.
+    pub fn new(expected: ExpectedCounts) -> Self {
+        Self {
+            expected,
+            tallies: BTreeMap::new(),
+        }
+    }
+
+    /// Counts an entity. `canonical` is the SNFEI input it was built from,
+    /// if known.
+    pub fn add(&mut self, entity: &EntityRecord, canonical: Option<&CanonicalInput>) {
+        let tally = self
+            .tallies
+            .entry(entity.jurisdiction_iso.clone())
+            .or_default();
+        tally.entities += 1;
+        if matches!(entity.identifiers.tier(), Some(1 | 2)) {
+            tally.tier1_or_2 += 1;
+        }
+        if canonical.is_some_and(|c| {
+            c.address_normalized
+                .as_deref()
+                .is_some_and(|a| !a.is_empty())
+        }) {
+            tally.with_address += 1;
+        }
+        if canonical.is_some_and(|c| {
+            c.registration_date
+                .as_deref()
+                .is_some_and(|d| !d.is_empty())
+        }) {
+            tally.with_registration_date += 1;
+        }
+    }
+
+    /// Counts a freshly built entity.
+    pub fn add_build_result(&mut self, result: &EntityBuildResult) {
+        self.add(&result.entity, Some(&result.snfei_result.canonical));
+    }
+
+    /// Returns the counts for `jurisdiction`.
+    pub fn tally(&self, jurisdiction: &str) -> CompletenessTally {
+        self.tallies.get(jurisdiction).copied().unwrap_or_default()
+    }
+
+    /// Scores every jurisdiction with an expected count or a known entity.
+    ///
+    /// The report is ordered by priority: lowest score first, ties broken by
+    /// the larger number of missing entities.
+    pub fn report(&self) -> Vec<JurisdictionScore> {
+        let mut jurisdictions: Vec<&String> =
+            self.expected.keys().chain(self.tallies.keys()).collect();
+        jurisdictions.sort();
+        jurisdictions.dedup();
+
+        let mut report: Vec<JurisdictionScore> =
+            jurisdictions.into_iter().map(|j| self.score(j)).collect();
+        report.sort_by(|a, b| {
+            a.score
+                .total_cmp(&b.score)
+                .then(b.missing.cmp(&a.missing))
+                .then(a.jurisdiction.cmp(&b.jurisdiction))
+        });
+        report
+    }
+
+    fn score(&self, jurisdiction: &str) -> JurisdictionScore {
+        let tally = self.tally(jurisdiction);
+        let expected = self.expected.get(jurisdiction).copied();
+        let coverage = match expected {
+            Some(0) | None => 1.0,
+            Some(n) => (tally.entities as f64 / n as f64).min(1.0),
+        };
+        let rate = |count: u64| {
+            if tally.entities == 0 {
+                0.0
+            } else {
+                count as f64 / tally.entities as f64
+            }
+        };
+        let identifier_rate = rate(tally.tier1_or_2);
+        let address_rate = rate(tally.with_address);
+        let registration_date_rate = rate(tally.with_registration_date);
+        JurisdictionScore {
+            jurisdiction: jurisdiction.to_string(),
+            expected,
+            tally,
+            missing: expected.unwrap_or(0).saturating_sub(tally.entities),
+            coverage,
+            identifier_rate,
+            address_rate,
+            registration_date_rate,
+            score: coverage * (identifier_rate + address_rate + registration_date_rate) / 3.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{AttestationInput, EntityBuilderInput, build_entity};
+    use crate::identifiers::SamUei;
+
+    fn build(name: &str, jurisdiction: &str, address: Option<&str>) -> EntityBuildResult {
+        build_entity(EntityBuilderInput {
+            source_id: None,
+            legal_name: name.to_string(),
+            entity_type: None,
+            jurisdiction: Some(jurisdiction.to_string()),
+            country_code: "US".to_string(),
+            address: address.map(str::to_string),
+            registration_date: address.map(|_| "1950-07-01".to_string()),
+            status: None,
+            attestation: AttestationInput {
+                attested_by: "did:web:example.gov".to_string(),
+                attestation_timestamp: "2025-11-28T14:30:00.000000Z".to_string(),
+                proof_type: None,
+                proof_value: None,
+                verification_method_uri: None,
+                proof_purpose: None,
+                anchor_uri: None,
+            },
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_scores_and_prioritizes_gaps() {
+        let expected =
+            read_expected_counts("jurisdiction,expected\nUS-TX,4\nUS-OH,2\nUS-VT,3\n".as_bytes())
+                .unwrap();
+        let mut completeness = Completeness::new(expected);
+
+        // US-OH: fully covered, complete inputs, one with a SAM UEI.
+        let mut with_uei = build("Ohio Water Authority", "US-OH", Some("1 Main St"));
+        with_uei.entity.identifiers = with_uei
+            .entity
+            .identifiers
+            .clone()
+            .with_sam_uei(SamUei::new("J6H4FB3N5YK7").unwrap());
+        completeness.add_build_result(&with_uei);
+        completeness.add_build_result(&build("Cleveland Parks", "US-OH", Some("2 Main St")));
+
+        // US-TX: half covered, no addresses.
+        completeness.add_build_result(&build("Austin ISD", "US-TX", None));
+        completeness.add_build_result(&build("Dallas ISD", "US-TX", None));
+
+        let report = completeness.report();
+        let order: Vec<&str> = report.iter().map(|s| s.jurisdiction.as_str()).collect();
+        assert_eq!(order, ["US-VT", "US-TX", "US-OH"]);
+
+        assert_eq!(report[0].missing, 3);
+        assert_eq!(report[0].score, 0.0);
+        assert_eq!(report[1].coverage, 0.5);
+        assert_eq!(report[1].address_rate, 0.0);
+        let ohio = &report[2];
+        assert_eq!(ohio.coverage, 1.0);
+        assert_eq!(ohio.identifier_rate, 0.5);
+        assert!((ohio.score - 2.5 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_records_without_canonical_input() {
+        let mut completeness = Completeness::new(ExpectedCounts::new());
+        completeness.add(&build("Acme", "US-CA", Some("1 Main St")).entity, None);
+        let tally = completeness.tally("US-CA");
+        assert_eq!(tally.entities, 1);
+        assert_eq!(tally.with_address, 0);
+        assert_eq!(completeness.report()[0].expected, None);
+    }
+}
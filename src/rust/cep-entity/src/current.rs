@@ -0,0 +1,372 @@
+//! Current-state entity tables.
+//!
+//! Consumers who want "all active school districts in US-TX" should not have
+//! to walk revision chains. [`CurrentEntities`] folds any mix of revisions
+//! into one row per entity, holding the head (highest) revision and its
+//! canonical hash. [`CurrentEntities::write_csv`] exports it as a flat table
+//! holding the rows the recipient's [`AccessScopes`] permit, optionally
+//! narrowed by an [`EntityQuery`].
+//!
+//! Every row carries `headHash`, the hash of the revision it was generated
+//! from, so a row can be traced back to, and verified against, its chain.
+//!
+//! ```rust,no_run
+//! use cep_core::AccessScopes;
+//! use cep_entity::current::CurrentEntities;
+//! use cep_entity::query::EntityQuery;
+//! use cep_entity::EntityStatusCode;
+//! # let revisions: Vec<cep_entity::EntityRecord> = Vec::new();
+//!
+//! let current = CurrentEntities::build(revisions).unwrap();
+//! let query = EntityQuery::new()
+//!     .jurisdiction("US-TX")
+//!     .status(EntityStatusCode::Active)
+//!     .name_contains("independent school district");
+//! current
+//!     .write_csv(std::io::stdout(), &AccessScopes::public(), Some(&query))
+//!     .unwrap();
+//! ```
+
+use crate::entity::EntityRecord;
+use crate::query::EntityQuery;
+use cep_core::{
+    AccessScopes, CanonicalHash, Canonicalize, CepError, CepResult, Classification, Classified,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// The head revision of one entity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrentEntity {
+    /// The latest revision.
+    pub record: EntityRecord,
+    /// Canonical hash of `record`.
+    pub head_hash: CanonicalHash,
+}
+
+/// One row per entity, keyed by verifiable ID.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CurrentEntities {
+    rows: BTreeMap<String, CurrentEntity>,
+}
+
+/// CSV columns, in [`CsvRow`] order.
+const CSV_HEADER: [&str; 12] = [
+    "verifiableId",
+    "legalName",
+    "jurisdictionIso",
+    "entityTypeUri",
+    "statusCode",
+    "statusEffectiveDate",
+    "samUei",
+    "lei",
+    "snfei",
+    "revisionNumber",
+    "attestedAt",
+    "headHash",
+];
+
+/// CSV row layout.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CsvRow<'a> {
+    verifiable_id: &'a str,
+    legal_name: &'a str,
+    jurisdiction_iso: &'a str,
+    entity_type_uri: &'a str,
+    status_code: &'a str,
+    status_effective_date: &'a str,
+    sam_uei: &'a str,
+    lei: &'a str,
+    snfei: &'a str,
+    revision_number: u32,
+    attested_at: String,
+    head_hash: &'a str,
+}
+
+impl CurrentEntities {
+    /// Folds revisions, in any order, into their heads.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::RevisionChain` if two different records claim the
+    /// same revision number of one entity.
+    pub fn build<I>(revisions: I) -> CepResult<Self>
+    where
+        I: IntoIterator<Item = EntityRecord>,
+    {
+        let mut current = Self::default();
+        for record in revisions {
+            current.apply(record)?;
+        }
+        Ok(current)
+    }
+
+    /// Applies one revision, replacing the entity's row if it is newer.
+    ///
+    /// # Errors
+    ///
+    /// See [`build`](Self::build).
+    pub fn apply(&mut self, record: EntityRecord) -> CepResult<()> {
+        let head_hash = record.calculate_hash();
+        match self.rows.get(&record.verifiable_id) {
+            Some(row) if row.record.revision_number > record.revision_number => Ok(()),
+            Some(row) if row.record.revision_number == record.revision_number => {
+                if row.head_hash == head_hash {
+                    Ok(())
+                } else {
+                    Err(CepError::RevisionChain(format!(
+                        "{}: two different records claim revision {}",
+                        record.verifiable_id, record.revision_number
+                    )))
+                }
+            }
+            _ => {
+                self.rows.insert(
+                    record.verifiable_id.clone(),
+                    CurrentEntity { record, head_hash },
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Number of entities.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns true if there are no entities.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the current row for `verifiable_id`.
+    pub fn get(&self, verifiable_id: &str) -> Option<&CurrentEntity> {
+        self.rows.get(verifiable_id)
+    }
+
+    /// Iterates over rows in verifiable ID order.
+    pub fn iter(&self) -> impl Iterator<Item = &CurrentEntity> {
+        self.rows.values()
+    }
+
+    /// Returns the rows whose head revision matches `query`.
+    pub fn select(&self, query: &EntityQuery) -> Vec<&CurrentEntity> {
+        self.iter()
+            .filter(|row| query.matches(&row.record))
+            .take(query.max_results().unwrap_or(usize::MAX))
+            .collect()
+    }
+
+    /// Writes the rows `scopes` permit (and that match `query`, if given) as
+    /// CSV with a header row, which is written even when no row is. Returns
+    /// the number of rows written.
+    pub fn write_csv<W: Write>(
+        &self,
+        writer: W,
+        scopes: &AccessScopes,
+        query: Option<&EntityQuery>,
+    ) -> CepResult<usize> {
+        let rows: Vec<&CurrentEntity> = scopes
+            .filter(self.iter())
+            .into_iter()
+            .filter(|row| query.is_none_or(|query| query.matches(&row.record)))
+            .take(
+                query
+                    .and_then(EntityQuery::max_results)
+                    .unwrap_or(usize::MAX),
+            )
+            .collect();
+        let mut csv = csv::WriterBuilder::new()
+            .has_headers(false)
+            .from_writer(writer);
+        csv.write_record(CSV_HEADER).map_err(csv_error)?;
+        for row in &rows {
+            let record = &row.record;
+            let ids = &record.identifiers;
+            csv.serialize(CsvRow {
+                verifiable_id: &record.verifiable_id,
+                legal_name: &record.legal_name,
+                jurisdiction_iso: &record.jurisdiction_iso,
+                entity_type_uri: record.entity_type_uri.as_deref().unwrap_or_default(),
+                status_code: record.status.status_code.as_str(),
+                status_effective_date: &record.status.status_effective_date,
+                sam_uei: ids
+                    .sam_uei
+                    .as_ref()
+                    .map(|id| id.as_str())
+                    .unwrap_or_default(),
+                lei: ids.lei.as_ref().map(|id| id.as_str()).unwrap_or_default(),
+                snfei: ids
+                    .snfei
+                    .as_ref()
+                    .map(|id| id.value.as_str())
+                    .unwrap_or_default(),
+                revision_number: record.revision_number,
+                attested_at: record
+                    .attestation
+                    .attestation_timestamp
+                    .to_canonical_string(),
+                head_hash: row.head_hash.as_hex(),
+            })
+            .map_err(csv_error)?;
+        }
+        csv.flush()
+            .map_err(|e| CepError::Configuration(format!("Failed to write CSV: {}", e)))?;
+        Ok(rows.len())
+    }
+}
+
+impl Classified for CurrentEntity {
+    fn classification(&self) -> Classification {
+        self.record.classification()
+    }
+}
+
+fn csv_error(error: csv::Error) -> CepError {
+    CepError::Configuration(format!("Failed to write CSV: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{EntityStatus, EntityStatusCode};
+    use crate::identifiers::{EntityIdentifiers, SamUei};
+    use cep_core::{Attestation, ProofType};
+
+    fn revision(id: &str, name: &str, revision: u32, status: EntityStatusCode) -> EntityRecord {
+        EntityRecord::new(
+            id.to_string(),
+            EntityIdentifiers::new().with_sam_uei(SamUei::new("J6H4FB3N5YK7").unwrap()),
+            name.to_string(),
+            "US-TX".to_string(),
+            EntityStatus {
+                status_code: status,
+                status_effective_date: "2020-01-15".to_string(),
+                status_termination_date: None,
+                successor_entity_id: None,
+            },
+            Attestation::new(
+                "did:web:tea.texas.example.gov".to_string(),
+                "2025-11-28T14:30:00.000000Z".parse().unwrap(),
+                ProofType::Ed25519Signature2020,
+                "z3FXQq...".to_string(),
+                "did:web:tea.texas.example.gov#key-1".to_string(),
+            ),
+        )
+        .with_revision(revision)
+    }
+
+    fn revisions() -> Vec<EntityRecord> {
+        vec![
+            revision(
+                "cep-entity:test:a",
+                "Austin ISD",
+                2,
+                EntityStatusCode::Active,
+            ),
+            revision(
+                "cep-entity:test:a",
+                "Austin ISD (old)",
+                1,
+                EntityStatusCode::Active,
+            ),
+            revision(
+                "cep-entity:test:b",
+                "Dallas ISD",
+                1,
+                EntityStatusCode::Active,
+            ),
+            revision(
+                "cep-entity:test:b",
+                "Dallas ISD",
+                2,
+                EntityStatusCode::Merged,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_folds_to_heads_in_any_order() {
+        let current = CurrentEntities::build(revisions()).unwrap();
+        assert_eq!(current.len(), 2);
+        let austin = current.get("cep-entity:test:a").unwrap();
+        assert_eq!(austin.record.legal_name, "Austin ISD");
+        assert_eq!(austin.head_hash, austin.record.calculate_hash());
+        assert_eq!(
+            current
+                .get("cep-entity:test:b")
+                .unwrap()
+                .record
+                .status
+                .status_code,
+            EntityStatusCode::Merged
+        );
+    }
+
+    #[test]
+    fn test_conflicting_revision_is_rejected() {
+        let mut conflicting = revisions();
+        conflicting.push(revision(
+            "cep-entity:test:a",
+            "Austin Independent School District",
+            2,
+            EntityStatusCode::Active,
+        ));
+        assert!(matches!(
+            CurrentEntities::build(conflicting),
+            Err(CepError::RevisionChain(_))
+        ));
+    }
+
+    #[test]
+    fn test_write_csv_with_query() {
+        let current = CurrentEntities::build(revisions()).unwrap();
+        let query = EntityQuery::new().status(EntityStatusCode::Active);
+        let mut out = Vec::new();
+        assert_eq!(
+            current
+                .write_csv(&mut out, &AccessScopes::public(), Some(&query))
+                .unwrap(),
+            1
+        );
+
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "verifiableId,legalName,jurisdictionIso,entityTypeUri,statusCode,statusEffectiveDate,samUei,lei,snfei,revisionNumber,attestedAt,headHash"
+        );
+        let head = current.get("cep-entity:test:a").unwrap();
+        assert!(lines[1].starts_with("cep-entity:test:a,Austin ISD,US-TX,,ACTIVE,"));
+        assert!(lines[1].ends_with(head.head_hash.as_hex()));
+    }
+
+    #[test]
+    fn test_write_csv_leaves_out_unpermitted_rows() {
+        let mut records = revisions();
+        records[0] = records[0]
+            .clone()
+            .with_classification(Classification::Internal);
+        let current = CurrentEntities::build(records).unwrap();
+        let write = |scopes: &AccessScopes, query: Option<&EntityQuery>| {
+            let mut out = Vec::new();
+            let count = current.write_csv(&mut out, scopes, query).unwrap();
+            (count, String::from_utf8(out).unwrap())
+        };
+
+        let (count, csv) = write(&AccessScopes::public(), None);
+        assert_eq!(count, 1);
+        assert!(!csv.contains("Austin ISD"));
+        let (count, _) = write(&AccessScopes::new(["cep:read:internal"]), None);
+        assert_eq!(count, 2);
+
+        // An empty selection still has its header row.
+        let query = EntityQuery::new().status(EntityStatusCode::Active);
+        let (count, csv) = write(&AccessScopes::public(), Some(&query));
+        assert_eq!(count, 0);
+        assert_eq!(csv.lines().collect::<Vec<_>>(), [CSV_HEADER.join(",")]);
+    }
+}
@@ -1,13 +1,19 @@
-/// CEP Entity Record definition.
-///
-/// The Entity Record is the foundational primitive in CEP. It represents a
-/// verified civic entity (government agency, contractor, nonprofit, individual).
-/// All relationships and exchanges reference attested entities.
+//! CEP Entity Record definition.
+//!
+//! The Entity Record is the foundational primitive in CEP. It represents a
+//! verified civic entity (government agency, contractor, nonprofit, individual).
+//! All relationships and exchanges reference attested entities.
 
 use crate::identifiers::EntityIdentifiers;
-use cep_core::canonical::{insert_if_present, insert_required, Canonicalize};
+use cep_core::canonical::{
+    insert_if_present, insert_number, insert_required, Canonicalize, HashDomain,
+};
 use cep_core::hash::CanonicalHash;
-use cep_core::{Attestation, SCHEMA_VERSION};
+use cep_core::spec::{CanonicalSpec, FieldEncoding, TypeSpec};
+use cep_core::{
+    validate_authority, Attestation, Authority, CanonicalTimestamp, Classification, Classified,
+    Freshness, Revisioned, SCHEMA_VERSION,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -57,6 +63,16 @@ impl Canonicalize for EntityStatus {
     }
 }
 
+impl CanonicalSpec for EntityStatus {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("EntityStatus")
+            .required("statusCode", FieldEncoding::String)
+            .required("statusEffectiveDate", FieldEncoding::String)
+            .optional("statusTerminationDate", FieldEncoding::String)
+            .optional("successorEntityId", FieldEncoding::String)
+    }
+}
+
 /// Entity resolution confidence metadata.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -78,12 +94,21 @@ impl Canonicalize for ResolutionConfidence {
         // Score formatted to 2 decimal places for consistency
         insert_required(&mut map, "score", &format!("{:.2}", self.score));
         if let Some(count) = self.source_record_count {
-            insert_required(&mut map, "sourceRecordCount", &count.to_string());
+            insert_number(&mut map, "sourceRecordCount", count);
         }
         map
     }
 }
 
+impl CanonicalSpec for ResolutionConfidence {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("ResolutionConfidence")
+            .optional("methodUri", FieldEncoding::String)
+            .required("score", FieldEncoding::Decimal { places: 2 })
+            .optional("sourceRecordCount", FieldEncoding::Integer)
+    }
+}
+
 /// A complete CEP Entity Record.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -131,6 +156,26 @@ pub struct EntityRecord {
 
     /// Monotonically increasing revision number.
     pub revision_number: u32,
+
+    /// Hash of the schema snapshot the record was validated against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_snapshot_hash: Option<CanonicalHash>,
+
+    /// Version of the vocabularies the record was validated against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vocab_version: Option<String>,
+
+    /// Access classification; absent means public.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub classification: Option<Classification>,
+
+    /// Statutory authority or agreement the record is shared under.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authority: Option<Authority>,
+
+    /// Publisher-declared refresh and expiry dates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freshness: Option<Freshness>,
 }
 
 impl EntityRecord {
@@ -157,6 +202,11 @@ impl EntityRecord {
             attestation,
             previous_record_hash: None,
             revision_number: 1,
+            schema_snapshot_hash: None,
+            vocab_version: None,
+            classification: None,
+            authority: None,
+            freshness: None,
         }
     }
 
@@ -196,6 +246,36 @@ impl EntityRecord {
         self
     }
 
+    /// Sets the schema snapshot hash the record was validated against.
+    pub fn with_schema_snapshot(mut self, hash: CanonicalHash) -> Self {
+        self.schema_snapshot_hash = Some(hash);
+        self
+    }
+
+    /// Sets the vocabulary version the record was validated against.
+    pub fn with_vocab_version(mut self, version: String) -> Self {
+        self.vocab_version = Some(version);
+        self
+    }
+
+    /// Sets the access classification.
+    pub fn with_classification(mut self, classification: Classification) -> Self {
+        self.classification = Some(classification);
+        self
+    }
+
+    /// Sets the authority the record is shared under.
+    pub fn with_authority(mut self, authority: Authority) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Sets the publisher-declared freshness dates.
+    pub fn with_freshness(mut self, freshness: Freshness) -> Self {
+        self.freshness = Some(freshness);
+        self
+    }
+
     /// Validates that the record has all required fields properly set.
     pub fn validate(&self) -> Result<(), String> {
         if self.schema_version != SCHEMA_VERSION {
@@ -216,9 +296,14 @@ impl EntityRecord {
         if self.jurisdiction_iso.is_empty() {
             return Err("jurisdictionIso is required".to_string());
         }
+        self.identifiers
+            .check_jurisdiction(&self.jurisdiction_iso)
+            .map_err(|e| e.to_string())?;
         if self.revision_number < 1 {
             return Err("revisionNumber must be >= 1".to_string());
         }
+        validate_authority(Classified::classification(self), self.authority.as_ref())
+            .map_err(|e| e.to_string())?;
         Ok(())
     }
 }
@@ -232,8 +317,15 @@ impl Canonicalize for EntityRecord {
         // Attestation is a nested object - serialize its canonical form
         let attestation_canonical = self.attestation.to_canonical_string();
         insert_required(&mut map, "attestation", &attestation_canonical);
+        if let Some(ref authority) = self.authority {
+            insert_required(&mut map, "authority", &authority.to_canonical_string());
+        }
+        insert_if_present(&mut map, "classification", self.classification.map(|c| c.as_str()));
 
         insert_if_present(&mut map, "entityTypeUri", self.entity_type_uri.as_deref());
+        if let Some(ref freshness) = self.freshness {
+            insert_required(&mut map, "freshness", &freshness.to_canonical_string());
+        }
 
         // Identifiers is a nested object
         let identifiers_canonical = self.identifiers.to_canonical_string();
@@ -256,7 +348,10 @@ impl Canonicalize for EntityRecord {
             insert_required(&mut map, "resolutionConfidence", &confidence_canonical);
         }
 
-        insert_required(&mut map, "revisionNumber", &self.revision_number.to_string());
+        insert_number(&mut map, "revisionNumber", self.revision_number);
+        if let Some(ref hash) = self.schema_snapshot_hash {
+            insert_required(&mut map, "schemaSnapshotHash", hash.as_hex());
+        }
         insert_required(&mut map, "schemaVersion", &self.schema_version);
 
         // Status is a nested object
@@ -264,21 +359,84 @@ impl Canonicalize for EntityRecord {
         insert_required(&mut map, "status", &status_canonical);
 
         insert_required(&mut map, "verifiableId", &self.verifiable_id);
+        insert_if_present(&mut map, "vocabVersion", self.vocab_version.as_deref());
 
         map
     }
+
+    fn hash_domain(&self) -> Option<HashDomain> {
+        Some(HashDomain::new("cep.entity", &self.schema_version))
+    }
+}
+
+impl Classified for EntityRecord {
+    fn classification(&self) -> Classification {
+        self.classification.unwrap_or_default()
+    }
+}
+
+impl Revisioned for EntityRecord {
+    fn verifiable_id(&self) -> &str {
+        &self.verifiable_id
+    }
+
+    fn revision_number(&self) -> u32 {
+        self.revision_number
+    }
+
+    fn previous_record_hash(&self) -> Option<&CanonicalHash> {
+        self.previous_record_hash.as_ref()
+    }
+
+    /// Dissolved and merged entities are revoked as of the start (UTC) of
+    /// their status effective date.
+    fn revoked_at(&self) -> Option<CanonicalTimestamp> {
+        match self.status.status_code {
+            EntityStatusCode::Dissolved | EntityStatusCode::Merged => {
+                format!("{}T00:00:00.000000Z", self.status.status_effective_date)
+                    .parse()
+                    .ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl CanonicalSpec for EntityRecord {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("EntityRecord")
+            .required("attestation", FieldEncoding::nested("Attestation"))
+            .optional("authority", FieldEncoding::nested("Authority"))
+            .optional("classification", FieldEncoding::String)
+            .optional("entityTypeUri", FieldEncoding::String)
+            .optional("freshness", FieldEncoding::nested("Freshness"))
+            .optional("identifiers", FieldEncoding::nested("EntityIdentifiers"))
+            .required("jurisdictionIso", FieldEncoding::String)
+            .required("legalName", FieldEncoding::String)
+            .optional("legalNameNormalized", FieldEncoding::String)
+            .optional("naicsCode", FieldEncoding::String)
+            .optional("previousRecordHash", FieldEncoding::Hash)
+            .optional("resolutionConfidence", FieldEncoding::nested("ResolutionConfidence"))
+            .required("revisionNumber", FieldEncoding::Integer)
+            .optional("schemaSnapshotHash", FieldEncoding::Hash)
+            .required("schemaVersion", FieldEncoding::String)
+            .required("status", FieldEncoding::nested("EntityStatus"))
+            .required("verifiableId", FieldEncoding::String)
+            .optional("vocabVersion", FieldEncoding::String)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use cep_core::{verify_chain, ChainLink, HashMode, ProofType};
     use crate::identifiers::SamUei;
 
     fn test_attestation() -> Attestation {
         Attestation::new(
             "cep-entity:sam-uei:ATTESTOR123A".to_string(),
             "2025-11-28T14:30:00.000000Z".parse().unwrap(),
-            "Ed25519Signature2020".to_string(),
+            ProofType::Ed25519Signature2020,
             "z3FXQqFwbZxKBxGxqFpCDabcdef1234567890".to_string(),
             "did:web:example.gov#key-1".to_string(),
         )
@@ -353,6 +511,18 @@ mod tests {
         assert!(entity.validate().is_err());
     }
 
+    #[test]
+    fn test_validation_cross_checks_fips_jurisdiction() {
+        let mut entity = test_entity();
+        entity.identifiers = entity
+            .identifiers
+            .with_fips_code(crate::identifiers::FipsCode::parse("0644000").unwrap());
+        assert!(entity.validate().is_ok());
+
+        entity.jurisdiction_iso = "US-NV".to_string();
+        assert!(entity.validate().unwrap_err().contains("is in CA"));
+    }
+
     #[test]
     fn test_hash_changes_with_content() {
         let e1 = test_entity();
@@ -361,6 +531,19 @@ mod tests {
         assert_ne!(e1.calculate_hash(), e2.calculate_hash());
     }
 
+    #[test]
+    fn test_domain_separated_hash_keeps_plain_hashes_verifiable() {
+        let entity = test_entity();
+        let plain = entity.calculate_hash();
+        let separated = entity.calculate_hash_with(HashMode::DomainSeparated);
+        assert_ne!(plain, separated);
+        assert_eq!(entity.hash_mode_of(&plain), Some(HashMode::Plain));
+        assert_eq!(
+            entity.hash_mode_of(&separated),
+            Some(HashMode::DomainSeparated)
+        );
+    }
+
     #[test]
     fn test_revision_chain() {
         let e1 = test_entity();
@@ -375,12 +558,87 @@ mod tests {
         assert_eq!(e2.revision_number, 2);
     }
 
+    #[test]
+    fn test_dissolved_entity_chain_verifies_and_is_revoked() {
+        let e1 = test_entity();
+        let mut e2 = test_entity()
+            .with_previous_hash(e1.calculate_hash())
+            .with_revision(2);
+        e2.status.status_code = EntityStatusCode::Dissolved;
+        e2.status.status_effective_date = "2024-06-30".to_string();
+
+        assert!(e1.revoked_at().is_none());
+        assert_eq!(
+            e2.revoked_at(),
+            Some("2024-06-30T00:00:00.000000Z".parse().unwrap())
+        );
+
+        let links = vec![ChainLink::Record(e1).into_stub(), ChainLink::Record(e2)];
+        assert!(verify_chain(&links).is_ok());
+    }
+
     // ========================================
     // TEST VECTOR OUTPUT
     // ========================================
     // This test outputs the canonical string and hash that other
     // implementations (Python, Java, C#, etc.) MUST match.
 
+    #[test]
+    fn test_snapshot_pin_is_hashed_only_when_present() {
+        let unpinned = test_entity();
+        assert!(!unpinned.to_canonical_string().contains("vocabVersion"));
+
+        let snapshot = CanonicalHash::from_canonical_string("snapshot");
+        let pinned = test_entity()
+            .with_schema_snapshot(snapshot.clone())
+            .with_vocab_version("1.0.0".to_string());
+        let fields = pinned.canonical_fields();
+        assert_eq!(fields["schemaSnapshotHash"], snapshot.as_hex());
+        assert_eq!(fields["vocabVersion"], "1.0.0");
+        assert_ne!(pinned.calculate_hash(), unpinned.calculate_hash());
+    }
+
+    #[test]
+    fn test_classification_is_hashed_only_when_present() {
+        let unclassified = test_entity();
+        assert!(!unclassified.to_canonical_string().contains("classification"));
+        assert_eq!(Classified::classification(&unclassified), Classification::Public);
+
+        let restricted = test_entity().with_classification(Classification::Restricted);
+        assert_eq!(restricted.canonical_fields()["classification"], "restricted");
+        assert_ne!(restricted.calculate_hash(), unclassified.calculate_hash());
+
+        let json = serde_json::to_string(&restricted).unwrap();
+        assert!(json.contains(r#""classification":"restricted""#));
+        let parsed: EntityRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.classification, Some(Classification::Restricted));
+    }
+
+    #[test]
+    fn test_restricted_record_requires_authority() {
+        let restricted = test_entity().with_classification(Classification::Restricted);
+        assert!(restricted.validate().unwrap_err().contains("authority"));
+
+        let cited = restricted.with_authority(
+            Authority::new("https://example.gov/data-sharing/2025-014".to_string())
+                .with_agreement("DSA-2025-014".to_string()),
+        );
+        assert!(cited.validate().is_ok());
+        assert!(cited.canonical_fields()["authority"].contains(r#""agreementId":"DSA-2025-014""#));
+    }
+
+    #[test]
+    fn test_canonical_spec_matches_fields() {
+        let spec = EntityRecord::type_spec();
+        let keys: Vec<String> = test_entity().canonical_fields().into_keys().collect();
+        for required in spec.required_field_names() {
+            assert!(keys.iter().any(|k| k == required), "missing required field {}", required);
+        }
+        for key in &keys {
+            assert!(spec.field_names().contains(&key.as_str()), "unlisted field {}", key);
+        }
+    }
+
     #[test]
     fn test_vector_basic_entity() {
         let entity = test_entity();
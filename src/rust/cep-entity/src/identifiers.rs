@@ -1,52 +1,132 @@
-/// Entity identifier types for CEP.
-///
-/// CEP supports multiple identifier schemes organized into tiers:
-///
-/// - **Tier 1 (Global)**: LEI (Legal Entity Identifier)
-/// - **Tier 2 (Federal)**: SAM.gov UEI
-/// - **Tier 3 (Sub-National)**: SNFEI (generated hash-based identifier)
-/// - **Extended**: Canadian BN, UK Companies House, etc.
-///
-/// # SNFEI Generation
-///
-/// For full SNFEI generation with normalization and localization, use the
-/// `cep_snfei` crate directly:
-///
-/// ```rust
-/// use cep_snfei::{generate_snfei, apply_localization};
-///
-/// let result = generate_snfei(
-///     "Springfield USD #12",
-///     "US",
-///     Some("123 Main St"),
-///     None,
-/// );
-/// let snfei = result.snfei;
-/// ```
+//! Entity identifier types for CEP.
+//!
+//! CEP supports multiple identifier schemes organized into tiers:
+//!
+//! - **Tier 1 (Global)**: LEI (Legal Entity Identifier)
+//! - **Tier 2 (Federal)**: SAM.gov UEI
+//! - **Tier 3 (Sub-National)**: SNFEI (generated hash-based identifier)
+//! - **Extended**: Canadian BN, UK Companies House, etc.
+//!
+//! NCES LEA IDs, Census GEOIDs, FIPS codes and GNIS feature IDs are carried
+//! in `additionalSchemes` under [`NCES_LEA_SCHEME_URI`],
+//! [`CENSUS_GEOID_SCHEME_URI`], [`FIPS_SCHEME_URI`] and [`GNIS_SCHEME_URI`],
+//! but are typed and validated like the named identifiers and count towards
+//! [`EntityIdentifiers::confidence_score`]. Those that start with a state
+//! FIPS code are cross-checked against the record's jurisdiction by
+//! [`EntityIdentifiers::check_jurisdiction`].
+//!
+//! An [`Ein`] is recorded according to an [`EinPolicy`]: either as itself
+//! under [`EIN_SCHEME_URI`], where it takes part in the canonical form, or
+//! only as a salted hash under [`EIN_LOOKUP_SCHEME_URI`], which lets holders
+//! of the salt match records by EIN without publishing it.
+//!
+//! # SNFEI Generation
+//!
+//! For full SNFEI generation with normalization and localization, use the
+//! `cep_snfei` crate directly:
+//!
+//! ```rust
+//! use cep_snfei::{generate_snfei, apply_localization};
+//!
+//! let result = generate_snfei(
+//!     "Springfield USD #12",
+//!     "US",
+//!     Some("123 Main St"),
+//!     None,
+//! );
+//! let snfei = result.snfei;
+//! ```
+//!
+//! There is a single [`Snfei`] type, defined in `cep_snfei` and re-exported
+//! here. Its one algorithm is the one in the SNFEI specification:
+//! `SHA256(legal_name_normalized|address_normalized|country_code|registration_date)`
+//! over the normalized canonical input, as 64 lowercase hex characters.
 use cep_core::canonical::{Canonicalize, insert_if_present};
+use cep_core::spec::{CanonicalSpec, FieldEncoding, TypeSpec};
+use cep_core::{CanonicalHash, CepError, CepResult};
+use cep_snfei::{SnfeiResult, TierIdentifiers};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
 
-// Re-export Snfei from cep-snfei for convenience
+// Re-export Snfei from cep-snfei so entity records and generation share one type.
 // Users can access full generation via cep_snfei::{generate_snfei, normalize_legal_name, ...}
 pub use cep_snfei::Snfei;
 
+/// Implements the string conversions shared by the validated identifier
+/// newtypes, all in terms of the type's `parse` function.
+macro_rules! identifier_conversions {
+    ($name:ident) => {
+        impl FromStr for $name {
+            type Err = CepError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::parse(s)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = CepError;
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                Self::parse(value)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = CepError;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                Self::parse(&value)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+    };
+}
+
 /// SAM.gov Unique Entity Identifier (12 alphanumeric characters).
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String")]
 pub struct SamUei(String);
 
 impl SamUei {
     /// Creates a new SAM UEI, validating the format.
     pub fn new(value: &str) -> Option<Self> {
-        if value.len() == 12
-            && value
-                .chars()
-                .all(|c| c.is_ascii_alphanumeric() && c.is_ascii_uppercase() || c.is_ascii_digit())
+        Self::parse(value).ok()
+    }
+
+    /// Parses a SAM UEI: 12 uppercase letters or digits.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidIdentifier` describing what is wrong.
+    pub fn parse(value: &str) -> CepResult<Self> {
+        if value.len() != 12 {
+            return Err(invalid("SAM UEI", value, "must be 12 characters"));
+        }
+        if !value
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
         {
-            Some(Self(value.to_string()))
-        } else {
-            None
+            return Err(invalid(
+                "SAM UEI",
+                value,
+                "must contain only uppercase letters and digits",
+            ));
         }
+        Ok(Self(value.to_string()))
     }
 
     pub fn as_str(&self) -> &str {
@@ -56,16 +136,32 @@ impl SamUei {
 
 /// Legal Entity Identifier per ISO 17442 (20 alphanumeric characters).
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String")]
 pub struct Lei(String);
 
 impl Lei {
     /// Creates a new LEI, validating the format.
     pub fn new(value: &str) -> Option<Self> {
-        if value.len() == 20 && value.chars().all(|c| c.is_ascii_alphanumeric()) {
-            Some(Self(value.to_uppercase()))
-        } else {
-            None
+        Self::parse(value).ok()
+    }
+
+    /// Parses an LEI: 20 letters or digits, stored uppercase.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidIdentifier` describing what is wrong.
+    pub fn parse(value: &str) -> CepResult<Self> {
+        if value.len() != 20 {
+            return Err(invalid("LEI", value, "must be 20 characters"));
         }
+        if !value.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(invalid(
+                "LEI",
+                value,
+                "must contain only letters and digits",
+            ));
+        }
+        Ok(Self(value.to_uppercase()))
     }
 
     pub fn as_str(&self) -> &str {
@@ -75,33 +171,471 @@ impl Lei {
 
 /// Canadian Business Number with program account.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String")]
 pub struct CanadianBn(String);
 
 impl CanadianBn {
     /// Creates a new Canadian BN (format: 123456789RC0001).
     pub fn new(value: &str) -> Option<Self> {
-        // Pattern: 9 digits + 2 letters + 4 digits
-        if value.len() == 15 {
-            let (digits1, rest) = value.split_at(9);
-            let (letters, digits2) = rest.split_at(2);
-            if digits1.chars().all(|c| c.is_ascii_digit())
-                && letters.chars().all(|c| c.is_ascii_uppercase())
-                && digits2.chars().all(|c| c.is_ascii_digit())
-            {
-                return Some(Self(value.to_string()));
-            }
+        Self::parse(value).ok()
+    }
+
+    /// Parses a Canadian BN: 9 digits, 2 uppercase letters, 4 digits.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidIdentifier` describing what is wrong.
+    pub fn parse(value: &str) -> CepResult<Self> {
+        // Checking for ASCII first keeps the byte splits on char boundaries.
+        if value.len() != 15 || !value.is_ascii() {
+            return Err(invalid("Canadian BN", value, "must be 15 ASCII characters"));
         }
-        None
+        let (digits1, rest) = value.split_at(9);
+        let (letters, digits2) = rest.split_at(2);
+        if !(digits1.chars().all(|c| c.is_ascii_digit())
+            && letters.chars().all(|c| c.is_ascii_uppercase())
+            && digits2.chars().all(|c| c.is_ascii_digit()))
+        {
+            return Err(invalid(
+                "Canadian BN",
+                value,
+                "must be 9 digits, 2 uppercase letters and 4 digits",
+            ));
+        }
+        Ok(Self(value.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The two-letter program identifier, e.g. `RC` (corporate income tax)
+    /// or `RR` (registered charity).
+    pub fn program(&self) -> &str {
+        &self.0[9..11]
+    }
+
+    /// Returns true for a CRA charity registration number (program `RR`).
+    pub fn is_registered_charity(&self) -> bool {
+        self.program() == "RR"
+    }
+}
+
+/// Scheme URI for Canadian Business Numbers.
+pub const CANADIAN_BN_SCHEME_URI: &str = "https://specs.civic-interconnect.org/schemes/canadian-bn";
+
+/// Scheme URI for CRA charity registration numbers: Canadian BNs with the
+/// `RR` program identifier.
+pub const CRA_CHARITY_SCHEME_URI: &str = "https://specs.civic-interconnect.org/schemes/cra-charity";
+
+/// Scheme URI for NCES Local Education Agency IDs.
+pub const NCES_LEA_SCHEME_URI: &str = "https://specs.civic-interconnect.org/schemes/nces-lea-id";
+
+/// Scheme URI for U.S. Census Bureau GEOIDs.
+pub const CENSUS_GEOID_SCHEME_URI: &str =
+    "https://specs.civic-interconnect.org/schemes/census-geoid";
+
+/// NCES Local Education Agency ID (7 digits: state FIPS code + 5 digits).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String")]
+pub struct NcesLeaId(String);
+
+impl NcesLeaId {
+    /// Creates a new NCES LEA ID, validating the format.
+    pub fn new(value: &str) -> Option<Self> {
+        Self::parse(value).ok()
+    }
+
+    /// Parses an NCES LEA ID: 7 digits starting with a state FIPS code.
+    ///
+    /// NCES also assigns 58 (DoD schools) and 59 (Bureau of Indian
+    /// Education) as state codes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidIdentifier` describing what is wrong.
+    pub fn parse(value: &str) -> CepResult<Self> {
+        if value.len() != 7 || !value.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid("NCES LEA ID", value, "must be 7 digits"));
+        }
+        let state = &value[..2];
+        if !(is_state_fips(state) || state == "58" || state == "59") {
+            return Err(invalid(
+                "NCES LEA ID",
+                value,
+                "must start with a state FIPS code",
+            ));
+        }
+        Ok(Self(value.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The two-digit state code.
+    pub fn state_code(&self) -> &str {
+        &self.0[..2]
+    }
+}
+
+/// The geographic level a Census GEOID identifies, from its length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GeoidLevel {
+    /// 2 digits: state.
+    State,
+    /// 5 digits: state + county.
+    County,
+    /// 7 digits: state + place, or state + school district.
+    Place,
+    /// 10 digits: state + county + county subdivision.
+    CountySubdivision,
+    /// 11 digits: state + county + tract.
+    Tract,
+    /// 12 digits: tract + block group.
+    BlockGroup,
+    /// 15 digits: tract + block.
+    Block,
+}
+
+impl GeoidLevel {
+    fn from_len(len: usize) -> Option<Self> {
+        match len {
+            2 => Some(Self::State),
+            5 => Some(Self::County),
+            7 => Some(Self::Place),
+            10 => Some(Self::CountySubdivision),
+            11 => Some(Self::Tract),
+            12 => Some(Self::BlockGroup),
+            15 => Some(Self::Block),
+            _ => None,
+        }
+    }
+}
+
+/// U.S. Census Bureau GEOID (2 to 15 digits, starting with a state FIPS code).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String")]
+pub struct CensusGeoid(String);
+
+impl CensusGeoid {
+    /// Creates a new Census GEOID, validating the format.
+    pub fn new(value: &str) -> Option<Self> {
+        Self::parse(value).ok()
+    }
+
+    /// Parses a Census GEOID: digits only, of a length that names a
+    /// geographic level, starting with a state FIPS code.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidIdentifier` describing what is wrong.
+    pub fn parse(value: &str) -> CepResult<Self> {
+        if !value.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid("Census GEOID", value, "must contain only digits"));
+        }
+        if GeoidLevel::from_len(value.len()).is_none() {
+            return Err(invalid(
+                "Census GEOID",
+                value,
+                "must be 2, 5, 7, 10, 11, 12 or 15 digits",
+            ));
+        }
+        if !is_state_fips(&value[..2]) {
+            return Err(invalid(
+                "Census GEOID",
+                value,
+                "must start with a state FIPS code",
+            ));
+        }
+        Ok(Self(value.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The geographic level this GEOID identifies.
+    pub fn level(&self) -> GeoidLevel {
+        GeoidLevel::from_len(self.0.len()).expect("length checked by parse")
+    }
+
+    /// The two-digit state FIPS code.
+    pub fn state_code(&self) -> &str {
+        &self.0[..2]
+    }
+}
+
+/// Scheme URI for FIPS state, county and place codes.
+pub const FIPS_SCHEME_URI: &str = "https://specs.civic-interconnect.org/schemes/fips";
+
+/// Scheme URI for USGS GNIS feature IDs.
+pub const GNIS_SCHEME_URI: &str = "https://specs.civic-interconnect.org/schemes/gnis-feature-id";
+
+/// The kind of area a FIPS code identifies, from its length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FipsLevel {
+    /// 2 digits: state.
+    State,
+    /// 5 digits: state + county.
+    County,
+    /// 7 digits: state + place.
+    Place,
+}
+
+/// FIPS state (2 digits), county (5 digits) or place (7 digits) code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String")]
+pub struct FipsCode(String);
+
+impl FipsCode {
+    /// Creates a new FIPS code, validating the format.
+    pub fn new(value: &str) -> Option<Self> {
+        Self::parse(value).ok()
+    }
+
+    /// Parses a FIPS code: 2, 5 or 7 digits starting with a state FIPS
+    /// code.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidIdentifier` describing what is wrong.
+    pub fn parse(value: &str) -> CepResult<Self> {
+        if !matches!(value.len(), 2 | 5 | 7) || !value.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid("FIPS code", value, "must be 2, 5 or 7 digits"));
+        }
+        if !is_state_fips(&value[..2]) {
+            return Err(invalid(
+                "FIPS code",
+                value,
+                "must start with a state FIPS code",
+            ));
+        }
+        Ok(Self(value.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The kind of area this code identifies.
+    pub fn level(&self) -> FipsLevel {
+        match self.0.len() {
+            2 => FipsLevel::State,
+            5 => FipsLevel::County,
+            _ => FipsLevel::Place,
+        }
+    }
+
+    /// The two-digit state FIPS code.
+    pub fn state_code(&self) -> &str {
+        &self.0[..2]
+    }
+}
+
+/// USGS Geographic Names Information System feature ID (1 to 10 digits).
+///
+/// GNIS publishes IDs both bare and zero-padded to 8 digits; they are
+/// stored without leading zeros so both forms compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String")]
+pub struct GnisFeatureId(String);
+
+impl GnisFeatureId {
+    /// Creates a new GNIS feature ID, validating the format.
+    pub fn new(value: &str) -> Option<Self> {
+        Self::parse(value).ok()
+    }
+
+    /// Parses a GNIS feature ID: 1 to 10 digits, not all zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidIdentifier` describing what is wrong.
+    pub fn parse(value: &str) -> CepResult<Self> {
+        if value.is_empty() || value.len() > 10 || !value.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid("GNIS feature ID", value, "must be 1 to 10 digits"));
+        }
+        let trimmed = value.trim_start_matches('0');
+        if trimmed.is_empty() {
+            return Err(invalid("GNIS feature ID", value, "must not be zero"));
+        }
+        Ok(Self(trimmed.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Scheme URI for IRS Employer Identification Numbers.
+pub const EIN_SCHEME_URI: &str = "https://specs.civic-interconnect.org/schemes/ein";
+
+/// Scheme URI for salted lookup hashes of EINs.
+pub const EIN_LOOKUP_SCHEME_URI: &str = "https://specs.civic-interconnect.org/schemes/ein-lookup";
+
+/// IRS Employer Identification Number, stored as `XX-XXXXXXX`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Ein(String);
+
+impl Ein {
+    /// Creates a new EIN, validating the format.
+    pub fn new(value: &str) -> Option<Self> {
+        Self::parse(value).ok()
+    }
+
+    /// Parses an EIN: 9 digits, with or without the hyphen after the
+    /// second, whose two-digit prefix the IRS has assigned.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidIdentifier` describing what is wrong.
+    pub fn parse(value: &str) -> CepResult<Self> {
+        let digits = match value.split_once('-') {
+            Some((prefix, rest)) if prefix.len() == 2 => format!("{}{}", prefix, rest),
+            Some(_) => return Err(invalid("EIN", value, "must be XX-XXXXXXX")),
+            None => value.to_string(),
+        };
+        if digits.len() != 9 || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid("EIN", value, "must be 9 digits"));
+        }
+        let prefix: u8 = digits[..2].parse().expect("two digits");
+        if matches!(
+            prefix,
+            0 | 7..=9 | 17..=19 | 28 | 29 | 49 | 69 | 70 | 78 | 79 | 89 | 96 | 97
+        ) {
+            return Err(invalid(
+                "EIN",
+                value,
+                "has a prefix the IRS does not assign",
+            ));
+        }
+        Ok(Self(format!("{}-{}", &digits[..2], &digits[2..])))
     }
 
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// The EIN with all but the last four digits masked: `XX-XXX1234`.
+    pub fn masked(&self) -> String {
+        format!("XX-XXX{}", &self.0[6..])
+    }
+
+    /// Salted lookup hash: SHA-256 over `salt|XXXXXXXXX` (the 9 digits).
+    pub fn lookup_hash(&self, salt: &str) -> CanonicalHash {
+        CanonicalHash::from_canonical_string(&format!("{}|{}{}", salt, &self.0[..2], &self.0[3..]))
+    }
+}
+
+/// How an EIN is recorded on an entity.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum EinPolicy {
+    /// Record the EIN itself; it is part of the canonical form and hash.
+    #[default]
+    Canonical,
+    /// Record only [`Ein::lookup_hash`] under this salt.
+    LookupOnly { salt: String },
+}
+
+identifier_conversions!(SamUei);
+identifier_conversions!(Lei);
+identifier_conversions!(CanadianBn);
+identifier_conversions!(NcesLeaId);
+identifier_conversions!(CensusGeoid);
+identifier_conversions!(FipsCode);
+identifier_conversions!(GnisFeatureId);
+identifier_conversions!(Ein);
+
+/// State FIPS codes with their USPS abbreviations, which are also the
+/// ISO 3166-2 subdivision codes used in `jurisdictionIso` (`US-OH`).
+const STATE_FIPS: &[(&str, &str)] = &[
+    ("01", "AL"),
+    ("02", "AK"),
+    ("04", "AZ"),
+    ("05", "AR"),
+    ("06", "CA"),
+    ("08", "CO"),
+    ("09", "CT"),
+    ("10", "DE"),
+    ("11", "DC"),
+    ("12", "FL"),
+    ("13", "GA"),
+    ("15", "HI"),
+    ("16", "ID"),
+    ("17", "IL"),
+    ("18", "IN"),
+    ("19", "IA"),
+    ("20", "KS"),
+    ("21", "KY"),
+    ("22", "LA"),
+    ("23", "ME"),
+    ("24", "MD"),
+    ("25", "MA"),
+    ("26", "MI"),
+    ("27", "MN"),
+    ("28", "MS"),
+    ("29", "MO"),
+    ("30", "MT"),
+    ("31", "NE"),
+    ("32", "NV"),
+    ("33", "NH"),
+    ("34", "NJ"),
+    ("35", "NM"),
+    ("36", "NY"),
+    ("37", "NC"),
+    ("38", "ND"),
+    ("39", "OH"),
+    ("40", "OK"),
+    ("41", "OR"),
+    ("42", "PA"),
+    ("44", "RI"),
+    ("45", "SC"),
+    ("46", "SD"),
+    ("47", "TN"),
+    ("48", "TX"),
+    ("49", "UT"),
+    ("50", "VT"),
+    ("51", "VA"),
+    ("53", "WA"),
+    ("54", "WV"),
+    ("55", "WI"),
+    ("56", "WY"),
+    ("60", "AS"),
+    ("66", "GU"),
+    ("69", "MP"),
+    ("72", "PR"),
+    ("74", "UM"),
+    ("78", "VI"),
+];
+
+/// Returns true for the two-digit FIPS codes of the states, DC and the
+/// inhabited territories.
+fn is_state_fips(code: &str) -> bool {
+    state_abbreviation(code).is_some()
+}
+
+/// USPS abbreviation for a state FIPS code.
+fn state_abbreviation(fips: &str) -> Option<&'static str> {
+    STATE_FIPS
+        .iter()
+        .find(|(code, _)| *code == fips)
+        .map(|(_, abbreviation)| *abbreviation)
+}
+
+fn invalid(scheme: &str, value: &str, reason: &str) -> CepError {
+    CepError::InvalidIdentifier(format!("{} '{}' {}", scheme, value, reason))
 }
 
 /// An additional identifier scheme not explicitly defined in the schema.
+///
+/// Deserialization validates values of the typed schemes (NCES LEA ID,
+/// Census GEOID, FIPS code, GNIS feature ID, EIN, EIN lookup hash,
+/// Canadian BN and CRA charity number); values of other schemes are taken as
+/// given.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", try_from = "AdditionalSchemeRepr")]
 pub struct AdditionalScheme {
     /// URI identifying the identifier scheme.
     pub scheme_uri: String,
@@ -109,6 +643,66 @@ pub struct AdditionalScheme {
     pub value: String,
 }
 
+/// Wire form of [`AdditionalScheme`], checked before it becomes one.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AdditionalSchemeRepr {
+    scheme_uri: String,
+    value: String,
+}
+
+impl TryFrom<AdditionalSchemeRepr> for AdditionalScheme {
+    type Error = CepError;
+
+    fn try_from(repr: AdditionalSchemeRepr) -> CepResult<Self> {
+        match repr.scheme_uri.as_str() {
+            NCES_LEA_SCHEME_URI => {
+                NcesLeaId::parse(&repr.value)?;
+            }
+            CENSUS_GEOID_SCHEME_URI => {
+                CensusGeoid::parse(&repr.value)?;
+            }
+            FIPS_SCHEME_URI => {
+                FipsCode::parse(&repr.value)?;
+            }
+            GNIS_SCHEME_URI => {
+                GnisFeatureId::parse(&repr.value)?;
+            }
+            EIN_SCHEME_URI => {
+                Ein::parse(&repr.value)?;
+            }
+            CANADIAN_BN_SCHEME_URI => {
+                CanadianBn::parse(&repr.value)?;
+            }
+            CRA_CHARITY_SCHEME_URI if !CanadianBn::parse(&repr.value)?.is_registered_charity() => {
+                return Err(invalid(
+                    "CRA charity number",
+                    &repr.value,
+                    "must have the RR program identifier",
+                ));
+            }
+            EIN_LOOKUP_SCHEME_URI => {
+                CanonicalHash::from_hex(&repr.value).ok_or_else(|| {
+                    invalid("EIN lookup hash", &repr.value, "must be 64 hex characters")
+                })?;
+            }
+            _ => {}
+        }
+        Ok(Self {
+            scheme_uri: repr.scheme_uri,
+            value: repr.value,
+        })
+    }
+}
+
+/// Confidence a Tier 3 entity gains for each NCES LEA ID, Census GEOID,
+/// FIPS code or GNIS feature ID it carries.
+pub const STATISTICAL_ID_CONFIDENCE: f64 = 0.05;
+
+/// Highest confidence a Tier 3 entity can reach through statistical
+/// identifiers; the same as a SAM UEI.
+const STATISTICAL_ID_CONFIDENCE_CAP: f64 = 0.95;
+
 /// Collection of all known identifiers for an entity.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -149,6 +743,171 @@ impl EntityIdentifiers {
         self
     }
 
+    pub fn with_canadian_bn(mut self, bn: CanadianBn) -> Self {
+        self.canadian_bn = Some(bn);
+        self
+    }
+
+    pub fn with_additional_scheme(mut self, scheme_uri: &str, value: &str) -> Self {
+        self.additional_schemes
+            .get_or_insert_with(Vec::new)
+            .push(AdditionalScheme {
+                scheme_uri: scheme_uri.to_string(),
+                value: value.to_string(),
+            });
+        self
+    }
+
+    pub fn with_nces_lea_id(self, id: NcesLeaId) -> Self {
+        self.with_additional_scheme(NCES_LEA_SCHEME_URI, id.as_str())
+    }
+
+    pub fn with_census_geoid(self, geoid: CensusGeoid) -> Self {
+        self.with_additional_scheme(CENSUS_GEOID_SCHEME_URI, geoid.as_str())
+    }
+
+    pub fn with_fips_code(self, code: FipsCode) -> Self {
+        self.with_additional_scheme(FIPS_SCHEME_URI, code.as_str())
+    }
+
+    pub fn with_gnis_feature_id(self, id: GnisFeatureId) -> Self {
+        self.with_additional_scheme(GNIS_SCHEME_URI, id.as_str())
+    }
+
+    /// Records `ein` as `policy` directs.
+    pub fn with_ein(self, ein: &Ein, policy: &EinPolicy) -> Self {
+        match policy {
+            EinPolicy::Canonical => self.with_additional_scheme(EIN_SCHEME_URI, ein.as_str()),
+            EinPolicy::LookupOnly { salt } => {
+                self.with_additional_scheme(EIN_LOOKUP_SCHEME_URI, ein.lookup_hash(salt).as_hex())
+            }
+        }
+    }
+
+    /// Returns the entity's EIN, if it was recorded under
+    /// [`EinPolicy::Canonical`].
+    pub fn ein(&self) -> Option<Ein> {
+        self.scheme_values(EIN_SCHEME_URI).find_map(Ein::new)
+    }
+
+    /// Returns true if the entity carries `ein`, either as itself or as a
+    /// lookup hash under `salt`.
+    pub fn has_ein(&self, ein: &Ein, salt: Option<&str>) -> bool {
+        if self.ein().as_ref() == Some(ein) {
+            return true;
+        }
+        let Some(salt) = salt else {
+            return false;
+        };
+        let hash = ein.lookup_hash(salt);
+        self.scheme_values(EIN_LOOKUP_SCHEME_URI)
+            .any(|value| value == hash.as_hex())
+    }
+
+    /// Returns the entity's NCES LEA ID, if it has a valid one.
+    pub fn nces_lea_id(&self) -> Option<NcesLeaId> {
+        self.scheme_values(NCES_LEA_SCHEME_URI)
+            .find_map(NcesLeaId::new)
+    }
+
+    /// Returns the entity's valid Census GEOIDs.
+    pub fn census_geoids(&self) -> Vec<CensusGeoid> {
+        self.scheme_values(CENSUS_GEOID_SCHEME_URI)
+            .filter_map(CensusGeoid::new)
+            .collect()
+    }
+
+    /// Returns the entity's valid FIPS codes.
+    pub fn fips_codes(&self) -> Vec<FipsCode> {
+        self.scheme_values(FIPS_SCHEME_URI)
+            .filter_map(FipsCode::new)
+            .collect()
+    }
+
+    /// Returns the entity's GNIS feature ID, if it has a valid one.
+    pub fn gnis_feature_id(&self) -> Option<GnisFeatureId> {
+        self.scheme_values(GNIS_SCHEME_URI)
+            .find_map(GnisFeatureId::new)
+    }
+
+    /// Checks that every identifier starting with a state FIPS code (NCES
+    /// LEA IDs, Census GEOIDs, FIPS codes) names the state of
+    /// `jurisdiction_iso`.
+    ///
+    /// A country-level `US` jurisdiction accepts any state. GNIS feature IDs
+    /// carry no state and are not checked, nor are NCES LEA IDs under the
+    /// DoD and BIE codes, which are not states.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidIdentifier` naming the first identifier
+    /// whose state disagrees with the jurisdiction.
+    pub fn check_jurisdiction(&self, jurisdiction_iso: &str) -> CepResult<()> {
+        let state_codes = self
+            .nces_lea_id()
+            .map(|id| (NCES_LEA_SCHEME_URI, id.0))
+            .into_iter()
+            .chain(
+                self.census_geoids()
+                    .into_iter()
+                    .map(|id| (CENSUS_GEOID_SCHEME_URI, id.0)),
+            )
+            .chain(
+                self.fips_codes()
+                    .into_iter()
+                    .map(|id| (FIPS_SCHEME_URI, id.0)),
+            );
+        for (scheme_uri, value) in state_codes {
+            let Some(state) = state_abbreviation(&value[..2]) else {
+                continue;
+            };
+            let consistent = match jurisdiction_iso.split_once('-') {
+                Some(("US", subdivision)) => subdivision == state,
+                None => jurisdiction_iso == "US",
+                // Territories have their own ISO 3166-1 codes (PR, GU, ...).
+                Some(_) => false,
+            } || jurisdiction_iso == state;
+            if !consistent {
+                return Err(CepError::InvalidIdentifier(format!(
+                    "{} '{}' is in {}, but the jurisdiction is {}",
+                    scheme_uri, value, state, jurisdiction_iso
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Values recorded under `scheme_uri` in `additionalSchemes`.
+    fn scheme_values<'a>(&'a self, scheme_uri: &'a str) -> impl Iterator<Item = &'a str> {
+        self.additional_schemes
+            .iter()
+            .flatten()
+            .filter(move |s| s.scheme_uri == scheme_uri)
+            .map(|s| s.value.as_str())
+    }
+
+    /// Scores how confidently these identifiers resolve the entity.
+    ///
+    /// A Tier 1 or Tier 2 result keeps its score: an LEI or SAM UEI only
+    /// counts once verified, which `snfei` records. Otherwise the score is
+    /// the SNFEI's own, plus [`STATISTICAL_ID_CONFIDENCE`] for each valid
+    /// NCES LEA ID, Census GEOID, FIPS code and GNIS feature ID, up to 0.95.
+    pub fn confidence_score(&self, snfei: &SnfeiResult) -> f64 {
+        if snfei.tier < 3 {
+            return snfei.confidence_score;
+        }
+        let statistical_ids = usize::from(self.nces_lea_id().is_some())
+            + self.census_geoids().len()
+            + self.fips_codes().len()
+            + usize::from(self.gnis_feature_id().is_some());
+        if statistical_ids == 0 {
+            return snfei.confidence_score;
+        }
+        let score = (snfei.confidence_score + STATISTICAL_ID_CONFIDENCE * statistical_ids as f64)
+            .min(STATISTICAL_ID_CONFIDENCE_CAP);
+        (score * 100.0).round() / 100.0
+    }
+
     /// Returns true if at least one identifier is present.
     pub fn has_any(&self) -> bool {
         self.sam_uei.is_some()
@@ -158,7 +917,21 @@ impl EntityIdentifiers {
             || self
                 .additional_schemes
                 .as_ref()
-                .map_or(false, |v| !v.is_empty())
+                .is_some_and(|v| !v.is_empty())
+    }
+
+    /// Returns the highest identifier tier present: 1 (LEI), 2 (SAM UEI) or
+    /// 3 (SNFEI). Extended schemes have no tier.
+    pub fn tier(&self) -> Option<u8> {
+        if self.lei.is_some() {
+            Some(1)
+        } else if self.sam_uei.is_some() {
+            Some(2)
+        } else if self.snfei.is_some() {
+            Some(3)
+        } else {
+            None
+        }
     }
 
     /// Returns the "best" identifier for use as the verifiable ID.
@@ -176,28 +949,38 @@ impl EntityIdentifiers {
         if let Some(ref bn) = self.canadian_bn {
             return Some(format!("cep-entity:canadian-bn:{}", bn.as_str()));
         }
-        if let Some(ref schemes) = self.additional_schemes {
-            if let Some(first) = schemes.first() {
-                return Some(format!("cep-entity:other:{}", first.value));
-            }
+        if let Some(ref schemes) = self.additional_schemes
+            && let Some(first) = schemes.first()
+        {
+            return Some(format!("cep-entity:other:{}", first.value));
         }
         None
     }
 }
 
+impl TierIdentifiers for EntityIdentifiers {
+    fn lei(&self) -> Option<&str> {
+        self.lei.as_ref().map(Lei::as_str)
+    }
+
+    fn sam_uei(&self) -> Option<&str> {
+        self.sam_uei.as_ref().map(SamUei::as_str)
+    }
+}
+
 impl Canonicalize for EntityIdentifiers {
     fn canonical_fields(&self) -> BTreeMap<String, String> {
         let mut map = BTreeMap::new();
 
         // Additional schemes serialized as JSON array string for canonical form
-        if let Some(ref schemes) = self.additional_schemes {
-            if !schemes.is_empty() {
-                // Sort by scheme_uri for determinism
-                let mut sorted: Vec<_> = schemes.iter().collect();
-                sorted.sort_by(|a, b| a.scheme_uri.cmp(&b.scheme_uri));
-                let json = serde_json::to_string(&sorted).unwrap_or_default();
-                map.insert("additionalSchemes".to_string(), json);
-            }
+        if let Some(ref schemes) = self.additional_schemes
+            && !schemes.is_empty()
+        {
+            // Sort by scheme_uri for determinism
+            let mut sorted: Vec<_> = schemes.iter().collect();
+            sorted.sort_by(|a, b| a.scheme_uri.cmp(&b.scheme_uri));
+            let json = serde_json::to_string(&sorted).unwrap_or_default();
+            map.insert("additionalSchemes".to_string(), json);
         }
 
         insert_if_present(
@@ -217,10 +1000,22 @@ impl Canonicalize for EntityIdentifiers {
     }
 }
 
+impl CanonicalSpec for EntityIdentifiers {
+    fn type_spec() -> TypeSpec {
+        // additionalSchemes is a JSON array sorted by schemeUri.
+        TypeSpec::new("EntityIdentifiers")
+            .optional("additionalSchemes", FieldEncoding::Json)
+            .optional("canadianBn", FieldEncoding::String)
+            .optional("lei", FieldEncoding::String)
+            .optional("samUei", FieldEncoding::String)
+            .optional("snfei", FieldEncoding::String)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cep_snfei::generate_snfei;
+    use cep_snfei::{IdentifierVerification, generate_snfei};
 
     #[test]
     fn test_sam_uei_valid() {
@@ -257,6 +1052,22 @@ mod tests {
         assert_ne!(snfei, snfei3);
     }
 
+    #[test]
+    fn test_snfei_matches_specification_formula() {
+        use cep_core::CanonicalHash;
+
+        let result = generate_snfei("Springfield USD", "US", None, None);
+        let hash_input = format!("{}||US|", result.canonical.legal_name_normalized);
+        let expected = CanonicalHash::from_canonical_string(&hash_input)
+            .as_hex()
+            .to_string();
+
+        // The identifier stored on an entity is the generated one, unchanged.
+        let ids = EntityIdentifiers::new().with_snfei(result.snfei);
+        assert_eq!(ids.canonical_fields()["snfei"], expected);
+        assert_eq!(Snfei::from_hash(&expected.to_uppercase()), ids.snfei);
+    }
+
     #[test]
     fn test_snfei_normalization_equivalence() {
         // Different surface forms should normalize to same SNFEI
@@ -276,6 +1087,36 @@ mod tests {
     #[test]
     fn test_canadian_bn_valid() {
         assert!(CanadianBn::new("123456789RC0001").is_some());
+        // Multi-byte input of the right byte length must not panic.
+        assert!(CanadianBn::new("12345678éRC0001").is_none());
+    }
+
+    #[test]
+    fn test_identifier_conversions() {
+        let uei: SamUei = "J6H4FB3N5YK7".parse().unwrap();
+        assert_eq!(uei.to_string(), "J6H4FB3N5YK7");
+        assert_eq!(uei.as_ref(), "J6H4FB3N5YK7");
+        assert_eq!(
+            Lei::try_from("5493001kjtiigc8y1r12").unwrap().as_str(),
+            "5493001KJTIIGC8Y1R12"
+        );
+
+        let err = SamUei::try_from("J6H4FB3N5YK").unwrap_err();
+        assert!(matches!(err, CepError::InvalidIdentifier(_)));
+        assert!(err.to_string().contains("must be 12 characters"));
+        let err = "123456789rc0001".parse::<CanadianBn>().unwrap_err();
+        assert!(err.to_string().contains("uppercase letters"));
+    }
+
+    #[test]
+    fn test_deserialize_validates() {
+        let uei: SamUei = serde_json::from_str("\"J6H4FB3N5YK7\"").unwrap();
+        assert_eq!(serde_json::to_string(&uei).unwrap(), "\"J6H4FB3N5YK7\"");
+
+        assert!(serde_json::from_str::<SamUei>("\"bad\"").is_err());
+        assert!(serde_json::from_str::<Lei>("\"5493001KJTIIGC8Y1R1!\"").is_err());
+        let err = serde_json::from_str::<EntityIdentifiers>(r#"{"canadianBn":"123"}"#).unwrap_err();
+        assert!(err.to_string().contains("Canadian BN"));
     }
 
     #[test]
@@ -304,4 +1145,188 @@ mod tests {
         // Should be alphabetical
         assert_eq!(keys, vec!["lei", "samUei"]);
     }
+
+    #[test]
+    fn test_nces_lea_id() {
+        let id = NcesLeaId::parse("0622710").unwrap();
+        assert_eq!(id.state_code(), "06");
+        assert!(NcesLeaId::new("5900001").is_some()); // Bureau of Indian Education
+        assert!(NcesLeaId::new("062271").is_none()); // too short
+        assert!(NcesLeaId::new("06A2710").is_none());
+        let err = NcesLeaId::parse("0322710").unwrap_err();
+        assert!(err.to_string().contains("state FIPS code"));
+    }
+
+    #[test]
+    fn test_census_geoid() {
+        assert_eq!(CensusGeoid::parse("06").unwrap().level(), GeoidLevel::State);
+        assert_eq!(
+            CensusGeoid::parse("17031").unwrap().level(),
+            GeoidLevel::County
+        );
+        assert_eq!(
+            CensusGeoid::parse("17031839100").unwrap().level(),
+            GeoidLevel::Tract
+        );
+        assert!(CensusGeoid::new("170318").is_none()); // no such level
+        assert!(CensusGeoid::new("58031").is_none()); // not a state code
+        assert!(CensusGeoid::new("17-31").is_none());
+    }
+
+    #[test]
+    fn test_statistical_ids_in_additional_schemes() {
+        let ids = EntityIdentifiers::new()
+            .with_nces_lea_id(NcesLeaId::parse("0622710").unwrap())
+            .with_census_geoid(CensusGeoid::parse("0622710").unwrap());
+        assert_eq!(ids.nces_lea_id().unwrap().as_str(), "0622710");
+        assert_eq!(ids.census_geoids().len(), 1);
+
+        let json = serde_json::to_string(&ids).unwrap();
+        assert!(json.contains(NCES_LEA_SCHEME_URI));
+        let back: EntityIdentifiers = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, ids);
+
+        let bad = format!(
+            r#"{{"additionalSchemes":[{{"schemeUri":"{}","value":"123"}}]}}"#,
+            NCES_LEA_SCHEME_URI
+        );
+        let err = serde_json::from_str::<EntityIdentifiers>(&bad).unwrap_err();
+        assert!(err.to_string().contains("NCES LEA ID"));
+    }
+
+    #[test]
+    fn test_confidence_score_counts_statistical_ids() {
+        let snfei = generate_snfei("Springfield USD", "US", None, None);
+        assert_eq!(snfei.confidence_score, 0.6);
+        let plain = EntityIdentifiers::new().with_snfei(snfei.snfei.clone());
+        assert_eq!(plain.confidence_score(&snfei), 0.6);
+
+        let ids = plain
+            .clone()
+            .with_nces_lea_id(NcesLeaId::parse("0622710").unwrap())
+            .with_census_geoid(CensusGeoid::parse("0622710").unwrap());
+        assert_eq!(ids.confidence_score(&snfei), 0.7);
+
+        let full = generate_snfei(
+            "Springfield Unified School District",
+            "US",
+            Some("1 Main St"),
+            Some("1950-01-01"),
+        );
+        assert_eq!(ids.confidence_score(&full), 0.95);
+
+        // An unverified SAM UEI does not count; a verified one does.
+        let ids = ids.with_sam_uei(SamUei::new("J6H4FB3N5YK7").unwrap());
+        assert_eq!(ids.confidence_score(&snfei), 0.7);
+        let verified = snfei.upgrade_with(&ids, IdentifierVerification::RegistryVerified);
+        assert_eq!(ids.confidence_score(&verified), 0.95);
+    }
+
+    #[test]
+    fn test_fips_code_and_gnis_feature_id() {
+        assert_eq!(FipsCode::parse("39").unwrap().level(), FipsLevel::State);
+        assert_eq!(FipsCode::parse("39049").unwrap().level(), FipsLevel::County);
+        let place = FipsCode::parse("3918000").unwrap();
+        assert_eq!(place.level(), FipsLevel::Place);
+        assert_eq!(place.state_code(), "39");
+        assert!(FipsCode::new("3904").is_none());
+        assert!(FipsCode::new("07049").is_none()); // 07 is unassigned
+
+        assert_eq!(
+            GnisFeatureId::parse("01085497").unwrap().as_str(),
+            "1085497"
+        );
+        assert_eq!(
+            GnisFeatureId::parse("1085497").unwrap(),
+            GnisFeatureId::parse("01085497").unwrap()
+        );
+        assert!(GnisFeatureId::new("0000").is_none());
+        assert!(GnisFeatureId::new("12345678901").is_none());
+    }
+
+    #[test]
+    fn test_check_jurisdiction() {
+        let ids = EntityIdentifiers::new()
+            .with_fips_code(FipsCode::parse("3918000").unwrap())
+            .with_gnis_feature_id(GnisFeatureId::parse("1085497").unwrap())
+            .with_nces_lea_id(NcesLeaId::parse("3904378").unwrap());
+        assert!(ids.check_jurisdiction("US-OH").is_ok());
+        assert!(ids.check_jurisdiction("US").is_ok());
+        let err = ids.check_jurisdiction("US-IN").unwrap_err();
+        assert!(err.to_string().contains("is in OH"));
+        assert!(ids.check_jurisdiction("CA-ON").is_err());
+
+        let pr = EntityIdentifiers::new().with_census_geoid(CensusGeoid::parse("72127").unwrap());
+        assert!(pr.check_jurisdiction("PR").is_ok());
+        assert!(pr.check_jurisdiction("US-PR").is_ok());
+        assert!(pr.check_jurisdiction("US-OH").is_err());
+    }
+
+    #[test]
+    fn test_ein_format_and_masking() {
+        let ein = Ein::parse("521234567").unwrap();
+        assert_eq!(ein.as_str(), "52-1234567");
+        assert_eq!(Ein::parse("52-1234567").unwrap(), ein);
+        assert_eq!(ein.masked(), "XX-XXX4567");
+
+        assert!(Ein::new("52-123456").is_none());
+        assert!(Ein::new("521-234567").is_none());
+        let err = Ein::parse("07-1234567").unwrap_err();
+        assert!(err.to_string().contains("prefix"));
+    }
+
+    #[test]
+    fn test_ein_policy() {
+        let ein = Ein::parse("52-1234567").unwrap();
+
+        let canonical = EntityIdentifiers::new().with_ein(&ein, &EinPolicy::Canonical);
+        assert_eq!(canonical.ein(), Some(ein.clone()));
+        assert!(canonical.canonical_fields()["additionalSchemes"].contains("52-1234567"));
+        assert!(canonical.has_ein(&ein, None));
+
+        let policy = EinPolicy::LookupOnly {
+            salt: "registry-salt".to_string(),
+        };
+        let hashed = EntityIdentifiers::new().with_ein(&ein, &policy);
+        assert_eq!(hashed.ein(), None);
+        assert!(!hashed.canonical_fields()["additionalSchemes"].contains("1234567"));
+        assert!(hashed.has_ein(&ein, Some("registry-salt")));
+        assert!(!hashed.has_ein(&ein, Some("other-salt")));
+        assert!(!hashed.has_ein(&Ein::parse("52-7654321").unwrap(), Some("registry-salt")));
+
+        let json = serde_json::to_string(&hashed).unwrap();
+        assert_eq!(
+            serde_json::from_str::<EntityIdentifiers>(&json).unwrap(),
+            hashed
+        );
+    }
+
+    #[test]
+    fn test_cra_charity_number() {
+        let bn = CanadianBn::parse("118830553RR0001").unwrap();
+        assert_eq!(bn.program(), "RR");
+        assert!(bn.is_registered_charity());
+        assert!(
+            !CanadianBn::parse("123456789RC0001")
+                .unwrap()
+                .is_registered_charity()
+        );
+
+        let ids = EntityIdentifiers::new()
+            .with_canadian_bn(bn.clone())
+            .with_additional_scheme(CRA_CHARITY_SCHEME_URI, bn.as_str());
+        assert_eq!(
+            ids.primary_identifier().unwrap(),
+            "cep-entity:canadian-bn:118830553RR0001"
+        );
+        let json = serde_json::to_string(&ids).unwrap();
+        assert_eq!(
+            serde_json::from_str::<EntityIdentifiers>(&json).unwrap(),
+            ids
+        );
+
+        let corporate = json.replace("RR0001\"}]", "RC0001\"}]");
+        let err = serde_json::from_str::<EntityIdentifiers>(&corporate).unwrap_err();
+        assert!(err.to_string().contains("RR program"));
+    }
 }
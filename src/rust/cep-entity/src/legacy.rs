@@ -0,0 +1,272 @@
+//! Imports from legacy entity identifier systems.
+//!
+//! A [`LegacyImport`] reads a dump of legacy id → attribute rows with an
+//! [`AdapterConfig`] (the legacy id is the row's `source_id`), builds an
+//! entity with an SNFEI for each row, and records the legacy id on the entity
+//! under the legacy system's scheme URI in `additionalSchemes`. The result is
+//! a crosswalk from every legacy id to its CEP verifiable ID.
+//!
+//! Every import also produces an [`ImportProvenance`] that names the exact
+//! source file (by hash), the adapter, the time of import and a hash over the
+//! whole mapping, so an auditor can later re-run the import and confirm the
+//! crosswalk was not altered.
+//!
+//! ```rust,no_run
+//! use cep_core::SystemClock;
+//! use cep_entity::adapters::{AdapterConfig, FieldMapping};
+//! use cep_entity::legacy::LegacyImport;
+//! # let attestation: cep_entity::AttestationInput = unimplemented!();
+//!
+//! let mut adapter = AdapterConfig::new("Vendor master", FieldMapping::named("NAME"));
+//! adapter.source_id = Some(FieldMapping::named("VENDOR_ID"));
+//! adapter.default_country_code = Some("US".to_string());
+//!
+//! let dump = std::fs::read("vendor_master.csv").unwrap();
+//! let import = LegacyImport::new("https://erp.example.gov/ids/vendor", adapter, attestation);
+//! let result = import.run(&dump, &SystemClock).unwrap();
+//! for entry in &result.crosswalk {
+//!     println!("{} -> {}", entry.legacy_id, entry.verifiable_id);
+//! }
+//! ```
+
+use crate::adapters::AdapterConfig;
+use crate::builder::{AttestationInput, EntityBuildResult, build_entity};
+use cep_core::{CanonicalHash, CanonicalTimestamp, CepError, CepResult, Clock};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// One legacy id and the entity it became.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrosswalkEntry {
+    /// Identifier in the legacy system.
+    pub legacy_id: String,
+    /// The entity's CEP verifiable ID.
+    pub verifiable_id: String,
+}
+
+/// Where a crosswalk came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportProvenance {
+    /// Scheme URI the legacy ids were recorded under.
+    pub scheme_uri: String,
+    /// Name of the adapter that read the dump.
+    pub adapter: String,
+    /// SHA-256 of the dump file as read.
+    pub source_hash: CanonicalHash,
+    /// When the import ran.
+    pub imported_at: CanonicalTimestamp,
+    /// Number of rows imported.
+    pub entity_count: usize,
+    /// Hash over the sorted `legacyId=verifiableId` pairs.
+    pub mapping_hash: CanonicalHash,
+}
+
+/// The output of a legacy import.
+#[derive(Debug, Clone)]
+pub struct ImportResult {
+    /// The built entities, in row order, each carrying its legacy id.
+    pub entities: Vec<EntityBuildResult>,
+    /// Legacy id → verifiable ID, in row order.
+    pub crosswalk: Vec<CrosswalkEntry>,
+    /// How the crosswalk was produced.
+    pub provenance: ImportProvenance,
+}
+
+impl ImportResult {
+    /// Verifiable IDs that more than one legacy id resolved to: records the
+    /// legacy system held twice for the same entity.
+    pub fn merged_legacy_ids(&self) -> BTreeMap<&str, Vec<&str>> {
+        let mut by_entity: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for entry in &self.crosswalk {
+            by_entity
+                .entry(&entry.verifiable_id)
+                .or_default()
+                .push(&entry.legacy_id);
+        }
+        by_entity.retain(|_, ids| ids.len() > 1);
+        by_entity
+    }
+}
+
+/// Imports a legacy id dump.
+#[derive(Debug, Clone)]
+pub struct LegacyImport {
+    scheme_uri: String,
+    adapter: AdapterConfig,
+    attestation: AttestationInput,
+}
+
+impl LegacyImport {
+    /// Creates an import that records legacy ids under `scheme_uri`.
+    ///
+    /// `adapter` must map the legacy id as `source_id`.
+    pub fn new(scheme_uri: &str, adapter: AdapterConfig, attestation: AttestationInput) -> Self {
+        Self {
+            scheme_uri: scheme_uri.to_string(),
+            adapter,
+            attestation,
+        }
+    }
+
+    /// Runs the import over the dump's bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::Configuration` if the adapter maps no `source_id`,
+    /// `CepError::MissingField` if a row has no legacy id, and
+    /// `CepError::InvalidIdentifier` if a legacy id appears twice. Adapter
+    /// and builder errors are passed through.
+    pub fn run(&self, dump: &[u8], clock: &dyn Clock) -> CepResult<ImportResult> {
+        if self.adapter.source_id.is_none() {
+            return Err(CepError::Configuration(format!(
+                "{}: legacy import needs a source_id mapping",
+                self.adapter.name
+            )));
+        }
+
+        let mut entities = Vec::new();
+        let mut crosswalk = Vec::new();
+        let mut seen = BTreeMap::new();
+        for (row, source) in self.adapter.read(dump)?.into_iter().enumerate() {
+            let legacy_id = source.source_id.clone().ok_or_else(|| {
+                CepError::MissingField(format!(
+                    "legacy id (entry {} of {})",
+                    row + 1,
+                    self.adapter.name
+                ))
+            })?;
+            if let Some(first) = seen.insert(legacy_id.clone(), row + 1) {
+                return Err(CepError::InvalidIdentifier(format!(
+                    "legacy id '{}' appears in entries {} and {}",
+                    legacy_id,
+                    first,
+                    row + 1
+                )));
+            }
+
+            let mut built = build_entity(source.into_builder_input(self.attestation.clone()))?;
+            built.entity.identifiers = built
+                .entity
+                .identifiers
+                .clone()
+                .with_additional_scheme(&self.scheme_uri, &legacy_id);
+            crosswalk.push(CrosswalkEntry {
+                legacy_id,
+                verifiable_id: built.entity.verifiable_id.clone(),
+            });
+            entities.push(built);
+        }
+
+        let provenance = ImportProvenance {
+            scheme_uri: self.scheme_uri.clone(),
+            adapter: self.adapter.name.clone(),
+            source_hash: sha256(dump),
+            imported_at: clock.now(),
+            entity_count: crosswalk.len(),
+            mapping_hash: mapping_hash(&crosswalk),
+        };
+        Ok(ImportResult {
+            entities,
+            crosswalk,
+            provenance,
+        })
+    }
+}
+
+/// Hash over the sorted `legacyId=verifiableId` pairs of a crosswalk.
+pub fn mapping_hash(crosswalk: &[CrosswalkEntry]) -> CanonicalHash {
+    let mut pairs: Vec<String> = crosswalk
+        .iter()
+        .map(|e| format!("{}={}", e.legacy_id, e.verifiable_id))
+        .collect();
+    pairs.sort();
+    CanonicalHash::from_canonical_string(&pairs.join("\n"))
+}
+
+fn sha256(bytes: &[u8]) -> CanonicalHash {
+    CanonicalHash::from_hex(&format!("{:x}", Sha256::digest(bytes)))
+        .expect("SHA-256 is 64 hex characters")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::FieldMapping;
+    use cep_core::FixedClock;
+
+    const SCHEME: &str = "https://erp.example.gov/ids/vendor";
+
+    fn import() -> LegacyImport {
+        let mut adapter = AdapterConfig::new("Vendor master", FieldMapping::named("NAME"));
+        adapter.source_id = Some(FieldMapping::named("VENDOR_ID"));
+        adapter.address = Some(FieldMapping::named("ADDRESS"));
+        adapter.default_country_code = Some("US".to_string());
+        adapter.default_jurisdiction = Some("US-OH".to_string());
+        LegacyImport::new(
+            SCHEME,
+            adapter,
+            AttestationInput {
+                attested_by: "did:web:erp.example.gov".to_string(),
+                attestation_timestamp: "2025-11-28T14:30:00.000000Z".to_string(),
+                proof_type: None,
+                proof_value: None,
+                verification_method_uri: None,
+                proof_purpose: None,
+                anchor_uri: None,
+            },
+        )
+    }
+
+    fn clock() -> FixedClock {
+        FixedClock::new("2026-03-01T09:00:00.000000Z".parse().unwrap())
+    }
+
+    const DUMP: &str = "VENDOR_ID,NAME,ADDRESS\n\
+                        V-001,Springfield Water District,1 Main St\n\
+                        V-002,City of Shelbyville,9 Elm St\n\
+                        V-107,SPRINGFIELD WATER DISTRICT,1 Main St\n";
+
+    #[test]
+    fn test_crosswalk_and_provenance() {
+        let result = import().run(DUMP.as_bytes(), &clock()).unwrap();
+        assert_eq!(result.crosswalk.len(), 3);
+
+        let first = &result.entities[0].entity;
+        assert_eq!(first.verifiable_id, result.crosswalk[0].verifiable_id);
+        let schemes = first.identifiers.additional_schemes.as_ref().unwrap();
+        assert_eq!(schemes[0].scheme_uri, SCHEME);
+        assert_eq!(schemes[0].value, "V-001");
+
+        // The legacy system held the water district twice.
+        let merged = result.merged_legacy_ids();
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged.values().next().unwrap(), &["V-001", "V-107"]);
+
+        let provenance = &result.provenance;
+        assert_eq!(provenance.entity_count, 3);
+        assert_eq!(provenance.mapping_hash, mapping_hash(&result.crosswalk));
+        assert_eq!(provenance.imported_at, clock().now());
+
+        // Re-running over the same dump reproduces the mapping.
+        let again = import().run(DUMP.as_bytes(), &clock()).unwrap();
+        assert_eq!(again.provenance, *provenance);
+    }
+
+    #[test]
+    fn test_rejects_missing_and_duplicate_legacy_ids() {
+        let missing = "VENDOR_ID,NAME,ADDRESS\n,Springfield Water District,1 Main St\n";
+        assert!(matches!(
+            import().run(missing.as_bytes(), &clock()),
+            Err(CepError::MissingField(_))
+        ));
+
+        let duplicate = "VENDOR_ID,NAME,ADDRESS\nV-001,A,1 Main St\nV-001,B,2 Main St\n";
+        assert!(matches!(
+            import().run(duplicate.as_bytes(), &clock()),
+            Err(CepError::InvalidIdentifier(_))
+        ));
+    }
+}
@@ -1,66 +1,96 @@
-/// # CEP Entity
-///
-/// Entity records for the Civic Exchange Protocol (CEP).
-///
-/// This crate defines the [`EntityRecord`] type, which represents a verified
-/// civic entity. Entities are the foundational primitive in CEP—all relationships
-/// and exchanges reference attested entities.
-///
-/// ## Example
-///
-/// ```rust
-/// use cep_entity::{EntityRecord, EntityIdentifiers, EntityStatus, EntityStatusCode};
-/// use cep_entity::identifiers::SamUei;
-/// use cep_core::{Attestation, Canonicalize};
-///
-/// // Create identifiers
-/// let identifiers = EntityIdentifiers::new()
-///     .with_sam_uei(SamUei::new("J6H4FB3N5YK7").unwrap());
-///
-/// // Create status
-/// let status = EntityStatus {
-///     status_code: EntityStatusCode::Active,
-///     status_effective_date: "2020-01-15".to_string(),
-///     status_termination_date: None,
-///     successor_entity_id: None,
-/// };
-///
-/// // Create attestation
-/// let attestation = Attestation::new(
-///     "cep-entity:sam-uei:ATTESTOR123A".to_string(),
-///     "2025-11-28T14:30:00.000000Z".parse().unwrap(),
-///     "Ed25519Signature2020".to_string(),
-///     "z3FXQq...".to_string(),
-///     "did:web:example.gov#key-1".to_string(),
-/// );
-///
-/// // Create entity record
-/// let entity = EntityRecord::new(
-///     "cep-entity:sam-uei:J6H4FB3N5YK7".to_string(),
-///     identifiers,
-///     "Acme Consulting LLC".to_string(),
-///     "US-CA".to_string(),
-///     status,
-///     attestation,
-/// );
-///
-/// // Generate canonical hash
-/// let hash = entity.calculate_hash();
-/// println!("Entity hash: {}", hash);
-/// ```
+//! # CEP Entity
+//!
+//! Entity records for the Civic Exchange Protocol (CEP).
+//!
+//! This crate defines the [`EntityRecord`] type, which represents a verified
+//! civic entity. Entities are the foundational primitive in CEP—all relationships
+//! and exchanges reference attested entities.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use cep_entity::{EntityRecord, EntityIdentifiers, EntityStatus, EntityStatusCode};
+//! use cep_entity::identifiers::SamUei;
+//! use cep_core::{Attestation, Canonicalize, ProofType};
+//!
+//! // Create identifiers
+//! let identifiers = EntityIdentifiers::new()
+//!     .with_sam_uei(SamUei::new("J6H4FB3N5YK7").unwrap());
+//!
+//! // Create status
+//! let status = EntityStatus {
+//!     status_code: EntityStatusCode::Active,
+//!     status_effective_date: "2020-01-15".to_string(),
+//!     status_termination_date: None,
+//!     successor_entity_id: None,
+//! };
+//!
+//! // Create attestation
+//! let attestation = Attestation::new(
+//!     "cep-entity:sam-uei:ATTESTOR123A".to_string(),
+//!     "2025-11-28T14:30:00.000000Z".parse().unwrap(),
+//!     ProofType::Ed25519Signature2020,
+//!     "z3FXQq...".to_string(),
+//!     "did:web:example.gov#key-1".to_string(),
+//! );
+//!
+//! // Create entity record
+//! let entity = EntityRecord::new(
+//!     "cep-entity:sam-uei:J6H4FB3N5YK7".to_string(),
+//!     identifiers,
+//!     "Acme Consulting LLC".to_string(),
+//!     "US-CA".to_string(),
+//!     status,
+//!     attestation,
+//! );
+//!
+//! // Generate canonical hash
+//! let hash = entity.calculate_hash();
+//! println!("Entity hash: {}", hash);
+//! ```
 
+pub mod adapters;
 pub mod builder;
+pub mod completeness;
+pub mod current;
 pub mod entity;
 pub mod identifiers;
+pub mod legacy;
+pub mod query;
+pub mod rollup;
+
+use cep_core::spec::{CanonicalSpec, CanonicalizationSpec};
+use cep_core::{Attestation, Authority, Freshness};
 
 // Re-export primary types
 pub use builder::{
-    AttestationInput, EntityBuildResult, EntityBuilderInput, EntityTypeCode, build_entity,
+    AttestationInput, EntityBuildResult, EntityBuilderInput, EntityTypeCode, TierChange,
+    build_entity, recompute_tiers,
 };
+pub use completeness::{Completeness, JurisdictionScore};
+pub use current::{CurrentEntities, CurrentEntity};
 pub use entity::{EntityRecord, EntityStatus, EntityStatusCode, ResolutionConfidence};
-pub use identifiers::{AdditionalScheme, CanadianBn, EntityIdentifiers, Lei, SamUei, Snfei};
+pub use identifiers::{
+    AdditionalScheme, CanadianBn, CensusGeoid, Ein, EinPolicy, EntityIdentifiers, FipsCode,
+    FipsLevel, GeoidLevel, GnisFeatureId, Lei, NcesLeaId, SamUei, Snfei,
+};
+pub use query::{EntityPredicate, EntityQuery, GovernmentLevel};
+pub use rollup::{Rollup, StatisticsRecord, TierCoverage};
 
 /// Expose the JSON Schema via cep-core.
 pub fn entity_schema_json() -> Option<&'static str> {
     cep_core::get_schema("entity")
-}
\ No newline at end of file
+}
+
+/// Canonicalization spec for entity records and their nested types.
+pub fn entity_canonical_spec() -> CanonicalizationSpec {
+    CanonicalizationSpec::current().with_types([
+        EntityRecord::type_spec(),
+        Attestation::type_spec(),
+        Authority::type_spec(),
+        Freshness::type_spec(),
+        EntityIdentifiers::type_spec(),
+        EntityStatus::type_spec(),
+        ResolutionConfidence::type_spec(),
+    ])
+}
@@ -0,0 +1,275 @@
+//! Typed entity queries.
+//!
+//! An [`EntityQuery`] is a conjunction of [`EntityPredicate`]s built with
+//! typed methods instead of string filters:
+//!
+//! ```rust
+//! use cep_entity::query::{EntityQuery, GovernmentLevel};
+//!
+//! let query = EntityQuery::new()
+//!     .jurisdiction("US-OH")
+//!     .government_level(GovernmentLevel::Local)
+//!     .name_contains("water");
+//! assert_eq!(query.predicates().len(), 3);
+//! ```
+//!
+//! Storage backends translate [`EntityQuery::predicates`] into their native
+//! query language. [`EntityQuery::matches`] is the reference semantics, and
+//! in-memory collections can use [`EntityQuery::filter`] directly.
+
+use crate::entity::{EntityRecord, EntityStatusCode};
+
+/// URI of the entity type vocabulary.
+pub const ENTITY_TYPE_VOCABULARY_URI: &str = "https://raw.githubusercontent.com/civic-interconnect/civic-exchange-protocol/main/vocabulary/entity-type.json";
+
+/// Level of government, derived from an entity's `entityTypeUri`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GovernmentLevel {
+    /// `federal-agency`
+    Federal,
+    /// `state-agency`
+    State,
+    /// `local-government`: cities, counties, townships and districts.
+    Local,
+}
+
+impl GovernmentLevel {
+    /// Returns the entity type vocabulary code for this level.
+    pub fn entity_type_code(&self) -> &'static str {
+        match self {
+            Self::Federal => "federal-agency",
+            Self::State => "state-agency",
+            Self::Local => "local-government",
+        }
+    }
+
+    /// Returns the entity type URI for this level.
+    pub fn entity_type_uri(&self) -> String {
+        format!("{}#{}", ENTITY_TYPE_VOCABULARY_URI, self.entity_type_code())
+    }
+
+    /// Returns the government level of `entity`, if it is a government entity.
+    pub fn of(entity: &EntityRecord) -> Option<Self> {
+        let code = entity
+            .entity_type_uri
+            .as_deref()?
+            .strip_prefix(ENTITY_TYPE_VOCABULARY_URI)?
+            .strip_prefix('#')?;
+        [Self::Federal, Self::State, Self::Local]
+            .into_iter()
+            .find(|level| level.entity_type_code() == code)
+    }
+}
+
+/// One condition on an entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntityPredicate {
+    /// `jurisdictionIso` equals the value, or is a subdivision of it
+    /// (`"US"` matches `"US-OH"`).
+    Jurisdiction(String),
+    /// The entity's type is the government level's entity type.
+    GovernmentLevel(GovernmentLevel),
+    /// `entityTypeUri` equals the value.
+    EntityTypeUri(String),
+    /// The legal name (or normalized legal name) contains the value,
+    /// ignoring case.
+    NameContains(String),
+    /// The current status code equals the value.
+    Status(EntityStatusCode),
+}
+
+impl EntityPredicate {
+    /// Evaluates the predicate against `entity`.
+    pub fn matches(&self, entity: &EntityRecord) -> bool {
+        match self {
+            Self::Jurisdiction(code) => {
+                entity.jurisdiction_iso == *code
+                    || entity
+                        .jurisdiction_iso
+                        .strip_prefix(code.as_str())
+                        .is_some_and(|rest| rest.starts_with('-'))
+            }
+            Self::GovernmentLevel(level) => GovernmentLevel::of(entity) == Some(*level),
+            Self::EntityTypeUri(uri) => entity.entity_type_uri.as_deref() == Some(uri.as_str()),
+            Self::NameContains(text) => {
+                let needle = text.to_lowercase();
+                entity.legal_name.to_lowercase().contains(&needle)
+                    || entity
+                        .legal_name_normalized
+                        .as_deref()
+                        .is_some_and(|n| n.to_lowercase().contains(&needle))
+            }
+            Self::Status(code) => entity.status.status_code == *code,
+        }
+    }
+}
+
+/// A typed entity search: every predicate must match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntityQuery {
+    predicates: Vec<EntityPredicate>,
+    limit: Option<usize>,
+}
+
+impl EntityQuery {
+    /// Creates a query that matches every entity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts to a jurisdiction (e.g. `"US-OH"`) and its subdivisions.
+    pub fn jurisdiction(self, code: &str) -> Self {
+        self.with(EntityPredicate::Jurisdiction(code.to_string()))
+    }
+
+    /// Restricts to one level of government.
+    pub fn government_level(self, level: GovernmentLevel) -> Self {
+        self.with(EntityPredicate::GovernmentLevel(level))
+    }
+
+    /// Restricts to an entity type URI.
+    pub fn entity_type_uri(self, uri: &str) -> Self {
+        self.with(EntityPredicate::EntityTypeUri(uri.to_string()))
+    }
+
+    /// Restricts to names containing `text`, ignoring case.
+    pub fn name_contains(self, text: &str) -> Self {
+        self.with(EntityPredicate::NameContains(text.to_string()))
+    }
+
+    /// Restricts to a status.
+    pub fn status(self, code: EntityStatusCode) -> Self {
+        self.with(EntityPredicate::Status(code))
+    }
+
+    /// Returns at most `limit` results.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Adds a predicate.
+    pub fn with(mut self, predicate: EntityPredicate) -> Self {
+        self.predicates.push(predicate);
+        self
+    }
+
+    /// The query's predicates, for backends to translate.
+    pub fn predicates(&self) -> &[EntityPredicate] {
+        &self.predicates
+    }
+
+    /// The maximum number of results, if limited.
+    pub fn max_results(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Returns true if `entity` satisfies every predicate.
+    pub fn matches(&self, entity: &EntityRecord) -> bool {
+        self.predicates.iter().all(|p| p.matches(entity))
+    }
+
+    /// Runs the query over an in-memory collection.
+    pub fn filter<'a, I>(&self, entities: I) -> Vec<&'a EntityRecord>
+    where
+        I: IntoIterator<Item = &'a EntityRecord>,
+    {
+        entities
+            .into_iter()
+            .filter(|e| self.matches(e))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::EntityStatus;
+    use crate::identifiers::{EntityIdentifiers, SamUei};
+    use cep_core::{Attestation, ProofType};
+
+    fn entity(name: &str, jurisdiction: &str, level: Option<GovernmentLevel>) -> EntityRecord {
+        let mut entity = EntityRecord::new(
+            format!("cep-entity:test:{}", name.len()),
+            EntityIdentifiers::new().with_sam_uei(SamUei::new("J6H4FB3N5YK7").unwrap()),
+            name.to_string(),
+            jurisdiction.to_string(),
+            EntityStatus {
+                status_code: EntityStatusCode::Active,
+                status_effective_date: "2020-01-15".to_string(),
+                status_termination_date: None,
+                successor_entity_id: None,
+            },
+            Attestation::new(
+                "did:web:example.gov".to_string(),
+                "2025-11-28T14:30:00.000000Z".parse().unwrap(),
+                ProofType::Ed25519Signature2020,
+                "z3FXQq...".to_string(),
+                "did:web:example.gov#key-1".to_string(),
+            ),
+        );
+        entity.entity_type_uri = level.map(|l| l.entity_type_uri());
+        entity
+    }
+
+    fn entities() -> Vec<EntityRecord> {
+        vec![
+            entity(
+                "Cleveland Water Department",
+                "US-OH",
+                Some(GovernmentLevel::Local),
+            ),
+            entity(
+                "Ohio Water Development Authority",
+                "US-OH",
+                Some(GovernmentLevel::State),
+            ),
+            entity(
+                "Columbus Water Works",
+                "US-GA",
+                Some(GovernmentLevel::Local),
+            ),
+            entity("Acme Water Consulting LLC", "US-OH", None),
+        ]
+    }
+
+    #[test]
+    fn test_typed_query() {
+        let entities = entities();
+        let query = EntityQuery::new()
+            .jurisdiction("US-OH")
+            .government_level(GovernmentLevel::Local)
+            .name_contains("WATER");
+        let found = query.filter(&entities);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].legal_name, "Cleveland Water Department");
+    }
+
+    #[test]
+    fn test_jurisdiction_matches_subdivisions_only() {
+        let entities = entities();
+        assert_eq!(
+            EntityQuery::new()
+                .jurisdiction("US")
+                .filter(&entities)
+                .len(),
+            4
+        );
+        assert_eq!(
+            EntityQuery::new()
+                .jurisdiction("US-O")
+                .filter(&entities)
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_empty_query_and_limit() {
+        let entities = entities();
+        assert_eq!(EntityQuery::new().filter(&entities).len(), 4);
+        assert_eq!(EntityQuery::new().limit(2).filter(&entities).len(), 2);
+        assert_eq!(GovernmentLevel::of(&entities[3]), None);
+    }
+}
@@ -0,0 +1,374 @@
+//! Cross-jurisdiction roll-ups.
+//!
+//! A [`Rollup`] counts entities by type per jurisdiction and measures how
+//! well each jurisdiction's entities are identified, by
+//! [identifier tier](crate::EntityIdentifiers::tier). Jurisdiction roll-ups
+//! can be merged upward with [`Rollup::by_country`].
+//!
+//! Federation-wide dashboards should not take counts on trust. A
+//! [`StatisticsRecord`] wraps a roll-up with the hash of the exact set of
+//! records it was computed from and the publishing node's attestation:
+//!
+//! ```rust
+//! use cep_core::{Ed25519Signer, Ed25519Verifier, FixedClock, KeyFile};
+//! use cep_entity::current::CurrentEntities;
+//! use cep_entity::rollup::{Rollup, StatisticsRecord};
+//! # let revisions: Vec<cep_entity::EntityRecord> = Vec::new();
+//!
+//! let current = CurrentEntities::build(revisions).unwrap();
+//! let keys = KeyFile::generate_ed25519();
+//! let clock = FixedClock::new("2026-01-01T00:00:00.000000Z".parse().unwrap());
+//!
+//! let stats = StatisticsRecord::issue(
+//!     Rollup::of(current.iter().map(|row| &row.record)),
+//!     "did:web:stats.example.gov",
+//!     "did:web:stats.example.gov#key-1",
+//!     &Ed25519Signer::new(keys.signing_key().unwrap()),
+//!     &clock,
+//! )
+//! .unwrap();
+//! stats
+//!     .verify_signature(&Ed25519Verifier::new(keys.verifying_key().unwrap()))
+//!     .unwrap();
+//! ```
+
+use crate::entity::EntityRecord;
+use cep_core::canonical::{insert_number, insert_required};
+use cep_core::signer::{Signer, Verifier};
+use cep_core::spec::{CanonicalSpec, FieldEncoding, TypeSpec};
+use cep_core::{Attestation, CanonicalHash, CanonicalTimestamp, Canonicalize, CepResult, Clock};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Key used for entities without an `entityTypeUri`.
+pub const UNSPECIFIED_TYPE: &str = "unspecified";
+
+/// Number of entities at each identifier tier.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TierCoverage {
+    /// Entities with an LEI.
+    pub tier1: u64,
+    /// Entities with a SAM UEI but no LEI.
+    pub tier2: u64,
+    /// Entities identified only by SNFEI.
+    pub tier3: u64,
+    /// Entities with none of the tiered identifiers.
+    pub untiered: u64,
+}
+
+impl TierCoverage {
+    /// Counts one entity at `tier`.
+    pub fn add(&mut self, tier: Option<u8>) {
+        match tier {
+            Some(1) => self.tier1 += 1,
+            Some(2) => self.tier2 += 1,
+            Some(3) => self.tier3 += 1,
+            _ => self.untiered += 1,
+        }
+    }
+
+    /// Total number of entities counted.
+    pub fn total(&self) -> u64 {
+        self.tier1 + self.tier2 + self.tier3 + self.untiered
+    }
+
+    fn merge(&mut self, other: &TierCoverage) {
+        self.tier1 += other.tier1;
+        self.tier2 += other.tier2;
+        self.tier3 += other.tier3;
+        self.untiered += other.untiered;
+    }
+}
+
+/// Entity counts per jurisdiction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rollup {
+    /// Canonical hash over the hashes of the records counted, sorted. Two
+    /// roll-ups with the same input hash were computed from the same records.
+    pub input_hash: CanonicalHash,
+
+    /// Entity counts by jurisdiction, then by `entityTypeUri`.
+    pub entities_by_type: BTreeMap<String, BTreeMap<String, u64>>,
+
+    /// Identifier tier coverage by jurisdiction.
+    pub identifier_coverage: BTreeMap<String, TierCoverage>,
+}
+
+impl Rollup {
+    /// Aggregates `entities` by jurisdiction.
+    pub fn of<'a, I>(entities: I) -> Self
+    where
+        I: IntoIterator<Item = &'a EntityRecord>,
+    {
+        let mut entities_by_type: BTreeMap<String, BTreeMap<String, u64>> = BTreeMap::new();
+        let mut identifier_coverage: BTreeMap<String, TierCoverage> = BTreeMap::new();
+        let mut hashes = Vec::new();
+        for entity in entities {
+            hashes.push(entity.calculate_hash().as_hex().to_string());
+            let jurisdiction = entity.jurisdiction_iso.clone();
+            let entity_type = entity
+                .entity_type_uri
+                .clone()
+                .unwrap_or_else(|| UNSPECIFIED_TYPE.to_string());
+            *entities_by_type
+                .entry(jurisdiction.clone())
+                .or_default()
+                .entry(entity_type)
+                .or_default() += 1;
+            identifier_coverage
+                .entry(jurisdiction)
+                .or_default()
+                .add(entity.identifiers.tier());
+        }
+        hashes.sort();
+        Self {
+            input_hash: CanonicalHash::from_canonical_string(&hashes.join(",")),
+            entities_by_type,
+            identifier_coverage,
+        }
+    }
+
+    /// Merges subdivisions into their country (`"US-TX"` into `"US"`).
+    pub fn by_country(&self) -> Self {
+        let mut rollup = Self {
+            input_hash: self.input_hash.clone(),
+            entities_by_type: BTreeMap::new(),
+            identifier_coverage: BTreeMap::new(),
+        };
+        for (jurisdiction, types) in &self.entities_by_type {
+            let country = rollup
+                .entities_by_type
+                .entry(country_of(jurisdiction).to_string())
+                .or_default();
+            for (entity_type, count) in types {
+                *country.entry(entity_type.clone()).or_default() += count;
+            }
+        }
+        for (jurisdiction, coverage) in &self.identifier_coverage {
+            rollup
+                .identifier_coverage
+                .entry(country_of(jurisdiction).to_string())
+                .or_default()
+                .merge(coverage);
+        }
+        rollup
+    }
+
+    /// Total number of entities counted.
+    pub fn entity_count(&self) -> u64 {
+        self.identifier_coverage.values().map(|c| c.total()).sum()
+    }
+}
+
+fn country_of(jurisdiction: &str) -> &str {
+    jurisdiction.split('-').next().unwrap_or(jurisdiction)
+}
+
+/// A roll-up attested by the node that computed it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatisticsRecord {
+    /// The statistics.
+    #[serde(flatten)]
+    pub rollup: Rollup,
+
+    /// When the statistics were computed.
+    pub generated_timestamp: CanonicalTimestamp,
+
+    /// The computing node's attestation over this record.
+    pub attestation: Attestation,
+}
+
+impl StatisticsRecord {
+    /// Issues a statistics record signed with `signer`, stamped with the
+    /// time of `clock`.
+    ///
+    /// # Errors
+    ///
+    /// Passes through the signer's errors.
+    pub fn issue(
+        rollup: Rollup,
+        attestor_id: &str,
+        verification_method: &str,
+        signer: &dyn Signer,
+        clock: &dyn Clock,
+    ) -> CepResult<Self> {
+        let generated_timestamp = clock.now();
+        let mut record = Self {
+            rollup,
+            generated_timestamp,
+            attestation: Attestation::new(
+                attestor_id.to_string(),
+                generated_timestamp,
+                signer.proof_type(),
+                String::new(),
+                verification_method.to_string(),
+            ),
+        };
+        let mut attestation = record.attestation.clone();
+        attestation.prove(&record, signer)?;
+        record.attestation = attestation;
+        Ok(record)
+    }
+
+    /// Verifies the record's signature with `verifier`, which holds the
+    /// attestor's public key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidSignature` if the record's proof type is not
+    /// the verifier's or the signature does not verify.
+    pub fn verify_signature(&self, verifier: &dyn Verifier) -> CepResult<()> {
+        self.attestation.verify(self, verifier)
+    }
+}
+
+impl Canonicalize for StatisticsRecord {
+    fn canonical_fields(&self) -> BTreeMap<String, String> {
+        let mut map = BTreeMap::new();
+
+        // Fields in alphabetical order
+        insert_required(
+            &mut map,
+            "attestation",
+            &self.attestation.to_canonical_string(),
+        );
+        insert_required(
+            &mut map,
+            "entitiesByType",
+            &serde_json::to_string(&self.rollup.entities_by_type).unwrap_or_default(),
+        );
+        insert_number(&mut map, "entityCount", self.rollup.entity_count());
+        insert_required(
+            &mut map,
+            "generatedTimestamp",
+            &self.generated_timestamp.to_canonical_string(),
+        );
+        insert_required(
+            &mut map,
+            "identifierCoverage",
+            &serde_json::to_string(&self.rollup.identifier_coverage).unwrap_or_default(),
+        );
+        insert_required(&mut map, "inputHash", self.rollup.input_hash.as_hex());
+
+        map
+    }
+}
+
+impl CanonicalSpec for StatisticsRecord {
+    fn type_spec() -> TypeSpec {
+        // The count maps are JSON objects with sorted keys.
+        TypeSpec::new("StatisticsRecord")
+            .required("attestation", FieldEncoding::nested("Attestation"))
+            .required("entitiesByType", FieldEncoding::Json)
+            .required("entityCount", FieldEncoding::Integer)
+            .required("generatedTimestamp", FieldEncoding::Timestamp)
+            .required("identifierCoverage", FieldEncoding::Json)
+            .required("inputHash", FieldEncoding::Hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{EntityStatus, EntityStatusCode};
+    use crate::identifiers::{EntityIdentifiers, Lei, SamUei};
+    use crate::query::GovernmentLevel;
+    use cep_core::CepError;
+    use cep_core::{Ed25519Signer, Ed25519Verifier, FixedClock, KeyFile, ProofType};
+
+    fn entity(id: &str, jurisdiction: &str, identifiers: EntityIdentifiers) -> EntityRecord {
+        EntityRecord::new(
+            format!("cep-entity:test:{}", id),
+            identifiers,
+            format!("Entity {}", id),
+            jurisdiction.to_string(),
+            EntityStatus {
+                status_code: EntityStatusCode::Active,
+                status_effective_date: "2020-01-15".to_string(),
+                status_termination_date: None,
+                successor_entity_id: None,
+            },
+            Attestation::new(
+                "did:web:example.gov".to_string(),
+                "2025-11-28T14:30:00.000000Z".parse().unwrap(),
+                ProofType::Ed25519Signature2020,
+                "z3FXQq...".to_string(),
+                "did:web:example.gov#key-1".to_string(),
+            ),
+        )
+    }
+
+    fn entities() -> Vec<EntityRecord> {
+        let uei = || EntityIdentifiers::new().with_sam_uei(SamUei::new("J6H4FB3N5YK7").unwrap());
+        vec![
+            entity("a", "US-TX", uei()).with_entity_type(GovernmentLevel::Local.entity_type_uri()),
+            entity("b", "US-TX", uei()).with_entity_type(GovernmentLevel::Local.entity_type_uri()),
+            entity(
+                "c",
+                "US-OH",
+                uei().with_lei(Lei::new("5493001KJTIIGC8Y1R12").unwrap()),
+            ),
+            entity("d", "US-OH", EntityIdentifiers::new()),
+        ]
+    }
+
+    #[test]
+    fn test_counts_per_jurisdiction_and_country() {
+        let rollup = Rollup::of(&entities());
+        let local = GovernmentLevel::Local.entity_type_uri();
+        assert_eq!(rollup.entities_by_type["US-TX"][&local], 2);
+        assert_eq!(rollup.entities_by_type["US-OH"][UNSPECIFIED_TYPE], 2);
+        assert_eq!(
+            rollup.identifier_coverage["US-OH"],
+            TierCoverage {
+                tier1: 1,
+                untiered: 1,
+                ..TierCoverage::default()
+            }
+        );
+
+        let national = rollup.by_country();
+        assert_eq!(national.entity_count(), 4);
+        assert_eq!(national.identifier_coverage["US"].tier2, 2);
+        assert_eq!(national.input_hash, rollup.input_hash);
+
+        // The input hash does not depend on order.
+        let mut reversed = entities();
+        reversed.reverse();
+        assert_eq!(Rollup::of(&reversed), rollup);
+    }
+
+    #[test]
+    fn test_signed_statistics_detect_tampering() {
+        let keys = KeyFile::generate_ed25519();
+        let clock = FixedClock::new("2026-01-01T00:00:00.000000Z".parse().unwrap());
+        let stats = StatisticsRecord::issue(
+            Rollup::of(&entities()),
+            "did:web:stats.example.gov",
+            "did:web:stats.example.gov#key-1",
+            &Ed25519Signer::new(keys.signing_key().unwrap()),
+            &clock,
+        )
+        .unwrap();
+        let verifier = Ed25519Verifier::new(keys.verifying_key().unwrap());
+
+        let json = serde_json::to_string(&stats).unwrap();
+        let received: StatisticsRecord = serde_json::from_str(&json).unwrap();
+        received.verify_signature(&verifier).unwrap();
+
+        let mut inflated = received.clone();
+        inflated
+            .rollup
+            .identifier_coverage
+            .get_mut("US-TX")
+            .unwrap()
+            .tier1 += 10;
+        assert!(matches!(
+            inflated.verify_signature(&verifier),
+            Err(CepError::InvalidSignature(_))
+        ));
+    }
+}
@@ -1,8 +1,8 @@
-/// Exchange builder: raw data -> canonical CEP ExchangeRecord.
-///
-/// Transforms heterogeneous input data into fully-formed ExchangeRecord.
+//! Exchange builder: raw data -> canonical CEP ExchangeRecord.
+//!
+//! Transforms heterogeneous input data into fully-formed ExchangeRecord.
 
-use cep_core::{Attestation, CanonicalTimestamp, CepError, CepResult};
+use cep_core::{Attestation, CanonicalTimestamp, CepError, CepResult, ProofType};
 
 use crate::{
     ExchangeCategorization, ExchangeParty, ExchangeRecord, ExchangeStatus, ExchangeStatusCode,
@@ -144,7 +144,7 @@ pub fn build_exchange(input: ExchangeBuilderInput) -> CepResult<ExchangeBuildRes
     // Build status
     let status = ExchangeStatus {
         status_code: ExchangeStatusCode::Completed,
-        status_effective_timestamp: occurred_timestamp.clone(),
+        status_effective_timestamp: occurred_timestamp,
     };
 
     // Generate relationship ID
@@ -246,7 +246,7 @@ fn build_attestation(input: &AttestationInput) -> CepResult<Attestation> {
     Ok(Attestation::new(
         input.attested_by.clone(),
         timestamp,
-        "ManualAttestation".to_string(),
+        ProofType::ManualAttestation,
         String::new(),
         verification_method_uri,
     ))
@@ -1,15 +1,20 @@
-/// CEP Exchange Record definition.
-///
-/// An Exchange Record represents a verifiable value exchange (financial, in-kind,
-/// or informational) between entities within an established relationship.
-/// This is the atomic unit of civic transparency.
+//! CEP Exchange Record definition.
+//!
+//! An Exchange Record represents a verifiable value exchange (financial, in-kind,
+//! or informational) between entities within an established relationship.
+//! This is the atomic unit of civic transparency.
 
 use crate::provenance::{ExchangeCategorization, ProvenanceChain};
 use crate::value::{ExchangeParty, ExchangeValue};
-use cep_core::canonical::{insert_if_present, insert_required, Canonicalize};
+use cep_core::canonical::{
+    insert_if_present, insert_list, insert_number, insert_required, Canonicalize, HashDomain,
+};
+use cep_core::spec::{CanonicalSpec, FieldEncoding, TypeSpec};
 use cep_core::hash::CanonicalHash;
 use cep_core::timestamp::CanonicalTimestamp;
-use cep_core::{Attestation, SCHEMA_VERSION};
+use cep_core::{
+    Attestation, Authority, Classification, Classified, Freshness, Revisioned, SCHEMA_VERSION,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -57,6 +62,14 @@ impl Canonicalize for ExchangeStatus {
     }
 }
 
+impl CanonicalSpec for ExchangeStatus {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("ExchangeStatus")
+            .required("statusCode", FieldEncoding::String)
+            .required("statusEffectiveTimestamp", FieldEncoding::Timestamp)
+    }
+}
+
 /// Reference to an authoritative source record.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -77,6 +90,15 @@ impl Canonicalize for SourceReference {
     }
 }
 
+impl CanonicalSpec for SourceReference {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("SourceReference")
+            .required("sourceRecordId", FieldEncoding::String)
+            .required("sourceSystemUri", FieldEncoding::String)
+            .optional("sourceUrl", FieldEncoding::String)
+    }
+}
+
 /// A complete CEP Exchange Record.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -129,10 +151,31 @@ pub struct ExchangeRecord {
 
     /// Revision number.
     pub revision_number: u32,
+
+    /// Hash of the schema snapshot the record was validated against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_snapshot_hash: Option<CanonicalHash>,
+
+    /// Version of the vocabularies the record was validated against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vocab_version: Option<String>,
+
+    /// Access classification; absent means public.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub classification: Option<Classification>,
+
+    /// Statutory authority or agreement the record is shared under.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authority: Option<Authority>,
+
+    /// Publisher-declared refresh and expiry dates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freshness: Option<Freshness>,
 }
 
 impl ExchangeRecord {
     /// Creates a new ExchangeRecord with required fields.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         verifiable_id: String,
         relationship_id: String,
@@ -160,6 +203,11 @@ impl ExchangeRecord {
             attestation,
             previous_record_hash: None,
             revision_number: 1,
+            schema_snapshot_hash: None,
+            vocab_version: None,
+            classification: None,
+            authority: None,
+            freshness: None,
         }
     }
 
@@ -189,6 +237,31 @@ impl ExchangeRecord {
         self.revision_number = revision;
         self
     }
+
+    pub fn with_schema_snapshot(mut self, hash: CanonicalHash) -> Self {
+        self.schema_snapshot_hash = Some(hash);
+        self
+    }
+
+    pub fn with_vocab_version(mut self, version: String) -> Self {
+        self.vocab_version = Some(version);
+        self
+    }
+
+    pub fn with_classification(mut self, classification: Classification) -> Self {
+        self.classification = Some(classification);
+        self
+    }
+
+    pub fn with_authority(mut self, authority: Authority) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    pub fn with_freshness(mut self, freshness: Freshness) -> Self {
+        self.freshness = Some(freshness);
+        self
+    }
 }
 
 impl Canonicalize for ExchangeRecord {
@@ -197,63 +270,136 @@ impl Canonicalize for ExchangeRecord {
 
         // All fields in alphabetical order
         insert_required(&mut map, "attestation", &self.attestation.to_canonical_string());
+        if let Some(ref authority) = self.authority {
+            insert_required(&mut map, "authority", &authority.to_canonical_string());
+        }
 
-        if let Some(ref cat) = self.categorization {
-            if cat.has_any() {
-                insert_required(&mut map, "categorization", &cat.to_canonical_string());
-            }
+        if let Some(ref cat) = self.categorization
+            && cat.has_any()
+        {
+            insert_required(&mut map, "categorization", &cat.to_canonical_string());
         }
+        insert_if_present(&mut map, "classification", self.classification.map(|c| c.as_str()));
 
         insert_required(&mut map, "exchangeTypeUri", &self.exchange_type_uri);
+        if let Some(ref freshness) = self.freshness {
+            insert_required(&mut map, "freshness", &freshness.to_canonical_string());
+        }
         insert_required(&mut map, "occurredTimestamp", &self.occurred_timestamp.to_canonical_string());
 
         if let Some(ref hash) = self.previous_record_hash {
             insert_required(&mut map, "previousRecordHash", hash.as_hex());
         }
 
-        if let Some(ref chain) = self.provenance_chain {
-            if chain.has_any() {
-                insert_required(&mut map, "provenanceChain", &chain.to_canonical_string());
-            }
+        if let Some(ref chain) = self.provenance_chain
+            && chain.has_any()
+        {
+            insert_required(&mut map, "provenanceChain", &chain.to_canonical_string());
         }
 
         insert_required(&mut map, "recipientEntity", &self.recipient_entity.to_canonical_string());
         insert_required(&mut map, "relationshipId", &self.relationship_id);
-        insert_required(&mut map, "revisionNumber", &self.revision_number.to_string());
+        insert_number(&mut map, "revisionNumber", self.revision_number);
+        if let Some(ref hash) = self.schema_snapshot_hash {
+            insert_required(&mut map, "schemaSnapshotHash", hash.as_hex());
+        }
         insert_required(&mut map, "schemaVersion", &self.schema_version);
 
         // Source references sorted by sourceSystemUri then sourceRecordId
         if let Some(ref refs) = self.source_references {
-            if !refs.is_empty() {
-                let mut sorted: Vec<_> = refs.iter().collect();
-                sorted.sort_by(|a, b| {
-                    (&a.source_system_uri, &a.source_record_id)
-                        .cmp(&(&b.source_system_uri, &b.source_record_id))
-                });
-                let json: Vec<String> = sorted.iter().map(|r| r.to_canonical_string()).collect();
-                map.insert("sourceReferences".to_string(), format!("[{}]", json.join(",")));
-            }
+            let mut sorted: Vec<_> = refs.iter().collect();
+            sorted.sort_by(|a, b| {
+                (&a.source_system_uri, &a.source_record_id)
+                    .cmp(&(&b.source_system_uri, &b.source_record_id))
+            });
+            insert_list(&mut map, "sourceReferences", sorted);
         }
 
         insert_required(&mut map, "sourceEntity", &self.source_entity.to_canonical_string());
         insert_required(&mut map, "status", &self.status.to_canonical_string());
         insert_required(&mut map, "value", &self.value.to_canonical_string());
         insert_required(&mut map, "verifiableId", &self.verifiable_id);
+        insert_if_present(&mut map, "vocabVersion", self.vocab_version.as_deref());
 
         map
     }
+
+    fn hash_domain(&self) -> Option<HashDomain> {
+        Some(HashDomain::new("cep.exchange", &self.schema_version))
+    }
+}
+
+impl Classified for ExchangeRecord {
+    fn classification(&self) -> Classification {
+        self.classification.unwrap_or_default()
+    }
+}
+
+impl Revisioned for ExchangeRecord {
+    fn verifiable_id(&self) -> &str {
+        &self.verifiable_id
+    }
+
+    fn revision_number(&self) -> u32 {
+        self.revision_number
+    }
+
+    fn previous_record_hash(&self) -> Option<&CanonicalHash> {
+        self.previous_record_hash.as_ref()
+    }
+
+    fn revoked_at(&self) -> Option<CanonicalTimestamp> {
+        matches!(
+            self.status.status_code,
+            ExchangeStatusCode::Reversed | ExchangeStatusCode::Canceled
+        )
+        .then_some(self.status.status_effective_timestamp)
+    }
+}
+
+impl CanonicalSpec for ExchangeRecord {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("ExchangeRecord")
+            .required("attestation", FieldEncoding::nested("Attestation"))
+            .optional("authority", FieldEncoding::nested("Authority"))
+            .optional("categorization", FieldEncoding::nested("ExchangeCategorization"))
+            .optional("classification", FieldEncoding::String)
+            .required("exchangeTypeUri", FieldEncoding::String)
+            .optional("freshness", FieldEncoding::nested("Freshness"))
+            .required("occurredTimestamp", FieldEncoding::Timestamp)
+            .optional("previousRecordHash", FieldEncoding::Hash)
+            .optional("provenanceChain", FieldEncoding::nested("ProvenanceChain"))
+            .required("recipientEntity", FieldEncoding::nested("ExchangeParty"))
+            .required("relationshipId", FieldEncoding::String)
+            .required("revisionNumber", FieldEncoding::Integer)
+            .optional("schemaSnapshotHash", FieldEncoding::Hash)
+            .required("schemaVersion", FieldEncoding::String)
+            .optional(
+                "sourceReferences",
+                FieldEncoding::nested_list(
+                    "SourceReference",
+                    &["sourceSystemUri", "sourceRecordId"],
+                ),
+            )
+            .required("sourceEntity", FieldEncoding::nested("ExchangeParty"))
+            .required("status", FieldEncoding::nested("ExchangeStatus"))
+            .required("value", FieldEncoding::nested("ExchangeValue"))
+            .required("verifiableId", FieldEncoding::String)
+            .optional("vocabVersion", FieldEncoding::String)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::provenance::IntermediaryEntity;
+    use cep_core::ProofType;
 
     fn test_attestation() -> Attestation {
         Attestation::new(
             "cep-entity:sam-uei:ATTESTOR123A".to_string(),
             "2025-11-28T14:30:00.000000Z".parse().unwrap(),
-            "Ed25519Signature2020".to_string(),
+            ProofType::Ed25519Signature2020,
             "z3FXQqFwbZxKBxGxqFpCDabcdef1234567890".to_string(),
             "did:web:example.gov#key-1".to_string(),
         )
@@ -333,6 +479,18 @@ mod tests {
     // TEST VECTOR OUTPUT
     // ========================================
 
+    #[test]
+    fn test_canonical_spec_matches_fields() {
+        let spec = ExchangeRecord::type_spec();
+        let keys: Vec<String> = test_exchange().canonical_fields().into_keys().collect();
+        for required in spec.required_field_names() {
+            assert!(keys.iter().any(|k| k == required), "missing required field {}", required);
+        }
+        for key in &keys {
+            assert!(spec.field_names().contains(&key.as_str()), "unlisted field {}", key);
+        }
+    }
+
     #[test]
     fn test_vector_basic_exchange() {
         let exchange = test_exchange()
@@ -1,9 +1,10 @@
-/// Provenance chain tracking for CEP exchanges.
-///
-/// Traces the compositional flow of funds through the civic graph.
-/// This is the Category Theory morphism path implementation.
+//! Provenance chain tracking for CEP exchanges.
+//!
+//! Traces the compositional flow of funds through the civic graph.
+//! This is the Category Theory morphism path implementation.
 
-use cep_core::canonical::{insert_if_present, insert_required, Canonicalize};
+use cep_core::canonical::{insert_if_present, insert_list, insert_required, Canonicalize};
+use cep_core::spec::{CanonicalSpec, FieldEncoding, TypeSpec};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -42,6 +43,14 @@ impl Canonicalize for IntermediaryEntity {
     }
 }
 
+impl CanonicalSpec for IntermediaryEntity {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("IntermediaryEntity")
+            .required("entityId", FieldEncoding::String)
+            .optional("roleUri", FieldEncoding::String)
+    }
+}
+
 /// Provenance chain tracing the flow of funds.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -101,7 +110,7 @@ impl ProvenanceChain {
     pub fn has_any(&self) -> bool {
         self.funding_chain_tag.is_some()
             || self.ultimate_source_entity_id.is_some()
-            || self.intermediary_entities.as_ref().map_or(false, |v| !v.is_empty())
+            || self.intermediary_entities.as_ref().is_some_and(|v| !v.is_empty())
             || self.parent_exchange_id.is_some()
     }
 }
@@ -114,12 +123,7 @@ impl Canonicalize for ProvenanceChain {
 
         // Intermediary entities serialized as array
         if let Some(ref entities) = self.intermediary_entities {
-            if !entities.is_empty() {
-                let json: Vec<String> = entities.iter()
-                    .map(|e| e.to_canonical_string())
-                    .collect();
-                map.insert("intermediaryEntities".to_string(), format!("[{}]", json.join(",")));
-            }
+            insert_list(&mut map, "intermediaryEntities", entities);
         }
 
         insert_if_present(&mut map, "parentExchangeId", self.parent_exchange_id.as_deref());
@@ -129,6 +133,20 @@ impl Canonicalize for ProvenanceChain {
     }
 }
 
+impl CanonicalSpec for ProvenanceChain {
+    fn type_spec() -> TypeSpec {
+        // Intermediaries keep their funding-flow order (no sort).
+        TypeSpec::new("ProvenanceChain")
+            .optional("fundingChainTag", FieldEncoding::String)
+            .optional(
+                "intermediaryEntities",
+                FieldEncoding::nested_list("IntermediaryEntity", &[]),
+            )
+            .optional("parentExchangeId", FieldEncoding::String)
+            .optional("ultimateSourceEntityId", FieldEncoding::String)
+    }
+}
+
 /// Categorization codes for reporting and analysis.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -200,6 +218,17 @@ impl Canonicalize for ExchangeCategorization {
     }
 }
 
+impl CanonicalSpec for ExchangeCategorization {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("ExchangeCategorization")
+            .optional("cfdaNumber", FieldEncoding::String)
+            .optional("gtasAccountCode", FieldEncoding::String)
+            .optional("localCategoryCode", FieldEncoding::String)
+            .optional("localCategoryLabel", FieldEncoding::String)
+            .optional("naicsCode", FieldEncoding::String)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,8 +1,9 @@
-/// Value types for CEP exchanges.
-///
-/// Supports monetary values (with currency) and in-kind contributions.
+//! Value types for CEP exchanges.
+//!
+//! Supports monetary values (with currency) and in-kind contributions.
 
 use cep_core::canonical::{format_amount, insert_if_present, insert_required, Canonicalize};
+use cep_core::spec::{CanonicalSpec, FieldEncoding, TypeSpec};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -101,6 +102,16 @@ impl Canonicalize for ExchangeValue {
     }
 }
 
+impl CanonicalSpec for ExchangeValue {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("ExchangeValue")
+            .required("amount", FieldEncoding::amount())
+            .required("currencyCode", FieldEncoding::String)
+            .optional("inKindDescription", FieldEncoding::String)
+            .required("valueTypeUri", FieldEncoding::String)
+    }
+}
+
 /// A party in an exchange (source or recipient).
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -147,6 +158,15 @@ impl Canonicalize for ExchangeParty {
     }
 }
 
+impl CanonicalSpec for ExchangeParty {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("ExchangeParty")
+            .optional("accountIdentifier", FieldEncoding::String)
+            .required("entityId", FieldEncoding::String)
+            .optional("roleUri", FieldEncoding::String)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
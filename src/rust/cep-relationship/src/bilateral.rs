@@ -1,10 +1,11 @@
-/// Bilateral party definitions for two-party relationships.
-///
-/// Bilateral relationships have clear directionality:
-/// - **Party A**: The initiating, granting, or contracting party
-/// - **Party B**: The receiving, performing, or beneficiary party
+//! Bilateral party definitions for two-party relationships.
+//!
+//! Bilateral relationships have clear directionality:
+//! - **Party A**: The initiating, granting, or contracting party
+//! - **Party B**: The receiving, performing, or beneficiary party
 
 use cep_core::canonical::{insert_required, Canonicalize};
+use cep_core::spec::{CanonicalSpec, FieldEncoding, TypeSpec};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -33,6 +34,14 @@ impl Canonicalize for Party {
     }
 }
 
+impl CanonicalSpec for Party {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("Party")
+            .required("entityId", FieldEncoding::String)
+            .required("roleUri", FieldEncoding::String)
+    }
+}
+
 /// Bilateral parties in a two-party relationship.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -59,6 +68,14 @@ impl Canonicalize for BilateralParties {
     }
 }
 
+impl CanonicalSpec for BilateralParties {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("BilateralParties")
+            .required("partyA", FieldEncoding::nested("Party"))
+            .required("partyB", FieldEncoding::nested("Party"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,8 +1,8 @@
-/// Relationship builder: raw data -> canonical CEP RelationshipRecord.
-///
-/// Transforms heterogeneous input data into fully-formed RelationshipRecord.
+//! Relationship builder: raw data -> canonical CEP RelationshipRecord.
+//!
+//! Transforms heterogeneous input data into fully-formed RelationshipRecord.
 
-use cep_core::{Attestation, CanonicalTimestamp, CepError, CepResult};
+use cep_core::{Attestation, CanonicalTimestamp, CepError, CepResult, ProofType};
 
 use crate::{
     BilateralParties, FinancialTerms, Party, RelationshipRecord, RelationshipStatus,
@@ -192,7 +192,7 @@ pub fn build_relationship(input: RelationshipBuilderInput) -> CepResult<Relation
 
     let status = RelationshipStatus {
         status_code,
-        status_effective_timestamp: effective_timestamp.clone(),
+        status_effective_timestamp: effective_timestamp,
     };
 
     // Build relationship type URI
@@ -286,7 +286,7 @@ fn build_attestation(input: &AttestationInput) -> CepResult<Attestation> {
     Ok(Attestation::new(
         input.attested_by.clone(),
         timestamp,
-        "ManualAttestation".to_string(),
+        ProofType::ManualAttestation,
         String::new(),
         verification_method_uri,
     ))
@@ -1,45 +1,48 @@
-/// # CEP Relationship
-///
-/// Relationship records for the Civic Exchange Protocol (CEP).
-///
-/// This crate defines the [`RelationshipRecord`] type, 
-/// which represents a verifiable legal or functional
-/// relationship between two or more attested entities.
-///
-/// ## Bilateral Relationships
-///
-/// Two-party relationships with clear directionality:
-///
-/// ```rust
-/// use cep_relationship::{RelationshipRecord, BilateralParties, Party, RelationshipStatus, RelationshipStatusCode};
-/// use cep_core::{Attestation, Canonicalize};
-///
-/// let parties = BilateralParties::new(
-///     Party::new("cep-entity:sam-uei:AGENCY123".to_string(), "role-uri".to_string()),
-///     Party::new("cep-entity:sam-uei:VENDOR456".to_string(), "role-uri".to_string()),
-/// );
-///
-/// // ... create relationship with parties
-/// ```
-///
-/// ## Multilateral Relationships
-///
-/// N-ary relationships with deterministic member ordering:
-///
-/// ```rust
-/// use cep_relationship::{MultilateralMembers, Member};
-///
-/// let mut members = MultilateralMembers::new();
-/// members.add(Member::new("entity-a".to_string(), "role".to_string()));
-/// members.add(Member::new("entity-b".to_string(), "role".to_string()));
-/// // Members are automatically sorted by entity_id for hash stability
-/// ```
+//! # CEP Relationship
+//!
+//! Relationship records for the Civic Exchange Protocol (CEP).
+//!
+//! This crate defines the [`RelationshipRecord`] type, 
+//! which represents a verifiable legal or functional
+//! relationship between two or more attested entities.
+//!
+//! ## Bilateral Relationships
+//!
+//! Two-party relationships with clear directionality:
+//!
+//! ```rust
+//! use cep_relationship::{RelationshipRecord, BilateralParties, Party, RelationshipStatus, RelationshipStatusCode};
+//! use cep_core::{Attestation, Canonicalize};
+//!
+//! let parties = BilateralParties::new(
+//!     Party::new("cep-entity:sam-uei:AGENCY123".to_string(), "role-uri".to_string()),
+//!     Party::new("cep-entity:sam-uei:VENDOR456".to_string(), "role-uri".to_string()),
+//! );
+//!
+//! // ... create relationship with parties
+//! ```
+//!
+//! ## Multilateral Relationships
+//!
+//! N-ary relationships with deterministic member ordering:
+//!
+//! ```rust
+//! use cep_relationship::{MultilateralMembers, Member};
+//!
+//! let mut members = MultilateralMembers::new();
+//! members.add(Member::new("entity-a".to_string(), "role".to_string()));
+//! members.add(Member::new("entity-b".to_string(), "role".to_string()));
+//! // Members are automatically sorted by entity_id for hash stability
+//! ```
 
 pub mod bilateral;
 pub mod builder;
 pub mod multilateral;
 pub mod relationship;
 
+use cep_core::spec::{CanonicalSpec, CanonicalizationSpec};
+use cep_core::{Attestation, Authority, Freshness};
+
 // Re-export primary types
 pub use bilateral::{BilateralParties, Party};
 pub use builder::{
@@ -56,4 +59,21 @@ pub use relationship::{
 /// Expose the JSON Schema via cep-core.
 pub fn relationship_schema_json() -> Option<&'static str> {
     cep_core::get_schema("relationship")
-}
\ No newline at end of file
+}
+
+/// Canonicalization spec for relationship records and their nested types.
+pub fn relationship_canonical_spec() -> CanonicalizationSpec {
+    CanonicalizationSpec::current().with_types([
+        RelationshipRecord::type_spec(),
+        Attestation::type_spec(),
+        Authority::type_spec(),
+        Freshness::type_spec(),
+        BilateralParties::type_spec(),
+        Party::type_spec(),
+        MultilateralMembers::type_spec(),
+        Member::type_spec(),
+        FinancialTerms::type_spec(),
+        RelationshipStatus::type_spec(),
+        SourceReference::type_spec(),
+    ])
+}
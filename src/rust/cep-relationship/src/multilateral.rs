@@ -1,14 +1,15 @@
-/// Multilateral member definitions for n-ary relationships.
-///
-/// Multilateral relationships involve more than two parties, such as:
-/// - Consortia
-/// - Joint ventures
-/// - Board memberships
-///
-/// Members are stored in a `BTreeSet` to guarantee deterministic ordering
-/// for hash stability across all implementations.
-
-use cep_core::canonical::{insert_required, Canonicalize};
+//! Multilateral member definitions for n-ary relationships.
+//!
+//! Multilateral relationships involve more than two parties, such as:
+//! - Consortia
+//! - Joint ventures
+//! - Board memberships
+//!
+//! Members are stored in a `BTreeSet` to guarantee deterministic ordering
+//! for hash stability across all implementations.
+
+use cep_core::canonical::{insert_list, insert_required, Canonicalize};
+use cep_core::spec::{CanonicalSpec, FieldEncoding, TypeSpec};
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, BTreeSet};
@@ -74,6 +75,15 @@ impl Canonicalize for Member {
     }
 }
 
+impl CanonicalSpec for Member {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("Member")
+            .required("entityId", FieldEncoding::String)
+            .optional("participationShare", FieldEncoding::Decimal { places: 4 })
+            .required("roleUri", FieldEncoding::String)
+    }
+}
+
 /// A collection of members in a multilateral relationship.
 ///
 /// Uses `BTreeSet` to guarantee members are always sorted by `entity_id`,
@@ -131,20 +141,20 @@ impl MultilateralMembers {
 
 impl Canonicalize for MultilateralMembers {
     fn canonical_fields(&self) -> BTreeMap<String, String> {
-        // Serialize as a JSON array for the canonical form
         // Members are already sorted by entity_id due to BTreeSet
-        let members_json: Vec<String> = self.0.iter()
-            .map(|m| m.to_canonical_string())
-            .collect();
-        
         let mut map = BTreeMap::new();
-        if !members_json.is_empty() {
-            map.insert("members".to_string(), format!("[{}]", members_json.join(",")));
-        }
+        insert_list(&mut map, "members", &self.0);
         map
     }
 }
 
+impl CanonicalSpec for MultilateralMembers {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("MultilateralMembers")
+            .optional("members", FieldEncoding::nested_list("Member", &["entityId"]))
+    }
+}
+
 impl FromIterator<Member> for MultilateralMembers {
     fn from_iter<I: IntoIterator<Item = Member>>(iter: I) -> Self {
         Self(iter.into_iter().collect())
@@ -1,18 +1,24 @@
-/// CEP Relationship Record definition.
-///
-/// A Relationship Record represents a verifiable legal or functional relationship
-/// between two or more attested entities.
-///
-/// Relationships can be:
-/// - **Bilateral**: Two-party relationships with clear directionality (contracts, grants)
-/// - **Multilateral**: N-ary relationships (consortia, boards, joint ventures)
+//! CEP Relationship Record definition.
+//!
+//! A Relationship Record represents a verifiable legal or functional relationship
+//! between two or more attested entities.
+//!
+//! Relationships can be:
+//! - **Bilateral**: Two-party relationships with clear directionality (contracts, grants)
+//! - **Multilateral**: N-ary relationships (consortia, boards, joint ventures)
 
 use crate::bilateral::BilateralParties;
 use crate::multilateral::MultilateralMembers;
-use cep_core::canonical::{format_amount, insert_if_present, insert_required, Canonicalize};
+use cep_core::canonical::{
+    format_amount, insert_if_present, insert_list, insert_number, insert_required, Canonicalize,
+    HashDomain,
+};
+use cep_core::spec::{CanonicalSpec, FieldEncoding, TypeSpec};
 use cep_core::hash::CanonicalHash;
 use cep_core::timestamp::CanonicalTimestamp;
-use cep_core::{Attestation, SCHEMA_VERSION};
+use cep_core::{
+    Attestation, Authority, Classification, Classified, Freshness, Revisioned, SCHEMA_VERSION,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -62,6 +68,14 @@ impl Canonicalize for RelationshipStatus {
     }
 }
 
+impl CanonicalSpec for RelationshipStatus {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("RelationshipStatus")
+            .required("statusCode", FieldEncoding::String)
+            .required("statusEffectiveTimestamp", FieldEncoding::Timestamp)
+    }
+}
+
 /// Financial terms of a relationship.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -92,6 +106,15 @@ impl Canonicalize for FinancialTerms {
     }
 }
 
+impl CanonicalSpec for FinancialTerms {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("FinancialTerms")
+            .required("currencyCode", FieldEncoding::String)
+            .optional("obligatedValue", FieldEncoding::amount())
+            .optional("totalValue", FieldEncoding::amount())
+    }
+}
+
 /// Reference to an authoritative source record.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -112,14 +135,67 @@ impl Canonicalize for SourceReference {
     }
 }
 
+impl CanonicalSpec for SourceReference {
+    fn type_spec() -> TypeSpec {
+        TypeSpec::new("SourceReference")
+            .required("sourceRecordId", FieldEncoding::String)
+            .required("sourceSystemUri", FieldEncoding::String)
+            .optional("sourceUrl", FieldEncoding::String)
+    }
+}
+
 /// The type of parties in a relationship (bilateral or multilateral).
+///
+/// In JSON the parties are the record's `bilateralParties` or
+/// `multilateralMembers` member, as in the schema; the other one is absent
+/// or null.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(untagged)]
+#[serde(try_from = "PartiesFields", into = "PartiesFields")]
 pub enum Parties {
     Bilateral(BilateralParties),
     Multilateral(MultilateralMembers),
 }
 
+/// The JSON members [`Parties`] is read from and written to.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PartiesFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bilateral_parties: Option<BilateralParties>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    multilateral_members: Option<MultilateralMembers>,
+}
+
+impl TryFrom<PartiesFields> for Parties {
+    type Error = String;
+
+    fn try_from(fields: PartiesFields) -> Result<Self, String> {
+        match (fields.bilateral_parties, fields.multilateral_members) {
+            (Some(bilateral), None) => Ok(Parties::Bilateral(bilateral)),
+            (None, Some(members)) => Ok(Parties::Multilateral(members)),
+            (Some(_), Some(_)) => {
+                Err("a relationship has bilateralParties or multilateralMembers, not both".into())
+            }
+            (None, None) => Err("missing field `bilateralParties` or `multilateralMembers`".into()),
+        }
+    }
+}
+
+impl From<Parties> for PartiesFields {
+    fn from(parties: Parties) -> Self {
+        match parties {
+            Parties::Bilateral(bilateral) => Self {
+                bilateral_parties: Some(bilateral),
+                multilateral_members: None,
+            },
+            Parties::Multilateral(members) => Self {
+                bilateral_parties: None,
+                multilateral_members: Some(members),
+            },
+        }
+    }
+}
+
 impl Canonicalize for Parties {
     fn canonical_fields(&self) -> BTreeMap<String, String> {
         match self {
@@ -151,6 +227,7 @@ pub struct RelationshipRecord {
     pub relationship_type_uri: String,
 
     /// The parties involved in this relationship.
+    #[serde(flatten)]
     pub parties: Parties,
 
     /// ID of the parent relationship (for subcontracts, task orders).
@@ -191,6 +268,26 @@ pub struct RelationshipRecord {
 
     /// Revision number.
     pub revision_number: u32,
+
+    /// Hash of the schema snapshot the record was validated against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_snapshot_hash: Option<CanonicalHash>,
+
+    /// Version of the vocabularies the record was validated against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vocab_version: Option<String>,
+
+    /// Access classification; absent means public.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub classification: Option<Classification>,
+
+    /// Statutory authority or agreement the record is shared under.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authority: Option<Authority>,
+
+    /// Publisher-declared refresh and expiry dates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freshness: Option<Freshness>,
 }
 
 impl RelationshipRecord {
@@ -220,6 +317,11 @@ impl RelationshipRecord {
             attestation,
             previous_record_hash: None,
             revision_number: 1,
+            schema_snapshot_hash: None,
+            vocab_version: None,
+            classification: None,
+            authority: None,
+            freshness: None,
         }
     }
 
@@ -249,6 +351,11 @@ impl RelationshipRecord {
             attestation,
             previous_record_hash: None,
             revision_number: 1,
+            schema_snapshot_hash: None,
+            vocab_version: None,
+            classification: None,
+            authority: None,
+            freshness: None,
         }
     }
 
@@ -290,6 +397,31 @@ impl RelationshipRecord {
         self.revision_number = revision;
         self
     }
+
+    pub fn with_schema_snapshot(mut self, hash: CanonicalHash) -> Self {
+        self.schema_snapshot_hash = Some(hash);
+        self
+    }
+
+    pub fn with_vocab_version(mut self, version: String) -> Self {
+        self.vocab_version = Some(version);
+        self
+    }
+
+    pub fn with_classification(mut self, classification: Classification) -> Self {
+        self.classification = Some(classification);
+        self
+    }
+
+    pub fn with_authority(mut self, authority: Authority) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    pub fn with_freshness(mut self, freshness: Freshness) -> Self {
+        self.freshness = Some(freshness);
+        self
+    }
 }
 
 impl Canonicalize for RelationshipRecord {
@@ -298,6 +430,10 @@ impl Canonicalize for RelationshipRecord {
 
         // All fields in alphabetical order
         insert_required(&mut map, "attestation", &self.attestation.to_canonical_string());
+        if let Some(ref authority) = self.authority {
+            insert_required(&mut map, "authority", &authority.to_canonical_string());
+        }
+        insert_if_present(&mut map, "classification", self.classification.map(|c| c.as_str()));
         insert_required(
             &mut map,
             "effectiveTimestamp",
@@ -309,6 +445,9 @@ impl Canonicalize for RelationshipRecord {
         if let Some(ref terms) = self.financial_terms {
             insert_required(&mut map, "financialTerms", &terms.to_canonical_string());
         }
+        if let Some(ref freshness) = self.freshness {
+            insert_required(&mut map, "freshness", &freshness.to_canonical_string());
+        }
         insert_required(&mut map, "jurisdictionIso", &self.jurisdiction_iso);
         insert_if_present(&mut map, "parentRelationshipId", self.parent_relationship_id.as_deref());
 
@@ -321,48 +460,113 @@ impl Canonicalize for RelationshipRecord {
             insert_required(&mut map, "previousRecordHash", hash.as_hex());
         }
         insert_required(&mut map, "relationshipTypeUri", &self.relationship_type_uri);
-        insert_required(&mut map, "revisionNumber", &self.revision_number.to_string());
+        insert_number(&mut map, "revisionNumber", self.revision_number);
+        if let Some(ref hash) = self.schema_snapshot_hash {
+            insert_required(&mut map, "schemaSnapshotHash", hash.as_hex());
+        }
         insert_required(&mut map, "schemaVersion", &self.schema_version);
 
         // Source references sorted by sourceSystemUri then sourceRecordId
         if let Some(ref refs) = self.source_references {
-            if !refs.is_empty() {
-                let mut sorted: Vec<_> = refs.iter().collect();
-                sorted.sort_by(|a, b| {
-                    (&a.source_system_uri, &a.source_record_id)
-                        .cmp(&(&b.source_system_uri, &b.source_record_id))
-                });
-                let json: Vec<String> = sorted.iter().map(|r| r.to_canonical_string()).collect();
-                map.insert("sourceReferences".to_string(), format!("[{}]", json.join(",")));
-            }
+            let mut sorted: Vec<_> = refs.iter().collect();
+            sorted.sort_by(|a, b| {
+                (&a.source_system_uri, &a.source_record_id)
+                    .cmp(&(&b.source_system_uri, &b.source_record_id))
+            });
+            insert_list(&mut map, "sourceReferences", sorted);
         }
 
         insert_required(&mut map, "status", &self.status.to_canonical_string());
 
         // Terms attributes already in a BTreeMap, so sorted
-        if let Some(ref attrs) = self.terms_attributes {
-            if !attrs.is_empty() {
-                let json = serde_json::to_string(attrs).unwrap_or_default();
-                map.insert("termsAttributes".to_string(), json);
-            }
+        if let Some(ref attrs) = self.terms_attributes
+            && !attrs.is_empty()
+        {
+            let json = serde_json::to_string(attrs).unwrap_or_default();
+            map.insert("termsAttributes".to_string(), json);
         }
 
         insert_required(&mut map, "verifiableId", &self.verifiable_id);
+        insert_if_present(&mut map, "vocabVersion", self.vocab_version.as_deref());
 
         map
     }
+
+    fn hash_domain(&self) -> Option<HashDomain> {
+        Some(HashDomain::new("cep.relationship", &self.schema_version))
+    }
+}
+
+impl Classified for RelationshipRecord {
+    fn classification(&self) -> Classification {
+        self.classification.unwrap_or_default()
+    }
+}
+
+impl Revisioned for RelationshipRecord {
+    fn verifiable_id(&self) -> &str {
+        &self.verifiable_id
+    }
+
+    fn revision_number(&self) -> u32 {
+        self.revision_number
+    }
+
+    fn previous_record_hash(&self) -> Option<&CanonicalHash> {
+        self.previous_record_hash.as_ref()
+    }
+
+    fn revoked_at(&self) -> Option<CanonicalTimestamp> {
+        (self.status.status_code == RelationshipStatusCode::Terminated)
+            .then_some(self.status.status_effective_timestamp)
+    }
+}
+
+impl CanonicalSpec for RelationshipRecord {
+    fn type_spec() -> TypeSpec {
+        // Exactly one of bilateralParties / multilateralMembers is present.
+        TypeSpec::new("RelationshipRecord")
+            .required("attestation", FieldEncoding::nested("Attestation"))
+            .optional("authority", FieldEncoding::nested("Authority"))
+            .optional("bilateralParties", FieldEncoding::nested("BilateralParties"))
+            .optional("classification", FieldEncoding::String)
+            .required("effectiveTimestamp", FieldEncoding::Timestamp)
+            .optional("expirationTimestamp", FieldEncoding::Timestamp)
+            .optional("financialTerms", FieldEncoding::nested("FinancialTerms"))
+            .optional("freshness", FieldEncoding::nested("Freshness"))
+            .required("jurisdictionIso", FieldEncoding::String)
+            .optional("multilateralMembers", FieldEncoding::nested("MultilateralMembers"))
+            .optional("parentRelationshipId", FieldEncoding::String)
+            .optional("previousRecordHash", FieldEncoding::Hash)
+            .required("relationshipTypeUri", FieldEncoding::String)
+            .required("revisionNumber", FieldEncoding::Integer)
+            .optional("schemaSnapshotHash", FieldEncoding::Hash)
+            .required("schemaVersion", FieldEncoding::String)
+            .optional(
+                "sourceReferences",
+                FieldEncoding::nested_list(
+                    "SourceReference",
+                    &["sourceSystemUri", "sourceRecordId"],
+                ),
+            )
+            .required("status", FieldEncoding::nested("RelationshipStatus"))
+            .optional("termsAttributes", FieldEncoding::Json)
+            .required("verifiableId", FieldEncoding::String)
+            .optional("vocabVersion", FieldEncoding::String)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::bilateral::Party;
+    use cep_core::ProofType;
 
     fn test_attestation() -> Attestation {
         Attestation::new(
             "cep-entity:sam-uei:ATTESTOR123A".to_string(),
             "2025-11-28T14:30:00.000000Z".parse().unwrap(),
-            "Ed25519Signature2020".to_string(),
+            ProofType::Ed25519Signature2020,
             "z3FXQqFwbZxKBxGxqFpCDabcdef1234567890".to_string(),
             "did:web:example.gov#key-1".to_string(),
         )
@@ -439,10 +643,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parties_use_schema_members() {
+        let rel = test_bilateral_relationship();
+        let mut json = serde_json::to_value(&rel).unwrap();
+        assert!(json.get("parties").is_none());
+        assert_eq!(
+            json["bilateralParties"]["partyA"]["entityId"],
+            "cep-entity:sam-uei:AGENCY12345A"
+        );
+        assert_eq!(
+            serde_json::from_value::<RelationshipRecord>(json.clone()).unwrap(),
+            rel
+        );
+
+        // The schema allows the unused member to be null.
+        json["multilateralMembers"] = serde_json::Value::Null;
+        assert_eq!(
+            serde_json::from_value::<RelationshipRecord>(json.clone()).unwrap(),
+            rel
+        );
+
+        json["multilateralMembers"] = serde_json::json!([]);
+        assert!(serde_json::from_value::<RelationshipRecord>(json.clone()).is_err());
+        json.as_object_mut().unwrap().remove("bilateralParties");
+        let multilateral: RelationshipRecord = serde_json::from_value(json.clone()).unwrap();
+        assert!(matches!(multilateral.parties, Parties::Multilateral(_)));
+        json.as_object_mut().unwrap().remove("multilateralMembers");
+        assert!(serde_json::from_value::<RelationshipRecord>(json).is_err());
+    }
+
     // ========================================
     // TEST VECTOR OUTPUT
     // ========================================
 
+    #[test]
+    fn test_canonical_spec_matches_fields() {
+        let spec = RelationshipRecord::type_spec();
+        let keys: Vec<String> = test_bilateral_relationship()
+            .canonical_fields()
+            .into_keys()
+            .collect();
+        for required in spec.required_field_names() {
+            assert!(keys.iter().any(|k| k == required), "missing required field {}", required);
+        }
+        for key in &keys {
+            assert!(spec.field_names().contains(&key.as_str()), "unlisted field {}", key);
+        }
+    }
+
     #[test]
     fn test_vector_bilateral_relationship() {
         let rel = test_bilateral_relationship().with_financial_terms(FinancialTerms {
@@ -0,0 +1,313 @@
+//! Checksummed display encoding for SNFEIs.
+//!
+//! Stewards copy SNFEIs into spreadsheets and emails by hand. The hex form
+//! gives no protection against typos, so this module defines an optional
+//! display encoding that carries a check value:
+//!
+//! ```text
+//!     SNFEI-XXXXXX-XXXXXX-XXXXXX-XXXXXX-XXXXXX-XXXXXX-XXXXXX-XXXXXX-XXXXCC
+//!           └────────────── 52 symbols: Crockford base32 of the hash ────┘└┘
+//!                                                           2 check symbols
+//! ```
+//!
+//! The check value is the base32 number modulo 1021 (a prime coprime with 32),
+//! written as two base32 symbols. It detects every single-symbol substitution
+//! and every adjacent transposition.
+//!
+//! Decoding is forgiving about presentation: case, hyphens and whitespace are
+//! ignored, and the Crockford look-alikes `O`, `I` and `L` are read as `0`, `1`
+//! and `1`. The canonical identifier is always the hex form; this encoding is
+//! never hashed.
+
+use std::fmt;
+
+use crate::generator::Snfei;
+
+/// Prefix of the checked display form.
+pub const CHECKED_PREFIX: &str = "SNFEI-";
+
+/// Crockford base32 alphabet.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Number of base32 symbols encoding the 32-byte hash (256 bits + 4 pad bits).
+const BODY_LEN: usize = 52;
+
+/// Number of check symbols.
+const CHECK_LEN: usize = 2;
+
+/// Modulus for the check value.
+const CHECK_MODULUS: u32 = 1021;
+
+/// Symbols per hyphen-separated group in the encoded form.
+const GROUP_LEN: usize = 6;
+
+/// Errors returned when decoding a checked SNFEI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckedSnfeiError {
+    /// The input contains a character outside the Crockford alphabet.
+    InvalidCharacter(char),
+    /// The input has the wrong number of symbols.
+    InvalidLength(usize),
+    /// The check symbols do not match the body (likely a typo).
+    ChecksumMismatch,
+    /// The padding bits are not zero, so the body is not a valid hash.
+    NonCanonicalPadding,
+}
+
+impl fmt::Display for CheckedSnfeiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter(c) => write!(f, "invalid character in checked SNFEI: {:?}", c),
+            Self::InvalidLength(n) => write!(
+                f,
+                "invalid checked SNFEI length: expected {} symbols, got {}",
+                BODY_LEN + CHECK_LEN,
+                n
+            ),
+            Self::ChecksumMismatch => write!(f, "checked SNFEI checksum mismatch"),
+            Self::NonCanonicalPadding => write!(f, "checked SNFEI has non-zero padding bits"),
+        }
+    }
+}
+
+impl std::error::Error for CheckedSnfeiError {}
+
+/// Encode an SNFEI in the checked display form.
+///
+/// # Example
+/// ```
+/// use cep_snfei::{decode_checked, encode_checked, generate_snfei};
+///
+/// let snfei = generate_snfei("Springfield USD", "US", None, None).snfei;
+/// let display = encode_checked(&snfei);
+/// assert!(display.starts_with("SNFEI-"));
+/// assert_eq!(decode_checked(&display).unwrap(), snfei);
+/// ```
+pub fn encode_checked(snfei: &Snfei) -> String {
+    let bytes = hex_to_bytes(snfei.value());
+    let mut symbols = to_base32(&bytes);
+    let check = checksum(&symbols);
+    symbols.push((check / 32) as u8);
+    symbols.push((check % 32) as u8);
+
+    let encoded: Vec<String> = symbols
+        .chunks(GROUP_LEN)
+        .map(|group| group.iter().map(|&v| ALPHABET[v as usize] as char).collect())
+        .collect();
+    format!("{}{}", CHECKED_PREFIX, encoded.join("-"))
+}
+
+/// Decode and verify a checked SNFEI.
+pub fn decode_checked(input: &str) -> Result<Snfei, CheckedSnfeiError> {
+    let trimmed = input.trim();
+    let body = if trimmed
+        .get(..CHECKED_PREFIX.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(CHECKED_PREFIX))
+    {
+        &trimmed[CHECKED_PREFIX.len()..]
+    } else {
+        trimmed
+    };
+
+    let mut symbols = Vec::with_capacity(BODY_LEN + CHECK_LEN);
+    for c in body.chars() {
+        if c == '-' || c.is_whitespace() {
+            continue;
+        }
+        symbols.push(symbol_value(c).ok_or(CheckedSnfeiError::InvalidCharacter(c))?);
+    }
+
+    if symbols.len() != BODY_LEN + CHECK_LEN {
+        return Err(CheckedSnfeiError::InvalidLength(symbols.len()));
+    }
+
+    let (data, check) = symbols.split_at(BODY_LEN);
+    let expected = u32::from(check[0]) * 32 + u32::from(check[1]);
+    if checksum(data) != expected {
+        return Err(CheckedSnfeiError::ChecksumMismatch);
+    }
+
+    let bytes = from_base32(data).ok_or(CheckedSnfeiError::NonCanonicalPadding)?;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    Ok(Snfei::from_hash(&hex).expect("32 decoded bytes always form a valid SNFEI"))
+}
+
+/// Returns true if `input` is a well-formed checked SNFEI with a valid checksum.
+pub fn verify_checked(input: &str) -> bool {
+    decode_checked(input).is_ok()
+}
+
+impl Snfei {
+    /// Encode in the checked display form. See [`encode_checked`].
+    pub fn to_checked_string(&self) -> String {
+        encode_checked(self)
+    }
+
+    /// Decode and verify the checked display form. See [`decode_checked`].
+    pub fn from_checked_str(input: &str) -> Result<Self, CheckedSnfeiError> {
+        decode_checked(input)
+    }
+}
+
+fn symbol_value(c: char) -> Option<u8> {
+    let c = match c.to_ascii_uppercase() {
+        'O' => '0',
+        'I' | 'L' => '1',
+        other => other,
+    };
+    ALPHABET.iter().position(|&a| a as char == c).map(|p| p as u8)
+}
+
+/// ISO 7064-style check: the base32 number modulo 1021.
+fn checksum(symbols: &[u8]) -> u32 {
+    symbols
+        .iter()
+        .fold(0u32, |acc, &v| (acc * 32 + u32::from(v)) % CHECK_MODULUS)
+}
+
+fn hex_to_bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("Snfei holds valid hex"))
+        .collect()
+}
+
+fn to_base32(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(BODY_LEN);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for &b in bytes {
+        buffer = (buffer << 8) | u32::from(b);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(((buffer >> bits) & 0x1f) as u8);
+        }
+    }
+    if bits > 0 {
+        out.push(((buffer << (5 - bits)) & 0x1f) as u8);
+    }
+    out
+}
+
+fn from_base32(symbols: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(32);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for &v in symbols {
+        buffer = (buffer << 5) | u32::from(v);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push(((buffer >> bits) & 0xff) as u8);
+        }
+    }
+    // Remaining bits are padding and must be zero.
+    if buffer & ((1 << bits) - 1) != 0 {
+        return None;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::generate_snfei;
+
+    fn sample() -> Snfei {
+        generate_snfei("Springfield School District", "US", None, None).snfei
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let snfei = sample();
+        let encoded = encode_checked(&snfei);
+        assert_eq!(decode_checked(&encoded).unwrap(), snfei);
+    }
+
+    #[test]
+    fn test_encoded_shape() {
+        let encoded = encode_checked(&sample());
+        let body = encoded.strip_prefix(CHECKED_PREFIX).unwrap();
+        let groups: Vec<&str> = body.split('-').collect();
+        assert_eq!(groups.len(), 9);
+        assert!(groups.iter().all(|g| g.len() == GROUP_LEN));
+    }
+
+    #[test]
+    fn test_known_vector() {
+        let snfei = Snfei::from_hash(&"0".repeat(64)).unwrap();
+        let encoded = encode_checked(&snfei);
+        assert_eq!(
+            encoded,
+            format!("SNFEI-{}", ["000000"; 9].join("-"))
+        );
+    }
+
+    #[test]
+    fn test_lenient_decoding() {
+        let snfei = sample();
+        let encoded = encode_checked(&snfei);
+        let body = encoded.strip_prefix(CHECKED_PREFIX).unwrap();
+        assert_eq!(decode_checked(body).unwrap(), snfei);
+
+        let sloppy = format!("snfei-{}", body.to_lowercase().replace('-', " ").replace('0', "o"));
+        assert_eq!(decode_checked(&sloppy).unwrap(), snfei);
+    }
+
+    #[test]
+    fn test_detects_every_single_substitution() {
+        let encoded = encode_checked(&sample());
+        let positions: Vec<usize> = encoded
+            .char_indices()
+            .skip(CHECKED_PREFIX.len())
+            .filter(|(_, c)| *c != '-')
+            .map(|(i, _)| i)
+            .collect();
+
+        for &pos in &positions {
+            let original = encoded.as_bytes()[pos];
+            for &replacement in ALPHABET.iter().filter(|&&a| a != original) {
+                let mut typo = encoded.clone().into_bytes();
+                typo[pos] = replacement;
+                let typo = String::from_utf8(typo).unwrap();
+                assert!(!verify_checked(&typo), "undetected typo: {}", typo);
+            }
+        }
+    }
+
+    #[test]
+    fn test_detects_adjacent_transpositions() {
+        let encoded = encode_checked(&sample());
+        let chars: Vec<char> = encoded.chars().collect();
+        let positions: Vec<usize> = (CHECKED_PREFIX.len()..chars.len())
+            .filter(|&i| chars[i] != '-')
+            .collect();
+
+        for pair in positions.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if chars[a] == chars[b] {
+                continue;
+            }
+            let mut swapped = chars.clone();
+            swapped.swap(a, b);
+            let swapped: String = swapped.into_iter().collect();
+            assert_eq!(decode_checked(&swapped), Err(CheckedSnfeiError::ChecksumMismatch));
+        }
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert_eq!(
+            decode_checked("SNFEI-123"),
+            Err(CheckedSnfeiError::InvalidLength(3))
+        );
+        assert_eq!(
+            decode_checked("SNFEI-U00000"),
+            Err(CheckedSnfeiError::InvalidCharacter('U'))
+        );
+        // Multi-byte characters straddling the prefix length must not panic.
+        assert!(decode_checked("aéééé").is_err());
+        assert!(decode_checked("SNFEé-0000").is_err());
+    }
+}
@@ -0,0 +1,305 @@
+//! Cross-version compatibility checks for SNFEI generation.
+//!
+//! Before rolling a new `cep-snfei` release across a fleet, operators need to
+//! know which stored SNFEIs the new version would compute differently.
+//! [`compare`] re-runs generation on previously recorded inputs and reports
+//! every divergence together with the normalization stage responsible for it.
+//!
+//! Stage attribution needs the [`NormalizationTrace`] that was recorded
+//! alongside the old SNFEI (from
+//! [`NormalizingFunctor::canonical_input_traced`]). [`compare`] traces the
+//! new run too and reports the first stage, as `name@version`, where the two
+//! traces part ways (see [`compare_traces`]). Without a trace only the
+//! changed canonical fields are reported, and without the old canonical
+//! input the divergence is reported with [`ChangedField::Unknown`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::generator::generate_snfei;
+use crate::localization::apply_localization;
+use crate::normalizer::CanonicalInput;
+use crate::pipeline::{
+    NormalizationTrace, NormalizingFunctor, REGISTRATION_DATE_STAGE, TraceDivergence,
+    compare_traces,
+};
+
+/// A previously generated SNFEI and the raw inputs it was generated from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompatRecord {
+    pub legal_name: String,
+    pub country_code: String,
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub registration_date: Option<String>,
+    /// SNFEI produced by the old version.
+    pub snfei: String,
+    /// Canonical input produced by the old version, if recorded.
+    #[serde(default)]
+    pub canonical: Option<CanonicalInput>,
+    /// Per-stage trace of the old version's normalization, if recorded.
+    #[serde(default)]
+    pub trace: Option<NormalizationTrace>,
+}
+
+/// How the current version should regenerate SNFEIs.
+#[derive(Debug, Clone, Default)]
+pub struct CompatConfig {
+    /// Jurisdiction whose localization rules are applied to the legal name
+    /// before generation (e.g. "us/ny"), matching the fleet's pipeline.
+    pub jurisdiction: Option<String>,
+}
+
+/// Canonical input field whose value changed between versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangedField {
+    LegalName,
+    Address,
+    CountryCode,
+    RegistrationDate,
+    /// Canonical fields agree but the hash input string differs.
+    HashInput,
+    /// No canonical input was recorded for the old result.
+    Unknown,
+}
+
+/// One record whose SNFEI changed.
+#[derive(Debug, Clone, Serialize)]
+pub struct Divergence {
+    /// Position of the record in the input.
+    pub index: usize,
+    pub old_snfei: String,
+    pub new_snfei: String,
+    /// First normalization stage, `name@version`, whose output differs, if
+    /// the old trace was recorded or only the registration date changed.
+    pub stage: Option<String>,
+    /// Where the old and new traces part ways, if the old trace was recorded.
+    pub trace_divergence: Option<TraceDivergence>,
+    /// Canonical fields whose value differs, in pipeline order.
+    pub fields: Vec<ChangedField>,
+    pub old_canonical: Option<CanonicalInput>,
+    pub new_canonical: CanonicalInput,
+}
+
+/// Result of [`compare`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CompatReport {
+    /// Number of records checked.
+    pub checked: usize,
+    /// Records whose SNFEI differs under the current version.
+    pub divergences: Vec<Divergence>,
+}
+
+impl CompatReport {
+    /// Returns true if every SNFEI is unchanged.
+    pub fn is_compatible(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+/// Regenerates every SNFEI in `old_results` and reports the ones that changed.
+///
+/// # Example
+/// ```
+/// use cep_snfei::compat::{compare, CompatConfig, CompatRecord};
+/// use cep_snfei::generate_snfei;
+///
+/// let old = generate_snfei("Acme LLC", "US", None, None);
+/// let record = CompatRecord {
+///     legal_name: "Acme LLC".to_string(),
+///     country_code: "US".to_string(),
+///     address: None,
+///     registration_date: None,
+///     snfei: old.snfei.value().to_string(),
+///     canonical: Some(old.canonical),
+///     trace: None,
+/// };
+/// let report = compare(&[record], &CompatConfig::default());
+/// assert!(report.is_compatible());
+/// ```
+pub fn compare(old_results: &[CompatRecord], config: &CompatConfig) -> CompatReport {
+    let mut report = CompatReport::default();
+    for (index, old) in old_results.iter().enumerate() {
+        report.checked += 1;
+
+        let legal_name = match &config.jurisdiction {
+            Some(jurisdiction) => apply_localization(&old.legal_name, jurisdiction),
+            None => old.legal_name.clone(),
+        };
+        let new = generate_snfei(
+            &legal_name,
+            &old.country_code,
+            old.address.as_deref(),
+            old.registration_date.as_deref(),
+        );
+        if new.snfei.value() == old.snfei {
+            continue;
+        }
+
+        let fields = match &old.canonical {
+            Some(old_canonical) => changed_fields(old_canonical, &new.canonical),
+            None => vec![ChangedField::Unknown],
+        };
+        let trace_divergence = old.trace.as_ref().and_then(|old_trace| {
+            let (_, new_trace) = NormalizingFunctor::standard().canonical_input_traced(
+                &legal_name,
+                &old.country_code,
+                old.address.as_deref(),
+                old.registration_date.as_deref(),
+            );
+            compare_traces(old_trace, &new_trace)
+        });
+        let stage = match &trace_divergence {
+            // A stage the new pipeline no longer runs is named by the old side.
+            Some(divergence) => divergence
+                .right
+                .as_ref()
+                .or(divergence.left.as_ref())
+                .map(|step| step.stage.clone()),
+            None if fields == [ChangedField::RegistrationDate] => {
+                Some(REGISTRATION_DATE_STAGE.to_string())
+            }
+            None => None,
+        };
+        report.divergences.push(Divergence {
+            index,
+            old_snfei: old.snfei.clone(),
+            new_snfei: new.snfei.value().to_string(),
+            stage,
+            trace_divergence,
+            fields,
+            old_canonical: old.canonical.clone(),
+            new_canonical: new.canonical,
+        });
+    }
+    report
+}
+
+fn changed_fields(old: &CanonicalInput, new: &CanonicalInput) -> Vec<ChangedField> {
+    let mut fields = Vec::new();
+    if old.legal_name_normalized != new.legal_name_normalized {
+        fields.push(ChangedField::LegalName);
+    }
+    if old.address_normalized != new.address_normalized {
+        fields.push(ChangedField::Address);
+    }
+    if old.country_code != new.country_code {
+        fields.push(ChangedField::CountryCode);
+    }
+    if old.registration_date != new.registration_date {
+        fields.push(ChangedField::RegistrationDate);
+    }
+    if fields.is_empty() {
+        fields.push(ChangedField::HashInput);
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::compute_snfei;
+    use crate::pipeline::{FnStage, Pipeline};
+
+    fn record(legal_name: &str, address: Option<&str>) -> CompatRecord {
+        recorded_by(NormalizingFunctor::standard(), legal_name, address)
+    }
+
+    /// A record as a version running `functor` would have stored it.
+    fn recorded_by(
+        functor: &NormalizingFunctor,
+        legal_name: &str,
+        address: Option<&str>,
+    ) -> CompatRecord {
+        let (canonical, trace) = functor.canonical_input_traced(legal_name, "US", address, None);
+        CompatRecord {
+            legal_name: legal_name.to_string(),
+            country_code: "US".to_string(),
+            address: address.map(str::to_string),
+            registration_date: None,
+            snfei: compute_snfei(&canonical).value().to_string(),
+            canonical: Some(canonical),
+            trace: Some(trace),
+        }
+    }
+
+    #[test]
+    fn test_unchanged_records_are_compatible() {
+        let records = vec![
+            record("Springfield School District", None),
+            record("Acme LLC", Some("123 Main St")),
+        ];
+        assert_eq!(
+            records[1].snfei,
+            generate_snfei("Acme LLC", "US", Some("123 Main St"), None)
+                .snfei
+                .value()
+        );
+        let report = compare(&records, &CompatConfig::default());
+        assert_eq!(report.checked, 2);
+        assert!(report.is_compatible());
+    }
+
+    #[test]
+    fn test_attributes_responsible_stage() {
+        // An old version whose legal form stage did not expand "llc".
+        let old_version = NormalizingFunctor {
+            legal_name: Pipeline::legal_name()
+                .replace(FnStage::new("legal_form", 0, str::to_string))
+                .unwrap(),
+            ..NormalizingFunctor::default()
+        };
+        let old = recorded_by(&old_version, "Acme LLC", None);
+
+        let report = compare(&[record("Other Co", None), old], &CompatConfig::default());
+        assert_eq!(report.divergences.len(), 1);
+        let divergence = &report.divergences[0];
+        assert_eq!(divergence.index, 1);
+        assert_eq!(divergence.stage.as_deref(), Some("legal_form@1"));
+        assert_eq!(divergence.fields, vec![ChangedField::LegalName]);
+        let trace = divergence.trace_divergence.as_ref().unwrap();
+        assert_eq!((trace.field, trace.step), ("legal_name", 4));
+        assert_eq!(trace.left.as_ref().unwrap().stage, "legal_form@0");
+
+        // A stage the new version dropped is named from the old trace.
+        let old_version = NormalizingFunctor {
+            address: Pipeline::address().with_stage(FnStage::new("zip_strip", 1, |text| {
+                text.trim_end_matches(|c: char| c.is_ascii_digit())
+                    .trim_end()
+                    .to_string()
+            })),
+            ..NormalizingFunctor::default()
+        };
+        let old = recorded_by(&old_version, "Acme LLC", Some("1 Main St 62701"));
+        let report = compare(&[old], &CompatConfig::default());
+        let divergence = &report.divergences[0];
+        assert_eq!(divergence.stage.as_deref(), Some("zip_strip@1"));
+        assert_eq!(divergence.fields, vec![ChangedField::Address]);
+    }
+
+    #[test]
+    fn test_hash_input_and_unknown_fields() {
+        let mut old = record("Acme LLC", None);
+        old.snfei = "0".repeat(64);
+        let report = compare(std::slice::from_ref(&old), &CompatConfig::default());
+        let divergence = &report.divergences[0];
+        assert_eq!(divergence.fields, vec![ChangedField::HashInput]);
+        assert_eq!(divergence.stage, None);
+        assert!(divergence.trace_divergence.is_none());
+
+        old.canonical = None;
+        old.trace = None;
+        let report = compare(&[old], &CompatConfig::default());
+        assert_eq!(report.divergences[0].fields, vec![ChangedField::Unknown]);
+        assert_eq!(report.divergences[0].stage, None);
+    }
+
+    #[test]
+    fn test_records_deserialize_without_optional_fields() {
+        let json = r#"[{"legal_name":"Acme LLC","country_code":"US","snfei":"abc"}]"#;
+        let records: Vec<CompatRecord> = serde_json::from_str(json).unwrap();
+        assert!(records[0].canonical.is_none());
+        assert!(records[0].trace.is_none());
+    }
+}
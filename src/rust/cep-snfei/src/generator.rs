@@ -1,39 +1,67 @@
-/// SNFEI Hash Generation.
-///
-/// This module computes the final SNFEI (Sub-National Federated Entity Identifier)
-/// from normalized entity attributes.
-///
-/// The SNFEI formula:
-///     SNFEI = SHA256(Concatenate[
-///         legal_name_normalized,
-///         address_normalized,
-///         country_code,
-///         registration_date
-///     ])
-///
-/// All inputs must pass through the Normalizing Functor before hashing.
+//! SNFEI Hash Generation.
+//!
+//! This module computes the final SNFEI (Sub-National Federated Entity Identifier)
+//! from normalized entity attributes.
+//!
+//! The SNFEI formula:
+//!     SNFEI = SHA256(Concatenate[
+//!         legal_name_normalized,
+//!         address_normalized,
+//!         country_code,
+//!         registration_date
+//!     ])
+//!
+//! All inputs must pass through the Normalizing Functor before hashing.
 
 use sha2::{Digest, Sha256};
+use std::str::FromStr;
 
 use crate::normalizer::{CanonicalInput, build_canonical_input};
+use cep_core::{CepError, CepResult};
 use serde::{Deserialize, Serialize};
 
 /// A validated SNFEI (64-character lowercase hex string).
+///
+/// Deserialization validates the value, so `{"value": "bad"}` is rejected.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "SnfeiRepr")]
 pub struct Snfei {
     pub value: String,
 }
 
+/// Wire form of [`Snfei`], checked before it becomes one.
+#[derive(Deserialize)]
+struct SnfeiRepr {
+    value: String,
+}
+
 impl Snfei {
     /// Create from an existing hash string.
     pub fn from_hash(hash: &str) -> Option<Self> {
-        if hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
-            Some(Self {
-                value: hash.to_lowercase(),
-            })
-        } else {
-            None
+        Self::parse(hash).ok()
+    }
+
+    /// Parses an SNFEI from 64 hex characters, stored lowercase.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidIdentifier` describing what is wrong.
+    pub fn parse(hash: &str) -> CepResult<Self> {
+        if hash.len() != 64 {
+            return Err(CepError::InvalidIdentifier(format!(
+                "SNFEI must be 64 hex characters, got {}",
+                hash.len()
+            )));
         }
+        if !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(CepError::InvalidIdentifier(format!(
+                "SNFEI '{}' contains non-hex characters",
+                hash
+            )));
+        }
+        Ok(Self {
+            value: hash.to_lowercase(),
+        })
     }
 
     /// Get the hash value.
@@ -57,6 +85,44 @@ impl std::fmt::Display for Snfei {
     }
 }
 
+impl FromStr for Snfei {
+    type Err = CepError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<&str> for Snfei {
+    type Error = CepError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<String> for Snfei {
+    type Error = CepError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl TryFrom<SnfeiRepr> for Snfei {
+    type Error = CepError;
+
+    fn try_from(repr: SnfeiRepr) -> Result<Self, Self::Error> {
+        Self::parse(&repr.value)
+    }
+}
+
+impl AsRef<str> for Snfei {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
 /// Result of SNFEI generation with metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SnfeiResult {
@@ -70,6 +136,139 @@ pub struct SnfeiResult {
     pub tier: u8,
     /// Fields that contributed to the SNFEI
     pub fields_used: Vec<String>,
+    /// True if an LEI or SAM UEI was supplied and passed format validation,
+    /// whether or not it raised the tier
+    #[serde(default)]
+    pub format_valid: bool,
+    /// How that identifier was checked against its registry; Tier 1 and
+    /// Tier 2 require more than [`IdentifierVerification::Unverified`]
+    #[serde(default)]
+    pub verification: IdentifierVerification,
+}
+
+/// How far the LEI or SAM UEI behind a result was checked.
+///
+/// A well-formed identifier alone does not prove the entity holds it, so a
+/// format-valid but unverified LEI or UEI leaves the result at Tier 3.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IdentifierVerification {
+    /// Not checked against its registry.
+    #[default]
+    Unverified,
+    /// Confirmed against GLEIF (LEI) or SAM.gov (UEI).
+    RegistryVerified,
+    /// Accepted without a registry check by explicit decision of the
+    /// caller, e.g. for offline batch runs.
+    OfflineAccepted,
+}
+
+impl IdentifierVerification {
+    /// Returns true if an identifier checked this far may raise the tier.
+    pub fn allows_federal_tier(self) -> bool {
+        !matches!(self, Self::Unverified)
+    }
+}
+
+/// Identifiers that can raise an SNFEI result above Tier 3.
+///
+/// Implemented by the entity identifier collection in `cep_entity`, and by
+/// anything else that knows an entity's LEI and SAM UEI.
+pub trait TierIdentifiers {
+    /// The entity's LEI, if known.
+    fn lei(&self) -> Option<&str>;
+    /// The entity's SAM UEI, if known.
+    fn sam_uei(&self) -> Option<&str>;
+}
+
+impl SnfeiResult {
+    /// Returns true if the identifier behind this result was confirmed
+    /// against its registry.
+    pub fn registry_verified(&self) -> bool {
+        self.verification == IdentifierVerification::RegistryVerified
+    }
+
+    /// Re-tiers this result against identifiers learned after it was
+    /// generated, e.g. an LEI obtained by an entity first seen at Tier 3.
+    ///
+    /// The result is scored as [`generate_snfei_with_confidence`] would
+    /// score it with those identifiers and `verification`. A result is never
+    /// downgraded: if the identifiers do not reach a better tier it is
+    /// returned unchanged.
+    pub fn upgrade_with(
+        &self,
+        identifiers: &impl TierIdentifiers,
+        verification: IdentifierVerification,
+    ) -> SnfeiResult {
+        let mut upgraded = self.clone();
+        upgraded.apply_federal_identifiers(identifiers.lei(), identifiers.sam_uei(), verification);
+        upgraded
+    }
+
+    /// Raises the tier for a format-valid LEI or SAM UEI checked as far as
+    /// `verification` allows. A Tier 3 result records a format-valid
+    /// identifier even when it may not raise the tier.
+    fn apply_federal_identifiers(
+        &mut self,
+        lei: Option<&str>,
+        sam_uei: Option<&str>,
+        verification: IdentifierVerification,
+    ) {
+        let (tier, confidence_score, field) = if lei.is_some_and(is_valid_lei) {
+            (1, 1.0, "lei")
+        } else if sam_uei.is_some_and(is_valid_sam_uei) {
+            (2, 0.95, "sam_uei")
+        } else {
+            return;
+        };
+        if tier >= self.tier {
+            return;
+        }
+        if verification.allows_federal_tier() {
+            self.tier = tier;
+            self.confidence_score = confidence_score;
+            self.fields_used = vec![
+                field.to_string(),
+                "legal_name".to_string(),
+                "country_code".to_string(),
+            ];
+        } else if self.tier < 3 {
+            return;
+        }
+        self.format_valid = true;
+        self.verification = verification;
+    }
+}
+
+/// Checks an ISO 17442 LEI: 18 letters or digits followed by two check
+/// digits, with the whole code read as a number (A = 10, ..., Z = 35)
+/// equal to 1 mod 97.
+fn is_valid_lei(lei: &str) -> bool {
+    if lei.len() != 20
+        || !lei.chars().all(|c| c.is_ascii_alphanumeric())
+        || !lei[18..].chars().all(|c| c.is_ascii_digit())
+    {
+        return false;
+    }
+    let remainder = lei.chars().fold(0u32, |acc, c| {
+        let value = c.to_digit(36).expect("alphanumeric");
+        if value >= 10 {
+            (acc * 100 + value) % 97
+        } else {
+            (acc * 10 + value) % 97
+        }
+    });
+    remainder == 1
+}
+
+/// Checks a SAM.gov UEI: 12 uppercase letters or digits, not starting with
+/// 0 and never using the letters I or O.
+fn is_valid_sam_uei(uei: &str) -> bool {
+    uei.len() == 12
+        && !uei.starts_with('0')
+        && uei
+            .chars()
+            .all(|c| (c.is_ascii_uppercase() && c != 'I' && c != 'O') || c.is_ascii_digit())
 }
 
 /// Compute SNFEI from canonical input.
@@ -124,12 +323,12 @@ pub fn generate_snfei(
     let has_address = canonical
         .address_normalized
         .as_deref()
-        .map_or(false, |s| !s.is_empty());
+        .is_some_and(|s| !s.is_empty());
 
     let has_registration_date = canonical
         .registration_date
         .as_deref()
-        .map_or(false, |s| !s.is_empty());
+        .is_some_and(|s| !s.is_empty());
 
     // Determine fields used
     let mut fields_used = vec!["legal_name".to_string(), "country_code".to_string()];
@@ -157,11 +356,7 @@ pub fn generate_snfei(
     confidence = confidence.min(0.9);
 
     // ensure confidence is between 0.0 and 1.0
-    if confidence < 0.0 {
-        confidence = 0.0;
-    } else if confidence > 1.0 {
-        confidence = 1.0;
-    }
+    confidence = confidence.clamp(0.0, 1.0);
 
     SnfeiResult {
         snfei,
@@ -169,6 +364,8 @@ pub fn generate_snfei(
         confidence_score: (confidence * 100.0).round() / 100.0,
         tier: 3,
         fields_used,
+        format_valid: false,
+        verification: IdentifierVerification::Unverified,
     }
 }
 
@@ -191,6 +388,11 @@ pub fn generate_snfei_simple(
 /// - Tier 1: Entity has LEI (global identifier) - confidence 1.0
 /// - Tier 2: Entity has SAM UEI (federal identifier) - confidence 0.95
 /// - Tier 3: Entity uses SNFEI (computed hash) - confidence varies
+///
+/// Tier 1 and Tier 2 need a format-valid identifier and a `verification`
+/// other than [`IdentifierVerification::Unverified`]. A format-valid but
+/// unverified identifier leaves the result at Tier 3 with `format_valid`
+/// set.
 pub fn generate_snfei_with_confidence(
     legal_name: &str,
     country_code: &str,
@@ -198,46 +400,11 @@ pub fn generate_snfei_with_confidence(
     registration_date: Option<&str>,
     lei: Option<&str>,
     sam_uei: Option<&str>,
+    verification: IdentifierVerification,
 ) -> SnfeiResult {
-    let canonical = build_canonical_input(legal_name, country_code, address, registration_date);
-    let snfei = compute_snfei(&canonical);
-
-    // Tier 1: LEI available
-    if let Some(lei_val) = lei {
-        if lei_val.len() == 20 {
-            return SnfeiResult {
-                snfei,
-                canonical,
-                confidence_score: 1.0,
-                tier: 1,
-                fields_used: vec![
-                    "lei".to_string(),
-                    "legal_name".to_string(),
-                    "country_code".to_string(),
-                ],
-            };
-        }
-    }
-
-    // Tier 2: SAM UEI available
-    if let Some(uei_val) = sam_uei {
-        if uei_val.len() == 12 {
-            return SnfeiResult {
-                snfei,
-                canonical,
-                confidence_score: 0.95,
-                tier: 2,
-                fields_used: vec![
-                    "sam_uei".to_string(),
-                    "legal_name".to_string(),
-                    "country_code".to_string(),
-                ],
-            };
-        }
-    }
-
-    // Tier 3: Computed SNFEI
-    generate_snfei(legal_name, country_code, address, registration_date)
+    let mut result = generate_snfei(legal_name, country_code, address, registration_date);
+    result.apply_federal_identifiers(lei, sam_uei, verification);
+    result
 }
 
 #[cfg(test)]
@@ -287,10 +454,12 @@ mod tests {
             None,
             Some("529900T8BM49AURSDO55"), // 20-char LEI
             None,
+            IdentifierVerification::RegistryVerified,
         );
 
         assert_eq!(result.tier, 1);
         assert_eq!(result.confidence_score, 1.0);
+        assert!(result.format_valid && result.registry_verified());
     }
 
     #[test]
@@ -302,10 +471,87 @@ mod tests {
             None,
             None,
             Some("J6H4FB3N5YK7"), // 12-char SAM UEI
+            IdentifierVerification::OfflineAccepted,
         );
 
         assert_eq!(result.tier, 2);
         assert_eq!(result.confidence_score, 0.95);
+        assert!(result.format_valid && !result.registry_verified());
+    }
+
+    #[test]
+    fn test_unverified_or_malformed_identifiers_stay_tier_3() {
+        let lei = Some("529900T8BM49AURSDO55");
+        let unverified = generate_snfei_with_confidence(
+            "Acme Corp",
+            "US",
+            None,
+            None,
+            lei,
+            None,
+            IdentifierVerification::Unverified,
+        );
+        assert_eq!(unverified.tier, 3);
+        assert!(unverified.format_valid);
+        assert_eq!(unverified.verification, IdentifierVerification::Unverified);
+
+        // Any 20 characters used to be enough for Tier 1; a bad check digit
+        // is not format-valid even when the caller vouches for it.
+        for lei in ["529900T8BM49AURSDO56", "ABCDEFGHIJKLMNOPQRST"] {
+            let result = generate_snfei_with_confidence(
+                "Acme Corp",
+                "US",
+                None,
+                None,
+                Some(lei),
+                None,
+                IdentifierVerification::OfflineAccepted,
+            );
+            assert_eq!(result.tier, 3);
+            assert!(!result.format_valid);
+        }
+        assert!(!is_valid_sam_uei("J6H4FB3N5YKO"));
+        assert!(!is_valid_sam_uei("06H4FB3N5YK7"));
+    }
+
+    struct Known(Option<&'static str>, Option<&'static str>);
+
+    impl TierIdentifiers for Known {
+        fn lei(&self) -> Option<&str> {
+            self.0
+        }
+
+        fn sam_uei(&self) -> Option<&str> {
+            self.1
+        }
+    }
+
+    #[test]
+    fn test_upgrade_with_later_identifiers() {
+        let tier3 = generate_snfei("Acme Corp", "US", Some("1 Main St"), None);
+        assert_eq!(tier3.tier, 3);
+
+        let verified = IdentifierVerification::RegistryVerified;
+        let unverified = IdentifierVerification::Unverified;
+        let tier2 = tier3.upgrade_with(&Known(None, Some("J6H4FB3N5YK7")), verified);
+        assert_eq!(tier2.tier, 2);
+        assert_eq!(tier2.confidence_score, 0.95);
+        assert_eq!(tier2.snfei, tier3.snfei);
+
+        let lei = Known(Some("529900T8BM49AURSDO55"), None);
+        assert_eq!(tier2.upgrade_with(&lei, unverified).tier, 2);
+        let tier1 = tier2.upgrade_with(&lei, verified);
+        assert_eq!(tier1.tier, 1);
+        assert_eq!(tier1.fields_used[0], "lei");
+
+        // Never downgrades, and nothing changes without a better identifier.
+        assert_eq!(
+            tier1
+                .upgrade_with(&Known(None, Some("J6H4FB3N5YK7")), verified)
+                .tier,
+            1
+        );
+        assert_eq!(tier3.upgrade_with(&Known(None, None), verified).tier, 3);
     }
 
     #[test]
@@ -324,4 +570,19 @@ mod tests {
         let snfei = Snfei::from_hash(invalid_hash);
         assert!(snfei.is_none());
     }
+
+    #[test]
+    fn test_snfei_conversions_and_serde() {
+        let hash = "A".repeat(64);
+        let snfei: Snfei = hash.parse().unwrap();
+        assert_eq!(snfei.as_ref(), "a".repeat(64));
+        assert_eq!(Snfei::try_from(hash.as_str()).unwrap(), snfei);
+
+        let err = Snfei::try_from("g".repeat(64)).unwrap_err();
+        assert!(matches!(err, CepError::InvalidIdentifier(_)));
+
+        let json = serde_json::to_string(&snfei).unwrap();
+        assert_eq!(serde_json::from_str::<Snfei>(&json).unwrap(), snfei);
+        assert!(serde_json::from_str::<Snfei>(r#"{"value":"bad"}"#).is_err());
+    }
 }
@@ -0,0 +1,416 @@
+//! Keyed SNFEIs with rotating federation keys.
+//!
+//! Where [`assess_reidentification`](crate::assess_reidentification)
+//! recommends keying, a federation shares a secret per deployment and issues
+//! HMAC-SHA256 over the canonical hash string instead of the bare SHA-256.
+//! Without the secret, enumerating inputs no longer reveals which entity an
+//! identifier names. The id of the key is embedded in the identifier so a
+//! verifier knows which secret produced it:
+//!
+//! ```text
+//!     ksnfei:<key-id>:<64 hex characters>
+//! ```
+//!
+//! Rotating the key would otherwise break linkage between records issued
+//! before and after the change. A [`SnfeiKeyRing`] keeps the previous keys
+//! alongside the current one during the transition:
+//!
+//! - [`SnfeiKeyRing::compute_all`] issues the identifier under every key, so
+//!   peers still on the old key can match;
+//! - [`SnfeiKeyRing::verify`] accepts an identifier under any key in the ring;
+//! - [`SnfeiKeyRing::rekey`] maps an old identifier to the current key, given
+//!   the canonical input it was issued for.
+//!
+//! Once every peer has switched, [`SnfeiKeyRing::retire`] drops the old key.
+//!
+//! ```rust
+//! use cep_snfei::build_canonical_input;
+//! use cep_snfei::keyed::{SnfeiKey, SnfeiKeyRing};
+//!
+//! let canonical = build_canonical_input("Springfield Water District", "US", None, None);
+//! let old = SnfeiKey::new("2025a", [7u8; 32].to_vec()).unwrap();
+//! let issued = SnfeiKeyRing::new(old.clone()).compute(&canonical);
+//!
+//! let ring = SnfeiKeyRing::new(old).rotate(SnfeiKey::new("2026a", [9u8; 32].to_vec()).unwrap());
+//! assert!(ring.verify(&canonical, &issued).is_ok());
+//! let current = ring.rekey(&canonical, &issued).unwrap();
+//! assert_eq!(current.key_id(), "2026a");
+//! assert_eq!(current, ring.compute(&canonical));
+//! ```
+
+use std::fmt;
+
+use cep_core::{CepError, CepResult};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::normalizer::CanonicalInput;
+
+/// Prefix of a keyed SNFEI.
+pub const KEYED_PREFIX: &str = "ksnfei:";
+
+/// Shortest secret accepted, in bytes.
+pub const MIN_SECRET_LEN: usize = 32;
+
+/// Longest key id accepted.
+const MAX_KEY_ID_LEN: usize = 32;
+
+/// A federation secret and the id it is published under.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SnfeiKey {
+    id: String,
+    secret: Vec<u8>,
+}
+
+impl fmt::Debug for SnfeiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnfeiKey")
+            .field("id", &self.id)
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
+
+impl SnfeiKey {
+    /// Creates a key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::Configuration` if the id is not 1 to 32 lowercase
+    /// letters, digits or hyphens, or the secret is shorter than
+    /// [`MIN_SECRET_LEN`] bytes.
+    pub fn new(id: &str, secret: Vec<u8>) -> CepResult<Self> {
+        if !is_valid_key_id(id) {
+            return Err(CepError::Configuration(format!(
+                "SNFEI key id '{}' must be 1-{} lowercase letters, digits or hyphens",
+                id, MAX_KEY_ID_LEN
+            )));
+        }
+        if secret.len() < MIN_SECRET_LEN {
+            return Err(CepError::Configuration(format!(
+                "SNFEI key '{}' secret is {} bytes, at least {} required",
+                id,
+                secret.len(),
+                MIN_SECRET_LEN
+            )));
+        }
+        Ok(Self {
+            id: id.to_string(),
+            secret,
+        })
+    }
+
+    /// The key's id.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Computes the keyed SNFEI of `canonical` under this key.
+    pub fn compute(&self, canonical: &CanonicalInput) -> KeyedSnfei {
+        KeyedSnfei {
+            key_id: self.id.clone(),
+            value: hex::encode(self.mac(canonical).finalize().into_bytes()),
+        }
+    }
+
+    fn mac(&self, canonical: &CanonicalInput) -> Hmac<Sha256> {
+        hmac_sha256(&self.secret, canonical.to_hash_string().as_bytes())
+    }
+}
+
+/// An SNFEI keyed with a federation secret.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyedSnfei {
+    key_id: String,
+    value: String,
+}
+
+impl KeyedSnfei {
+    /// Parses `ksnfei:<key-id>:<64 hex>`, storing the hex lowercase.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidIdentifier` describing what is wrong.
+    pub fn parse(text: &str) -> CepResult<Self> {
+        let invalid = |reason: &str| {
+            CepError::InvalidIdentifier(format!("keyed SNFEI '{}' {}", text, reason))
+        };
+        let rest = text
+            .strip_prefix(KEYED_PREFIX)
+            .ok_or_else(|| invalid(&format!("must start with '{}'", KEYED_PREFIX)))?;
+        let (key_id, value) = rest
+            .split_once(':')
+            .ok_or_else(|| invalid("has no key id"))?;
+        if !is_valid_key_id(key_id) {
+            return Err(invalid("has an invalid key id"));
+        }
+        if value.len() != 64 || !value.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid("must end in 64 hex characters"));
+        }
+        Ok(Self {
+            key_id: key_id.to_string(),
+            value: value.to_lowercase(),
+        })
+    }
+
+    /// Id of the key that produced this identifier.
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// The HMAC as lowercase hex.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for KeyedSnfei {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}:{}", KEYED_PREFIX, self.key_id, self.value)
+    }
+}
+
+impl std::str::FromStr for KeyedSnfei {
+    type Err = CepError;
+
+    fn from_str(s: &str) -> CepResult<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for KeyedSnfei {
+    type Error = CepError;
+
+    fn try_from(value: String) -> CepResult<Self> {
+        Self::parse(&value)
+    }
+}
+
+impl From<KeyedSnfei> for String {
+    fn from(snfei: KeyedSnfei) -> Self {
+        snfei.to_string()
+    }
+}
+
+/// The current key and any previous keys still in transition.
+#[derive(Debug, Clone)]
+pub struct SnfeiKeyRing {
+    current: SnfeiKey,
+    previous: Vec<SnfeiKey>,
+}
+
+impl SnfeiKeyRing {
+    /// Creates a ring with a single key.
+    pub fn new(current: SnfeiKey) -> Self {
+        Self {
+            current,
+            previous: Vec::new(),
+        }
+    }
+
+    /// Makes `key` current and keeps the old current key for the transition.
+    /// A previous key with the same id as `key` is replaced.
+    pub fn rotate(mut self, key: SnfeiKey) -> Self {
+        let old = std::mem::replace(&mut self.current, key);
+        self.previous.insert(0, old);
+        let current_id = self.current.id.clone();
+        self.previous.retain(|k| k.id != current_id);
+        self
+    }
+
+    /// Drops a previous key once every peer has moved off it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::Configuration` if `key_id` is the current key or
+    /// not in the ring.
+    pub fn retire(&mut self, key_id: &str) -> CepResult<()> {
+        if key_id == self.current.id {
+            return Err(CepError::Configuration(format!(
+                "cannot retire the current SNFEI key '{}'",
+                key_id
+            )));
+        }
+        let before = self.previous.len();
+        self.previous.retain(|k| k.id != key_id);
+        if self.previous.len() == before {
+            return Err(CepError::Configuration(format!(
+                "SNFEI key '{}' is not in the ring",
+                key_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// The current key.
+    pub fn current(&self) -> &SnfeiKey {
+        &self.current
+    }
+
+    /// Ids of all keys, current first.
+    pub fn key_ids(&self) -> Vec<&str> {
+        std::iter::once(&self.current)
+            .chain(&self.previous)
+            .map(|k| k.id())
+            .collect()
+    }
+
+    /// Computes the keyed SNFEI under the current key.
+    pub fn compute(&self, canonical: &CanonicalInput) -> KeyedSnfei {
+        self.current.compute(canonical)
+    }
+
+    /// Computes the keyed SNFEI under every key, current first, for issuing
+    /// during a transition.
+    pub fn compute_all(&self, canonical: &CanonicalInput) -> Vec<KeyedSnfei> {
+        std::iter::once(&self.current)
+            .chain(&self.previous)
+            .map(|k| k.compute(canonical))
+            .collect()
+    }
+
+    /// Checks that `snfei` was issued for `canonical` under a key in the ring.
+    /// The value is compared in constant time.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::InvalidIdentifier` if the key id is unknown and
+    /// `CepError::HashMismatch` if the value does not match.
+    pub fn verify(&self, canonical: &CanonicalInput, snfei: &KeyedSnfei) -> CepResult<()> {
+        let key = self.key(snfei.key_id())?;
+        let value = hex::decode(&snfei.value).map_err(|e| {
+            CepError::InvalidIdentifier(format!("malformed keyed SNFEI '{}': {}", snfei, e))
+        })?;
+        key.mac(canonical)
+            .verify_slice(&value)
+            .map_err(|_| CepError::HashMismatch {
+                expected: key.compute(canonical).to_string(),
+                actual: snfei.to_string(),
+            })
+    }
+
+    /// Verifies `snfei` and returns the identifier for the same canonical
+    /// input under the current key, linking records across a rotation.
+    ///
+    /// # Errors
+    ///
+    /// As [`Self::verify`].
+    pub fn rekey(&self, canonical: &CanonicalInput, snfei: &KeyedSnfei) -> CepResult<KeyedSnfei> {
+        self.verify(canonical, snfei)?;
+        Ok(self.compute(canonical))
+    }
+
+    fn key(&self, key_id: &str) -> CepResult<&SnfeiKey> {
+        std::iter::once(&self.current)
+            .chain(&self.previous)
+            .find(|k| k.id == key_id)
+            .ok_or_else(|| {
+                CepError::InvalidIdentifier(format!("unknown SNFEI key id '{}'", key_id))
+            })
+    }
+}
+
+fn is_valid_key_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_KEY_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// HMAC-SHA256 (RFC 2104) of `message`, ready to finalize or verify.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(message);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::normalizer::build_canonical_input;
+
+    fn key(id: &str, byte: u8) -> SnfeiKey {
+        SnfeiKey::new(id, vec![byte; MIN_SECRET_LEN]).unwrap()
+    }
+
+    #[test]
+    fn test_hmac_rfc4231_vectors() {
+        assert_eq!(
+            hex::encode(
+                hmac_sha256(b"Jefe", b"what do ya want for nothing?")
+                    .finalize()
+                    .into_bytes()
+            ),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // Key longer than the block size is hashed first.
+        assert_eq!(
+            hex::encode(
+                hmac_sha256(
+                    &[0xaa; 131],
+                    b"Test Using Larger Than Block-Size Key - Hash Key First"
+                )
+                .finalize()
+                .into_bytes()
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_keyed_snfei_format() {
+        let canonical = build_canonical_input("Acme Corp", "US", None, None);
+        let snfei = key("2026a", 1).compute(&canonical);
+        let text = snfei.to_string();
+        assert!(text.starts_with("ksnfei:2026a:"));
+        let upper = text.replace(snfei.value(), &snfei.value().to_uppercase());
+        assert_eq!(KeyedSnfei::parse(&upper).unwrap(), snfei);
+        assert_ne!(snfei.value(), crate::compute_snfei(&canonical).value());
+        assert_ne!(snfei.value(), key("2026b", 2).compute(&canonical).value());
+
+        let json = serde_json::to_string(&snfei).unwrap();
+        assert_eq!(json, format!("\"{}\"", text));
+        assert_eq!(serde_json::from_str::<KeyedSnfei>(&json).unwrap(), snfei);
+
+        for bad in [
+            "snfei:2026a:00",
+            "ksnfei:00",
+            "ksnfei:Bad:00",
+            "ksnfei:k:zz",
+        ] {
+            assert!(KeyedSnfei::parse(bad).is_err(), "{}", bad);
+        }
+        assert!(SnfeiKey::new("k", vec![0; 16]).is_err());
+        assert!(!format!("{:?}", key("k", 42)).contains("42"));
+    }
+
+    #[test]
+    fn test_rotation_keeps_linkage() {
+        let canonical = build_canonical_input("Springfield Water District", "US", None, None);
+        let before = SnfeiKeyRing::new(key("2025a", 1));
+        let issued = before.compute(&canonical);
+
+        let mut ring = before.rotate(key("2026a", 2));
+        assert_eq!(ring.key_ids(), ["2026a", "2025a"]);
+        let all = ring.compute_all(&canonical);
+        assert_eq!(all[1], issued);
+        assert!(ring.verify(&canonical, &issued).is_ok());
+        assert_eq!(ring.rekey(&canonical, &issued).unwrap(), all[0]);
+
+        let other = build_canonical_input("Shelbyville Water District", "US", None, None);
+        assert!(matches!(
+            ring.verify(&other, &issued),
+            Err(CepError::HashMismatch { .. })
+        ));
+
+        assert!(ring.retire("2026a").is_err());
+        ring.retire("2025a").unwrap();
+        assert!(matches!(
+            ring.verify(&canonical, &issued),
+            Err(CepError::InvalidIdentifier(_))
+        ));
+    }
+}
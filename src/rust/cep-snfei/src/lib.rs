@@ -1,57 +1,71 @@
-/// CEP SNFEI: Entity Resolution and SNFEI Generation.
-///
-/// This crate implements the Normalizing Functor architecture for generating
-/// deterministic entity identifiers (SNFEIs) from heterogeneous source data.
-///
-/// # Architecture
-///
-/// ```text
-///     ┌──────────────┐     ┌────────────────┐     ┌─────────────┐
-///     │  Raw Entity  │     │  Intermediate  │     │  Canonical  │
-///     │    Data      │────>│    Canonical   │────>│   Entity    │
-///     │              │  L  │                │  N  │             │
-///     └──────────────┘     └────────────────┘     └─────────────┘
-///                                                         │
-///                                                         │ SHA-256
-///                                                         V
-///                                                 ┌──────────────┐
-///                                                 │    SNFEI     │
-///                                                 │  (64-char)   │
-///                                                 └──────────────┘
-///
-///     L = Localization Functor (jurisdiction-specific transforms)
-///     N = Normalizing Functor (universal normalization)
-/// ```
-///
-/// # Usage
-///
-/// ```rust
-/// use cep_snfei::{generate_snfei, normalize_legal_name, apply_localization};
-///
-/// // Simple SNFEI generation
-/// let result = generate_snfei(
-///     "Springfield USD #12",
-///     "US",
-///     Some("123 Main St"),
-///     None,
-/// );
-///
-/// // Access the SNFEI value and canonical inputs
-/// let snfei = result.snfei.value();
-/// let inputs = &result.canonical;
-///
-/// assert_eq!(inputs.country_code, "US");
-/// assert!(!snfei.is_empty());
-///
-/// // With jurisdiction-specific localization
-/// let localized = apply_localization("MTA", "us/ny");
-/// assert_eq!(localized, "metropolitan transportation authority");
-/// ```
+//! CEP SNFEI: Entity Resolution and SNFEI Generation.
+//!
+//! This crate implements the Normalizing Functor architecture for generating
+//! deterministic entity identifiers (SNFEIs) from heterogeneous source data.
+//!
+//! # Architecture
+//!
+//! ```text
+//!     ┌──────────────┐     ┌────────────────┐     ┌─────────────┐
+//!     │  Raw Entity  │     │  Intermediate  │     │  Canonical  │
+//!     │    Data      │────>│    Canonical   │────>│   Entity    │
+//!     │              │  L  │                │  N  │             │
+//!     └──────────────┘     └────────────────┘     └─────────────┘
+//!                                                         │
+//!                                                         │ SHA-256
+//!                                                         V
+//!                                                 ┌──────────────┐
+//!                                                 │    SNFEI     │
+//!                                                 │  (64-char)   │
+//!                                                 └──────────────┘
+//!
+//!     L = Localization Functor (jurisdiction-specific transforms)
+//!     N = Normalizing Functor (universal normalization)
+//! ```
+//!
+//! # Usage
+//!
+//! ```rust
+//! use cep_snfei::{generate_snfei, normalize_legal_name, apply_localization};
+//!
+//! // Simple SNFEI generation
+//! let result = generate_snfei(
+//!     "Springfield USD #12",
+//!     "US",
+//!     Some("123 Main St"),
+//!     None,
+//! );
+//!
+//! // Access the SNFEI value and canonical inputs
+//! let snfei = result.snfei.value();
+//! let inputs = &result.canonical;
+//!
+//! assert_eq!(inputs.country_code, "US");
+//! assert!(!snfei.is_empty());
+//!
+//! // With jurisdiction-specific localization
+//! let localized = apply_localization("MTA", "us/ny");
+//! assert_eq!(localized, "metropolitan transportation authority");
+//! ```
 
 
+mod checked;
+pub mod compat;
 mod generator;
+pub mod keyed;
 mod localization;
 mod normalizer;
+pub mod pipeline;
+mod privacy;
+
+// Re-export checked display encoding
+pub use checked::{
+    decode_checked,
+    encode_checked,
+    verify_checked,
+    CheckedSnfeiError,
+    CHECKED_PREFIX,
+};
 
 // Re-export generator types
 pub use generator::{
@@ -59,8 +73,10 @@ pub use generator::{
     generate_snfei,
     generate_snfei_simple,
     generate_snfei_with_confidence,
+    IdentifierVerification,
     Snfei,
     SnfeiResult,
+    TierIdentifiers,
 };
 
 // Re-export normalization types
@@ -72,6 +88,16 @@ pub use normalizer::{
     CanonicalInput,
 };
 
+// Re-export privacy analysis
+pub use privacy::{
+    assess_reidentification,
+    reverse_lookup,
+    KeyingRecommendation,
+    PrivacyAssessment,
+    PrivacyPolicy,
+    PrivacyVocabulary,
+};
+
 // Re-export localization types
 pub use localization::{
     apply_localization,
@@ -79,4 +105,9 @@ pub use localization::{
     LocalizationConfig,
     LocalizationRegistry,
     LocalizationRule,
-};
\ No newline at end of file
+};
+
+/// Expose the canonical input JSON Schema via cep-core.
+pub fn canonical_input_schema_json() -> Option<&'static str> {
+    cep_core::get_schema("cep.snfei.canonical-input")
+}
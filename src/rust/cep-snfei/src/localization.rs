@@ -1,16 +1,16 @@
-/// Localization Functor - Jurisdiction-Specific Transforms.
-///
-/// The Localization Functor L transforms raw entity names using
-/// jurisdiction-specific rules before universal normalization:
-///
-/// ```markdown
-///     L: RawLocal → IntermediateCanonical
-///     N: IntermediateCanonical → FinalCanonical
-///
-///     SNFEI = Hash(N(L(raw_data)))
-/// ```
-///
-/// Dependencies required for the localization logic
+//! Localization Functor - Jurisdiction-Specific Transforms.
+//!
+//! The Localization Functor L transforms raw entity names using
+//! jurisdiction-specific rules before universal normalization:
+//!
+//! ```markdown
+//!     L: RawLocal → IntermediateCanonical
+//!     N: IntermediateCanonical → FinalCanonical
+//!
+//!     SNFEI = Hash(N(L(raw_data)))
+//! ```
+
+// Dependencies required for the localization logic
 // Required for static initialization of the global config map
 use lazy_static::lazy_static;
 use regex::Regex;
@@ -24,7 +24,7 @@ use std::sync::Mutex;
 // =============================================================================
 
 /// Represents a single localization transformation rule.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct LocalizationRule {
     pub pattern: String,
     pub replacement: String,
@@ -33,17 +33,6 @@ pub struct LocalizationRule {
     pub context: Option<String>,
 }
 
-impl Default for LocalizationRule {
-    fn default() -> Self {
-        LocalizationRule {
-            pattern: String::new(),
-            replacement: String::new(),
-            is_regex: false,
-            context: None,
-        }
-    }
-}
-
 /// Configuration loaded for a specific jurisdiction, potentially merged from parents.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LocalizationConfig {
@@ -335,9 +324,7 @@ impl LocalizationRegistry {
 
     /// Load config from YAML file. Simulates Python's `_load_yaml`.
     fn load_yaml(&mut self, jurisdiction: &str) -> Option<LocalizationConfig> {
-        if self.config_dir.is_none() {
-            return None;
-        }
+        self.config_dir.as_ref()?;
 
         // --- Python-matching logic for path derivation ---
         let config_path = {
@@ -469,7 +456,7 @@ impl LocalizationRegistry {
                 }
                 // 5. Return empty config as last resort
                 return LocalizationConfig {
-                    jurisdiction: jurisdiction,
+                    jurisdiction,
                     parent: None,
                     ..Default::default()
                 };
@@ -1,13 +1,13 @@
-/// Universal Normalization Pipeline.
-///
-/// The Normalizing Functor N transforms the category of Raw Entity Data
-/// into the category of Canonical Entity Data:
-///
-/// ```markdown
-///     N: RawEntity → CanonicalEntity
-/// ```
-/// Where N preserves identity (same entity always maps to same canonical form)
-/// and composition (N(L(x)) = N ∘ L(x) where L is the localization functor).
+//! Universal Normalization Pipeline.
+//!
+//! The Normalizing Functor N transforms the category of Raw Entity Data
+//! into the category of Canonical Entity Data:
+//!
+//! ```markdown
+//!     N: RawEntity → CanonicalEntity
+//! ```
+//! Where N preserves identity (same entity always maps to same canonical form)
+//! and composition (N(L(x)) = N ∘ L(x) where L is the localization functor).
 
 use lazy_static::lazy_static;
 use regex::Regex;
@@ -15,6 +15,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use unicode_normalization::UnicodeNormalization;
 
+use crate::generator::{compute_snfei, Snfei};
+use crate::pipeline::NormalizingFunctor;
+use cep_core::{CepError, CepResult};
+
 // =============================================================================
 // LEGAL SUFFIX EXPANSIONS
 // =============================================================================
@@ -203,7 +207,7 @@ lazy_static! {
 // =============================================================================
 
 /// Convert Unicode to ASCII equivalent.
-fn to_ascii(text: &str) -> String {
+pub(crate) fn to_ascii(text: &str) -> String {
     // First, apply NFD normalization to decompose characters
     let normalized: String = text.nfkd().collect();
     
@@ -230,14 +234,14 @@ fn to_ascii(text: &str) -> String {
 }
 
 /// Remove all punctuation from text.
-fn remove_punctuation(text: &str) -> String {
+pub(crate) fn remove_punctuation(text: &str) -> String {
     text.chars()
         .filter(|c| c.is_alphanumeric() || c.is_whitespace())
         .collect()
 }
 
 /// Collapse multiple whitespace to single space and trim.
-fn collapse_whitespace(text: &str) -> String {
+pub(crate) fn collapse_whitespace(text: &str) -> String {
     let mut result = String::with_capacity(text.len());
     let mut prev_space = true; // Start true to trim leading
     
@@ -260,31 +264,43 @@ fn collapse_whitespace(text: &str) -> String {
     result
 }
 
-/// Expand a single token if it matches known abbreviations.
-fn expand_token(token: &str) -> String {
-    let lower = token.to_lowercase();
-    
-    // Check legal suffixes first (highest priority)
-    if let Some(&expansion) = LEGAL_SUFFIX_EXPANSIONS.get(lower.as_str()) {
-        return expansion.to_string();
-    }
-    
-    // Check common abbreviations
-    if let Some(&expansion) = COMMON_ABBREVIATIONS.get(lower.as_str()) {
-        return expansion.to_string();
-    }
-    
-    lower
-}
-
-/// Expand all abbreviations in text.
-fn expand_abbreviations(text: &str) -> String {
+/// Replace every token found in `expansions` with its expansion.
+fn expand_tokens(text: &str, expansions: &HashMap<&'static str, &'static str>) -> String {
     text.split_whitespace()
-        .map(expand_token)
+        .map(|token| expansions.get(token).copied().unwrap_or(token))
         .collect::<Vec<_>>()
         .join(" ")
 }
 
+/// Expand legal entity suffixes (llc→limited liability company).
+pub(crate) fn expand_legal_forms(text: &str) -> String {
+    expand_tokens(text, &LEGAL_SUFFIX_EXPANSIONS)
+}
+
+/// Expand common abbreviations (usd→unified school district).
+pub(crate) fn expand_common_abbreviations(text: &str) -> String {
+    expand_tokens(text, &COMMON_ABBREVIATIONS)
+}
+
+/// Remove secondary unit designators (apt, suite, floor, etc.).
+pub(crate) fn strip_secondary_units(text: &str) -> String {
+    SECONDARY_UNIT_PATTERNS
+        .iter()
+        .fold(text.to_string(), |text, pattern| {
+            pattern.replace_all(&text, "").to_string()
+        })
+}
+
+/// Expand postal abbreviations (st→street, ave→avenue, n→north).
+pub(crate) fn expand_postal_abbreviations(text: &str) -> String {
+    expand_tokens(text, &US_ADDRESS_EXPANSIONS)
+}
+
+/// Remove stop words, including a leading one.
+pub(crate) fn strip_stop_words(text: &str) -> String {
+    remove_stop_words(text, false)
+}
+
 /// Remove stop words from text.
 fn remove_stop_words(text: &str, preserve_initial: bool) -> String {
     let tokens: Vec<&str> = text.split_whitespace().collect();
@@ -313,14 +329,16 @@ fn remove_stop_words(text: &str, preserve_initial: bool) -> String {
 
 /// Apply the universal normalization pipeline to a legal name.
 ///
-/// Pipeline (in order):
+/// Pipeline (in order; stage names in
+/// [`Pipeline::legal_name`](crate::pipeline::Pipeline::legal_name)):
 /// 1. Convert to lowercase
 /// 2. ASCII transliteration (é→e, ñ→n, etc.)
 /// 3. Remove punctuation (ALL punctuation including hyphens)
 /// 4. Collapse whitespace
-/// 5. Expand abbreviations (inc→incorporated, usd→unified school district)
-/// 6. Remove stop words (the, of, a, an, and, for, in, on, at, to, by)
-/// 7. Final trim
+/// 5. Expand legal forms (inc→incorporated, llc→limited liability company)
+/// 6. Expand abbreviations (usd→unified school district)
+/// 7. Remove stop words (the, of, a, an, and, for, in, on, at, to, by)
+/// 8. Final trim
 ///
 /// # Arguments
 /// * `name` - Raw legal name from source system
@@ -339,32 +357,13 @@ pub fn normalize_legal_name(name: &str) -> String {
     if name.is_empty() {
         return String::new();
     }
-    
-    // 1. Lowercase
-    let text = name.to_lowercase();
-    
-    // 2. ASCII transliteration
-    let text = to_ascii(&text);
-    
-    // 3. Remove punctuation
-    let text = remove_punctuation(&text);
-    
-    // 4. Collapse whitespace
-    let text = collapse_whitespace(&text);
-    
-    // 5. Expand abbreviations
-    let text = expand_abbreviations(&text);
-    
-    // 6. Remove stop words
-    let text = remove_stop_words(&text, false);
-    
-    // 7. Final collapse and trim
-    collapse_whitespace(&text)
+    NormalizingFunctor::standard().legal_name.apply(name)
 }
 
 /// Normalize a street address for SNFEI hashing.
 ///
-/// Pipeline:
+/// Pipeline (see [`Pipeline::address`](crate::pipeline::Pipeline::address) for
+/// the stage names):
 /// 1. Lowercase
 /// 2. ASCII transliteration
 /// 3. Remove secondary unit designators (apt, suite, floor, etc.)
@@ -389,38 +388,7 @@ pub fn normalize_address(address: &str) -> String {
     if address.is_empty() {
         return String::new();
     }
-    
-    // 1. Lowercase
-    let mut text = address.to_lowercase();
-    
-    // 2. ASCII transliteration
-    text = to_ascii(&text);
-    
-    // 3. Remove secondary unit designators
-    for pattern in SECONDARY_UNIT_PATTERNS.iter() {
-        text = pattern.replace_all(&text, "").to_string();
-    }
-    
-    // 4. Remove punctuation
-    text = remove_punctuation(&text);
-    
-    // 5. Collapse whitespace first
-    text = collapse_whitespace(&text);
-    
-    // 6. Expand postal abbreviations
-    let tokens: Vec<String> = text
-        .split_whitespace()
-        .map(|t| {
-            US_ADDRESS_EXPANSIONS
-                .get(t)
-                .map(|&s| s.to_string())
-                .unwrap_or_else(|| t.to_string())
-        })
-        .collect();
-    text = tokens.join(" ");
-    
-    // 7. Final trim
-    text.trim().to_string()
+    NormalizingFunctor::standard().address.apply(address)
 }
 
 /// Normalize a registration date to ISO 8601 format.
@@ -459,7 +427,7 @@ pub fn normalize_registration_date(date_str: &str) -> Option<String> {
                     let month: u32 = caps.get(1)?.as_str().parse().ok()?;
                     let day: u32 = caps.get(2)?.as_str().parse().ok()?;
                     let year: u32 = caps.get(3)?.as_str().parse().ok()?;
-                    if month >= 1 && month <= 12 && day >= 1 && day <= 31 {
+                    if (1..=12).contains(&month) && (1..=31).contains(&day) {
                         return Some(format!("{:04}-{:02}-{:02}", year, month, day));
                     }
                 }
@@ -481,7 +449,7 @@ pub fn normalize_registration_date(date_str: &str) -> Option<String> {
 // =============================================================================
 
 /// Normalized input for SNFEI hashing.
-#[derive(Debug, Clone, Serialize, Deserialize)] 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CanonicalInput {
     pub legal_name_normalized: String,
     pub address_normalized: Option<String>,
@@ -506,6 +474,25 @@ impl CanonicalInput {
         ];
         parts.join("|")
     }
+
+    /// Recompute the SNFEI from these fields and compare it to `snfei`.
+    ///
+    /// Used when auditing a persisted canonical input against the identifier
+    /// it is said to have produced.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::HashMismatch` if the recomputed SNFEI differs.
+    pub fn verify_against(&self, snfei: &Snfei) -> CepResult<()> {
+        let actual = compute_snfei(self);
+        if actual != *snfei {
+            return Err(CepError::HashMismatch {
+                expected: snfei.value().to_string(),
+                actual: actual.value().to_string(),
+            });
+        }
+        Ok(())
+    }
 }
 
 /// Build a canonical input structure from raw entity data.
@@ -708,4 +695,35 @@ mod tests {
         assert_eq!(input.country_code, "US");
         assert_eq!(input.registration_date, Some("1990-03-15".to_string()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_canonical_input_round_trip_and_verify() {
+        let result = crate::generate_snfei(
+            "Springfield USD #12",
+            "US",
+            Some("123 N. Main St."),
+            Some("03/15/1990"),
+        );
+        let json = serde_json::to_string(&result.canonical).unwrap();
+        let persisted: CanonicalInput = serde_json::from_str(&json).unwrap();
+        assert_eq!(persisted, result.canonical);
+        assert!(persisted.verify_against(&result.snfei).is_ok());
+
+        let tampered = CanonicalInput {
+            registration_date: Some("1990-03-16".to_string()),
+            ..persisted
+        };
+        assert!(matches!(
+            tampered.verify_against(&result.snfei),
+            Err(CepError::HashMismatch { .. })
+        ));
+
+        let schema = cep_core::SchemaRegistry::new()
+            .unwrap()
+            .get_schema("snfei-canonical-input", None)
+            .unwrap();
+        let value = serde_json::to_value(&result.canonical).unwrap();
+        let properties = schema["properties"].as_object().unwrap();
+        assert!(value.as_object().unwrap().keys().all(|k| properties.contains_key(k)));
+    }
+}
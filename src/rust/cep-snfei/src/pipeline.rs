@@ -0,0 +1,565 @@
+//! The Normalizing Functor as an ordered pipeline of named, versioned stages.
+//!
+//! Each [`Stage`] has a name and a version, written `name@version`
+//! (`case_fold@1`, `legal_form@1`, ...). A [`Pipeline`] applies its stages in
+//! order, and its [`Pipeline::version_hash`] is a hash over the stage ids, so
+//! any change to which stages run, their order, or their versions yields a
+//! different hash. The standard legal name and address pipelines together
+//! with the date rules make up the SNFEI algorithm; their composed hash is
+//! [`algorithm_version`].
+//!
+//! Deployments can inspect the stages, add their own, and pin the exact
+//! stage list they were validated against:
+//!
+//! ```rust
+//! use cep_snfei::pipeline::{FnStage, NormalizingFunctor, Pipeline};
+//!
+//! let standard = Pipeline::legal_name();
+//! assert_eq!(standard.stage_ids()[0], "case_fold@1");
+//! standard.pin(&standard.stage_ids()).unwrap();
+//!
+//! fn drop_hash_numbers(text: &str) -> String {
+//!     text.split_whitespace()
+//!         .filter(|t| !t.chars().all(|c| c.is_ascii_digit()))
+//!         .collect::<Vec<_>>()
+//!         .join(" ")
+//! }
+//! let custom = standard
+//!     .insert_after("punct_strip", FnStage::new("number_strip", 1, drop_hash_numbers))
+//!     .unwrap();
+//! assert_eq!(custom.apply("Springfield USD #12"), "springfield unified school district");
+//! assert_ne!(custom.version_hash(), Pipeline::legal_name().version_hash());
+//!
+//! let functor = NormalizingFunctor { legal_name: custom, ..NormalizingFunctor::default() };
+//! assert_ne!(functor.algorithm_version(), cep_snfei::pipeline::algorithm_version());
+//! ```
+//!
+//! When two implementations disagree on a hash,
+//! [`NormalizingFunctor::canonical_input_traced`] records every stage's input
+//! and output as a [`NormalizationTrace`], and [`compare_traces`] finds the
+//! first stage where two traces part ways. Traces serialize to JSON so one
+//! can come from the Python implementation.
+
+use std::fmt;
+use std::sync::Arc;
+
+use cep_core::{CanonicalHash, CepError, CepResult};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+use crate::normalizer::{
+    CanonicalInput, collapse_whitespace, expand_common_abbreviations, expand_legal_forms,
+    expand_postal_abbreviations, normalize_registration_date, remove_punctuation,
+    strip_secondary_units, strip_stop_words, to_ascii,
+};
+
+/// Id of the fixed registration date rules, which are not a pipeline.
+pub(crate) const REGISTRATION_DATE_STAGE: &str = "date_iso@1";
+
+/// One named, versioned normalization step.
+///
+/// A stage must be deterministic, and any change to its output for some
+/// input must come with a new version.
+pub trait Stage: Send + Sync {
+    /// Stage name, unique within a pipeline.
+    fn name(&self) -> &str;
+
+    /// Stage version.
+    fn version(&self) -> u32;
+
+    /// Transforms the text.
+    fn apply(&self, text: &str) -> String;
+
+    /// `name@version`.
+    fn id(&self) -> String {
+        format!("{}@{}", self.name(), self.version())
+    }
+}
+
+/// A stage backed by a plain function.
+#[derive(Clone, Copy)]
+pub struct FnStage {
+    name: &'static str,
+    version: u32,
+    f: fn(&str) -> String,
+}
+
+impl FnStage {
+    pub fn new(name: &'static str, version: u32, f: fn(&str) -> String) -> Self {
+        Self { name, version, f }
+    }
+}
+
+impl Stage for FnStage {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn apply(&self, text: &str) -> String {
+        (self.f)(text)
+    }
+}
+
+/// An ordered list of stages.
+#[derive(Clone, Default)]
+pub struct Pipeline {
+    stages: Vec<Arc<dyn Stage>>,
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.stage_ids()).finish()
+    }
+}
+
+impl Pipeline {
+    /// Creates an empty pipeline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The standard legal name pipeline.
+    pub fn legal_name() -> Self {
+        Self::new()
+            .with_stage(FnStage::new("case_fold", 1, str::to_lowercase))
+            .with_stage(FnStage::new("ascii_fold", 1, to_ascii))
+            .with_stage(FnStage::new("punct_strip", 1, remove_punctuation))
+            .with_stage(FnStage::new("whitespace_collapse", 1, collapse_whitespace))
+            .with_stage(FnStage::new("legal_form", 1, expand_legal_forms))
+            .with_stage(FnStage::new(
+                "abbreviation_expand",
+                1,
+                expand_common_abbreviations,
+            ))
+            .with_stage(FnStage::new("stop_word_strip", 1, strip_stop_words))
+            .with_stage(FnStage::new("trim", 1, collapse_whitespace))
+    }
+
+    /// The standard street address pipeline.
+    pub fn address() -> Self {
+        Self::new()
+            .with_stage(FnStage::new("case_fold", 1, str::to_lowercase))
+            .with_stage(FnStage::new("ascii_fold", 1, to_ascii))
+            .with_stage(FnStage::new("unit_strip", 1, strip_secondary_units))
+            .with_stage(FnStage::new("punct_strip", 1, remove_punctuation))
+            .with_stage(FnStage::new("whitespace_collapse", 1, collapse_whitespace))
+            .with_stage(FnStage::new(
+                "postal_expand",
+                1,
+                expand_postal_abbreviations,
+            ))
+    }
+
+    /// Appends a stage.
+    pub fn with_stage(mut self, stage: impl Stage + 'static) -> Self {
+        self.stages.push(Arc::new(stage));
+        self
+    }
+
+    /// Inserts a stage right after the stage named `after`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::Configuration` if there is no such stage or the
+    /// new stage's name is already taken.
+    pub fn insert_after(mut self, after: &str, stage: impl Stage + 'static) -> CepResult<Self> {
+        self.check_name_free(stage.name())?;
+        let index = self.position(after)?;
+        self.stages.insert(index + 1, Arc::new(stage));
+        Ok(self)
+    }
+
+    /// Replaces the stage of the same name, e.g. with another version.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::Configuration` if there is no such stage.
+    pub fn replace(mut self, stage: impl Stage + 'static) -> CepResult<Self> {
+        let index = self.position(stage.name())?;
+        self.stages[index] = Arc::new(stage);
+        Ok(self)
+    }
+
+    /// Removes the stage named `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::Configuration` if there is no such stage.
+    pub fn remove(mut self, name: &str) -> CepResult<Self> {
+        let index = self.position(name)?;
+        self.stages.remove(index);
+        Ok(self)
+    }
+
+    /// Stage ids (`name@version`) in order.
+    pub fn stage_ids(&self) -> Vec<String> {
+        self.stages.iter().map(|stage| stage.id()).collect()
+    }
+
+    /// Checks that the pipeline runs exactly the `expected` stage ids, in
+    /// order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CepError::UnsupportedVersion` naming the first difference.
+    pub fn pin<S: AsRef<str>>(&self, expected: &[S]) -> CepResult<()> {
+        let actual = self.stage_ids();
+        let longest = actual.len().max(expected.len());
+        for i in 0..longest {
+            let want = expected.get(i).map(AsRef::as_ref);
+            let have = actual.get(i).map(String::as_str);
+            if want != have {
+                return Err(CepError::UnsupportedVersion(format!(
+                    "normalization stage {} is {}, pinned {}",
+                    i + 1,
+                    have.unwrap_or("missing"),
+                    want.unwrap_or("missing")
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs every stage in order.
+    pub fn apply(&self, text: &str) -> String {
+        self.stages
+            .iter()
+            .fold(text.to_string(), |text, stage| stage.apply(&text))
+    }
+
+    /// Runs every stage in order, recording each one's input and output.
+    pub fn apply_traced(&self, text: &str) -> (String, Vec<StageTrace>) {
+        let mut trace = Vec::with_capacity(self.stages.len());
+        let mut current = text.to_string();
+        for stage in &self.stages {
+            let output = stage.apply(&current);
+            trace.push(StageTrace {
+                stage: stage.id(),
+                input: current,
+                output: output.clone(),
+            });
+            current = output;
+        }
+        (current, trace)
+    }
+
+    /// Hash over the stage ids, one per line.
+    pub fn version_hash(&self) -> CanonicalHash {
+        CanonicalHash::from_canonical_string(&self.stage_ids().join("\n"))
+    }
+
+    fn position(&self, name: &str) -> CepResult<usize> {
+        self.stages
+            .iter()
+            .position(|stage| stage.name() == name)
+            .ok_or_else(|| CepError::Configuration(format!("no normalization stage '{}'", name)))
+    }
+
+    fn check_name_free(&self, name: &str) -> CepResult<()> {
+        if self.stages.iter().any(|stage| stage.name() == name) {
+            return Err(CepError::Configuration(format!(
+                "normalization stage '{}' already exists",
+                name
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The legal name and address pipelines that produce a [`CanonicalInput`].
+#[derive(Debug, Clone)]
+pub struct NormalizingFunctor {
+    pub legal_name: Pipeline,
+    pub address: Pipeline,
+}
+
+impl Default for NormalizingFunctor {
+    fn default() -> Self {
+        Self {
+            legal_name: Pipeline::legal_name(),
+            address: Pipeline::address(),
+        }
+    }
+}
+
+lazy_static! {
+    static ref STANDARD: NormalizingFunctor = NormalizingFunctor::default();
+}
+
+impl NormalizingFunctor {
+    /// The standard pipelines, shared.
+    pub fn standard() -> &'static NormalizingFunctor {
+        &STANDARD
+    }
+
+    /// Builds the canonical input as
+    /// [`build_canonical_input`](crate::build_canonical_input) does, with
+    /// these pipelines.
+    pub fn canonical_input(
+        &self,
+        legal_name: &str,
+        country_code: &str,
+        address: Option<&str>,
+        registration_date: Option<&str>,
+    ) -> CanonicalInput {
+        CanonicalInput {
+            legal_name_normalized: if legal_name.is_empty() {
+                String::new()
+            } else {
+                self.legal_name.apply(legal_name)
+            },
+            address_normalized: address
+                .filter(|a| !a.is_empty())
+                .map(|a| self.address.apply(a))
+                .filter(|a| !a.is_empty()),
+            country_code: country_code.to_uppercase(),
+            registration_date: registration_date.and_then(normalize_registration_date),
+        }
+    }
+
+    /// Builds the canonical input as [`Self::canonical_input`] does, with a
+    /// trace of both pipelines. A field that is not normalized (an empty
+    /// name, a missing address) has an empty trace.
+    pub fn canonical_input_traced(
+        &self,
+        legal_name: &str,
+        country_code: &str,
+        address: Option<&str>,
+        registration_date: Option<&str>,
+    ) -> (CanonicalInput, NormalizationTrace) {
+        let canonical = self.canonical_input(legal_name, country_code, address, registration_date);
+        let legal_name = if legal_name.is_empty() {
+            Vec::new()
+        } else {
+            self.legal_name.apply_traced(legal_name).1
+        };
+        let address = match address.filter(|a| !a.is_empty()) {
+            Some(address) => self.address.apply_traced(address).1,
+            None => Vec::new(),
+        };
+        (
+            canonical,
+            NormalizationTrace {
+                legal_name,
+                address,
+            },
+        )
+    }
+
+    /// Hash over both pipelines' stage ids and the date rules: the SNFEI
+    /// algorithm version these pipelines implement.
+    pub fn algorithm_version(&self) -> CanonicalHash {
+        CanonicalHash::from_canonical_string(&format!(
+            "legal_name={}\naddress={}\nregistration_date={}",
+            self.legal_name.stage_ids().join(","),
+            self.address.stage_ids().join(","),
+            REGISTRATION_DATE_STAGE
+        ))
+    }
+}
+
+/// The SNFEI algorithm version: [`NormalizingFunctor::algorithm_version`] of
+/// the standard pipelines.
+pub fn algorithm_version() -> CanonicalHash {
+    NormalizingFunctor::standard().algorithm_version()
+}
+
+/// One stage's input and output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageTrace {
+    /// Stage id, `name@version`.
+    pub stage: String,
+    pub input: String,
+    pub output: String,
+}
+
+/// Per-stage trace of one normalization.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NormalizationTrace {
+    pub legal_name: Vec<StageTrace>,
+    pub address: Vec<StageTrace>,
+}
+
+/// Where two traces first differ.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceDivergence {
+    /// `legal_name` or `address`.
+    pub field: &'static str,
+    /// Zero-based stage position.
+    pub step: usize,
+    /// The left trace's stage, or `None` if its pipeline is shorter.
+    pub left: Option<StageTrace>,
+    /// The right trace's stage, or `None` if its pipeline is shorter.
+    pub right: Option<StageTrace>,
+}
+
+impl TraceDivergence {
+    /// Returns true if both sides ran the same stage on the same input and
+    /// got different output: the stage itself is implemented differently.
+    /// Otherwise the pipelines differ (another stage or version) or the
+    /// inputs already differed.
+    pub fn is_stage_mismatch(&self) -> bool {
+        match (&self.left, &self.right) {
+            (Some(left), Some(right)) => left.stage == right.stage && left.input == right.input,
+            _ => false,
+        }
+    }
+}
+
+/// Returns the first stage at which the traces differ, legal name first,
+/// or `None` if they agree throughout.
+pub fn compare_traces(
+    left: &NormalizationTrace,
+    right: &NormalizationTrace,
+) -> Option<TraceDivergence> {
+    let fields = [
+        ("legal_name", &left.legal_name, &right.legal_name),
+        ("address", &left.address, &right.address),
+    ];
+    for (field, left, right) in fields {
+        for step in 0..left.len().max(right.len()) {
+            let (l, r) = (left.get(step), right.get(step));
+            if l != r {
+                return Some(TraceDivergence {
+                    field,
+                    step,
+                    left: l.cloned(),
+                    right: r.cloned(),
+                });
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::normalizer::build_canonical_input;
+
+    #[test]
+    fn test_standard_pipelines_match_normalizer() {
+        let functor = NormalizingFunctor::standard();
+        let inputs = [
+            (
+                "The Springfield Unified Sch. Dist., Inc.",
+                "123 N. Main St., Suite 400",
+            ),
+            ("Société Générale Ltd", "Apt 4, 9 Rue de l'Église"),
+            ("", ""),
+        ];
+        for (name, address) in inputs {
+            let expected = build_canonical_input(name, "us", Some(address), Some("1990"));
+            let actual = functor.canonical_input(name, "us", Some(address), Some("1990"));
+            assert_eq!(actual.to_hash_string(), expected.to_hash_string());
+        }
+        assert_eq!(
+            Pipeline::legal_name().stage_ids(),
+            [
+                "case_fold@1",
+                "ascii_fold@1",
+                "punct_strip@1",
+                "whitespace_collapse@1",
+                "legal_form@1",
+                "abbreviation_expand@1",
+                "stop_word_strip@1",
+                "trim@1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_edit_and_pin() {
+        fn legal_form_v2(text: &str) -> String {
+            expand_legal_forms(text).replace("limitada", "limited")
+        }
+
+        let pipeline = Pipeline::legal_name()
+            .replace(FnStage::new("legal_form", 2, legal_form_v2))
+            .unwrap();
+        assert_eq!(pipeline.stage_ids()[4], "legal_form@2");
+        assert_eq!(pipeline.apply("Acme Ltda"), "acme limited");
+        assert_ne!(
+            pipeline.version_hash(),
+            Pipeline::legal_name().version_hash()
+        );
+
+        let err = pipeline
+            .pin(&Pipeline::legal_name().stage_ids())
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("legal_form@2, pinned legal_form@1")
+        );
+        assert!(pipeline.pin(&pipeline.stage_ids()).is_ok());
+
+        let shorter = pipeline.clone().remove("trim").unwrap();
+        assert!(shorter.pin(&pipeline.stage_ids()).is_err());
+        assert!(
+            pipeline
+                .clone()
+                .insert_after("nope", FnStage::new("x", 1, str::to_string))
+                .is_err()
+        );
+        assert!(
+            pipeline
+                .insert_after("trim", FnStage::new("case_fold", 2, str::to_string))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_algorithm_version_is_stable() {
+        assert_eq!(
+            algorithm_version(),
+            NormalizingFunctor::default().algorithm_version()
+        );
+        let mut functor = NormalizingFunctor::default();
+        functor.address = functor.address.remove("unit_strip").unwrap();
+        assert_ne!(functor.algorithm_version(), algorithm_version());
+    }
+
+    #[test]
+    fn test_trace_and_compare() {
+        let functor = NormalizingFunctor::standard();
+        let (canonical, trace) =
+            functor.canonical_input_traced("Acme Ltda.", "BR", Some("Apt 2, 1 Main St"), None);
+        assert_eq!(canonical.legal_name_normalized, "acme limitada");
+        assert_eq!(trace.legal_name.len(), 8);
+        assert_eq!(trace.legal_name[0].input, "Acme Ltda.");
+        assert_eq!(trace.legal_name[4].stage, "legal_form@1");
+        assert_eq!(trace.legal_name[4].input, "acme ltda");
+        assert_eq!(
+            trace.legal_name.last().unwrap().output,
+            canonical.legal_name_normalized
+        );
+        assert_eq!(trace.address.last().unwrap().output, "1 main street");
+        assert_eq!(compare_traces(&trace, &trace), None);
+
+        // Another implementation whose legal form table lacks "ltda".
+        let json = serde_json::to_string(&trace).unwrap();
+        let mut other: NormalizationTrace = serde_json::from_str(&json).unwrap();
+        for step in &mut other.legal_name[4..] {
+            step.output = step.output.replace("limitada", "ltda");
+            if step.stage != "legal_form@1" {
+                step.input = step.input.replace("limitada", "ltda");
+            }
+        }
+        let divergence = compare_traces(&trace, &other).unwrap();
+        assert_eq!((divergence.field, divergence.step), ("legal_name", 4));
+        assert!(divergence.is_stage_mismatch());
+
+        let (_, shorter) = NormalizingFunctor {
+            legal_name: Pipeline::legal_name().remove("trim").unwrap(),
+            ..NormalizingFunctor::default()
+        }
+        .canonical_input_traced("Acme Ltda.", "BR", Some("Apt 2, 1 Main St"), None);
+        let divergence = compare_traces(&trace, &shorter).unwrap();
+        assert_eq!(divergence.step, 7);
+        assert!(divergence.right.is_none() && !divergence.is_stage_mismatch());
+    }
+}
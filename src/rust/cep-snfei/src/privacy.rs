@@ -0,0 +1,293 @@
+//! Re-identification analysis for SNFEIs.
+//!
+//! An SNFEI is an unkeyed SHA-256 over normalized inputs. Anyone who can
+//! enumerate plausible inputs (business registries, address files) can hash
+//! them and match the results against published SNFEIs. This module estimates
+//! how practical that dictionary attack is for a given policy and recommends
+//! whether a keyed SNFEI ([`crate::keyed`]) should be used instead.
+//!
+//! The vocabulary passed in models what an attacker could plausibly
+//! enumerate. Normalization shrinks it: raw variants that normalize to the
+//! same canonical value cost the attacker nothing extra.
+
+use std::collections::HashSet;
+
+use crate::generator::{Snfei, compute_snfei};
+use crate::normalizer::{
+    CanonicalInput, build_canonical_input, normalize_address, normalize_legal_name,
+    normalize_registration_date,
+};
+
+/// Candidate values an attacker could enumerate.
+#[derive(Debug, Clone, Default)]
+pub struct PrivacyVocabulary {
+    /// Plausible legal names.
+    pub names: Vec<String>,
+    /// Plausible street addresses. Empty means the attacker is assumed to
+    /// know the address (or it is not part of the SNFEI).
+    pub addresses: Vec<String>,
+    /// Plausible registration dates. Empty means the attacker must enumerate
+    /// [`PrivacyPolicy::registration_date_span_days`] dates.
+    pub registration_dates: Vec<String>,
+}
+
+/// The SNFEI configuration being assessed and the attacker model.
+#[derive(Debug, Clone)]
+pub struct PrivacyPolicy {
+    /// Country codes SNFEIs are issued under.
+    pub country_codes: Vec<String>,
+    /// Whether the address is part of the SNFEI input.
+    pub include_address: bool,
+    /// Whether the registration date is part of the SNFEI input.
+    pub include_registration_date: bool,
+    /// Days an attacker must try when registration dates are unknown.
+    pub registration_date_span_days: u64,
+    /// Attacker throughput in SHA-256 evaluations per second.
+    pub attacker_hashes_per_second: f64,
+    /// Exhaustive search below this many seconds is considered feasible.
+    pub min_attack_seconds: f64,
+}
+
+impl Default for PrivacyPolicy {
+    fn default() -> Self {
+        Self {
+            country_codes: vec!["US".to_string()],
+            include_address: false,
+            include_registration_date: false,
+            registration_date_span_days: 100 * 365,
+            // A single commodity GPU.
+            attacker_hashes_per_second: 1e9,
+            // One year.
+            min_attack_seconds: 365.0 * 24.0 * 3600.0,
+        }
+    }
+}
+
+/// Whether SNFEIs under the assessed policy should be keyed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyingRecommendation {
+    /// Exhaustive search is impractical; the unkeyed SNFEI is acceptable.
+    Unkeyed,
+    /// Exhaustive search is practical; use a keyed SNFEI.
+    Keyed,
+}
+
+/// Result of [`assess_reidentification`].
+#[derive(Debug, Clone)]
+pub struct PrivacyAssessment {
+    /// Distinct normalized names in the vocabulary.
+    pub distinct_names: usize,
+    /// Raw names that collapsed onto another name during normalization.
+    pub collapsed_names: usize,
+    /// Number of distinct canonical inputs an attacker must hash.
+    pub candidate_space: u128,
+    /// log2 of `candidate_space`.
+    pub work_bits: f64,
+    /// Expected time to exhaust the candidate space.
+    pub estimated_seconds: f64,
+    /// Whether a keyed SNFEI is recommended.
+    pub recommendation: KeyingRecommendation,
+    /// Human-readable findings supporting the recommendation.
+    pub findings: Vec<String>,
+}
+
+/// Estimates how feasible it is to reverse SNFEIs issued under `policy`.
+///
+/// # Example
+/// ```
+/// use cep_snfei::{assess_reidentification, KeyingRecommendation, PrivacyPolicy, PrivacyVocabulary};
+///
+/// let vocab = PrivacyVocabulary {
+///     names: vec!["Acme LLC".to_string(), "ACME, L.L.C.".to_string()],
+///     ..Default::default()
+/// };
+/// let assessment = assess_reidentification(&vocab, &PrivacyPolicy::default());
+/// assert_eq!(assessment.distinct_names, 1);
+/// assert_eq!(assessment.recommendation, KeyingRecommendation::Keyed);
+/// ```
+pub fn assess_reidentification(
+    vocab: &PrivacyVocabulary,
+    policy: &PrivacyPolicy,
+) -> PrivacyAssessment {
+    let names = distinct(vocab.names.iter().map(|n| normalize_legal_name(n)));
+    let collapsed_names = vocab.names.len() - names.len();
+    let mut findings = Vec::new();
+
+    let mut candidate_space = (names.len() as u128).max(1);
+    candidate_space *= (policy.country_codes.len() as u128).max(1);
+
+    if policy.include_address && !vocab.addresses.is_empty() {
+        let addresses = distinct(vocab.addresses.iter().map(|a| normalize_address(a)));
+        candidate_space = candidate_space.saturating_mul(addresses.len() as u128);
+        findings.push(format!("{} distinct normalized addresses", addresses.len()));
+    } else if policy.include_address {
+        findings.push("address assumed known to the attacker".to_string());
+    }
+
+    if policy.include_registration_date {
+        let dates = if vocab.registration_dates.is_empty() {
+            u128::from(policy.registration_date_span_days)
+        } else {
+            distinct(
+                vocab
+                    .registration_dates
+                    .iter()
+                    .filter_map(|d| normalize_registration_date(d)),
+            )
+            .len() as u128
+        };
+        candidate_space = candidate_space.saturating_mul(dates.max(1));
+        findings.push(format!("{} candidate registration dates", dates));
+    }
+
+    if collapsed_names > 0 {
+        findings.push(format!(
+            "{} name variants collapse under normalization",
+            collapsed_names
+        ));
+    }
+
+    let estimated_seconds = candidate_space as f64 / policy.attacker_hashes_per_second;
+    let recommendation = if estimated_seconds < policy.min_attack_seconds {
+        findings.push(format!(
+            "exhaustive search takes about {:.0} seconds; keyed SNFEIs recommended",
+            estimated_seconds
+        ));
+        KeyingRecommendation::Keyed
+    } else {
+        KeyingRecommendation::Unkeyed
+    };
+
+    PrivacyAssessment {
+        distinct_names: names.len(),
+        collapsed_names,
+        candidate_space,
+        work_bits: (candidate_space as f64).log2(),
+        estimated_seconds,
+        recommendation,
+        findings,
+    }
+}
+
+/// Attempts to reverse `snfei` by hashing every candidate in `vocab`.
+///
+/// Registration dates are only tried from the vocabulary. Stops after
+/// `max_candidates` hashes. Intended for demonstrating feasibility to policy
+/// staff, not for production use.
+pub fn reverse_lookup(
+    snfei: &Snfei,
+    vocab: &PrivacyVocabulary,
+    policy: &PrivacyPolicy,
+    max_candidates: usize,
+) -> Option<CanonicalInput> {
+    let addresses: Vec<Option<&str>> = if policy.include_address && !vocab.addresses.is_empty() {
+        vocab.addresses.iter().map(|a| Some(a.as_str())).collect()
+    } else {
+        vec![None]
+    };
+    let dates: Vec<Option<&str>> =
+        if policy.include_registration_date && !vocab.registration_dates.is_empty() {
+            vocab
+                .registration_dates
+                .iter()
+                .map(|d| Some(d.as_str()))
+                .collect()
+        } else {
+            vec![None]
+        };
+
+    let mut tried = 0;
+    for name in &vocab.names {
+        for country in &policy.country_codes {
+            for address in &addresses {
+                for date in &dates {
+                    if tried == max_candidates {
+                        return None;
+                    }
+                    tried += 1;
+                    let canonical = build_canonical_input(name, country, *address, *date);
+                    if &compute_snfei(&canonical) == snfei {
+                        return Some(canonical);
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+fn distinct(values: impl Iterator<Item = String>) -> HashSet<String> {
+    values.filter(|v| !v.is_empty()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::generate_snfei;
+
+    fn vocab() -> PrivacyVocabulary {
+        PrivacyVocabulary {
+            names: vec![
+                "Springfield School District".to_string(),
+                "SPRINGFIELD SCHOOL DISTRICT".to_string(),
+                "Shelbyville Water Authority".to_string(),
+            ],
+            addresses: vec!["123 Main St".to_string(), "456 Oak Ave".to_string()],
+            registration_dates: vec![],
+        }
+    }
+
+    #[test]
+    fn test_normalization_collapses_variants() {
+        let assessment = assess_reidentification(&vocab(), &PrivacyPolicy::default());
+        assert_eq!(assessment.distinct_names, 2);
+        assert_eq!(assessment.collapsed_names, 1);
+        assert_eq!(assessment.candidate_space, 2);
+        assert_eq!(assessment.recommendation, KeyingRecommendation::Keyed);
+    }
+
+    #[test]
+    fn test_optional_fields_grow_candidate_space() {
+        let policy = PrivacyPolicy {
+            include_address: true,
+            include_registration_date: true,
+            ..Default::default()
+        };
+        let assessment = assess_reidentification(&vocab(), &policy);
+        assert_eq!(assessment.candidate_space, 2 * 2 * 36_500);
+    }
+
+    #[test]
+    fn test_large_space_is_acceptable_unkeyed() {
+        let policy = PrivacyPolicy {
+            attacker_hashes_per_second: 1.0,
+            min_attack_seconds: 10.0,
+            ..Default::default()
+        };
+        let mut vocab = vocab();
+        vocab.names = (0..100).map(|i| format!("Entity {}", i)).collect();
+        let assessment = assess_reidentification(&vocab, &policy);
+        assert_eq!(assessment.recommendation, KeyingRecommendation::Unkeyed);
+    }
+
+    #[test]
+    fn test_reverse_lookup_recovers_input() {
+        let policy = PrivacyPolicy {
+            include_address: true,
+            ..Default::default()
+        };
+        let target = generate_snfei(
+            "Shelbyville Water Authority",
+            "US",
+            Some("456 Oak Ave"),
+            None,
+        );
+        let found = reverse_lookup(&target.snfei, &vocab(), &policy, 1_000).unwrap();
+        assert_eq!(
+            found.legal_name_normalized,
+            target.canonical.legal_name_normalized
+        );
+
+        assert!(reverse_lookup(&target.snfei, &vocab(), &policy, 2).is_none());
+    }
+}